@@ -0,0 +1,2 @@
+-- Commit the user last reviewed for an attempt, used for "diff since last view"
+ALTER TABLE task_attempts ADD COLUMN last_viewed_oid TEXT;
@@ -0,0 +1,3 @@
+-- JSON object of environment variables applied to every execution in a project.
+-- Values may reference host variables as ${NAME}, resolved at spawn time.
+ALTER TABLE projects ADD COLUMN env_template TEXT NOT NULL DEFAULT '{}';
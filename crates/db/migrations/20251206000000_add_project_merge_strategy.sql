@@ -0,0 +1,3 @@
+-- How task attempts are merged into their target branch: merge, squash or rebase
+ALTER TABLE projects ADD COLUMN merge_strategy TEXT NOT NULL DEFAULT 'squash'
+    CHECK (merge_strategy IN ('merge', 'squash', 'rebase'));
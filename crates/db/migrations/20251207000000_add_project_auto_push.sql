@@ -0,0 +1,2 @@
+-- Push the attempt branch to its remote after every successful auto-commit
+ALTER TABLE projects ADD COLUMN auto_push BOOLEAN NOT NULL DEFAULT FALSE;
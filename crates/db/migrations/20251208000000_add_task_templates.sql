@@ -0,0 +1,14 @@
+-- Reusable task definitions whose title/description may contain {placeholder}s
+CREATE TABLE task_templates (
+    id                          BLOB PRIMARY KEY,
+    project_id                  BLOB,  -- NULL for global templates
+    name                        TEXT NOT NULL CHECK(name != ''),
+    title                       TEXT NOT NULL,
+    description                 TEXT,
+    default_executor_profile_id TEXT,  -- JSON ExecutorProfileId
+    created_at                  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at                  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_templates_project_id ON task_templates(project_id);
@@ -0,0 +1,2 @@
+-- Regex that stops a coding agent when its output matches, e.g. to break runaway loops
+ALTER TABLE projects ADD COLUMN abort_on_output_pattern TEXT;
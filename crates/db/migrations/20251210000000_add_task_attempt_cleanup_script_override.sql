@@ -0,0 +1,2 @@
+-- Per-attempt cleanup script used instead of the project's cleanup_script
+ALTER TABLE task_attempts ADD COLUMN cleanup_script_override TEXT;
@@ -0,0 +1,2 @@
+-- Executor profile (JSON ExecutorProfileId) used when an attempt is created without one
+ALTER TABLE projects ADD COLUMN default_executor_profile_id TEXT;
@@ -0,0 +1,3 @@
+-- MsgStore sequence number of the logged message, so log readers can resume from a cursor.
+-- NULL for rows written before sequencing existed; readers fall back to line order.
+ALTER TABLE execution_process_logs ADD COLUMN seq INTEGER;
@@ -0,0 +1,2 @@
+-- Template for commit messages vibe-kanban creates; NULL keeps the built-in messages
+ALTER TABLE projects ADD COLUMN commit_message_template TEXT;
@@ -0,0 +1,2 @@
+-- Environment variables (JSON object) applied to every process of the attempt
+ALTER TABLE task_attempts ADD COLUMN env_overrides TEXT;
@@ -0,0 +1,4 @@
+-- Which hosting provider a PR merge was opened on; existing PRs are all GitHub
+ALTER TABLE merges ADD COLUMN pr_provider TEXT CHECK (pr_provider IN ('github', 'gitlab'));
+
+UPDATE merges SET pr_provider = 'github' WHERE merge_type = 'pr';
@@ -0,0 +1,2 @@
+-- Template for attempt branch names; NULL keeps the built-in `{prefix}/{short_id}-{title}` format
+ALTER TABLE projects ADD COLUMN branch_template TEXT;
@@ -0,0 +1,2 @@
+-- Branch new attempts of a templated task start from; NULL uses the usual default
+ALTER TABLE task_templates ADD COLUMN default_base_branch TEXT;
@@ -0,0 +1,3 @@
+-- JSON array of MCP server definitions (name, command, args, env) the project
+-- adds to its agents' MCP configs
+ALTER TABLE projects ADD COLUMN mcp_servers TEXT NOT NULL DEFAULT '[]';
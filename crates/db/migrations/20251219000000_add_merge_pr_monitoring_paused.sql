@@ -0,0 +1,2 @@
+-- Open PRs with monitoring paused are skipped by the PR monitor's polling
+ALTER TABLE merges ADD COLUMN pr_monitoring_paused BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,2 @@
+-- Newline-separated sparse-checkout patterns for attempt worktrees; NULL keeps a full checkout
+ALTER TABLE projects ADD COLUMN worktree_sparse_patterns TEXT;
@@ -0,0 +1,16 @@
+PRAGMA foreign_keys = ON;
+
+-- Local feed of notable project events, recorded whether or not analytics is enabled
+CREATE TABLE project_activities (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    kind        TEXT NOT NULL,
+    task_id     BLOB,
+    attempt_id  BLOB,
+    actor       TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_project_activities_project_id_created_at
+    ON project_activities(project_id, created_at);
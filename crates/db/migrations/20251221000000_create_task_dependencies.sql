@@ -0,0 +1,14 @@
+PRAGMA foreign_keys = ON;
+
+-- `task_id` can't move to in progress until `blocked_by_task_id` is done
+CREATE TABLE task_dependencies (
+    task_id             BLOB NOT NULL,
+    blocked_by_task_id  BLOB NOT NULL,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_id, blocked_by_task_id),
+    CHECK (task_id != blocked_by_task_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (blocked_by_task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_dependencies_blocked_by_task_id ON task_dependencies(blocked_by_task_id);
@@ -0,0 +1,2 @@
+-- Pinned attempts keep their worktree: expiry cleanup skips them
+ALTER TABLE task_attempts ADD COLUMN pin_worktree BOOLEAN NOT NULL DEFAULT FALSE;
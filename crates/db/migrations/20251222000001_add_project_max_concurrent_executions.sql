@@ -0,0 +1,2 @@
+-- Per-project cap on coding agent executions running at once; NULL for no project cap
+ALTER TABLE projects ADD COLUMN max_concurrent_executions INTEGER;
@@ -0,0 +1,2 @@
+-- Archived projects are hidden from the default project list and skipped by background work
+ALTER TABLE projects ADD COLUMN archived BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,13 @@
+PRAGMA foreign_keys = ON;
+
+-- Regex that picks the app URL (or port) out of the dev server's output; NULL uses built-in patterns
+ALTER TABLE projects ADD COLUMN dev_server_url_pattern TEXT;
+
+-- URL a dev server process reported it is serving on, detected from its output
+CREATE TABLE dev_server_urls (
+    execution_process_id  BLOB PRIMARY KEY,
+    url                   TEXT NOT NULL,
+    port                  INTEGER,
+    detected_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
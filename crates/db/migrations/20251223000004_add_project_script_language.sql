@@ -0,0 +1,2 @@
+-- Interpreter for setup, cleanup and dev scripts; NULL uses the platform default
+ALTER TABLE projects ADD COLUMN script_language TEXT;
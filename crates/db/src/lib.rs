@@ -25,6 +25,17 @@ impl DBService {
         Ok(DBService { pool })
     }
 
+    /// A fresh, migrated in-memory database, for tests
+    pub async fn new_in_memory() -> Result<DBService, Error> {
+        // Every connection to `sqlite::memory:` opens its own empty database
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await?;
+        sqlx::migrate!("./migrations").run(&pool).await?;
+        Ok(DBService { pool })
+    }
+
     pub async fn new_with_after_connect<F>(after_connect: F) -> Result<DBService, Error>
     where
         F: for<'a> Fn(
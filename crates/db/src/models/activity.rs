@@ -0,0 +1,198 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::Task;
+
+/// What happened in a project activity feed entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    TaskCreated,
+    TaskDeleted,
+    AttemptStarted,
+    AttemptMerged,
+    PrOpened,
+    DevServerStarted,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct Activity {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub kind: ActivityKind,
+    pub task_id: Option<Uuid>,
+    pub attempt_id: Option<Uuid>,
+    /// User id of the installation that performed the action
+    pub actor: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateActivity {
+    pub project_id: Uuid,
+    pub kind: ActivityKind,
+    pub task_id: Option<Uuid>,
+    pub attempt_id: Option<Uuid>,
+}
+
+impl CreateActivity {
+    /// Activity about a task as a whole
+    pub fn for_task(kind: ActivityKind, task: &Task) -> Self {
+        Self {
+            project_id: task.project_id,
+            kind,
+            task_id: Some(task.id),
+            attempt_id: None,
+        }
+    }
+
+    /// Activity about one of a task's attempts
+    pub fn for_attempt(kind: ActivityKind, task: &Task, attempt_id: Uuid) -> Self {
+        Self {
+            attempt_id: Some(attempt_id),
+            ..Self::for_task(kind, task)
+        }
+    }
+}
+
+impl Activity {
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateActivity,
+        actor: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            Activity,
+            r#"INSERT INTO project_activities (id, project_id, kind, task_id, attempt_id, actor)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         kind as "kind!: ActivityKind",
+                         task_id as "task_id: Uuid",
+                         attempt_id as "attempt_id: Uuid",
+                         actor,
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.kind,
+            data.task_id,
+            data.attempt_id,
+            actor
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Activity,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      kind as "kind!: ActivityKind",
+                      task_id as "task_id: Uuid",
+                      attempt_id as "attempt_id: Uuid",
+                      actor,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM project_activities
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Newest-first page of a project's activity, starting after the `before` entry when
+    /// given. Entries recorded in the same instant are ordered by insertion, so paging
+    /// never skips them.
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        before: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Activity,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      kind as "kind!: ActivityKind",
+                      task_id as "task_id: Uuid",
+                      attempt_id as "attempt_id: Uuid",
+                      actor,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM project_activities
+               WHERE project_id = $1
+                 AND ($2 IS NULL
+                      OR (created_at, rowid) < (SELECT created_at, rowid
+                                                FROM project_activities
+                                                WHERE id = $2))
+               ORDER BY created_at DESC, rowid DESC
+               LIMIT $3"#,
+            project_id,
+            before,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DBService,
+        models::project::{CreateProject, Project},
+    };
+
+    #[tokio::test]
+    async fn test_pages_do_not_skip_entries_with_equal_timestamps() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let project = Project::create(
+            &db.pool,
+            &CreateProject {
+                name: "Feed".to_string(),
+                git_repo_path: "/tmp/feed-repo".to_string(),
+                ..Default::default()
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+
+        let mut recorded = Vec::new();
+        for _ in 0..5 {
+            let data = CreateActivity {
+                project_id: project.id,
+                kind: ActivityKind::AttemptStarted,
+                task_id: None,
+                attempt_id: Some(Uuid::new_v4()),
+            };
+            recorded.push(Activity::create(&db.pool, &data, "user").await.unwrap().id);
+        }
+        // A batch of attempts started within the same instant
+        sqlx::query("UPDATE project_activities SET created_at = '2025-01-01 00:00:00.000'")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        let mut seen = Vec::new();
+        let mut before = None;
+        loop {
+            let page = Activity::find_by_project_id(&db.pool, project.id, before, 2)
+                .await
+                .unwrap();
+            let Some(last) = page.last() else {
+                break;
+            };
+            before = Some(last.id);
+            seen.extend(page.iter().map(|activity| activity.id));
+        }
+        recorded.reverse();
+        assert_eq!(seen, recorded);
+    }
+}
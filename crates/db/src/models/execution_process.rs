@@ -37,6 +37,8 @@ pub enum ExecutionProcessStatus {
     Completed,
     Failed,
     Killed,
+    /// Stopped after producing no output for the configured idle timeout
+    TimedOut,
 }
 
 #[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS)]
@@ -72,6 +74,16 @@ pub struct ExecutionProcess {
     pub updated_at: DateTime<Utc>,
 }
 
+/// URL a dev server process reported it is serving on
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct DevServerUrl {
+    pub execution_process_id: Uuid,
+    pub url: String,
+    #[ts(type = "number | null")]
+    pub port: Option<i64>,
+    pub detected_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Deserialize, TS)]
 pub struct CreateExecutionProcess {
     pub task_attempt_id: Uuid,
@@ -87,7 +99,7 @@ pub struct UpdateExecutionProcess {
     pub completed_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExecutionContext {
     pub execution_process: ExecutionProcess,
     pub task_attempt: TaskAttempt,
@@ -246,6 +258,58 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Find every running execution process, dev servers included, for a specific project
+    pub async fn find_running_by_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcess,
+            r#"SELECT ep.id as "id!: Uuid", ep.task_attempt_id as "task_attempt_id!: Uuid", ep.run_reason as "run_reason!: ExecutionProcessRunReason", ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
+                      ep.before_head_commit, ep.after_head_commit, ep.status as "status!: ExecutionProcessStatus", ep.exit_code,
+                      ep.dropped, ep.started_at as "started_at!: DateTime<Utc>", ep.completed_at as "completed_at?: DateTime<Utc>", ep.created_at as "created_at!: DateTime<Utc>", ep.updated_at as "updated_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN task_attempts ta ON ep.task_attempt_id = ta.id
+               JOIN tasks t ON ta.task_id = t.id
+               WHERE ep.status = 'running' AND t.project_id = ?
+               ORDER BY ep.created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Count running coding agent executions, across all projects or within one
+    pub async fn count_running_coding_agents(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM execution_processes ep
+               JOIN task_attempts ta ON ep.task_attempt_id = ta.id
+               JOIN tasks t ON ta.task_id = t.id
+               WHERE ep.status = 'running' AND ep.run_reason = 'codingagent'
+                 AND ($1 IS NULL OR t.project_id = $1)"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Number of execution processes in each status, keyed by the stored status name
+    pub async fn count_by_status(pool: &SqlitePool) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT status as "status!: String", COUNT(*) as "count!: i64"
+               FROM execution_processes
+               GROUP BY status
+               ORDER BY status"#
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| (r.status, r.count)).collect())
+    }
+
     /// Find running dev servers for a specific project
     pub async fn find_running_dev_servers_by_project(
         pool: &SqlitePool,
@@ -370,6 +434,44 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Record the URL detected in a dev server's output, replacing any earlier one
+    pub async fn set_dev_server_url(
+        pool: &SqlitePool,
+        id: Uuid,
+        url: &str,
+        port: Option<i64>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO dev_server_urls (execution_process_id, url, port)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(execution_process_id) DO UPDATE SET
+                   url = excluded.url,
+                   port = excluded.port,
+                   detected_at = datetime('now', 'subsec')"#,
+            id,
+            url,
+            port
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_dev_server_url(
+        pool: &SqlitePool,
+        id: Uuid,
+    ) -> Result<Option<DevServerUrl>, sqlx::Error> {
+        sqlx::query_as!(
+            DevServerUrl,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid", url, port, detected_at as "detected_at!: DateTime<Utc>"
+               FROM dev_server_urls
+               WHERE execution_process_id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     /// Create a new execution process
     pub async fn create(
         pool: &SqlitePool,
@@ -11,6 +11,8 @@ pub struct ExecutionProcessLogs {
     pub logs: String, // JSONL format
     pub byte_size: i64,
     pub inserted_at: DateTime<Utc>,
+    /// Sequence number of the logged message; `None` for rows from before sequencing
+    pub seq: Option<i64>,
 }
 
 impl ExecutionProcessLogs {
@@ -25,7 +27,8 @@ impl ExecutionProcessLogs {
                 execution_id as "execution_id!: Uuid",
                 logs,
                 byte_size,
-                inserted_at as "inserted_at!: DateTime<Utc>"
+                inserted_at as "inserted_at!: DateTime<Utc>",
+                seq
                FROM execution_process_logs 
                WHERE execution_id = $1
                ORDER BY inserted_at ASC"#,
@@ -47,6 +50,31 @@ impl ExecutionProcessLogs {
         Ok(messages)
     }
 
+    /// Parse JSONL logs along with each message's sequence number. Sequenced rows come
+    /// first in sequence order; rows without a stored sequence number (legacy rows and
+    /// messages written outside the log stream) follow in insertion order, numbered
+    /// past the highest stored one so sequence numbers never repeat.
+    pub fn parse_sequenced_logs(records: &[Self]) -> Result<Vec<(u64, LogMsg)>, serde_json::Error> {
+        let mut ordered: Vec<&Self> = records.iter().collect();
+        // Stable, so unsequenced rows keep their insertion order
+        ordered.sort_by_key(|record| (record.seq.is_none(), record.seq));
+
+        let mut messages = Vec::new();
+        let mut next_seq = 0;
+        for record in ordered {
+            for line in record.logs.lines() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let msg: LogMsg = serde_json::from_str(line)?;
+                let seq = record.seq.map(|s| s as u64).unwrap_or(next_seq);
+                next_seq = next_seq.max(seq + 1);
+                messages.push((seq, msg));
+            }
+        }
+        Ok(messages)
+    }
+
     /// Convert Vec<LogMsg> to JSONL format
     pub fn serialize_logs(messages: &[LogMsg]) -> Result<String, serde_json::Error> {
         let mut jsonl = String::new();
@@ -63,14 +91,16 @@ impl ExecutionProcessLogs {
         pool: &SqlitePool,
         execution_id: Uuid,
         jsonl_line: &str,
+        seq: Option<i64>,
     ) -> Result<(), sqlx::Error> {
         let byte_size = jsonl_line.len() as i64;
         sqlx::query!(
-            r#"INSERT INTO execution_process_logs (execution_id, logs, byte_size, inserted_at)
-               VALUES ($1, $2, $3, datetime('now', 'subsec'))"#,
+            r#"INSERT INTO execution_process_logs (execution_id, logs, byte_size, inserted_at, seq)
+               VALUES ($1, $2, $3, datetime('now', 'subsec'), $4)"#,
             execution_id,
             jsonl_line,
-            byte_size
+            byte_size,
+            seq
         )
         .execute(pool)
         .await?;
@@ -78,3 +108,46 @@ impl ExecutionProcessLogs {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(seq: Option<i64>, line: &str) -> ExecutionProcessLogs {
+        let logs = format!(
+            "{}\n",
+            serde_json::to_string(&LogMsg::Stdout(line.to_string())).unwrap()
+        );
+        ExecutionProcessLogs {
+            execution_id: Uuid::nil(),
+            byte_size: logs.len() as i64,
+            logs,
+            inserted_at: Utc::now(),
+            seq,
+        }
+    }
+
+    #[test]
+    fn test_unsequenced_rows_never_reuse_a_sequence_number() {
+        let records = [
+            record(Some(5), "a"),
+            record(None, "start failed"),
+            record(Some(6), "b"),
+            record(None, "late"),
+        ];
+
+        let parsed = ExecutionProcessLogs::parse_sequenced_logs(&records).unwrap();
+        let seqs: Vec<u64> = parsed.iter().map(|(seq, _)| *seq).collect();
+        assert_eq!(seqs, vec![5, 6, 7, 8]);
+        assert!(matches!(&parsed[2].1, LogMsg::Stdout(s) if s == "start failed"));
+        assert!(matches!(&parsed[3].1, LogMsg::Stdout(s) if s == "late"));
+
+        let legacy = [record(None, "x"), record(None, "y")];
+        let seqs: Vec<u64> = ExecutionProcessLogs::parse_sequenced_logs(&legacy)
+            .unwrap()
+            .iter()
+            .map(|(seq, _)| *seq)
+            .collect();
+        assert_eq!(seqs, vec![0, 1]);
+    }
+}
@@ -14,6 +14,16 @@ pub enum MergeStatus {
     Unknown,
 }
 
+/// Hosting service a pull/merge request was opened on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum GitProvider {
+    #[default]
+    GitHub,
+    GitLab,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Merge {
@@ -37,7 +47,10 @@ pub struct PrMerge {
     pub task_attempt_id: Uuid,
     pub created_at: DateTime<Utc>,
     pub target_branch_name: String,
+    pub provider: GitProvider,
     pub pr_info: PullRequestInfo,
+    /// True when the PR monitor skips this PR while polling
+    pub monitoring_paused: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -68,6 +81,8 @@ struct MergeRow {
     pr_status: Option<MergeStatus>,
     pr_merged_at: Option<DateTime<Utc>>,
     pr_merge_commit_sha: Option<String>,
+    pr_provider: Option<GitProvider>,
+    pr_monitoring_paused: bool,
     created_at: DateTime<Utc>,
 }
 
@@ -104,6 +119,8 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_provider as "pr_provider?: GitProvider",
+                pr_monitoring_paused as "pr_monitoring_paused!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
             "#,
@@ -124,6 +141,7 @@ impl Merge {
         target_branch_name: &str,
         pr_number: i64,
         pr_url: &str,
+        provider: GitProvider,
     ) -> Result<PrMerge, sqlx::Error> {
         let id = Uuid::new_v4();
         let now = Utc::now();
@@ -131,8 +149,8 @@ impl Merge {
         sqlx::query_as!(
             MergeRow,
             r#"INSERT INTO merges (
-                id, task_attempt_id, merge_type, pr_number, pr_url, pr_status, created_at, target_branch_name
-            ) VALUES ($1, $2, 'pr', $3, $4, 'open', $5, $6)
+                id, task_attempt_id, merge_type, pr_number, pr_url, pr_status, created_at, target_branch_name, pr_provider
+            ) VALUES ($1, $2, 'pr', $3, $4, 'open', $5, $6, $7)
             RETURNING 
                 id as "id!: Uuid",
                 task_attempt_id as "task_attempt_id!: Uuid",
@@ -143,6 +161,8 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_provider as "pr_provider?: GitProvider",
+                pr_monitoring_paused as "pr_monitoring_paused!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
             "#,
@@ -151,14 +171,34 @@ impl Merge {
             pr_number,
             pr_url,
             now,
-            target_branch_name
+            target_branch_name,
+            provider
         )
         .fetch_one(pool)
         .await
         .map(Into::into)
     }
 
-    /// Get all open PRs for monitoring
+    /// Pause or resume PR monitor polling for a PR merge
+    pub async fn set_monitoring_paused(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+        paused: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE merges
+            SET pr_monitoring_paused = $1
+            WHERE id = $2 AND merge_type = 'pr'"#,
+            paused,
+            merge_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get all open PRs for monitoring, skipping those with monitoring paused
     pub async fn get_open_prs(pool: &SqlitePool) -> Result<Vec<PrMerge>, sqlx::Error> {
         let rows = sqlx::query_as!(
             MergeRow,
@@ -172,10 +212,12 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_provider as "pr_provider?: GitProvider",
+                pr_monitoring_paused as "pr_monitoring_paused!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
                FROM merges 
-               WHERE merge_type = 'pr' AND pr_status = 'open'
+               WHERE merge_type = 'pr' AND pr_status = 'open' AND pr_monitoring_paused = FALSE
                ORDER BY created_at DESC"#,
         )
         .fetch_all(pool)
@@ -231,6 +273,8 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_provider as "pr_provider?: GitProvider",
+                pr_monitoring_paused as "pr_monitoring_paused!: bool",
                 target_branch_name as "target_branch_name!: String",
                 created_at as "created_at!: DateTime<Utc>"
             FROM merges 
@@ -277,6 +321,7 @@ impl From<MergeRow> for PrMerge {
             id: row.id,
             task_attempt_id: row.task_attempt_id,
             target_branch_name: row.target_branch_name,
+            provider: row.pr_provider.unwrap_or_default(),
             pr_info: PullRequestInfo {
                 number: row.pr_number.expect("pr merge must have pr_number"),
                 url: row.pr_url.expect("pr merge must have pr_url"),
@@ -284,6 +329,7 @@ impl From<MergeRow> for PrMerge {
                 merged_at: row.pr_merged_at,
                 merge_commit_sha: row.pr_merge_commit_sha,
             },
+            monitoring_paused: row.pr_monitoring_paused,
             created_at: row.created_at,
         }
     }
@@ -1,3 +1,6 @@
+use serde::{Deserialize, Deserializer};
+
+pub mod activity;
 pub mod execution_process;
 pub mod execution_process_logs;
 pub mod executor_session;
@@ -9,3 +12,14 @@ pub mod shared_task;
 pub mod tag;
 pub mod task;
 pub mod task_attempt;
+pub mod task_template;
+
+/// Read a present field, `null` included, as `Some` so that an omitted field
+/// (`None`) can be told apart from one being cleared (`Some(None)`).
+pub(crate) fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
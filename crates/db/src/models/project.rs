@@ -1,12 +1,18 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use chrono::{DateTime, Utc};
+use executors::{
+    actions::script::ScriptRequestLanguage, mcp_config::McpServerDefinition,
+    profile::ExecutorProfileId,
+};
 use serde::{Deserialize, Serialize};
-use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type, types::Json};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
+use super::double_option;
+
 #[derive(Debug, Error)]
 pub enum ProjectError {
     #[error(transparent)]
@@ -21,6 +27,20 @@ pub enum ProjectError {
     CreateFailed(String),
 }
 
+/// How a task attempt's branch is merged into its target branch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// Merge commit with both branch tips as parents
+    Merge,
+    /// Single commit on the target branch containing all task changes
+    #[default]
+    Squash,
+    /// Rebase the task commits onto the target branch, then fast-forward
+    Rebase,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct Project {
     pub id: Uuid,
@@ -31,6 +51,35 @@ pub struct Project {
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
     pub remote_project_id: Option<Uuid>,
+    /// Environment variables for every execution; values may reference host vars as `${NAME}`
+    #[ts(type = "Record<string, string>")]
+    pub env_template: sqlx::types::Json<HashMap<String, String>>,
+    pub merge_strategy: MergeStrategy,
+    /// Push the attempt branch to its remote after each successful auto-commit
+    pub auto_push: bool,
+    /// Regex matched against agent output; a match stops the execution
+    pub abort_on_output_pattern: Option<String>,
+    /// Executor profile used for new attempts that don't specify one
+    #[ts(type = "ExecutorProfileId | null")]
+    pub default_executor_profile_id: Option<Json<ExecutorProfileId>>,
+    /// Commit message template with `{title}`, `{description}`, `{task_id}` and `{branch}` placeholders
+    pub commit_message_template: Option<String>,
+    /// Branch name template with `{prefix}`, `{short_id}`, `{title}` and `{task_id}` placeholders
+    pub branch_template: Option<String>,
+    /// MCP servers added to this project's coding agent runs, without touching the agents' global configs
+    #[ts(type = "Array<McpServerDefinition>")]
+    pub mcp_servers: Json<Vec<McpServerDefinition>>,
+    /// Sparse-checkout patterns (one per line, gitignore syntax) for new worktrees; empty checks out everything
+    pub worktree_sparse_patterns: Option<String>,
+    /// Coding agent executions this project may run at once; the global cap still applies
+    #[ts(type = "number | null")]
+    pub max_concurrent_executions: Option<i64>,
+    /// Regex that picks the app URL (or port) out of the dev server's output; common patterns are tried when unset
+    pub dev_server_url_pattern: Option<String>,
+    /// Interpreter for the setup, cleanup and dev scripts; the platform default when unset
+    pub script_language: Option<ScriptRequestLanguage>,
+    /// Hidden from the default project list; still reachable by id
+    pub archived: bool,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
@@ -46,7 +95,7 @@ pub struct ProjectWithTaskCounts {
     pub inreview_count: i64,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Default, Deserialize, TS)]
 pub struct CreateProject {
     pub name: String,
     pub git_repo_path: String,
@@ -55,6 +104,31 @@ pub struct CreateProject {
     pub dev_script: Option<String>,
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
+    #[serde(default)]
+    pub env_template: HashMap<String, String>,
+    #[serde(default)]
+    pub merge_strategy: MergeStrategy,
+    #[serde(default)]
+    pub auto_push: bool,
+    #[serde(default)]
+    pub abort_on_output_pattern: Option<String>,
+    #[serde(default)]
+    pub default_executor_profile_id: Option<ExecutorProfileId>,
+    #[serde(default)]
+    pub commit_message_template: Option<String>,
+    #[serde(default)]
+    pub branch_template: Option<String>,
+    #[serde(default)]
+    pub mcp_servers: Vec<McpServerDefinition>,
+    #[serde(default)]
+    pub worktree_sparse_patterns: Option<String>,
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub max_concurrent_executions: Option<i64>,
+    #[serde(default)]
+    pub dev_server_url_pattern: Option<String>,
+    #[serde(default)]
+    pub script_language: Option<ScriptRequestLanguage>,
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -65,6 +139,29 @@ pub struct UpdateProject {
     pub dev_script: Option<String>,
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
+    /// Omit to keep the current template
+    #[serde(default)]
+    pub env_template: Option<HashMap<String, String>>,
+    /// Omit to keep the current strategy
+    #[serde(default)]
+    pub merge_strategy: Option<MergeStrategy>,
+    #[serde(default)]
+    pub auto_push: Option<bool>,
+    pub abort_on_output_pattern: Option<String>,
+    pub default_executor_profile_id: Option<ExecutorProfileId>,
+    pub commit_message_template: Option<String>,
+    pub branch_template: Option<String>,
+    /// Omit to keep the current servers
+    #[serde(default)]
+    pub mcp_servers: Option<Vec<McpServerDefinition>>,
+    /// Omit to keep the current patterns, or send `null` to clear them
+    #[serde(default, deserialize_with = "double_option")]
+    #[ts(optional)]
+    pub worktree_sparse_patterns: Option<Option<String>>,
+    #[ts(type = "number | null")]
+    pub max_concurrent_executions: Option<i64>,
+    pub dev_server_url_pattern: Option<String>,
+    pub script_language: Option<ScriptRequestLanguage>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -82,6 +179,12 @@ pub enum SearchMatchType {
 }
 
 impl Project {
+    /// Language the project's scripts run as: its own setting, otherwise the platform
+    /// default (PowerShell on Windows, the user's shell elsewhere).
+    pub fn effective_script_language(&self) -> ScriptRequestLanguage {
+        self.script_language.unwrap_or_default()
+    }
+
     pub async fn count(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
         sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!: i64" FROM projects"#)
             .fetch_one(pool)
@@ -99,6 +202,19 @@ impl Project {
                       cleanup_script,
                       copy_files,
                       remote_project_id as "remote_project_id: Uuid",
+                      env_template as "env_template!: sqlx::types::Json<HashMap<String, String>>",
+                      merge_strategy as "merge_strategy!: MergeStrategy",
+                      auto_push as "auto_push!: bool",
+                      abort_on_output_pattern,
+                      default_executor_profile_id as "default_executor_profile_id?: Json<ExecutorProfileId>",
+                      commit_message_template,
+                      branch_template,
+                      mcp_servers as "mcp_servers!: Json<Vec<McpServerDefinition>>",
+                      worktree_sparse_patterns,
+                      max_concurrent_executions,
+                      dev_server_url_pattern,
+                      script_language as "script_language: ScriptRequestLanguage",
+                      archived as "archived!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -110,6 +226,7 @@ impl Project {
 
     pub async fn find_all_with_task_counts(
         pool: &SqlitePool,
+        include_archived: bool,
     ) -> Result<Vec<ProjectWithTaskCounts>, sqlx::Error> {
         let records = sqlx::query!(
             r#"SELECT
@@ -121,6 +238,19 @@ impl Project {
                 p.cleanup_script,
                 p.copy_files,
                 p.remote_project_id as "remote_project_id: Uuid",
+                p.env_template as "env_template!: sqlx::types::Json<HashMap<String, String>>",
+                p.merge_strategy as "merge_strategy!: MergeStrategy",
+                p.auto_push as "auto_push!: bool",
+                p.abort_on_output_pattern,
+                p.default_executor_profile_id as "default_executor_profile_id?: Json<ExecutorProfileId>",
+                p.commit_message_template,
+                p.branch_template,
+                p.mcp_servers as "mcp_servers!: Json<Vec<McpServerDefinition>>",
+                p.worktree_sparse_patterns,
+                p.max_concurrent_executions,
+                p.dev_server_url_pattern,
+                p.script_language as "script_language: ScriptRequestLanguage",
+                p.archived as "archived!: bool",
                 p.created_at as "created_at!: DateTime<Utc>",
                 p.updated_at as "updated_at!: DateTime<Utc>",
                 COALESCE(SUM(CASE WHEN t.status = 'inprogress' THEN 1 ELSE 0 END), 0) as "inprogress_count!: i64",
@@ -135,8 +265,10 @@ impl Project {
                 FROM task_attempts
                 GROUP BY task_id
             ) ot ON ot.task_id = t.id
+            WHERE $1 OR p.archived = FALSE
             GROUP BY p.id
-            ORDER BY p.created_at DESC"#
+            ORDER BY p.created_at DESC"#,
+            include_archived
         )
         .fetch_all(pool)
         .await?;
@@ -153,6 +285,19 @@ impl Project {
                     cleanup_script: r.cleanup_script,
                     copy_files: r.copy_files,
                     remote_project_id: r.remote_project_id,
+                    env_template: r.env_template,
+                    merge_strategy: r.merge_strategy,
+                    auto_push: r.auto_push,
+                    abort_on_output_pattern: r.abort_on_output_pattern,
+                    default_executor_profile_id: r.default_executor_profile_id,
+                    commit_message_template: r.commit_message_template,
+                    branch_template: r.branch_template,
+                    mcp_servers: r.mcp_servers,
+                    worktree_sparse_patterns: r.worktree_sparse_patterns,
+                    max_concurrent_executions: r.max_concurrent_executions,
+                    dev_server_url_pattern: r.dev_server_url_pattern,
+                    script_language: r.script_language,
+                    archived: r.archived,
                     created_at: r.created_at,
                     updated_at: r.updated_at,
                 },
@@ -166,16 +311,30 @@ impl Project {
             .collect())
     }
 
-    /// Find the most actively used projects based on recent task activity
+    /// Find the most actively used unarchived projects based on recent task activity
     pub async fn find_most_active(pool: &SqlitePool, limit: i32) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
             r#"
             SELECT p.id as "id!: Uuid", p.name, p.git_repo_path, p.setup_script, p.dev_script, p.cleanup_script, p.copy_files, 
                    p.remote_project_id as "remote_project_id: Uuid",
+                   p.env_template as "env_template!: sqlx::types::Json<HashMap<String, String>>",
+                   p.merge_strategy as "merge_strategy!: MergeStrategy",
+                   p.auto_push as "auto_push!: bool",
+                   p.abort_on_output_pattern,
+                   p.default_executor_profile_id as "default_executor_profile_id?: Json<ExecutorProfileId>",
+                   p.commit_message_template,
+                   p.branch_template,
+                   p.mcp_servers as "mcp_servers!: Json<Vec<McpServerDefinition>>",
+                   p.worktree_sparse_patterns,
+                   p.max_concurrent_executions,
+                   p.dev_server_url_pattern,
+                   p.script_language as "script_language: ScriptRequestLanguage",
+                   p.archived as "archived!: bool",
                    p.created_at as "created_at!: DateTime<Utc>", p.updated_at as "updated_at!: DateTime<Utc>"
             FROM projects p
-            WHERE p.id IN (
+            WHERE p.archived = FALSE
+              AND p.id IN (
                 SELECT DISTINCT t.project_id
                 FROM tasks t
                 INNER JOIN task_attempts ta ON ta.task_id = t.id
@@ -200,6 +359,19 @@ impl Project {
                       cleanup_script,
                       copy_files,
                       remote_project_id as "remote_project_id: Uuid",
+                      env_template as "env_template!: sqlx::types::Json<HashMap<String, String>>",
+                      merge_strategy as "merge_strategy!: MergeStrategy",
+                      auto_push as "auto_push!: bool",
+                      abort_on_output_pattern,
+                      default_executor_profile_id as "default_executor_profile_id?: Json<ExecutorProfileId>",
+                      commit_message_template,
+                      branch_template,
+                      mcp_servers as "mcp_servers!: Json<Vec<McpServerDefinition>>",
+                      worktree_sparse_patterns,
+                      max_concurrent_executions,
+                      dev_server_url_pattern,
+                      script_language as "script_language: ScriptRequestLanguage",
+                      archived as "archived!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -224,6 +396,19 @@ impl Project {
                       cleanup_script,
                       copy_files,
                       remote_project_id as "remote_project_id: Uuid",
+                      env_template as "env_template!: sqlx::types::Json<HashMap<String, String>>",
+                      merge_strategy as "merge_strategy!: MergeStrategy",
+                      auto_push as "auto_push!: bool",
+                      abort_on_output_pattern,
+                      default_executor_profile_id as "default_executor_profile_id?: Json<ExecutorProfileId>",
+                      commit_message_template,
+                      branch_template,
+                      mcp_servers as "mcp_servers!: Json<Vec<McpServerDefinition>>",
+                      worktree_sparse_patterns,
+                      max_concurrent_executions,
+                      dev_server_url_pattern,
+                      script_language as "script_language: ScriptRequestLanguage",
+                      archived as "archived!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -249,6 +434,19 @@ impl Project {
                       cleanup_script,
                       copy_files,
                       remote_project_id as "remote_project_id: Uuid",
+                      env_template as "env_template!: sqlx::types::Json<HashMap<String, String>>",
+                      merge_strategy as "merge_strategy!: MergeStrategy",
+                      auto_push as "auto_push!: bool",
+                      abort_on_output_pattern,
+                      default_executor_profile_id as "default_executor_profile_id?: Json<ExecutorProfileId>",
+                      commit_message_template,
+                      branch_template,
+                      mcp_servers as "mcp_servers!: Json<Vec<McpServerDefinition>>",
+                      worktree_sparse_patterns,
+                      max_concurrent_executions,
+                      dev_server_url_pattern,
+                      script_language as "script_language: ScriptRequestLanguage",
+                      archived as "archived!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -274,6 +472,19 @@ impl Project {
                       cleanup_script,
                       copy_files,
                       remote_project_id as "remote_project_id: Uuid",
+                      env_template as "env_template!: sqlx::types::Json<HashMap<String, String>>",
+                      merge_strategy as "merge_strategy!: MergeStrategy",
+                      auto_push as "auto_push!: bool",
+                      abort_on_output_pattern,
+                      default_executor_profile_id as "default_executor_profile_id?: Json<ExecutorProfileId>",
+                      commit_message_template,
+                      branch_template,
+                      mcp_servers as "mcp_servers!: Json<Vec<McpServerDefinition>>",
+                      worktree_sparse_patterns,
+                      max_concurrent_executions,
+                      dev_server_url_pattern,
+                      script_language as "script_language: ScriptRequestLanguage",
+                      archived as "archived!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -285,11 +496,17 @@ impl Project {
         .await
     }
 
-    pub async fn create(
-        pool: &SqlitePool,
+    pub async fn create<'e, E>(
+        executor: E,
         data: &CreateProject,
         project_id: Uuid,
-    ) -> Result<Self, sqlx::Error> {
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let env_template = sqlx::types::Json(&data.env_template);
+        let default_executor_profile_id = data.default_executor_profile_id.clone().map(Json);
+        let mcp_servers = Json(&data.mcp_servers);
         sqlx::query_as!(
             Project,
             r#"INSERT INTO projects (
@@ -299,9 +516,21 @@ impl Project {
                     setup_script,
                     dev_script,
                     cleanup_script,
-                    copy_files
+                    copy_files,
+                    env_template,
+                    merge_strategy,
+                    auto_push,
+                    abort_on_output_pattern,
+                    default_executor_profile_id,
+                    commit_message_template,
+                    branch_template,
+                    mcp_servers,
+                    worktree_sparse_patterns,
+                    max_concurrent_executions,
+                    dev_server_url_pattern,
+                    script_language
                 ) VALUES (
-                    $1, $2, $3, $4, $5, $6, $7
+                    $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19
                 )
                 RETURNING id as "id!: Uuid",
                           name,
@@ -311,6 +540,19 @@ impl Project {
                           cleanup_script,
                           copy_files,
                           remote_project_id as "remote_project_id: Uuid",
+                          env_template as "env_template!: sqlx::types::Json<HashMap<String, String>>",
+                          merge_strategy as "merge_strategy!: MergeStrategy",
+                          auto_push as "auto_push!: bool",
+                          abort_on_output_pattern,
+                          default_executor_profile_id as "default_executor_profile_id?: Json<ExecutorProfileId>",
+                          commit_message_template,
+                          branch_template,
+                          mcp_servers as "mcp_servers!: Json<Vec<McpServerDefinition>>",
+                          worktree_sparse_patterns,
+                          max_concurrent_executions,
+                          dev_server_url_pattern,
+                          script_language as "script_language: ScriptRequestLanguage",
+                          archived as "archived!: bool",
                           created_at as "created_at!: DateTime<Utc>",
                           updated_at as "updated_at!: DateTime<Utc>""#,
             project_id,
@@ -320,8 +562,20 @@ impl Project {
             data.dev_script,
             data.cleanup_script,
             data.copy_files,
+            env_template,
+            data.merge_strategy,
+            data.auto_push,
+            data.abort_on_output_pattern,
+            default_executor_profile_id,
+            data.commit_message_template,
+            data.branch_template,
+            mcp_servers,
+            data.worktree_sparse_patterns,
+            data.max_concurrent_executions,
+            data.dev_server_url_pattern,
+            data.script_language,
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await
     }
 
@@ -335,7 +589,22 @@ impl Project {
         dev_script: Option<String>,
         cleanup_script: Option<String>,
         copy_files: Option<String>,
+        env_template: HashMap<String, String>,
+        merge_strategy: MergeStrategy,
+        auto_push: bool,
+        abort_on_output_pattern: Option<String>,
+        default_executor_profile_id: Option<ExecutorProfileId>,
+        commit_message_template: Option<String>,
+        branch_template: Option<String>,
+        mcp_servers: Vec<McpServerDefinition>,
+        worktree_sparse_patterns: Option<String>,
+        max_concurrent_executions: Option<i64>,
+        dev_server_url_pattern: Option<String>,
+        script_language: Option<ScriptRequestLanguage>,
     ) -> Result<Self, sqlx::Error> {
+        let env_template = sqlx::types::Json(env_template);
+        let default_executor_profile_id = default_executor_profile_id.map(Json);
+        let mcp_servers = Json(mcp_servers);
         sqlx::query_as!(
             Project,
             r#"UPDATE projects
@@ -344,7 +613,19 @@ impl Project {
                    setup_script = $4,
                    dev_script = $5,
                    cleanup_script = $6,
-                   copy_files = $7
+                   copy_files = $7,
+                   env_template = $8,
+                   merge_strategy = $9,
+                   auto_push = $10,
+                   abort_on_output_pattern = $11,
+                   default_executor_profile_id = $12,
+                   commit_message_template = $13,
+                   branch_template = $14,
+                   mcp_servers = $15,
+                   worktree_sparse_patterns = $16,
+                   max_concurrent_executions = $17,
+                   dev_server_url_pattern = $18,
+                   script_language = $19
                WHERE id = $1
                RETURNING id as "id!: Uuid",
                          name,
@@ -354,6 +635,19 @@ impl Project {
                          cleanup_script,
                          copy_files,
                          remote_project_id as "remote_project_id: Uuid",
+                         env_template as "env_template!: sqlx::types::Json<HashMap<String, String>>",
+                         merge_strategy as "merge_strategy!: MergeStrategy",
+                         auto_push as "auto_push!: bool",
+                         abort_on_output_pattern,
+                         default_executor_profile_id as "default_executor_profile_id?: Json<ExecutorProfileId>",
+                         commit_message_template,
+                         branch_template,
+                         mcp_servers as "mcp_servers!: Json<Vec<McpServerDefinition>>",
+                         worktree_sparse_patterns,
+                         max_concurrent_executions,
+                         dev_server_url_pattern,
+                         script_language as "script_language: ScriptRequestLanguage",
+                         archived as "archived!: bool",
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             id,
@@ -363,6 +657,18 @@ impl Project {
             dev_script,
             cleanup_script,
             copy_files,
+            env_template,
+            merge_strategy,
+            auto_push,
+            abort_on_output_pattern,
+            default_executor_profile_id,
+            commit_message_template,
+            branch_template,
+            mcp_servers,
+            worktree_sparse_patterns,
+            max_concurrent_executions,
+            dev_server_url_pattern,
+            script_language,
         )
         .fetch_one(pool)
         .await
@@ -408,6 +714,24 @@ impl Project {
         Ok(())
     }
 
+    pub async fn set_archived(
+        pool: &SqlitePool,
+        id: Uuid,
+        archived: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE projects
+               SET archived = $2
+               WHERE id = $1"#,
+            id,
+            archived
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
         let result = sqlx::query!("DELETE FROM projects WHERE id = $1", id)
             .execute(pool)
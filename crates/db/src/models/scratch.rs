@@ -22,6 +22,16 @@ pub struct DraftFollowUpData {
     pub message: String,
     #[serde(default)]
     pub variant: Option<String>,
+    /// Images attached to the draft
+    #[serde(default)]
+    pub image_ids: Vec<Uuid>,
+}
+
+/// A follow-up waiting for the attempt's current execution to finish
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct QueuedFollowUpData {
+    pub data: DraftFollowUpData,
+    pub queued_at: DateTime<Utc>,
 }
 
 /// The payload of a scratch, tagged by type. The type is part of the composite primary key.
@@ -36,6 +46,7 @@ pub struct DraftFollowUpData {
 pub enum ScratchPayload {
     DraftTask(String),
     DraftFollowUp(DraftFollowUpData),
+    FollowUpQueue(Vec<QueuedFollowUpData>),
 }
 
 impl ScratchPayload {
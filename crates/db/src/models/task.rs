@@ -1,6 +1,8 @@
+use std::collections::{HashMap, HashSet};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
+use sqlx::{Executor, FromRow, QueryBuilder, Sqlite, SqlitePool, Type};
 use strum_macros::{Display, EnumString};
 use ts_rs::TS;
 use uuid::Uuid;
@@ -45,6 +47,9 @@ pub struct TaskWithAttemptStatus {
     pub last_attempt_failed: bool,
     pub executor: String,
     pub latest_task_attempt_id: Option<Uuid>,
+    /// The task's labels, by name
+    #[serde(default)]
+    pub labels: Vec<TaskLabel>,
 }
 
 impl std::ops::Deref for TaskWithAttemptStatus {
@@ -67,6 +72,27 @@ pub struct TaskRelationships {
     pub children: Vec<Task>,          // Tasks created by this attempt
 }
 
+/// Tasks a task is blocked by and tasks it blocks
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskDependencies {
+    pub blocked_by: Vec<Task>,
+    pub blocking: Vec<Task>,
+}
+
+/// Color given to labels created without one
+pub const DEFAULT_LABEL_COLOR: &str = "#6b7280";
+
+/// A project-scoped label that can be attached to any of the project's tasks
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskLabel {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    /// Hex color, e.g. `#ef4444`
+    pub color: String,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize, Deserialize, TS)]
 pub struct CreateTask {
     pub project_id: Uuid,
@@ -181,7 +207,7 @@ impl Task {
      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
      ORDER BY ep.created_at DESC
      LIMIT 1
-  ) IN ('failed','killed') THEN 1 ELSE 0 END
+  ) IN ('failed','killed','timedout') THEN 1 ELSE 0 END
                                  AS "last_attempt_failed!: i64",
 
   ( SELECT ta.executor
@@ -209,6 +235,7 @@ ORDER BY t.created_at DESC"#,
         let orchestrator_task_id = TaskAttempt::find_orchestrator_by_project_id(pool, project_id)
             .await?
             .map(|attempt| attempt.task_id);
+        let mut labels = Self::find_labels_by_project_id(pool, project_id).await?;
 
         let tasks = records
             .into_iter()
@@ -237,6 +264,7 @@ ORDER BY t.created_at DESC"#,
                     last_attempt_failed: rec.last_attempt_failed != 0,
                     executor: rec.executor,
                     latest_task_attempt_id: rec.latest_task_attempt_id,
+                    labels: labels.remove(&rec.id).unwrap_or_default(),
                 })
             })
             .collect();
@@ -256,6 +284,43 @@ ORDER BY t.created_at DESC"#,
         .await
     }
 
+    /// Tasks whose title or description contains `query`, ignoring ASCII case.
+    /// Title prefix matches rank first, then other title matches, then description
+    /// matches; ties go to the most recently updated task.
+    pub async fn search(
+        pool: &SqlitePool,
+        query: &str,
+        project_id: Option<Uuid>,
+        status: Option<TaskStatus>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let escaped = escape_like(query);
+        let contains = format!("%{escaped}%");
+        let prefix = format!("{escaped}%");
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE (title LIKE $1 ESCAPE '\' OR description LIKE $1 ESCAPE '\')
+                 AND ($2 IS NULL OR project_id = $2)
+                 AND ($3 IS NULL OR status = $3)
+               ORDER BY CASE
+                          WHEN title LIKE $4 ESCAPE '\' THEN 0
+                          WHEN title LIKE $1 ESCAPE '\' THEN 1
+                          ELSE 2
+                        END,
+                        updated_at DESC
+               LIMIT $5"#,
+            contains,
+            project_id,
+            status,
+            prefix,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
@@ -304,11 +369,14 @@ ORDER BY t.created_at DESC"#,
         .await
     }
 
-    pub async fn create(
-        pool: &SqlitePool,
+    pub async fn create<'e, E>(
+        executor: E,
         data: &CreateTask,
         task_id: Uuid,
-    ) -> Result<Self, sqlx::Error> {
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
         let status = data.status.clone().unwrap_or_default();
         sqlx::query_as!(
             Task,
@@ -323,7 +391,7 @@ ORDER BY t.created_at DESC"#,
             data.parent_task_attempt,
             data.shared_task_id
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await
     }
 
@@ -553,6 +621,339 @@ ORDER BY t.created_at DESC"#,
         })
     }
 
+    /// Tasks that must be done before this one can start
+    pub async fn find_blockers(pool: &SqlitePool, task_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_task_attempt as "parent_task_attempt: Uuid", t.shared_task_id as "shared_task_id: Uuid", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks t
+               JOIN task_dependencies d ON d.blocked_by_task_id = t.id
+               WHERE d.task_id = $1
+               ORDER BY t.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Tasks blocked by this one
+    pub async fn find_dependents(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_task_attempt as "parent_task_attempt: Uuid", t.shared_task_id as "shared_task_id: Uuid", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks t
+               JOIN task_dependencies d ON d.task_id = t.id
+               WHERE d.blocked_by_task_id = $1
+               ORDER BY t.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_dependencies(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<TaskDependencies, sqlx::Error> {
+        Ok(TaskDependencies {
+            blocked_by: Self::find_blockers(pool, task_id).await?,
+            blocking: Self::find_dependents(pool, task_id).await?,
+        })
+    }
+
+    /// Blockers of this task that aren't done yet
+    pub async fn find_unfinished_blockers(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let blockers = Self::find_blockers(pool, task_id).await?;
+        Ok(blockers
+            .into_iter()
+            .filter(|t| t.status != TaskStatus::Done)
+            .collect())
+    }
+
+    /// Whether `task_id` is blocked by `other_id`, directly or through other blockers
+    pub async fn is_transitively_blocked_by(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        other_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"WITH RECURSIVE blockers(id) AS (
+                   SELECT blocked_by_task_id FROM task_dependencies WHERE task_id = $1
+                   UNION
+                   SELECT d.blocked_by_task_id
+                   FROM task_dependencies d
+                   JOIN blockers b ON d.task_id = b.id
+               )
+               SELECT EXISTS(SELECT 1 FROM blockers WHERE id = $2) as "blocked!: bool""#,
+            task_id,
+            other_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(row.blocked)
+    }
+
+    /// Mark `task_id` as blocked by `blocked_by_task_id`; adding an existing link is a no-op
+    pub async fn add_dependency(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        blocked_by_task_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT OR IGNORE INTO task_dependencies (task_id, blocked_by_task_id) VALUES ($1, $2)",
+            task_id,
+            blocked_by_task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn remove_dependency(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        blocked_by_task_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM task_dependencies WHERE task_id = $1 AND blocked_by_task_id = $2",
+            task_id,
+            blocked_by_task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Labels attached to a task, by name
+    pub async fn find_labels(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<TaskLabel>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskLabel,
+            r#"SELECT l.id as "id!: Uuid", l.project_id as "project_id!: Uuid", l.name, l.color, l.created_at as "created_at!: DateTime<Utc>"
+               FROM labels l
+               JOIN task_labels tl ON tl.label_id = l.id
+               WHERE tl.task_id = $1
+               ORDER BY l.name ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Labels of every labelled task in a project, keyed by task id
+    async fn find_labels_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<HashMap<Uuid, Vec<TaskLabel>>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT tl.task_id as "task_id!: Uuid", l.id as "id!: Uuid", l.project_id as "project_id!: Uuid", l.name, l.color, l.created_at as "created_at!: DateTime<Utc>"
+               FROM task_labels tl
+               JOIN labels l ON l.id = tl.label_id
+               WHERE l.project_id = $1
+               ORDER BY l.name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut labels: HashMap<Uuid, Vec<TaskLabel>> = HashMap::new();
+        for row in rows {
+            labels.entry(row.task_id).or_default().push(TaskLabel {
+                id: row.id,
+                project_id: row.project_id,
+                name: row.name,
+                color: row.color,
+                created_at: row.created_at,
+            });
+        }
+        Ok(labels)
+    }
+
+    /// Every label defined in a project, by name
+    pub async fn find_project_labels(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<TaskLabel>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskLabel,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>"
+               FROM labels
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Attach the project label called `name` to a task, creating the label if the
+    /// project doesn't have it yet. A given `color` also recolors an existing label.
+    pub async fn add_label(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        project_id: Uuid,
+        name: &str,
+        color: Option<&str>,
+    ) -> Result<TaskLabel, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let new_color = color.unwrap_or(DEFAULT_LABEL_COLOR);
+        let label = sqlx::query_as!(
+            TaskLabel,
+            r#"INSERT INTO labels (id, project_id, name, color)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id, name) DO UPDATE SET color = COALESCE($5, color)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            name,
+            new_color,
+            color
+        )
+        .fetch_one(pool)
+        .await?;
+        sqlx::query!(
+            "INSERT OR IGNORE INTO task_labels (task_id, label_id) VALUES ($1, $2)",
+            task_id,
+            label.id
+        )
+        .execute(pool)
+        .await?;
+        Self::touch_labelled_tasks(pool, label.id).await?;
+        Ok(label)
+    }
+
+    pub async fn remove_label(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        label_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM task_labels WHERE task_id = $1 AND label_id = $2",
+            task_id,
+            label_id
+        )
+        .execute(pool)
+        .await?;
+        Self::touch(pool, task_id).await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Rename and/or recolor a project label. Returns `None` if the project has no
+    /// label with this id.
+    pub async fn update_label(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        label_id: Uuid,
+        name: Option<&str>,
+        color: Option<&str>,
+    ) -> Result<Option<TaskLabel>, sqlx::Error> {
+        let label = sqlx::query_as!(
+            TaskLabel,
+            r#"UPDATE labels
+               SET name = COALESCE($3, name), color = COALESCE($4, color)
+               WHERE id = $1 AND project_id = $2
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>""#,
+            label_id,
+            project_id,
+            name,
+            color
+        )
+        .fetch_optional(pool)
+        .await?;
+        if label.is_some() {
+            Self::touch_labelled_tasks(pool, label_id).await?;
+        }
+        Ok(label)
+    }
+
+    /// Delete a project label, detaching it from every task that carries it
+    pub async fn delete_label(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        label_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let task_ids = sqlx::query_scalar!(
+            r#"SELECT task_id as "task_id!: Uuid" FROM task_labels WHERE label_id = $1"#,
+            label_id
+        )
+        .fetch_all(pool)
+        .await?;
+        let result = sqlx::query!(
+            "DELETE FROM labels WHERE id = $1 AND project_id = $2",
+            label_id,
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        if result.rows_affected() > 0 {
+            for task_id in task_ids {
+                Self::touch(pool, task_id).await?;
+            }
+        }
+        Ok(result.rows_affected())
+    }
+
+    /// Bump a task's `updated_at`, so task streams resend it with its current labels
+    async fn touch(pool: &SqlitePool, task_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE tasks SET updated_at = datetime('now', 'subsec') WHERE id = $1",
+            task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Bump `updated_at` on the tasks carrying a label, so task streams resend them
+    /// with their current labels
+    async fn touch_labelled_tasks(pool: &SqlitePool, label_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE tasks SET updated_at = datetime('now', 'subsec')
+             WHERE id IN (SELECT task_id FROM task_labels WHERE label_id = $1)",
+            label_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Ids of the project's tasks that carry every one of `names` (matched ignoring case)
+    pub async fn find_ids_with_labels(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        names: &[String],
+    ) -> Result<HashSet<Uuid>, sqlx::Error> {
+        let names: HashSet<String> = names.iter().map(|n| n.to_ascii_lowercase()).collect();
+        if names.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            "SELECT tl.task_id FROM task_labels tl JOIN labels l ON l.id = tl.label_id WHERE l.project_id = ",
+        );
+        builder.push_bind(project_id);
+        builder.push(" AND l.name IN (");
+        {
+            let mut separated = builder.separated(", ");
+            for name in &names {
+                separated.push_bind(name);
+            }
+        }
+        builder.push(") GROUP BY tl.task_id HAVING COUNT(DISTINCT l.id) = ");
+        builder.push_bind(names.len() as i64);
+
+        let ids = builder.build_query_scalar::<Uuid>().fetch_all(pool).await?;
+        Ok(ids.into_iter().collect())
+    }
+
     /// Get or create the orchestrator task for a project
     /// Returns the existing orchestrator task or creates a new one
     pub async fn get_or_create_orchestrator(
@@ -584,3 +985,413 @@ ORDER BY t.created_at DESC"#,
         Self::create(pool, &create_data, task_id).await
     }
 }
+
+/// Escape `%`, `_` and the escape character itself so `value` matches literally
+/// in a `LIKE ... ESCAPE '\'` pattern
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DBService, models::project::CreateProject};
+
+    async fn create_project(pool: &SqlitePool, name: &str) -> Project {
+        Project::create(
+            pool,
+            &CreateProject {
+                name: name.to_string(),
+                git_repo_path: format!("/tmp/{name}-repo"),
+                ..Default::default()
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn create_task(pool: &SqlitePool, project_id: Uuid, title: &str) -> Task {
+        Task::create(
+            pool,
+            &CreateTask::from_title_description(project_id, title.to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap()
+    }
+
+    fn names(labels: &[TaskLabel]) -> Vec<&str> {
+        labels.iter().map(|label| label.name.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_add_label_reuses_and_recolors_project_labels() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let project = create_project(pool, "labels").await;
+        let first = create_task(pool, project.id, "First").await;
+        let second = create_task(pool, project.id, "Second").await;
+
+        let bug = Task::add_label(pool, first.id, project.id, "bug", None)
+            .await
+            .unwrap();
+        assert_eq!(bug.color, DEFAULT_LABEL_COLOR);
+
+        // Same name ignoring case: the label is reused, and a color recolors it
+        let reused = Task::add_label(pool, second.id, project.id, "BUG", Some("#ef4444"))
+            .await
+            .unwrap();
+        assert_eq!(reused.id, bug.id);
+        assert_eq!(reused.name, "bug");
+        assert_eq!(reused.color, "#ef4444");
+
+        // Adding without a color keeps the current one, and adding twice is a no-op
+        let again = Task::add_label(pool, first.id, project.id, "bug", None)
+            .await
+            .unwrap();
+        assert_eq!(again.color, "#ef4444");
+        Task::add_label(pool, first.id, project.id, "frontend", None)
+            .await
+            .unwrap();
+        assert_eq!(
+            names(&Task::find_labels(pool, first.id).await.unwrap()),
+            ["bug", "frontend"]
+        );
+        assert_eq!(
+            names(&Task::find_project_labels(pool, project.id).await.unwrap()),
+            ["bug", "frontend"]
+        );
+
+        // Labels are scoped to their project
+        let other = create_project(pool, "other").await;
+        let other_task = create_task(pool, other.id, "Elsewhere").await;
+        let other_bug = Task::add_label(pool, other_task.id, other.id, "bug", None)
+            .await
+            .unwrap();
+        assert_ne!(other_bug.id, bug.id);
+
+        assert_eq!(Task::remove_label(pool, first.id, bug.id).await.unwrap(), 1);
+        assert_eq!(
+            names(&Task::find_labels(pool, first.id).await.unwrap()),
+            ["frontend"]
+        );
+        assert_eq!(Task::remove_label(pool, first.id, bug.id).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_find_ids_with_labels_requires_every_label() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let project = create_project(pool, "filter").await;
+        let both = create_task(pool, project.id, "Both").await;
+        let bug_only = create_task(pool, project.id, "Bug only").await;
+        create_task(pool, project.id, "Unlabelled").await;
+        for (task, name) in [(&both, "Bug"), (&both, "frontend"), (&bug_only, "bug")] {
+            Task::add_label(pool, task.id, project.id, name, None)
+                .await
+                .unwrap();
+        }
+
+        let find = |names: &[&str]| {
+            let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+            async move {
+                Task::find_ids_with_labels(pool, project.id, &names)
+                    .await
+                    .unwrap()
+            }
+        };
+        assert_eq!(find(&["bug"]).await, HashSet::from([both.id, bug_only.id]));
+        assert_eq!(find(&["BUG", "Frontend"]).await, HashSet::from([both.id]));
+        // Repeating a name doesn't raise the number of labels required
+        assert_eq!(find(&["bug", "Bug"]).await.len(), 2);
+        assert!(find(&["bug", "missing"]).await.is_empty());
+        assert!(find(&[]).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_task_list_carries_labels() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let project = create_project(pool, "board").await;
+        let labelled = create_task(pool, project.id, "Labelled").await;
+        let plain = create_task(pool, project.id, "Plain").await;
+        Task::add_label(pool, labelled.id, project.id, "ui", None)
+            .await
+            .unwrap();
+        Task::add_label(pool, labelled.id, project.id, "api", None)
+            .await
+            .unwrap();
+
+        let tasks = Task::find_by_project_id_with_attempt_status(pool, project.id)
+            .await
+            .unwrap();
+        let labels_of = |id: Uuid| {
+            names(&tasks.iter().find(|task| task.id == id).unwrap().labels)
+                .into_iter()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels_of(labelled.id), ["api", "ui"]);
+        assert!(labels_of(plain.id).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_project_labels_can_be_renamed_and_deleted() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let project = create_project(pool, "manage").await;
+        let task = create_task(pool, project.id, "Task").await;
+        let label = Task::add_label(pool, task.id, project.id, "bgu", None)
+            .await
+            .unwrap();
+        Task::add_label(pool, task.id, project.id, "ui", None)
+            .await
+            .unwrap();
+
+        let renamed = Task::update_label(pool, project.id, label.id, Some("bug"), None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(renamed.name, "bug");
+        assert_eq!(renamed.color, DEFAULT_LABEL_COLOR);
+        let recolored = Task::update_label(pool, project.id, label.id, None, Some("#22c55e"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(recolored.name, "bug");
+        assert_eq!(recolored.color, "#22c55e");
+
+        // Names stay unique within the project, ignoring case
+        let err = Task::update_label(pool, project.id, label.id, Some("UI"), None)
+            .await
+            .unwrap_err();
+        assert!(
+            err.as_database_error()
+                .is_some_and(|e| e.is_unique_violation())
+        );
+
+        // Another project's id doesn't reach the label
+        let other = create_project(pool, "other").await;
+        assert!(
+            Task::update_label(pool, other.id, label.id, Some("x"), None)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(
+            Task::delete_label(pool, other.id, label.id).await.unwrap(),
+            0
+        );
+
+        assert_eq!(
+            Task::delete_label(pool, project.id, label.id)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            names(&Task::find_labels(pool, task.id).await.unwrap()),
+            ["ui"]
+        );
+        assert_eq!(
+            names(&Task::find_project_labels(pool, project.id).await.unwrap()),
+            ["ui"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_is_transitively_blocked_by_follows_chains() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let project = create_project(pool, "chains").await;
+        let a = create_task(pool, project.id, "A").await;
+        let b = create_task(pool, project.id, "B").await;
+        let c = create_task(pool, project.id, "C").await;
+        let unrelated = create_task(pool, project.id, "Unrelated").await;
+
+        // A waits on B, which waits on C
+        Task::add_dependency(pool, a.id, b.id).await.unwrap();
+        Task::add_dependency(pool, b.id, c.id).await.unwrap();
+        assert!(
+            Task::is_transitively_blocked_by(pool, a.id, b.id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            Task::is_transitively_blocked_by(pool, a.id, c.id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !Task::is_transitively_blocked_by(pool, c.id, a.id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !Task::is_transitively_blocked_by(pool, a.id, unrelated.id)
+                .await
+                .unwrap()
+        );
+
+        // A cycle that slipped into the table still terminates
+        Task::add_dependency(pool, c.id, a.id).await.unwrap();
+        assert!(
+            Task::is_transitively_blocked_by(pool, a.id, a.id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            Task::is_transitively_blocked_by(pool, c.id, b.id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !Task::is_transitively_blocked_by(pool, a.id, unrelated.id)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_unfinished_blockers_skips_done_tasks() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let project = create_project(pool, "blockers").await;
+        let task = create_task(pool, project.id, "Ship").await;
+        let review = create_task(pool, project.id, "Review").await;
+        let qa = create_task(pool, project.id, "QA").await;
+        Task::add_dependency(pool, task.id, review.id)
+            .await
+            .unwrap();
+        Task::add_dependency(pool, task.id, qa.id).await.unwrap();
+        // Adding the same link again is a no-op
+        Task::add_dependency(pool, task.id, qa.id).await.unwrap();
+
+        let titles = |tasks: Vec<Task>| {
+            let mut titles = tasks.into_iter().map(|t| t.title).collect::<Vec<_>>();
+            titles.sort();
+            titles
+        };
+        assert_eq!(
+            titles(Task::find_unfinished_blockers(pool, task.id).await.unwrap()),
+            ["QA", "Review"]
+        );
+
+        Task::update_status(pool, review.id, TaskStatus::Done)
+            .await
+            .unwrap();
+        assert_eq!(
+            titles(Task::find_unfinished_blockers(pool, task.id).await.unwrap()),
+            ["QA"]
+        );
+        let dependencies = Task::find_dependencies(pool, qa.id).await.unwrap();
+        assert!(dependencies.blocked_by.is_empty());
+        assert_eq!(titles(dependencies.blocking), ["Ship"]);
+    }
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like("plain"), "plain");
+        assert_eq!(escape_like("100%_done"), "100\\%\\_done");
+        assert_eq!(escape_like("C:\\temp"), "C:\\\\temp");
+    }
+
+    #[tokio::test]
+    async fn test_search_ranks_title_prefix_then_title_then_description() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let project = create_project(pool, "search").await;
+        let in_description = Task::create(
+            pool,
+            &CreateTask::from_title_description(
+                project.id,
+                "Refactor session store".to_string(),
+                Some("Needed before the login rework".to_string()),
+            ),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let in_title = create_task(pool, project.id, "Fix login redirect").await;
+        let prefix = create_task(pool, project.id, "Login page").await;
+        create_task(pool, project.id, "Unrelated").await;
+
+        let ids = |tasks: Vec<Task>| tasks.into_iter().map(|t| t.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(Task::search(pool, "LOGIN", None, None, 10).await.unwrap()),
+            [prefix.id, in_title.id, in_description.id]
+        );
+        assert_eq!(
+            ids(Task::search(pool, "login", None, None, 2).await.unwrap()),
+            [prefix.id, in_title.id]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_treats_like_wildcards_literally() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let project = create_project(pool, "wildcards").await;
+        let percent = create_task(pool, project.id, "Reach 100% coverage").await;
+        create_task(pool, project.id, "Reach 1000 users").await;
+        let underscore = create_task(pool, project.id, "Rename snake_case fields").await;
+        create_task(pool, project.id, "Rename snakeXcase fields").await;
+        let backslash = create_task(pool, project.id, "Fix C:\\temp paths").await;
+        create_task(pool, project.id, "Fix C:temp paths").await;
+
+        for (query, expected) in [
+            ("100%", percent.id),
+            ("snake_case", underscore.id),
+            ("C:\\temp", backslash.id),
+        ] {
+            let found = Task::search(pool, query, None, None, 10).await.unwrap();
+            assert_eq!(
+                found.iter().map(|t| t.id).collect::<Vec<_>>(),
+                [expected],
+                "query {query:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_scopes_by_project_and_status() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let project = create_project(pool, "scoped").await;
+        let other_project = create_project(pool, "other").await;
+        let todo = create_task(pool, project.id, "Deploy staging").await;
+        let done = create_task(pool, project.id, "Deploy production").await;
+        Task::update_status(pool, done.id, TaskStatus::Done)
+            .await
+            .unwrap();
+        let elsewhere = create_task(pool, other_project.id, "Deploy docs").await;
+
+        let mut everywhere = Task::search(pool, "deploy", None, None, 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.id)
+            .collect::<Vec<_>>();
+        everywhere.sort();
+        let mut expected = vec![todo.id, done.id, elsewhere.id];
+        expected.sort();
+        assert_eq!(everywhere, expected);
+
+        let in_project = Task::search(pool, "deploy", Some(other_project.id), None, 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            in_project.iter().map(|t| t.id).collect::<Vec<_>>(),
+            [elsewhere.id]
+        );
+
+        let finished = Task::search(pool, "deploy", Some(project.id), Some(TaskStatus::Done), 10)
+            .await
+            .unwrap();
+        assert_eq!(finished.iter().map(|t| t.id).collect::<Vec<_>>(), [done.id]);
+    }
+}
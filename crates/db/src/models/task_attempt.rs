@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
-use executors::executors::BaseCodingAgent;
+use executors::{env::EnvOverrides, executors::BaseCodingAgent};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool, Type};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type, types::Json};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
@@ -46,6 +46,14 @@ pub struct TaskAttempt {
     pub worktree_deleted: bool, // Flag indicating if worktree has been cleaned up
     pub setup_completed_at: Option<DateTime<Utc>>, // When setup script was last completed
     pub is_orchestrator: bool,  // Flag indicating this is a global orchestrator session
+    /// Cleanup script run for this attempt instead of the project's
+    pub cleanup_script_override: Option<String>,
+    /// Environment variables set on every process of this attempt, over the project's
+    #[ts(type = "EnvOverrides | null")]
+    pub env_overrides: Option<Json<EnvOverrides>>,
+    /// Keep the worktree past expiry cleanup. Pinned worktrees still take up
+    /// disk space; removing them is up to the user.
+    pub pin_worktree: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -111,6 +119,9 @@ impl TaskAttempt {
                               worktree_deleted AS "worktree_deleted!: bool",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                               is_orchestrator AS "is_orchestrator!: bool",
+                              cleanup_script_override,
+                              env_overrides AS "env_overrides?: Json<EnvOverrides>",
+                              pin_worktree AS "pin_worktree!: bool",
                               created_at AS "created_at!: DateTime<Utc>",
                               updated_at AS "updated_at!: DateTime<Utc>"
                        FROM task_attempts
@@ -132,6 +143,9 @@ impl TaskAttempt {
                               worktree_deleted AS "worktree_deleted!: bool",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                               is_orchestrator AS "is_orchestrator!: bool",
+                              cleanup_script_override,
+                              env_overrides AS "env_overrides?: Json<EnvOverrides>",
+                              pin_worktree AS "pin_worktree!: bool",
                               created_at AS "created_at!: DateTime<Utc>",
                               updated_at AS "updated_at!: DateTime<Utc>"
                        FROM task_attempts
@@ -164,6 +178,9 @@ impl TaskAttempt {
                        ta.worktree_deleted  AS "worktree_deleted!: bool",
                        ta.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                        ta.is_orchestrator   AS "is_orchestrator!: bool",
+                       ta.cleanup_script_override,
+                       ta.env_overrides     AS "env_overrides?: Json<EnvOverrides>",
+                       ta.pin_worktree      AS "pin_worktree!: bool",
                        ta.created_at        AS "created_at!: DateTime<Utc>",
                        ta.updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts ta
@@ -215,12 +232,29 @@ impl TaskAttempt {
     }
 
     /// Helper function to mark a worktree as deleted in the database
-    pub async fn mark_worktree_deleted(
+    pub async fn mark_worktree_deleted<'e, E>(
+        executor: E,
+        attempt_id: Uuid,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query!(
+            "UPDATE task_attempts SET worktree_deleted = TRUE, updated_at = datetime('now') WHERE id = ?",
+            attempt_id
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Clear the deleted flag once the worktree has been recreated
+    pub async fn mark_worktree_restored(
         pool: &SqlitePool,
         attempt_id: Uuid,
     ) -> Result<(), sqlx::Error> {
         sqlx::query!(
-            "UPDATE task_attempts SET worktree_deleted = TRUE, updated_at = datetime('now') WHERE id = ?",
+            "UPDATE task_attempts SET worktree_deleted = FALSE, updated_at = datetime('now') WHERE id = ?",
             attempt_id
         )
         .execute(pool)
@@ -240,6 +274,9 @@ impl TaskAttempt {
                        worktree_deleted  AS "worktree_deleted!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                        is_orchestrator   AS "is_orchestrator!: bool",
+                       cleanup_script_override,
+                       env_overrides     AS "env_overrides?: Json<EnvOverrides>",
+                       pin_worktree      AS "pin_worktree!: bool",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts
@@ -262,6 +299,9 @@ impl TaskAttempt {
                        worktree_deleted  AS "worktree_deleted!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                        is_orchestrator   AS "is_orchestrator!: bool",
+                       cleanup_script_override,
+                       env_overrides     AS "env_overrides?: Json<EnvOverrides>",
+                       pin_worktree      AS "pin_worktree!: bool",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts
@@ -309,6 +349,26 @@ impl TaskAttempt {
             .collect())
     }
 
+    /// `(attempt_id, container_ref)` of a project's attempts whose worktree hasn't been deleted
+    pub async fn find_worktrees_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<(Uuid, String)>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"SELECT ta.id as "id!: Uuid", ta.container_ref
+               FROM task_attempts ta
+               JOIN tasks t ON ta.task_id = t.id
+               WHERE t.project_id = $1 AND ta.worktree_deleted = FALSE"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(records
+            .into_iter()
+            .filter_map(|r| r.container_ref.map(|path| (r.id, path)))
+            .collect())
+    }
+
     pub async fn container_ref_exists(
         pool: &SqlitePool,
         container_ref: &str,
@@ -340,6 +400,8 @@ impl TaskAttempt {
             JOIN tasks t ON ta.task_id = t.id
             JOIN projects p ON t.project_id = p.id
             WHERE ta.worktree_deleted = FALSE
+                AND ta.pin_worktree = FALSE
+                AND p.archived = FALSE
                 -- Exclude attempts with any running processes (in progress)
                 AND ta.id NOT IN (
                     SELECT DISTINCT ep2.task_attempt_id
@@ -375,19 +437,22 @@ impl TaskAttempt {
             .collect())
     }
 
-    pub async fn create(
-        pool: &SqlitePool,
+    pub async fn create<'e, E>(
+        executor: E,
         data: &CreateTaskAttempt,
         id: Uuid,
         task_id: Uuid,
-    ) -> Result<Self, TaskAttemptError> {
+    ) -> Result<Self, TaskAttemptError>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
         // let prefixed_id = format!("vibe-kanban-{}", attempt_id);
         // Insert the record into the database
         Ok(sqlx::query_as!(
             TaskAttempt,
             r#"INSERT INTO task_attempts (id, task_id, container_ref, branch, target_branch, executor, worktree_deleted, setup_completed_at, is_orchestrator)
                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, target_branch, executor as "executor!",  worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", is_orchestrator as "is_orchestrator!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, target_branch, executor as "executor!",  worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", is_orchestrator as "is_orchestrator!: bool", cleanup_script_override, env_overrides as "env_overrides?: Json<EnvOverrides>", pin_worktree as "pin_worktree!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             task_id,
             Option::<String>::None, // Container isn't known yet
@@ -398,7 +463,7 @@ impl TaskAttempt {
             Option::<DateTime<Utc>>::None, // setup_completed_at is None during creation
             data.is_orchestrator
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?)
     }
 
@@ -418,6 +483,94 @@ impl TaskAttempt {
         Ok(())
     }
 
+    /// Set or clear (`None`) the cleanup script used for this attempt instead of the project's.
+    pub async fn set_cleanup_script_override(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        script: Option<&str>,
+    ) -> Result<(), TaskAttemptError> {
+        sqlx::query!(
+            "UPDATE task_attempts SET cleanup_script_override = $1, updated_at = datetime('now') WHERE id = $2",
+            script,
+            attempt_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Set or clear (`None`) the environment variables applied to this attempt's processes.
+    pub async fn set_env_overrides(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        env_overrides: Option<&EnvOverrides>,
+    ) -> Result<(), TaskAttemptError> {
+        let env_overrides = env_overrides.map(Json);
+        sqlx::query!(
+            "UPDATE task_attempts SET env_overrides = $1, updated_at = datetime('now') WHERE id = $2",
+            env_overrides,
+            attempt_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Pin or unpin the attempt's worktree, exempting it from expiry cleanup.
+    pub async fn set_pin_worktree(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        pinned: bool,
+    ) -> Result<(), TaskAttemptError> {
+        sqlx::query!(
+            "UPDATE task_attempts SET pin_worktree = $1, updated_at = datetime('now') WHERE id = $2",
+            pinned,
+            attempt_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Cleanup script to run for this attempt: its override if set, otherwise the project's.
+    pub fn effective_cleanup_script(&self, project_script: Option<String>) -> Option<String> {
+        self.cleanup_script_override.clone().or(project_script)
+    }
+
+    /// Commit the user last reviewed the diff at, if a marker has been set.
+    pub async fn find_last_viewed_oid(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+    ) -> Result<Option<String>, TaskAttemptError> {
+        let row = sqlx::query!(
+            "SELECT last_viewed_oid FROM task_attempts WHERE id = $1",
+            attempt_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.and_then(|r| r.last_viewed_oid))
+    }
+
+    pub async fn update_last_viewed_oid(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        oid: &str,
+    ) -> Result<(), TaskAttemptError> {
+        sqlx::query!(
+            "UPDATE task_attempts SET last_viewed_oid = $1, updated_at = datetime('now') WHERE id = $2",
+            oid,
+            attempt_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn update_branch_name(
         pool: &SqlitePool,
         attempt_id: Uuid,
@@ -503,6 +656,9 @@ impl TaskAttempt {
                        ta.worktree_deleted  AS "worktree_deleted!: bool",
                        ta.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                        ta.is_orchestrator   AS "is_orchestrator!: bool",
+                       ta.cleanup_script_override,
+                       ta.env_overrides     AS "env_overrides?: Json<EnvOverrides>",
+                       ta.pin_worktree      AS "pin_worktree!: bool",
                        ta.created_at        AS "created_at!: DateTime<Utc>",
                        ta.updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts ta
@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use thiserror::Error;
+use ts_rs::TS;
+use utils::text::render_placeholders;
+use uuid::Uuid;
+
+use super::{double_option, task::CreateTask};
+
+#[derive(Debug, Error)]
+pub enum TaskTemplateError {
+    #[error("Missing values for placeholders: {}", .0.join(", "))]
+    MissingValues(Vec<String>),
+    #[error("Task template belongs to a different project")]
+    ProjectMismatch,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskTemplate {
+    pub id: Uuid,
+    /// Project the template is scoped to; `None` for templates usable in any project
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    /// Title with `{placeholder}`s (or `{{placeholder}}`s) filled in on instantiation
+    pub title: String,
+    pub description: Option<String>,
+    #[ts(type = "ExecutorProfileId | null")]
+    pub default_executor_profile_id: Option<Json<ExecutorProfileId>>,
+    /// Base branch for attempts started from the instantiated task
+    pub default_base_branch: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateTaskTemplate {
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub default_executor_profile_id: Option<ExecutorProfileId>,
+    #[serde(default)]
+    pub default_base_branch: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateTaskTemplate {
+    pub name: Option<String>,
+    pub title: Option<String>,
+    /// Omit to keep the current description, or send `null` to clear it
+    #[serde(default, deserialize_with = "double_option")]
+    #[ts(optional)]
+    pub description: Option<Option<String>>,
+    /// Omit to keep the current profile, or send `null` to clear it
+    #[serde(default, deserialize_with = "double_option")]
+    #[ts(optional)]
+    pub default_executor_profile_id: Option<Option<ExecutorProfileId>>,
+    /// Omit to keep the current base branch, or send `null` to clear it
+    #[serde(default, deserialize_with = "double_option")]
+    #[ts(optional)]
+    pub default_base_branch: Option<Option<String>>,
+}
+
+impl TaskTemplate {
+    pub async fn find_all(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        // With a project filter, global templates are included alongside the project's own
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id?: Uuid", name, title, description, default_executor_profile_id as "default_executor_profile_id?: Json<ExecutorProfileId>", default_base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_templates
+               WHERE $1 IS NULL OR project_id IS NULL OR project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id?: Uuid", name, title, description, default_executor_profile_id as "default_executor_profile_id?: Json<ExecutorProfileId>", default_base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_templates
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, data: &CreateTaskTemplate) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let profile = data.default_executor_profile_id.clone().map(Json);
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"INSERT INTO task_templates (id, project_id, name, title, description, default_executor_profile_id, default_base_branch)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid", project_id as "project_id?: Uuid", name, title, description, default_executor_profile_id as "default_executor_profile_id?: Json<ExecutorProfileId>", default_base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.name,
+            data.title,
+            data.description,
+            profile,
+            data.default_base_branch
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateTaskTemplate,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let title = data.title.as_ref().unwrap_or(&existing.title);
+        let description = match &data.description {
+            Some(description) => description.clone(),
+            None => existing.description,
+        };
+        let profile = match &data.default_executor_profile_id {
+            Some(profile) => profile.clone().map(Json),
+            None => existing.default_executor_profile_id,
+        };
+        let base_branch = match &data.default_base_branch {
+            Some(branch) => branch.clone(),
+            None => existing.default_base_branch,
+        };
+
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"UPDATE task_templates
+               SET name = $2, title = $3, description = $4, default_executor_profile_id = $5, default_base_branch = $6, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id?: Uuid", name, title, description, default_executor_profile_id as "default_executor_profile_id?: Json<ExecutorProfileId>", default_base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            title,
+            description,
+            profile,
+            base_branch
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_templates WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Render the template into a task for `project_id`, returning it together with
+    /// the template's default executor profile.
+    pub fn instantiate(
+        &self,
+        project_id: Uuid,
+        values: &HashMap<String, String>,
+    ) -> Result<(CreateTask, Option<ExecutorProfileId>), TaskTemplateError> {
+        if self.project_id.is_some_and(|id| id != project_id) {
+            return Err(TaskTemplateError::ProjectMismatch);
+        }
+
+        let mut missing = Vec::new();
+        let title = render_placeholders(&self.title, values).unwrap_or_else(|names| {
+            missing.extend(names);
+            String::new()
+        });
+        let description = self.description.as_deref().map(|description| {
+            render_placeholders(description, values).unwrap_or_else(|names| {
+                for name in names {
+                    if !missing.contains(&name) {
+                        missing.push(name);
+                    }
+                }
+                String::new()
+            })
+        });
+        if !missing.is_empty() {
+            return Err(TaskTemplateError::MissingValues(missing));
+        }
+
+        Ok((
+            CreateTask::from_title_description(project_id, title, description),
+            self.default_executor_profile_id
+                .as_ref()
+                .map(|profile| profile.0.clone()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+
+    fn template(project_id: Option<Uuid>) -> TaskTemplate {
+        TaskTemplate {
+            id: Uuid::new_v4(),
+            project_id,
+            name: "bug-report".to_string(),
+            title: "Fix {component} crash".to_string(),
+            description: Some("Reported by {reporter}: {component} crashes on start".to_string()),
+            default_executor_profile_id: Some(Json(ExecutorProfileId::with_variant(
+                BaseCodingAgent::ClaudeCode,
+                "PLAN".to_string(),
+            ))),
+            default_base_branch: Some("develop".to_string()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_instantiate_renders_placeholders_and_profile() {
+        let project_id = Uuid::new_v4();
+        let values = HashMap::from([
+            ("component".to_string(), "sidebar".to_string()),
+            ("reporter".to_string(), "QA".to_string()),
+        ]);
+
+        let (task, profile) = template(Some(project_id))
+            .instantiate(project_id, &values)
+            .unwrap();
+        assert_eq!(task.project_id, project_id);
+        assert_eq!(task.title, "Fix sidebar crash");
+        assert_eq!(
+            task.description.as_deref(),
+            Some("Reported by QA: sidebar crashes on start")
+        );
+        assert_eq!(
+            profile,
+            Some(ExecutorProfileId::with_variant(
+                BaseCodingAgent::ClaudeCode,
+                "PLAN".to_string()
+            ))
+        );
+
+        let partial = HashMap::from([("component".to_string(), "sidebar".to_string())]);
+        assert!(matches!(
+            template(None).instantiate(project_id, &partial),
+            Err(TaskTemplateError::MissingValues(names)) if names == vec!["reporter".to_string()]
+        ));
+        assert!(matches!(
+            template(Some(Uuid::new_v4())).instantiate(project_id, &values),
+            Err(TaskTemplateError::ProjectMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_update_tells_omitted_fields_from_cleared() {
+        let parse = |json: &str| serde_json::from_str::<UpdateTaskTemplate>(json).unwrap();
+        let omitted = parse("{}");
+        assert_eq!(omitted.description, None);
+        assert_eq!(omitted.default_executor_profile_id, None);
+        assert_eq!(omitted.default_base_branch, None);
+
+        let cleared = parse(
+            r#"{"description": null, "default_executor_profile_id": null, "default_base_branch": null}"#,
+        );
+        assert_eq!(cleared.description, Some(None));
+        assert_eq!(cleared.default_executor_profile_id, Some(None));
+        assert_eq!(cleared.default_base_branch, Some(None));
+
+        assert_eq!(
+            parse(r#"{"default_base_branch": "main"}"#).default_base_branch,
+            Some(Some("main".to_string()))
+        );
+        assert_eq!(
+            parse(r#"{"description": "Steps"}"#).description,
+            Some(Some("Steps".to_string()))
+        );
+    }
+}
@@ -3,7 +3,13 @@ use std::sync::Arc;
 use anyhow::Error as AnyhowError;
 use async_trait::async_trait;
 use axum::response::sse::Event;
-use db::{DBService, models::task_attempt::TaskAttemptError};
+use db::{
+    DBService,
+    models::{
+        activity::{Activity, CreateActivity},
+        task_attempt::TaskAttemptError,
+    },
+};
 use executors::executors::ExecutorError;
 use futures::{StreamExt, TryStreamExt};
 use git2::Error as Git2Error;
@@ -23,6 +29,7 @@ use services::services::{
     pr_monitor::{PrMonitorHandle, PrMonitorService},
     queued_message::QueuedMessageService,
     share::{RemoteSync, RemoteSyncHandle, ShareConfig, SharePublisher},
+    task_registry::TaskRegistry,
     worktree_manager::WorktreeError,
 };
 use sqlx::Error as SqlxError;
@@ -96,6 +103,8 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn queued_message_service(&self) -> &QueuedMessageService;
 
+    fn task_registry(&self) -> &TaskRegistry;
+
     fn auth_context(&self) -> &AuthContext;
 
     fn share_publisher(&self) -> Result<SharePublisher, RemoteClientNotConfigured>;
@@ -105,19 +114,20 @@ pub trait Deployment: Clone + Send + Sync + 'static {
     fn spawn_remote_sync(&self, config: ShareConfig) {
         let deployment = self.clone();
         let handle_slot = self.share_sync_handle().clone();
-        tokio::spawn(async move {
-            tracing::info!("Starting shared task sync");
-
-            let remote_sync_handle = RemoteSync::spawn(
-                deployment.db().clone(),
-                config,
-                deployment.auth_context().clone(),
-            );
-            {
-                let mut guard = handle_slot.lock().await;
-                *guard = Some(remote_sync_handle);
-            }
-        });
+        self.task_registry()
+            .spawn("remote_sync_start", false, async move {
+                tracing::info!("Starting shared task sync");
+
+                let remote_sync_handle = RemoteSync::spawn(
+                    deployment.db().clone(),
+                    config,
+                    deployment.auth_context().clone(),
+                );
+                {
+                    let mut guard = handle_slot.lock().await;
+                    *guard = Some(remote_sync_handle);
+                }
+            });
     }
 
     async fn update_sentry_scope(&self) -> Result<(), DeploymentError> {
@@ -140,7 +150,14 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                 analytics_service: analytics_service.clone(),
             });
         let publisher = self.share_publisher().ok();
-        PrMonitorService::spawn(db, analytics, publisher).await
+        PrMonitorService::spawn(
+            db,
+            self.events().clone(),
+            analytics,
+            publisher,
+            self.task_registry(),
+        )
+        .await
     }
 
     async fn track_if_analytics_allowed(&self, event_name: &str, properties: Value) {
@@ -151,6 +168,14 @@ pub trait Deployment: Clone + Send + Sync + 'static {
         }
     }
 
+    /// Add an entry to the project's activity feed. Local-only, so recorded
+    /// regardless of the analytics setting; failures are logged, not returned.
+    async fn record_activity(&self, activity: CreateActivity) {
+        if let Err(e) = Activity::create(&self.db().pool, &activity, self.user_id()).await {
+            tracing::warn!("Failed to record {:?} activity: {}", activity.kind, e);
+        }
+    }
+
     async fn stream_events(
         &self,
     ) -> futures::stream::BoxStream<'static, Result<Event, std::io::Error>> {
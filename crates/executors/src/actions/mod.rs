@@ -11,7 +11,9 @@ use crate::{
         coding_agent_initial::CodingAgentInitialRequest, script::ScriptRequest,
     },
     approvals::ExecutorApprovalService,
+    env::ExecutionEnv,
     executors::{BaseCodingAgent, ExecutorError, SpawnedChild},
+    profile::ExecutorProfileId,
 };
 pub mod coding_agent_follow_up;
 pub mod coding_agent_initial;
@@ -26,6 +28,31 @@ pub enum ExecutorActionType {
     ScriptRequest,
 }
 
+impl ExecutorActionType {
+    /// A coding agent request that resumes `session_id` when the attempt has one,
+    /// or starts a fresh session otherwise.
+    pub fn coding_agent_request(
+        prompt: String,
+        session_id: Option<String>,
+        executor_profile_id: ExecutorProfileId,
+        is_orchestrator: bool,
+    ) -> Self {
+        match session_id {
+            Some(session_id) => Self::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt,
+                session_id,
+                executor_profile_id,
+                is_orchestrator,
+            }),
+            None => Self::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt,
+                executor_profile_id,
+                is_orchestrator,
+            }),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct ExecutorAction {
     pub typ: ExecutorActionType,
@@ -71,6 +98,7 @@ pub trait Executable {
         &self,
         current_dir: &Path,
         approvals: Arc<dyn ExecutorApprovalService>,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError>;
 }
 
@@ -80,7 +108,8 @@ impl Executable for ExecutorAction {
         &self,
         current_dir: &Path,
         approvals: Arc<dyn ExecutorApprovalService>,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
-        self.typ.spawn(current_dir, approvals).await
+        self.typ.spawn(current_dir, approvals, env).await
     }
 }
@@ -3,6 +3,7 @@ use std::{path::Path, sync::Arc};
 use async_trait::async_trait;
 use command_group::AsyncCommandGroup;
 use serde::{Deserialize, Serialize};
+use sqlx::Type;
 use tokio::process::Command;
 use ts_rs::TS;
 use workspace_utils::shell::get_shell_command;
@@ -10,12 +11,53 @@ use workspace_utils::shell::get_shell_command;
 use crate::{
     actions::Executable,
     approvals::ExecutorApprovalService,
+    env::ExecutionEnv,
     executors::{ExecutorError, SpawnedChild},
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+/// Interpreter a script is run with
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, Type)]
+#[sqlx(type_name = "TEXT")]
 pub enum ScriptRequestLanguage {
+    /// The user's shell on Unix, `bash` (e.g. Git Bash) on Windows
     Bash,
+    /// POSIX `sh`
+    Sh,
+    /// Windows PowerShell on Windows, `pwsh` elsewhere
+    PowerShell,
+    /// Windows `cmd.exe`
+    Cmd,
+}
+
+/// Used for projects that don't pick a language: PowerShell on Windows, where
+/// `bash` usually isn't installed, and the user's shell elsewhere
+impl Default for ScriptRequestLanguage {
+    fn default() -> Self {
+        if cfg!(windows) {
+            Self::PowerShell
+        } else {
+            Self::Bash
+        }
+    }
+}
+
+impl ScriptRequestLanguage {
+    /// Program and leading arguments that run a script passed as the final argument
+    fn command(self) -> (String, &'static [&'static str]) {
+        match self {
+            Self::Bash if cfg!(windows) => ("bash".to_string(), &["-c"]),
+            Self::Bash => (get_shell_command().0, &["-c"]),
+            Self::Sh => ("sh".to_string(), &["-c"]),
+            Self::PowerShell => {
+                let program = if cfg!(windows) { "powershell" } else { "pwsh" };
+                (
+                    program.to_string(),
+                    &["-NoProfile", "-NonInteractive", "-Command"],
+                )
+            }
+            Self::Cmd => ("cmd".to_string(), &["/C"]),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -39,20 +81,47 @@ impl Executable for ScriptRequest {
         &self,
         current_dir: &Path,
         _approvals: Arc<dyn ExecutorApprovalService>,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (shell_cmd, shell_arg) = get_shell_command();
-        let mut command = Command::new(shell_cmd);
+        let (program, args) = self.language.command();
+        let mut command = Command::new(program);
         command
             .kill_on_drop(true)
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
-            .arg(shell_arg)
+            .args(args)
             .arg(&self.script)
             .current_dir(current_dir);
+        env.apply_to_command(&mut command);
 
         let child = command.group_spawn()?;
 
         Ok(child.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(windows)]
+    #[test]
+    fn test_default_language_is_powershell() {
+        assert_eq!(
+            ScriptRequestLanguage::default(),
+            ScriptRequestLanguage::PowerShell
+        );
+        let (program, _) = ScriptRequestLanguage::default().command();
+        assert_eq!(program, "powershell");
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_default_language_runs_the_user_shell() {
+        let (program, args) = ScriptRequestLanguage::default().command();
+        let (shell, shell_arg) = get_shell_command();
+        assert_eq!(program, shell);
+        assert_eq!(args, &[shell_arg]);
+    }
+}
@@ -1,19 +1,37 @@
 //! Conversation export utilities for passing conversation history to different agents.
 //!
 //! This module provides functionality to export normalized conversation entries
-//! to a markdown format that can be passed as context to a new agent.
+//! to a markdown format that can be passed as context to a new agent, or to JSON
+//! for tools that want the structured entries.
+
+use uuid::Uuid;
 
 use crate::logs::{
     ActionType, CommandExitStatus, NormalizedEntry, NormalizedEntryType, ToolStatus,
 };
 
-/// Maximum length of the exported conversation in characters.
+/// Default maximum length of the exported conversation in characters.
 /// If exceeded, older entries are truncated to fit.
-const MAX_EXPORT_LENGTH: usize = 50_000;
+pub const DEFAULT_MAX_EXPORT_LENGTH: usize = 50_000;
+
+/// Largest export length a caller may ask for.
+pub const MAX_EXPORT_LENGTH_LIMIT: usize = 2_000_000;
+
+/// Smallest export length a caller may ask for, so the header survives truncation.
+const MIN_EXPORT_LENGTH: usize = 1_000;
 
 /// Maximum length of command output to include in export.
 const MAX_OUTPUT_LENGTH: usize = 500;
 
+/// Default size of the stdout/stderr tail kept per process in the raw logs section.
+pub const DEFAULT_RAW_LOG_TAIL_BYTES: usize = 8 * 1024;
+
+/// GitHub rejects pull request bodies longer than this many characters.
+pub const PR_BODY_MAX_LENGTH: usize = 65_536;
+
+/// Room left in a PR body for the truncation header and note.
+const PR_BODY_TRUNCATION_HEADROOM: usize = 1024;
+
 /// Result of exporting a conversation to markdown.
 #[derive(Debug, Clone, serde::Serialize, ts_rs::TS)]
 pub struct ExportResult {
@@ -23,6 +41,82 @@ pub struct ExportResult {
     pub message_count: usize,
     /// Whether the export was truncated due to length.
     pub truncated: bool,
+    /// The length limit the conversation was truncated to, in characters.
+    pub max_chars: usize,
+}
+
+/// Result of exporting a conversation to JSON.
+#[derive(Debug, Clone, serde::Serialize, ts_rs::TS)]
+pub struct JsonExportResult {
+    /// Name of the executor that generated the conversation.
+    pub executor: String,
+    /// The task attempt the conversation belongs to.
+    pub attempt_id: Uuid,
+    /// Number of entries included in the export.
+    pub message_count: usize,
+    /// Whether older entries were dropped to fit `max_entries`.
+    pub truncated: bool,
+    /// The exported entries, oldest first.
+    pub entries: Vec<NormalizedEntry>,
+}
+
+/// Raw stdout/stderr captured from one execution process.
+#[derive(Debug, Clone)]
+pub struct RawProcessLogs {
+    pub process_id: Uuid,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Export normalized conversation entries as structured JSON, skipping the same
+/// entry types as the markdown export. Nothing is dropped unless `max_entries`
+/// is given, in which case only the most recent entries are kept.
+pub fn export_to_json(
+    entries: &[NormalizedEntry],
+    original_executor: &str,
+    attempt_id: Uuid,
+    max_entries: Option<usize>,
+) -> JsonExportResult {
+    let mut exported: Vec<NormalizedEntry> = entries
+        .iter()
+        .filter(|entry| is_exported(&entry.entry_type))
+        .cloned()
+        .collect();
+
+    let truncated = match max_entries {
+        Some(max) if exported.len() > max => {
+            exported.drain(..exported.len() - max);
+            true
+        }
+        _ => false,
+    };
+
+    JsonExportResult {
+        executor: original_executor.to_string(),
+        attempt_id,
+        message_count: exported.len(),
+        truncated,
+        entries: exported,
+    }
+}
+
+/// Whether an entry type carries conversation content worth exporting.
+fn is_exported(entry_type: &NormalizedEntryType) -> bool {
+    !matches!(
+        entry_type,
+        NormalizedEntryType::Thinking
+            | NormalizedEntryType::Loading
+            | NormalizedEntryType::NextAction { .. }
+            | NormalizedEntryType::ContextUsage { .. }
+            | NormalizedEntryType::Usage { .. }
+    )
+}
+
+/// The length limit a markdown export uses when asked for `max_chars`.
+pub fn effective_max_chars(max_chars: Option<usize>) -> usize {
+    max_chars
+        .unwrap_or(DEFAULT_MAX_EXPORT_LENGTH)
+        .clamp(MIN_EXPORT_LENGTH, MAX_EXPORT_LENGTH_LIMIT)
 }
 
 /// Export normalized conversation entries to a markdown format suitable for passing to another agent.
@@ -30,10 +124,17 @@ pub struct ExportResult {
 /// # Arguments
 /// * `entries` - The normalized conversation entries to export
 /// * `original_executor` - Name of the executor that generated the conversation (e.g., "CLAUDE_CODE")
+/// * `max_chars` - Length limit overriding `DEFAULT_MAX_EXPORT_LENGTH`, clamped to
+///   `MAX_EXPORT_LENGTH_LIMIT`
 ///
 /// # Returns
 /// An `ExportResult` containing the markdown text and metadata about the export.
-pub fn export_to_markdown(entries: &[NormalizedEntry], original_executor: &str) -> ExportResult {
+pub fn export_to_markdown(
+    entries: &[NormalizedEntry],
+    original_executor: &str,
+    max_chars: Option<usize>,
+) -> ExportResult {
+    let max_chars = effective_max_chars(max_chars);
     let mut parts: Vec<String> = Vec::new();
     let mut message_count = 0;
 
@@ -59,8 +160,8 @@ pub fn export_to_markdown(entries: &[NormalizedEntry], original_executor: &str)
     let mut markdown = parts.join("\n");
 
     // Check if truncation is needed
-    let truncated = if markdown.len() > MAX_EXPORT_LENGTH {
-        markdown = truncate_from_start(&markdown, MAX_EXPORT_LENGTH);
+    let truncated = if markdown.len() > max_chars {
+        markdown = truncate_from_start(&markdown, max_chars);
         true
     } else {
         false
@@ -70,9 +171,71 @@ pub fn export_to_markdown(entries: &[NormalizedEntry], original_executor: &str)
         markdown,
         message_count,
         truncated,
+        max_chars,
     }
 }
 
+/// Append a "Raw logs" section holding the last `tail_bytes` of each process's
+/// stdout and stderr. Added after truncation so the conversation limit never
+/// cuts into it.
+pub fn append_raw_logs(result: &mut ExportResult, logs: &[RawProcessLogs], tail_bytes: usize) {
+    let mut section = String::from("\n\n---\n\n## Raw logs\n");
+
+    for log in logs {
+        section.push_str(&format!("\n### Process {}\n", log.process_id));
+        if log.stdout.is_empty() && log.stderr.is_empty() {
+            section.push_str("\n(no output)\n");
+            continue;
+        }
+        for (label, output) in [("stdout", &log.stdout), ("stderr", &log.stderr)] {
+            if output.is_empty() {
+                continue;
+            }
+            let tail = tail_str(output, tail_bytes);
+            let marker = if tail.len() < output.len() {
+                "[...earlier output omitted...]\n"
+            } else {
+                ""
+            };
+            let fence = code_fence(tail);
+            section.push_str(&format!(
+                "\n#### {}\n\n{fence}text\n{}{}\n{fence}\n",
+                label,
+                marker,
+                tail.trim_end()
+            ));
+        }
+    }
+
+    result.markdown.push_str(&section);
+}
+
+/// A backtick fence longer than any backtick run in `content`, so the content
+/// can't close the code block early
+fn code_fence(content: &str) -> String {
+    let longest_run = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest_run.max(2) + 1)
+}
+
+/// Turn a markdown export into a pull request body within GitHub's size
+/// limit, keeping the most recent part and noting when anything was cut.
+pub fn to_pr_body(result: &ExportResult) -> String {
+    // Byte length bounds the character count, so this never undercounts
+    let over_limit = result.markdown.len() > PR_BODY_MAX_LENGTH;
+    let mut body = if over_limit {
+        truncate_from_start(
+            &result.markdown,
+            PR_BODY_MAX_LENGTH - PR_BODY_TRUNCATION_HEADROOM,
+        )
+    } else {
+        result.markdown.clone()
+    };
+    if over_limit || result.truncated {
+        body.push_str("\n\n_Conversation truncated to fit the pull request description._");
+    }
+    body
+}
+
 /// Format a single entry to markdown. Returns None if the entry should be skipped.
 fn format_entry(entry: &NormalizedEntry) -> Option<String> {
     match &entry.entry_type {
@@ -100,7 +263,8 @@ fn format_entry(entry: &NormalizedEntry) -> Option<String> {
         NormalizedEntryType::Thinking
         | NormalizedEntryType::Loading
         | NormalizedEntryType::NextAction { .. }
-        | NormalizedEntryType::ContextUsage { .. } => None,
+        | NormalizedEntryType::ContextUsage { .. }
+        | NormalizedEntryType::Usage { .. } => None,
     }
 }
 
@@ -205,6 +369,19 @@ fn truncate_str(s: &str, max_len: usize) -> &str {
     }
 }
 
+/// Keep the last max_len bytes of a string, starting at a valid UTF-8 boundary.
+fn tail_str(s: &str, max_len: usize) -> &str {
+    if s.len() <= max_len {
+        s
+    } else {
+        let mut start = s.len() - max_len;
+        while !s.is_char_boundary(start) {
+            start += 1;
+        }
+        &s[start..]
+    }
+}
+
 /// Truncate markdown from the start to fit within max_len, preserving the header and footer.
 fn truncate_from_start(markdown: &str, max_len: usize) -> String {
     if markdown.len() <= max_len {
@@ -239,7 +416,7 @@ mod tests {
     #[test]
     fn test_export_empty_entries() {
         let entries: Vec<NormalizedEntry> = vec![];
-        let result = export_to_markdown(&entries, "CLAUDE_CODE");
+        let result = export_to_markdown(&entries, "CLAUDE_CODE", None);
 
         assert!(result.markdown.contains("Previous Conversation"));
         assert!(result.markdown.contains("CLAUDE_CODE"));
@@ -256,7 +433,7 @@ mod tests {
             metadata: None,
         }];
 
-        let result = export_to_markdown(&entries, "CLAUDE_CODE");
+        let result = export_to_markdown(&entries, "CLAUDE_CODE", None);
 
         assert!(result.markdown.contains("**User:** Hello, please help me"));
         assert_eq!(result.message_count, 1);
@@ -271,7 +448,7 @@ mod tests {
             metadata: None,
         }];
 
-        let result = export_to_markdown(&entries, "GEMINI");
+        let result = export_to_markdown(&entries, "GEMINI", None);
 
         assert!(
             result
@@ -298,7 +475,7 @@ mod tests {
             },
         ];
 
-        let result = export_to_markdown(&entries, "CLAUDE_CODE");
+        let result = export_to_markdown(&entries, "CLAUDE_CODE", None);
 
         assert!(!result.markdown.contains("Internal reasoning"));
         assert!(result.markdown.contains("Here's my answer"));
@@ -321,7 +498,7 @@ mod tests {
             metadata: None,
         }];
 
-        let result = export_to_markdown(&entries, "CLAUDE_CODE");
+        let result = export_to_markdown(&entries, "CLAUDE_CODE", None);
 
         assert!(result.markdown.contains("Edit File: src/main.rs"));
         assert_eq!(result.message_count, 1);
@@ -343,15 +520,130 @@ mod tests {
             metadata: None,
         }];
 
-        let result = export_to_markdown(&entries, "CLAUDE_CODE");
+        let result = export_to_markdown(&entries, "CLAUDE_CODE", None);
 
         assert!(result.markdown.contains("[FAILED]"));
         assert!(result.markdown.contains("npm test"));
     }
 
+    #[test]
+    fn test_export_json_keeps_latest_entries() {
+        let entries: Vec<NormalizedEntry> = ["first", "thinking", "second", "third"]
+            .iter()
+            .map(|content| NormalizedEntry {
+                timestamp: None,
+                entry_type: if *content == "thinking" {
+                    NormalizedEntryType::Thinking
+                } else {
+                    NormalizedEntryType::AssistantMessage
+                },
+                content: content.to_string(),
+                metadata: None,
+            })
+            .collect();
+        let attempt_id = Uuid::new_v4();
+
+        let result = export_to_json(&entries, "CLAUDE_CODE", attempt_id, None);
+        assert_eq!(result.message_count, 3);
+        assert!(!result.truncated);
+        assert_eq!(result.attempt_id, attempt_id);
+
+        let result = export_to_json(&entries, "CLAUDE_CODE", attempt_id, Some(2));
+        assert!(result.truncated);
+        let contents: Vec<_> = result.entries.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(contents, vec!["second", "third"]);
+    }
+
+    #[test]
+    fn test_export_max_chars_override() {
+        let entries: Vec<NormalizedEntry> = (0..200)
+            .map(|i| NormalizedEntry {
+                timestamp: None,
+                entry_type: NormalizedEntryType::AssistantMessage,
+                content: format!("message number {i}"),
+                metadata: None,
+            })
+            .collect();
+
+        let result = export_to_markdown(&entries, "CLAUDE_CODE", None);
+        assert!(!result.truncated);
+        assert_eq!(result.max_chars, DEFAULT_MAX_EXPORT_LENGTH);
+
+        let result = export_to_markdown(&entries, "CLAUDE_CODE", Some(2_000));
+        assert!(result.truncated);
+        assert_eq!(result.max_chars, 2_000);
+        assert!(result.markdown.contains("message number 199"));
+        assert!(!result.markdown.contains("message number 0\n"));
+
+        let result = export_to_markdown(&entries, "CLAUDE_CODE", Some(usize::MAX));
+        assert_eq!(result.max_chars, MAX_EXPORT_LENGTH_LIMIT);
+    }
+
     #[test]
     fn test_truncate_str() {
         assert_eq!(truncate_str("hello", 10), "hello");
         assert_eq!(truncate_str("hello world", 5), "hello");
+        assert_eq!(tail_str("hello world", 5), "world");
+    }
+
+    #[test]
+    fn test_append_raw_logs() {
+        let mut result = export_to_markdown(&[], "CLAUDE_CODE", None);
+        let process_id = Uuid::new_v4();
+        let logs = vec![RawProcessLogs {
+            process_id,
+            stdout: "building\ndone\n".to_string(),
+            stderr: "warning: unused\nerror: failed\n".to_string(),
+        }];
+
+        append_raw_logs(&mut result, &logs, 14);
+
+        let (conversation, raw) = result.markdown.split_once("## Raw logs").unwrap();
+        assert!(conversation.contains("Previous Conversation"));
+        assert!(raw.contains(&format!("### Process {process_id}")));
+        assert!(raw.contains("#### stdout\n\n```text\nbuilding\ndone\n```"));
+        assert!(raw.contains("[...earlier output omitted...]\nerror: failed\n```"));
+        assert!(!raw.contains("warning: unused"));
+    }
+
+    #[test]
+    fn test_raw_logs_fence_outlasts_backticks_in_output() {
+        assert_eq!(code_fence("plain output"), "```");
+        assert_eq!(code_fence("a `b` c"), "```");
+        assert_eq!(code_fence("```rust\nfn main() {}\n```"), "````");
+
+        let mut result = export_to_markdown(&[], "CLAUDE_CODE", None);
+        let logs = vec![RawProcessLogs {
+            process_id: Uuid::new_v4(),
+            stdout: "Here you go:\n```rust\nfn main() {}\n```\n".to_string(),
+            stderr: String::new(),
+        }];
+        append_raw_logs(&mut result, &logs, 1024);
+        assert!(
+            result
+                .markdown
+                .contains("````text\nHere you go:\n```rust\nfn main() {}\n```\n````")
+        );
+    }
+
+    #[test]
+    fn test_to_pr_body_truncates_to_limit() {
+        let short = ExportResult {
+            markdown: "**User:** hi\n".to_string(),
+            message_count: 1,
+            truncated: false,
+            max_chars: DEFAULT_MAX_EXPORT_LENGTH,
+        };
+        assert_eq!(to_pr_body(&short), short.markdown);
+
+        let long = ExportResult {
+            markdown: "**Assistant:** lorem ipsum\n".repeat(PR_BODY_MAX_LENGTH / 10),
+            message_count: PR_BODY_MAX_LENGTH / 10,
+            truncated: false,
+            max_chars: DEFAULT_MAX_EXPORT_LENGTH,
+        };
+        let body = to_pr_body(&long);
+        assert!(body.chars().count() <= PR_BODY_MAX_LENGTH);
+        assert!(body.ends_with("_Conversation truncated to fit the pull request description._"));
     }
 }
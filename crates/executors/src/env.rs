@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+use ts_rs::TS;
+
+use crate::{actions::ExecutorActionType, mcp_config::McpServerDefinition};
+
+/// Which processes of an attempt a secret is injected into
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SecretScope {
+    #[default]
+    All,
+    CodingAgent, // Agent processes only, including the MCP servers they start
+    Script,      // Setup, cleanup and dev server scripts only
+}
+
+impl SecretScope {
+    pub fn includes(self, action: &ExecutorActionType) -> bool {
+        let is_script = matches!(action, ExecutorActionType::ScriptRequest(_));
+        match self {
+            Self::All => true,
+            Self::CodingAgent => !is_script,
+            Self::Script => is_script,
+        }
+    }
+}
+
+/// An environment variable whose value is kept out of logs and only exposed to
+/// the processes its scope includes.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ScopedSecret {
+    pub name: String,
+    pub value: String,
+    #[serde(default)]
+    pub scope: SecretScope,
+}
+
+/// Environment variables set on every process of one task attempt. `Debug`
+/// prints only the variable names so values never end up in logs.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(transparent)]
+pub struct EnvOverrides(pub HashMap<String, String>);
+
+impl std::fmt::Debug for EnvOverrides {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<_> = self.0.keys().collect();
+        names.sort();
+        f.debug_tuple("EnvOverrides").field(&names).finish()
+    }
+}
+
+/// Shown instead of a secret's value in API responses; a secret sent back with
+/// it keeps its stored value.
+pub const REDACTED_SECRET_VALUE: &str = "[REDACTED]";
+
+/// `secrets` with their values masked, for responses leaving the server.
+pub fn redact_secret_values(secrets: &[ScopedSecret]) -> Vec<ScopedSecret> {
+    secrets
+        .iter()
+        .map(|secret| ScopedSecret {
+            value: REDACTED_SECRET_VALUE.to_string(),
+            ..secret.clone()
+        })
+        .collect()
+}
+
+/// Put back the `stored` value of each secret in `updated` that still holds
+/// the mask, matching secrets by name.
+pub fn restore_redacted_secret_values(updated: &mut [ScopedSecret], stored: &[ScopedSecret]) {
+    for secret in updated
+        .iter_mut()
+        .filter(|s| s.value == REDACTED_SECRET_VALUE)
+    {
+        if let Some(previous) = stored.iter().find(|s| s.name == secret.name) {
+            secret.value = previous.value.clone();
+        }
+    }
+}
+
+/// Extra environment variables applied to every process an executor spawns.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionEnv {
+    pub vars: HashMap<String, String>,
+    /// The project's own MCP servers, added for this run only by executors that
+    /// take MCP servers on the command line (Claude Code and Codex)
+    pub mcp_servers: Vec<McpServerDefinition>,
+}
+
+impl ExecutionEnv {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.vars.insert(key.into(), value.into());
+    }
+
+    /// Merge `other` into `self`; keys in `other` win.
+    pub fn merge(&mut self, other: &ExecutionEnv) {
+        self.vars
+            .extend(other.vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    pub fn apply_to_command(&self, command: &mut Command) {
+        command.envs(&self.vars);
+    }
+
+    /// Names of the variables set, sorted, for logging without exposing values.
+    pub fn var_names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.vars.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+
+    /// Add the `secrets` whose scope includes `action`.
+    pub fn insert_scoped_secrets(&mut self, secrets: &[ScopedSecret], action: &ExecutorActionType) {
+        for secret in secrets.iter().filter(|s| s.scope.includes(action)) {
+            self.insert(&secret.name, &secret.value);
+        }
+    }
+
+    /// Build an environment from a template whose values may reference host
+    /// variables as `${NAME}`. Returns the env along with the names of any
+    /// references `lookup` could not resolve; those expand to an empty string.
+    pub fn from_template(
+        template: &HashMap<String, String>,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> (Self, Vec<String>) {
+        let mut env = Self::new();
+        let mut unresolved = Vec::new();
+        for (key, value) in template {
+            env.insert(key, expand_refs(value, &lookup, &mut unresolved));
+        }
+        unresolved.sort();
+        unresolved.dedup();
+        (env, unresolved)
+    }
+}
+
+fn expand_refs(
+    value: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+    unresolved: &mut Vec<String>,
+) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            // Unterminated reference, keep it literally
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let name = &after[..end];
+        match lookup(name) {
+            Some(resolved) => out.push_str(&resolved),
+            None => unresolved.push(name.to_string()),
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+    use crate::{
+        actions::{
+            Executable,
+            coding_agent_initial::CodingAgentInitialRequest,
+            script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+        },
+        approvals::NoopExecutorApprovalService,
+        executors::BaseCodingAgent,
+        profile::ExecutorProfileId,
+    };
+
+    #[test]
+    fn test_env_overrides_debug_hides_values() {
+        let overrides = EnvOverrides(HashMap::from([
+            ("FLAG_TOKEN".to_string(), "tok-123".to_string()),
+            ("API_URL".to_string(), "https://example.test".to_string()),
+        ]));
+
+        let printed = format!("{overrides:?}");
+        assert_eq!(printed, r#"EnvOverrides(["API_URL", "FLAG_TOKEN"])"#);
+        assert!(!printed.contains("tok-123"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_agent_scoped_secret_is_hidden_from_scripts() {
+        let secrets = vec![ScopedSecret {
+            name: "VK_AGENT_KEY".to_string(),
+            value: "sk-agent".to_string(),
+            scope: SecretScope::CodingAgent,
+        }];
+        let agent_action =
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt: "hello".to_string(),
+                executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+                is_orchestrator: false,
+            });
+        let script = ScriptRequest {
+            script: "printf '%s' \"${VK_AGENT_KEY-unset}\"".to_string(),
+            language: ScriptRequestLanguage::Bash,
+            context: ScriptContext::SetupScript,
+        };
+
+        let mut agent_env = ExecutionEnv::new();
+        agent_env.insert_scoped_secrets(&secrets, &agent_action);
+        assert_eq!(agent_env.vars["VK_AGENT_KEY"], "sk-agent");
+
+        let mut script_env = ExecutionEnv::new();
+        script_env
+            .insert_scoped_secrets(&secrets, &ExecutorActionType::ScriptRequest(script.clone()));
+        assert!(!script_env.vars.contains_key("VK_AGENT_KEY"));
+
+        let mut spawned = script
+            .spawn(
+                &std::env::temp_dir(),
+                Arc::new(NoopExecutorApprovalService),
+                &script_env,
+            )
+            .await
+            .unwrap();
+        let mut stdout = String::new();
+        spawned
+            .child
+            .inner()
+            .stdout
+            .take()
+            .unwrap()
+            .read_to_string(&mut stdout)
+            .await
+            .unwrap();
+        spawned.child.wait().await.unwrap();
+        assert_eq!(stdout, "unset");
+    }
+
+    #[test]
+    fn test_masked_secret_values_round_trip_to_stored_values() {
+        let stored = vec![ScopedSecret {
+            name: "API_KEY".to_string(),
+            value: "sk-stored".to_string(),
+            scope: SecretScope::All,
+        }];
+        let mut updated = redact_secret_values(&stored);
+        assert_eq!(updated[0].value, REDACTED_SECRET_VALUE);
+
+        updated.push(ScopedSecret {
+            name: "NEW_KEY".to_string(),
+            value: "sk-new".to_string(),
+            scope: SecretScope::Script,
+        });
+        restore_redacted_secret_values(&mut updated, &stored);
+        assert_eq!(updated[0].value, "sk-stored");
+        assert_eq!(updated[1].value, "sk-new");
+    }
+
+    #[test]
+    fn test_from_template_reports_unresolved_refs() {
+        let template = HashMap::from([
+            ("A".to_string(), "x-${HOST_A}-${MISSING}".to_string()),
+            ("B".to_string(), "${unterminated".to_string()),
+        ]);
+        let (env, unresolved) = ExecutionEnv::from_template(&template, |name| {
+            (name == "HOST_A").then(|| "resolved".to_string())
+        });
+        assert_eq!(env.vars["A"], "x-resolved-");
+        assert_eq!(env.vars["B"], "${unterminated");
+        assert_eq!(unresolved, vec!["MISSING".to_string()]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_template_vars_reach_child_process() {
+        let template = HashMap::from([
+            ("VK_PLAIN".to_string(), "plain".to_string()),
+            (
+                "VK_FROM_HOST".to_string(),
+                "host:${VK_HOST_VAR}".to_string(),
+            ),
+        ]);
+        let (env, unresolved) = ExecutionEnv::from_template(&template, |name| {
+            (name == "VK_HOST_VAR").then(|| "secret".to_string())
+        });
+        assert!(unresolved.is_empty());
+
+        let request = ScriptRequest {
+            script: "printf '%s|%s' \"$VK_PLAIN\" \"$VK_FROM_HOST\"".to_string(),
+            language: ScriptRequestLanguage::Bash,
+            context: ScriptContext::SetupScript,
+        };
+        let dir = std::env::temp_dir();
+        let mut spawned = request
+            .spawn(&dir, Arc::new(NoopExecutorApprovalService), &env)
+            .await
+            .unwrap();
+
+        let mut stdout = String::new();
+        spawned
+            .child
+            .inner()
+            .stdout
+            .take()
+            .unwrap()
+            .read_to_string(&mut stdout)
+            .await
+            .unwrap();
+        spawned.child.wait().await.unwrap();
+        assert_eq!(stdout, "plain|host:secret");
+    }
+}
@@ -1,11 +1,15 @@
 use std::{
     path::{Path, PathBuf},
     process::Stdio,
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
 };
 
 use agent_client_protocol as proto;
 use agent_client_protocol::Agent as _;
+use async_trait::async_trait;
 use command_group::{AsyncCommandGroup, AsyncGroupChild};
 use futures::StreamExt;
 use tokio::{io::AsyncWriteExt, process::Command, sync::mpsc};
@@ -19,9 +23,42 @@ use workspace_utils::stream_lines::LinesStreamExt;
 use super::{AcpClient, SessionManager};
 use crate::{
     command::CommandParts,
-    executors::{ExecutorError, ExecutorExitResult, SpawnedChild, acp::AcpEvent},
+    env::ExecutionEnv,
+    executors::{
+        BoxedInputSender, ExecutorError, ExecutorExitResult, InputSender, SpawnedChild,
+        acp::AcpEvent,
+    },
 };
 
+/// Delivers user input (e.g. `/compress`) to a running ACP session as an extra prompt turn
+struct AcpInputSender {
+    tx: mpsc::UnboundedSender<String>,
+    session_active: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl InputSender for AcpInputSender {
+    async fn send_user_input(&self, content: String) -> Result<(), ExecutorError> {
+        if !self.session_active.load(Ordering::SeqCst) {
+            return Err(ExecutorError::FollowUpNotSupported(
+                "no active ACP session".to_string(),
+            ));
+        }
+        self.tx
+            .send(content)
+            .map_err(|_| ExecutorError::FollowUpNotSupported("ACP session has ended".to_string()))
+    }
+}
+
+/// Next input queued for the session. Once the queue is empty the channel is closed,
+/// so input arriving after the last turn fails to send instead of being dropped.
+fn take_queued_input(rx: &mut mpsc::UnboundedReceiver<String>) -> Option<String> {
+    rx.try_recv().ok().or_else(|| {
+        rx.close();
+        rx.try_recv().ok()
+    })
+}
+
 /// Reusable harness for ACP-based conns (Gemini, Qwen, etc.)
 pub struct AcpAgentHarness {
     session_namespace: String,
@@ -54,6 +91,7 @@ impl AcpAgentHarness {
         current_dir: &Path,
         prompt: String,
         command_parts: CommandParts,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let (program_path, args) = command_parts.into_resolved().await?;
         let mut command = Command::new(program_path);
@@ -65,11 +103,12 @@ impl AcpAgentHarness {
             .current_dir(current_dir)
             .args(&args)
             .env("NODE_NO_WARNINGS", "1");
+        env.apply_to_command(&mut command);
 
         let mut child = command.group_spawn()?;
 
         let (exit_tx, exit_rx) = tokio::sync::oneshot::channel::<ExecutorExitResult>();
-        Self::bootstrap_acp_connection(
+        let input_sender = Self::bootstrap_acp_connection(
             &mut child,
             current_dir.to_path_buf(),
             None,
@@ -82,7 +121,7 @@ impl AcpAgentHarness {
         Ok(SpawnedChild {
             child,
             exit_signal: Some(exit_rx),
-            input_sender: None,
+            input_sender: Some(input_sender),
         })
     }
 
@@ -92,6 +131,7 @@ impl AcpAgentHarness {
         prompt: String,
         session_id: &str,
         command_parts: CommandParts,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let (program_path, args) = command_parts.into_resolved().await?;
         let mut command = Command::new(program_path);
@@ -103,11 +143,12 @@ impl AcpAgentHarness {
             .current_dir(current_dir)
             .args(&args)
             .env("NODE_NO_WARNINGS", "1");
+        env.apply_to_command(&mut command);
 
         let mut child = command.group_spawn()?;
 
         let (exit_tx, exit_rx) = tokio::sync::oneshot::channel::<ExecutorExitResult>();
-        Self::bootstrap_acp_connection(
+        let input_sender = Self::bootstrap_acp_connection(
             &mut child,
             current_dir.to_path_buf(),
             Some(session_id.to_string()),
@@ -120,7 +161,7 @@ impl AcpAgentHarness {
         Ok(SpawnedChild {
             child,
             exit_signal: Some(exit_rx),
-            input_sender: None,
+            input_sender: Some(input_sender),
         })
     }
 
@@ -131,7 +172,7 @@ impl AcpAgentHarness {
         prompt: String,
         exit_signal: Option<tokio::sync::oneshot::Sender<ExecutorExitResult>>,
         session_namespace: String,
-    ) -> Result<(), ExecutorError> {
+    ) -> Result<BoxedInputSender, ExecutorError> {
         // Take child's stdio for ACP wiring
         let orig_stdout = child.inner().stdout.take().ok_or_else(|| {
             ExecutorError::Io(std::io::Error::new(
@@ -219,6 +260,14 @@ impl AcpAgentHarness {
 
         let mut exit_signal_tx = exit_signal;
 
+        // User input sent while the session runs, delivered as follow-up prompt turns
+        let (input_tx, mut input_rx) = mpsc::unbounded_channel::<String>();
+        let session_active = Arc::new(AtomicBool::new(false));
+        let input_sender = AcpInputSender {
+            tx: input_tx,
+            session_active: session_active.clone(),
+        };
+
         // Run ACP client in a LocalSet
         tokio::task::spawn_blocking(move || {
             let rt = tokio::runtime::Builder::new_current_thread()
@@ -347,46 +396,57 @@ impl AcpAgentHarness {
                             }
                         });
 
-                        // Save prompt to session
-                        let _ = session_manager.append_raw_line(
-                            &display_session_id,
-                            &serde_json::to_string(&serde_json::json!({ "user": prompt_to_send }))
-                                .unwrap_or_default(),
-                        );
-
-                        // Build prompt request
-                        let req = proto::PromptRequest {
-                            session_id: proto::SessionId(acp_session_id.clone().into()),
-                            prompt: vec![proto::ContentBlock::Text(proto::TextContent {
-                                annotations: None,
-                                text: prompt_to_send,
+                        session_active.store(true, Ordering::SeqCst);
+                        // The first prompt was recorded as the user's own text, not the
+                        // resume prompt, which would nest the history on every follow-up
+                        let mut next_prompt = Some(prompt_to_send);
+                        while let Some(text) = next_prompt.take() {
+                            // Build prompt request
+                            let req = proto::PromptRequest {
+                                session_id: proto::SessionId(acp_session_id.clone().into()),
+                                prompt: vec![proto::ContentBlock::Text(proto::TextContent {
+                                    annotations: None,
+                                    text,
+                                    meta: None,
+                                })],
                                 meta: None,
-                            })],
-                            meta: None,
-                        };
+                            };
 
-                        // Send the prompt and await completion to obtain stop_reason
-                        match conn.prompt(req).await {
-                            Ok(resp) => {
-                                // Emit done with stop_reason
-                                let stop_reason =
-                                    serde_json::to_string(&resp.stop_reason).unwrap_or_default();
-                                let _ = log_tx.send(AcpEvent::Done(stop_reason).to_string());
-                            }
-                            Err(e) => {
-                                tracing::debug!("error {} {e} {:?}", e.code, e.data);
-                                if e.code == agent_client_protocol::ErrorCode::INTERNAL_ERROR.code
-                                    && e.data
-                                        .as_ref()
-                                        .is_some_and(|d| d == "server shut down unexpectedly")
-                                {
-                                    tracing::debug!("ACP server killed");
-                                } else {
-                                    let _ =
-                                        log_tx.send(AcpEvent::Error(format!("{e}")).to_string());
+                            // Send the prompt and await completion to obtain stop_reason
+                            match conn.prompt(req).await {
+                                Ok(resp) => {
+                                    // Emit done with stop_reason
+                                    let stop_reason = serde_json::to_string(&resp.stop_reason)
+                                        .unwrap_or_default();
+                                    let _ = log_tx.send(AcpEvent::Done(stop_reason).to_string());
+                                }
+                                Err(e) => {
+                                    tracing::debug!("error {} {e} {:?}", e.code, e.data);
+                                    if e.code
+                                        == agent_client_protocol::ErrorCode::INTERNAL_ERROR.code
+                                        && e.data
+                                            .as_ref()
+                                            .is_some_and(|d| d == "server shut down unexpectedly")
+                                    {
+                                        tracing::debug!("ACP server killed");
+                                    } else {
+                                        let _ = log_tx
+                                            .send(AcpEvent::Error(format!("{e}")).to_string());
+                                    }
+                                    break;
                                 }
                             }
+
+                            // Run input that arrived during the turn before finishing,
+                            // saving it to the session like the first prompt
+                            next_prompt = take_queued_input(&mut input_rx);
+                            if let Some(input) = &next_prompt {
+                                let _ = event_tx.send(AcpEvent::User(input.clone()));
+                            }
                         }
+                        session_active.store(false, Ordering::SeqCst);
+                        input_rx.close();
+
                         // Notify container of completion
                         if let Some(tx) = exit_signal_tx.take() {
                             let _ = tx.send(ExecutorExitResult::Success);
@@ -410,6 +470,61 @@ impl AcpAgentHarness {
             });
         });
 
-        Ok(())
+        Ok(Box::new(input_sender))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_input_sender_requires_active_session() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let session_active = Arc::new(AtomicBool::new(false));
+        let sender = AcpInputSender {
+            tx,
+            session_active: session_active.clone(),
+        };
+
+        assert!(matches!(
+            sender.send_user_input("/compress".to_string()).await,
+            Err(ExecutorError::FollowUpNotSupported(_))
+        ));
+
+        session_active.store(true, Ordering::SeqCst);
+        sender
+            .send_user_input("/compress".to_string())
+            .await
+            .unwrap();
+        assert_eq!(rx.recv().await.as_deref(), Some("/compress"));
+
+        drop(rx);
+        assert!(matches!(
+            sender.send_user_input("/compress".to_string()).await,
+            Err(ExecutorError::FollowUpNotSupported(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_input_after_last_turn_is_rejected() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let sender = AcpInputSender {
+            tx,
+            session_active: Arc::new(AtomicBool::new(true)),
+        };
+
+        sender
+            .send_user_input("/compress".to_string())
+            .await
+            .unwrap();
+        assert_eq!(take_queued_input(&mut rx).as_deref(), Some("/compress"));
+        assert_eq!(take_queued_input(&mut rx), None);
+
+        // The session is finishing but has not cleared its flag yet
+        assert!(matches!(
+            sender.send_user_input("/compress".to_string()).await,
+            Err(ExecutorError::FollowUpNotSupported(_))
+        ));
     }
 }
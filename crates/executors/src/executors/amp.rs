@@ -2,25 +2,35 @@ use std::{path::Path, process::Stdio, sync::Arc};
 
 use async_trait::async_trait;
 use command_group::AsyncCommandGroup;
+use futures::StreamExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use tokio::{io::AsyncWriteExt, process::Command};
+use tokio::{
+    io::AsyncWriteExt,
+    process::{ChildStdin, Command},
+    sync::Mutex,
+};
 use ts_rs::TS;
 use workspace_utils::msg_store::MsgStore;
 
 use crate::{
     command::{CmdOverrides, CommandBuilder, apply_overrides},
+    env::ExecutionEnv,
     executors::{
-        AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+        AppendPrompt, ExecutorError, InputSender, PromptAffixes, SpawnedChild,
+        StandardCodingAgentExecutor,
         claude::{ClaudeLogProcessor, HistoryStrategy},
     },
     logs::{stderr_processor::normalize_stderr_logs, utils::EntryIndexProvider},
+    stdout_dup::duplicate_stdout,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
 pub struct Amp {
     #[serde(default)]
     pub append_prompt: AppendPrompt,
+    #[serde(flatten)]
+    pub prompt_affixes: PromptAffixes,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[schemars(
         title = "Dangerously Allow All",
@@ -34,21 +44,121 @@ pub struct Amp {
 impl Amp {
     fn build_command_builder(&self) -> CommandBuilder {
         let mut builder = CommandBuilder::new("npx -y @sourcegraph/amp@0.0.1764081384-g1961a8")
-            .params(["--execute", "--stream-json"]);
+            .params(["--execute", "--stream-json", "--stream-json-input"]);
         if self.dangerously_allow_all.unwrap_or(false) {
             builder = builder.extend_params(["--dangerously-allow-all"]);
         }
         apply_overrides(builder, &self.cmd)
     }
+
+    /// Spawn amp reading stream-json user messages from stdin. Stdin stays open
+    /// for the turn so further input (e.g. compaction) can be sent, and is closed
+    /// once amp reports the turn's result so the process exits.
+    async fn spawn_streaming(
+        mut command: Command,
+        prompt: String,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let mut child = command.group_spawn()?;
+        let stdin = child
+            .inner()
+            .stdin
+            .take()
+            .ok_or_else(|| ExecutorError::Io(std::io::Error::other("Amp missing stdin")))?;
+        let input_sender = AmpInputSender {
+            stdin: Arc::new(Mutex::new(Some(stdin))),
+        };
+        input_sender.send_user_input(prompt).await?;
+
+        let mut stdout = duplicate_stdout(&mut child)?;
+        let closer = input_sender.clone();
+        tokio::spawn(async move {
+            let mut buffer = String::new();
+            while let Some(Ok(chunk)) = stdout.next().await {
+                buffer.push_str(&chunk);
+                while let Some(newline) = buffer.find('\n') {
+                    let line: String = buffer.drain(..=newline).collect();
+                    if is_result_line(&line) {
+                        closer.close().await;
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(SpawnedChild {
+            child,
+            exit_signal: None,
+            input_sender: Some(Box::new(input_sender)),
+        })
+    }
+}
+
+fn is_result_line(line: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(line)
+        .is_ok_and(|v| v.get("type").and_then(|t| t.as_str()) == Some("result"))
+}
+
+/// Writes user messages to amp's stream-json stdin while the turn is running
+#[derive(Clone)]
+struct AmpInputSender {
+    stdin: Arc<Mutex<Option<ChildStdin>>>,
+}
+
+impl AmpInputSender {
+    /// Close stdin so amp exits once it has finished the current turn
+    async fn close(&self) {
+        if let Some(mut stdin) = self.stdin.lock().await.take() {
+            let _ = stdin.shutdown().await;
+        }
+    }
+}
+
+#[async_trait]
+impl InputSender for AmpInputSender {
+    async fn send_user_input(&self, content: String) -> Result<(), ExecutorError> {
+        let mut guard = self.stdin.lock().await;
+        let Some(stdin) = guard.as_mut() else {
+            return Err(ExecutorError::Io(std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "Amp is no longer accepting input",
+            )));
+        };
+        let message = serde_json::json!({
+            "type": "user",
+            "message": {
+                "role": "user",
+                "content": [{ "type": "text", "text": content }]
+            }
+        });
+        let line = format!("{message}\n");
+        if let Err(e) = async {
+            stdin.write_all(line.as_bytes()).await?;
+            stdin.flush().await
+        }
+        .await
+        {
+            // The process has gone away; stop accepting input
+            guard.take();
+            return Err(ExecutorError::Io(e));
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl StandardCodingAgentExecutor for Amp {
-    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
         let command_parts = self.build_command_builder().build_initial()?;
         let (executable_path, args) = command_parts.into_resolved().await?;
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_with(&self.prompt_affixes, prompt);
 
         let mut command = Command::new(executable_path);
         command
@@ -58,16 +168,9 @@ impl StandardCodingAgentExecutor for Amp {
             .stderr(Stdio::piped())
             .current_dir(current_dir)
             .args(&args);
+        env.apply_to_command(&mut command);
 
-        let mut child = command.group_spawn()?;
-
-        // Feed the prompt in, then close the pipe so amp sees EOF
-        if let Some(mut stdin) = child.inner().stdin.take() {
-            stdin.write_all(combined_prompt.as_bytes()).await?;
-            stdin.shutdown().await?;
-        }
-
-        Ok(child.into())
+        Self::spawn_streaming(command, combined_prompt).await
     }
 
     async fn spawn_follow_up(
@@ -75,6 +178,7 @@ impl StandardCodingAgentExecutor for Amp {
         current_dir: &Path,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         // 1) Fork the thread synchronously to obtain new thread id
         let builder = self.build_command_builder();
@@ -116,7 +220,9 @@ impl StandardCodingAgentExecutor for Amp {
         ])?;
         let (continue_program, continue_args) = continue_line.into_resolved().await?;
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_with(&self.prompt_affixes, prompt);
 
         let mut command = Command::new(continue_program);
         command
@@ -126,16 +232,9 @@ impl StandardCodingAgentExecutor for Amp {
             .stderr(Stdio::piped())
             .current_dir(current_dir)
             .args(&continue_args);
+        env.apply_to_command(&mut command);
 
-        let mut child = command.group_spawn()?;
-
-        // Feed the prompt in, then close the pipe so amp sees EOF
-        if let Some(mut stdin) = child.inner().stdin.take() {
-            stdin.write_all(combined_prompt.as_bytes()).await?;
-            stdin.shutdown().await?;
-        }
-
-        Ok(child.into())
+        Self::spawn_streaming(command, combined_prompt).await
     }
 
     fn normalize_logs(&self, msg_store: Arc<MsgStore>, current_dir: &Path) {
@@ -158,3 +257,29 @@ impl StandardCodingAgentExecutor for Amp {
         dirs::home_dir().map(|home| home.join(".config").join("amp").join("settings.json"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_input_sender_rejects_input_after_close() {
+        let sender = AmpInputSender {
+            stdin: Arc::new(Mutex::new(None)),
+        };
+
+        assert!(matches!(
+            sender.send_user_input("/compact".to_string()).await,
+            Err(ExecutorError::Io(e)) if e.kind() == std::io::ErrorKind::BrokenPipe
+        ));
+    }
+
+    #[test]
+    fn test_result_line_detection() {
+        assert!(is_result_line(
+            r#"{"type":"result","subtype":"success","is_error":false}"#
+        ));
+        assert!(!is_result_line(r#"{"type":"assistant","message":{}}"#));
+        assert!(!is_result_line("not json"));
+    }
+}
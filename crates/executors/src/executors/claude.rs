@@ -22,16 +22,18 @@ use self::{client::ClaudeAgentClient, protocol::ProtocolPeer, types::PermissionM
 use crate::{
     approvals::ExecutorApprovalService,
     command::{CmdOverrides, CommandBuilder, CommandParts, apply_overrides},
+    env::ExecutionEnv,
     executors::{
-        AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
-        codex::client::LogWriter,
+        AppendPrompt, AvailabilityInfo, ExecutorError, PromptAffixes, SpawnedChild,
+        StandardCodingAgentExecutor, codex::client::LogWriter,
     },
     logs::{
         ActionType, FileChange, NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
-        TodoItem, ToolStatus,
+        TodoItem, TokenUsage, ToolStatus,
         stderr_processor::normalize_stderr_logs,
         utils::{EntryIndexProvider, patch::ConversationPatch},
     },
+    mcp_config::claude_mcp_config,
     stdout_dup::create_stdout_pipe_writer,
     token_tracker,
 };
@@ -129,6 +131,8 @@ use derivative::Derivative;
 pub struct ClaudeCode {
     #[serde(default)]
     pub append_prompt: AppendPrompt,
+    #[serde(flatten)]
+    pub prompt_affixes: PromptAffixes,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub claude_code_router: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -250,10 +254,15 @@ impl StandardCodingAgentExecutor for ClaudeCode {
         self.is_orchestrator = is_orchestrator;
     }
 
-    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
         let command_builder = self.build_command_builder().await;
         let command_parts = command_builder.build_initial()?;
-        self.spawn_internal(current_dir, prompt, command_parts)
+        self.spawn_internal(current_dir, prompt, command_parts, env)
             .await
     }
 
@@ -262,6 +271,7 @@ impl StandardCodingAgentExecutor for ClaudeCode {
         current_dir: &Path,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let command_builder = self.build_command_builder().await;
         let command_parts = command_builder.build_follow_up(&[
@@ -269,7 +279,7 @@ impl StandardCodingAgentExecutor for ClaudeCode {
             "--resume".to_string(),
             session_id.to_string(),
         ])?;
-        self.spawn_internal(current_dir, prompt, command_parts)
+        self.spawn_internal(current_dir, prompt, command_parts, env)
             .await
     }
 
@@ -317,9 +327,12 @@ impl ClaudeCode {
         current_dir: &Path,
         prompt: &str,
         command_parts: CommandParts,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let (program_path, args) = command_parts.into_resolved().await?;
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_with(&self.prompt_affixes, prompt);
 
         let mut command = Command::new(program_path);
         command
@@ -329,6 +342,14 @@ impl ClaudeCode {
             .stderr(Stdio::piped())
             .current_dir(current_dir)
             .args(&args);
+        env.apply_to_command(&mut command);
+        // The project's MCP servers load next to the user's own for this run only.
+        // Added after the command line is split, which would mangle the JSON.
+        if !env.mcp_servers.is_empty() {
+            command
+                .arg("--mcp-config")
+                .arg(claude_mcp_config(&env.mcp_servers).to_string());
+        }
 
         // Remove ANTHROPIC_API_KEY if disable_api_key is enabled
         if self.disable_api_key.unwrap_or(false) {
@@ -506,14 +527,19 @@ impl ClaudeLogProcessor {
                                 msg_store.push_patch(patch);
                             }
                         }
-                        Err(_) => {
-                            // Handle non-JSON output as raw system message
+                        Err(e) => {
+                            // Handle non-JSON output as raw system message; a line that
+                            // looks like a JSON event but doesn't parse is a parse error
                             if !trimmed.is_empty() {
-                                let entry = NormalizedEntry {
-                                    timestamp: None,
-                                    entry_type: NormalizedEntryType::SystemMessage,
-                                    content: trimmed.to_string(),
-                                    metadata: None,
+                                let entry = if trimmed.starts_with('{') {
+                                    NormalizedEntry::parse_error(e, trimmed)
+                                } else {
+                                    NormalizedEntry {
+                                        timestamp: None,
+                                        entry_type: NormalizedEntryType::SystemMessage,
+                                        content: trimmed.to_string(),
+                                        metadata: None,
+                                    }
                                 };
 
                                 let patch_id = entry_index_provider.next();
@@ -1269,7 +1295,14 @@ impl ClaudeLogProcessor {
                 }
                 ClaudeStreamEvent::Unknown => {}
             },
-            ClaudeJson::Result { is_error, .. } => {
+            ClaudeJson::Result {
+                is_error, usage, ..
+            } => {
+                if let Some(usage) = usage {
+                    let entry = NormalizedEntry::usage(usage.to_token_usage());
+                    let idx = entry_index_provider.next();
+                    patches.push(ConversationPatch::add_normalized_entry(idx, entry));
+                }
                 if matches!(self.strategy, HistoryStrategy::AmpResume) && is_error.unwrap_or(false)
                 {
                     let entry = NormalizedEntry {
@@ -1660,6 +1693,8 @@ pub enum ClaudeJson {
         num_turns: Option<u32>,
         #[serde(default, alias = "sessionId")]
         session_id: Option<String>,
+        #[serde(default)]
+        usage: Option<ClaudeUsage>,
     },
     #[serde(rename = "approval_response")]
     ApprovalResponse {
@@ -1815,6 +1850,17 @@ pub struct ClaudeUsage {
     pub service_tier: Option<String>,
 }
 
+impl ClaudeUsage {
+    pub fn to_token_usage(&self) -> TokenUsage {
+        TokenUsage {
+            input_tokens: self.input_tokens.unwrap_or(0),
+            output_tokens: self.output_tokens.unwrap_or(0),
+            cached_tokens: self.cache_creation_input_tokens.unwrap_or(0)
+                + self.cache_read_input_tokens.unwrap_or(0),
+        }
+    }
+}
+
 /// Structured tool data for Claude tools based on real samples
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 #[serde(tag = "name", content = "input")]
@@ -2106,6 +2152,26 @@ mod tests {
         assert_eq!(entries.len(), 0); // Should be ignored like in old implementation
     }
 
+    #[test]
+    fn test_result_message_usage() {
+        let result_json = r#"{"type":"result","subtype":"success","is_error":false,"result":"Done","usage":{"input_tokens":120,"output_tokens":45,"cache_creation_input_tokens":300,"cache_read_input_tokens":700}}"#;
+        let parsed: ClaudeJson = serde_json::from_str(result_json).unwrap();
+
+        let entries = normalize(&parsed, "");
+        assert_eq!(entries.len(), 1);
+        let NormalizedEntryType::Usage { usage } = &entries[0].entry_type else {
+            panic!("expected usage entry, got {:?}", entries[0].entry_type);
+        };
+        assert_eq!(
+            *usage,
+            TokenUsage {
+                input_tokens: 120,
+                output_tokens: 45,
+                cached_tokens: 1000,
+            }
+        );
+    }
+
     #[test]
     fn test_thinking_content() {
         let thinking_json = r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"thinking","thinking":"Let me think about this..."}]}}"#;
@@ -2216,6 +2282,7 @@ mod tests {
             approvals: None,
             model: None,
             append_prompt: AppendPrompt::default(),
+            prompt_affixes: PromptAffixes::default(),
             dangerously_skip_permissions: None,
             cmd: crate::command::CmdOverrides {
                 base_command_override: None,
@@ -2253,6 +2320,38 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_truncated_json_line_becomes_parse_error() {
+        use std::sync::Arc;
+
+        use workspace_utils::{log_msg::LogMsg, msg_store::MsgStore};
+
+        let msg_store = Arc::new(MsgStore::new());
+        msg_store.push_stdout("{\"type\":\"assistant\",\"message\":{\"role\":\n".to_string());
+        msg_store.push_stdout("plain text output\n".to_string());
+        msg_store.push_finished();
+
+        ClaudeLogProcessor::process_logs(
+            msg_store.clone(),
+            std::path::Path::new("/tmp/test-worktree"),
+            EntryIndexProvider::start_from(&msg_store),
+            HistoryStrategy::Default,
+        );
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+        let patches: Vec<String> = msg_store
+            .get_history()
+            .into_iter()
+            .filter_map(|msg| match msg {
+                LogMsg::JsonPatch(patch) => serde_json::to_string(&patch).ok(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(patches.len(), 2);
+        assert!(patches[0].contains(r#""type":"parse_error""#));
+        assert!(patches[1].contains(r#""type":"system_message""#));
+    }
+
     #[test]
     fn test_session_id_extraction() {
         let system_json = r#"{"type":"system","session_id":"test-session-123"}"#;
@@ -32,11 +32,13 @@ use self::{
 use crate::{
     approvals::ExecutorApprovalService,
     command::{CmdOverrides, CommandBuilder, CommandParts, apply_overrides},
+    env::ExecutionEnv,
     executors::{
-        AppendPrompt, AvailabilityInfo, ExecutorError, ExecutorExitResult, SpawnedChild,
-        StandardCodingAgentExecutor,
+        AppendPrompt, AvailabilityInfo, ExecutorError, ExecutorExitResult, PromptAffixes,
+        SpawnedChild, StandardCodingAgentExecutor,
         codex::{jsonrpc::ExitSignalSender, normalize_logs::Error},
     },
+    mcp_config::{McpServerDefinition, codex_mcp_overrides},
     stdout_dup::create_stdout_pipe_writer,
 };
 
@@ -106,6 +108,8 @@ pub enum ReasoningSummaryFormat {
 pub struct Codex {
     #[serde(default)]
     pub append_prompt: AppendPrompt,
+    #[serde(flatten)]
+    pub prompt_affixes: PromptAffixes,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sandbox: Option<SandboxMode>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -147,9 +151,15 @@ impl StandardCodingAgentExecutor for Codex {
         self.approvals = Some(approvals);
     }
 
-    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
         let command_parts = self.build_command_builder().build_initial()?;
-        self.spawn(current_dir, prompt, command_parts, None).await
+        self.spawn(current_dir, prompt, command_parts, None, env)
+            .await
     }
 
     async fn spawn_follow_up(
@@ -157,9 +167,10 @@ impl StandardCodingAgentExecutor for Codex {
         current_dir: &Path,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let command_parts = self.build_command_builder().build_follow_up(&[])?;
-        self.spawn(current_dir, prompt, command_parts, Some(session_id))
+        self.spawn(current_dir, prompt, command_parts, Some(session_id), env)
             .await
     }
 
@@ -215,7 +226,11 @@ impl Codex {
         apply_overrides(builder, &self.cmd)
     }
 
-    fn build_new_conversation_params(&self, cwd: &Path) -> NewConversationParams {
+    fn build_new_conversation_params(
+        &self,
+        cwd: &Path,
+        mcp_servers: &[McpServerDefinition],
+    ) -> NewConversationParams {
         let sandbox = match self.sandbox.as_ref() {
             None | Some(SandboxMode::Auto) => Some(CodexSandboxMode::WorkspaceWrite), // match the Auto preset in codex
             Some(SandboxMode::ReadOnly) => Some(CodexSandboxMode::ReadOnly),
@@ -241,7 +256,7 @@ impl Codex {
             cwd: Some(cwd.to_string_lossy().to_string()),
             approval_policy,
             sandbox,
-            config: self.build_config_overrides(),
+            config: self.build_config_overrides(mcp_servers),
             base_instructions: self.base_instructions.clone(),
             include_apply_patch_tool: self.include_apply_patch_tool,
             model_provider: self.model_provider.clone(),
@@ -250,8 +265,12 @@ impl Codex {
         }
     }
 
-    fn build_config_overrides(&self) -> Option<HashMap<String, Value>> {
-        let mut overrides = HashMap::new();
+    /// Reasoning settings plus the project's MCP servers for this run
+    fn build_config_overrides(
+        &self,
+        mcp_servers: &[McpServerDefinition],
+    ) -> Option<HashMap<String, Value>> {
+        let mut overrides = codex_mcp_overrides(mcp_servers);
 
         if let Some(effort) = &self.model_reasoning_effort {
             overrides.insert(
@@ -289,8 +308,11 @@ impl Codex {
         prompt: &str,
         command_parts: CommandParts,
         resume_session: Option<&str>,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_with(&self.prompt_affixes, prompt);
         let (program_path, args) = command_parts.into_resolved().await?;
 
         let mut process = Command::new(program_path);
@@ -304,6 +326,7 @@ impl Codex {
             .env("NODE_NO_WARNINGS", "1")
             .env("NO_COLOR", "1")
             .env("RUST_LOG", "error");
+        env.apply_to_command(&mut process);
 
         let mut child = process.group_spawn()?;
 
@@ -317,7 +340,7 @@ impl Codex {
         let new_stdout = create_stdout_pipe_writer(&mut child)?;
         let (exit_signal_tx, exit_signal_rx) = tokio::sync::oneshot::channel();
 
-        let params = self.build_new_conversation_params(current_dir);
+        let params = self.build_new_conversation_params(current_dir, &env.mcp_servers);
         let resume_session = resume_session.map(|s| s.to_string());
         let auto_approve = matches!(
             (&self.sandbox, &self.ask_for_approval),
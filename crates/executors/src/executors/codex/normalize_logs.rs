@@ -38,8 +38,8 @@ use crate::{
     executors::codex::session::SessionHandler,
     logs::{
         ActionType, CommandExitStatus, CommandRunResult, FileChange, NormalizedEntry,
-        NormalizedEntryError, NormalizedEntryType, TodoItem, ToolResult, ToolResultValueType,
-        ToolStatus,
+        NormalizedEntryError, NormalizedEntryType, TodoItem, TokenUsage, ToolResult,
+        ToolResultValueType, ToolStatus,
         stderr_processor::normalize_stderr_logs,
         utils::{
             ConversationPatch, EntryIndexProvider,
@@ -219,6 +219,7 @@ struct LogState {
     patches: HashMap<String, PatchState>,
     web_searches: HashMap<String, WebSearchState>,
     token_usage_info: Option<TokenUsageInfo>,
+    usage_entry_index: Option<usize>,
 }
 
 enum StreamingTextKind {
@@ -237,6 +238,7 @@ impl LogState {
             patches: HashMap::new(),
             web_searches: HashMap::new(),
             token_usage_info: None,
+            usage_entry_index: None,
         }
     }
 
@@ -352,6 +354,15 @@ fn normalize_file_changes(
         .collect()
 }
 
+fn token_usage(info: &TokenUsageInfo) -> TokenUsage {
+    let total = &info.total_token_usage;
+    TokenUsage {
+        input_tokens: total.input_tokens.max(0) as u64,
+        output_tokens: total.output_tokens.max(0) as u64,
+        cached_tokens: total.cached_input_tokens.max(0) as u64,
+    }
+}
+
 fn format_todo_status(status: &StepStatus) -> String {
     match status {
         StepStatus::Pending => "pending",
@@ -973,6 +984,15 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
                 }
                 EventMsg::TokenCount(payload) => {
                     if let Some(info) = payload.info {
+                        // Codex reports running totals, so keep a single entry up to date
+                        let entry = NormalizedEntry::usage(token_usage(&info));
+                        match state.usage_entry_index {
+                            Some(index) => replace_normalized_entry(&msg_store, index, entry),
+                            None => {
+                                state.usage_entry_index =
+                                    Some(add_normalized_entry(&msg_store, &entry_index, entry));
+                            }
+                        }
                         state.token_usage_info = Some(info);
                     }
                 }
@@ -1111,14 +1131,7 @@ impl ToNormalizedEntry for Error {
                 content: error.clone(),
                 metadata: None,
             },
-            Error::AuthRequired { error } => NormalizedEntry {
-                timestamp: None,
-                entry_type: NormalizedEntryType::ErrorMessage {
-                    error_type: NormalizedEntryError::SetupRequired,
-                },
-                content: error.clone(),
-                metadata: None,
-            },
+            Error::AuthRequired { error } => NormalizedEntry::auth_required(error.clone()),
         }
     }
 }
@@ -23,8 +23,10 @@ use workspace_utils::{msg_store::MsgStore, path::get_vibe_kanban_temp_dir};
 
 use crate::{
     command::{CmdOverrides, CommandBuilder, apply_overrides},
+    env::ExecutionEnv,
     executors::{
-        AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+        AppendPrompt, AvailabilityInfo, ExecutorError, PromptAffixes, SpawnedChild,
+        StandardCodingAgentExecutor,
     },
     logs::{
         NormalizedEntry, NormalizedEntryType, plain_text_processor::PlainTextLogProcessor,
@@ -37,6 +39,8 @@ use crate::{
 pub struct Copilot {
     #[serde(default)]
     pub append_prompt: AppendPrompt,
+    #[serde(flatten)]
+    pub prompt_affixes: PromptAffixes,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -97,14 +101,21 @@ impl Copilot {
 
 #[async_trait]
 impl StandardCodingAgentExecutor for Copilot {
-    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
         let log_dir = Self::create_temp_log_dir(current_dir).await?;
         let command_parts = self
             .build_command_builder(&log_dir.to_string_lossy())
             .build_initial()?;
         let (program_path, args) = command_parts.into_resolved().await?;
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_with(&self.prompt_affixes, prompt);
 
         let mut command = Command::new(program_path);
         command
@@ -115,6 +126,7 @@ impl StandardCodingAgentExecutor for Copilot {
             .current_dir(current_dir)
             .args(&args)
             .env("NODE_NO_WARNINGS", "1");
+        env.apply_to_command(&mut command);
 
         let mut child = command.group_spawn()?;
 
@@ -135,6 +147,7 @@ impl StandardCodingAgentExecutor for Copilot {
         current_dir: &Path,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let log_dir = Self::create_temp_log_dir(current_dir).await?;
         let command_parts = self
@@ -142,7 +155,9 @@ impl StandardCodingAgentExecutor for Copilot {
             .build_follow_up(&["--resume".to_string(), session_id.to_string()])?;
         let (program_path, args) = command_parts.into_resolved().await?;
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_with(&self.prompt_affixes, prompt);
 
         let mut command = Command::new(program_path);
 
@@ -154,6 +169,7 @@ impl StandardCodingAgentExecutor for Copilot {
             .current_dir(current_dir)
             .args(&args)
             .env("NODE_NO_WARNINGS", "1");
+        env.apply_to_command(&mut command);
 
         let mut child = command.group_spawn()?;
 
@@ -1,5 +1,11 @@
 use core::str;
-use std::{collections::HashMap, path::Path, process::Stdio, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use command_group::AsyncCommandGroup;
@@ -17,8 +23,10 @@ use workspace_utils::{
 
 use crate::{
     command::{CmdOverrides, CommandBuilder, apply_overrides},
+    env::ExecutionEnv,
     executors::{
-        AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+        AppendPrompt, AvailabilityInfo, ExecutorError, PromptAffixes, SpawnedChild,
+        StandardCodingAgentExecutor,
     },
     logs::{
         ActionType, FileChange, NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
@@ -35,6 +43,8 @@ const CURSOR_AUTH_REQUIRED_MSG: &str = "Authentication required. Please run 'cur
 pub struct CursorAgent {
     #[serde(default)]
     pub append_prompt: AppendPrompt,
+    #[serde(flatten)]
+    pub prompt_affixes: PromptAffixes,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[schemars(description = "Force allow commands unless explicitly denied")]
     pub force: Option<bool>,
@@ -68,14 +78,21 @@ impl CursorAgent {
 
 #[async_trait]
 impl StandardCodingAgentExecutor for CursorAgent {
-    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
         mcp::ensure_mcp_server_trust(self, current_dir).await;
 
         let command_parts = self.build_command_builder().build_initial()?;
 
         let (executable_path, args) = command_parts.into_resolved().await?;
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_with(&self.prompt_affixes, prompt);
 
         let mut command = Command::new(executable_path);
         command
@@ -85,6 +102,7 @@ impl StandardCodingAgentExecutor for CursorAgent {
             .stderr(Stdio::piped())
             .current_dir(current_dir)
             .args(&args);
+        env.apply_to_command(&mut command);
 
         let mut child = command.group_spawn()?;
 
@@ -101,6 +119,7 @@ impl StandardCodingAgentExecutor for CursorAgent {
         current_dir: &Path,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         mcp::ensure_mcp_server_trust(self, current_dir).await;
 
@@ -109,7 +128,9 @@ impl StandardCodingAgentExecutor for CursorAgent {
             .build_follow_up(&["--resume".to_string(), session_id.to_string()])?;
         let (executable_path, args) = command_parts.into_resolved().await?;
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_with(&self.prompt_affixes, prompt);
 
         let mut command = Command::new(executable_path);
         command
@@ -119,6 +140,7 @@ impl StandardCodingAgentExecutor for CursorAgent {
             .stderr(Stdio::piped())
             .current_dir(current_dir)
             .args(&args);
+        env.apply_to_command(&mut command);
 
         let mut child = command.group_spawn()?;
 
@@ -158,14 +180,7 @@ impl StandardCodingAgentExecutor for CursorAgent {
             while let Some(Ok(chunk)) = stderr.next().await {
                 let content = strip_ansi_escapes::strip_str(&chunk);
                 if content.contains(CURSOR_AUTH_REQUIRED_MSG) {
-                    let error_message = NormalizedEntry {
-                        timestamp: None,
-                        entry_type: NormalizedEntryType::ErrorMessage {
-                            error_type: NormalizedEntryError::SetupRequired,
-                        },
-                        content: content.to_string(),
-                        metadata: None,
-                    };
+                    let error_message = NormalizedEntry::auth_required(content.to_string());
                     let id = entry_index_provider_stderr.next();
                     msg_store_stderr
                         .push_patch(ConversationPatch::add_normalized_entry(id, error_message));
@@ -479,6 +494,15 @@ impl StandardCodingAgentExecutor for CursorAgent {
             return AvailabilityInfo::NotFound;
         }
 
+        let xdg_config_home = std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from);
+        if let Some(timestamp) = dirs::home_dir()
+            .and_then(|home| last_login_timestamp(&home, xdg_config_home.as_deref()))
+        {
+            return AvailabilityInfo::LoginDetected {
+                last_auth_timestamp: timestamp,
+            };
+        }
+
         let config_files_found = self
             .default_mcp_config_path()
             .map(|p| p.exists())
@@ -491,6 +515,31 @@ impl StandardCodingAgentExecutor for CursorAgent {
         }
     }
 }
+/// When `cursor-agent login` last wrote its credentials, as a Unix timestamp.
+///
+/// On Linux the tokens go to `$XDG_CONFIG_HOME/cursor/auth.json` (by default
+/// `~/.config/cursor/auth.json`); on macOS they live in the Keychain, so the
+/// `authInfo` that login records in `~/.cursor/cli-config.json` is used as well.
+fn last_login_timestamp(home: &Path, xdg_config_home: Option<&Path>) -> Option<i64> {
+    let config_dir = xdg_config_home
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| home.join(".config"));
+    let token_file = config_dir.join("cursor").join("auth.json");
+    let cli_config = home.join(".cursor").join("cli-config.json");
+    let cli_config_has_login = std::fs::read_to_string(&cli_config)
+        .ok()
+        .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+        .is_some_and(|config| config.get("authInfo").is_some_and(|info| !info.is_null()));
+
+    [Some(token_file), cli_config_has_login.then_some(cli_config)]
+        .into_iter()
+        .flatten()
+        .filter_map(|path| std::fs::metadata(path).ok()?.modified().ok())
+        .filter_map(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .max()
+}
+
 /* ===========================
 Typed Cursor JSON structures
 =========================== */
@@ -1194,12 +1243,37 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_last_login_timestamp_reads_the_login_files() {
+        let home = std::env::temp_dir().join(format!("vk-cursor-home-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(home.join(".cursor")).unwrap();
+        assert_eq!(last_login_timestamp(&home, None), None);
+
+        // A CLI config without a login doesn't count
+        let cli_config = home.join(".cursor").join("cli-config.json");
+        std::fs::write(&cli_config, r#"{"authInfo": null}"#).unwrap();
+        assert_eq!(last_login_timestamp(&home, None), None);
+        std::fs::write(&cli_config, r#"{"authInfo": {"email": "dev@example.com"}}"#).unwrap();
+        assert!(last_login_timestamp(&home, None).is_some());
+        std::fs::remove_file(&cli_config).unwrap();
+
+        let xdg_config_home = home.join("xdg");
+        std::fs::create_dir_all(xdg_config_home.join("cursor")).unwrap();
+        std::fs::write(xdg_config_home.join("cursor").join("auth.json"), "{}").unwrap();
+        assert_eq!(last_login_timestamp(&home, None), None);
+        let timestamp = last_login_timestamp(&home, Some(&xdg_config_home)).unwrap();
+        assert!((chrono::Utc::now().timestamp() - timestamp).abs() < 60);
+
+        std::fs::remove_dir_all(&home).unwrap();
+    }
+
     #[tokio::test]
     async fn test_cursor_streaming_patch_generation() {
         // Avoid relying on feature flag in tests; construct with a dummy command
         let executor = CursorAgent {
             // No command field needed anymore
             append_prompt: AppendPrompt::default(),
+            prompt_affixes: PromptAffixes::default(),
             force: None,
             model: None,
             cmd: Default::default(),
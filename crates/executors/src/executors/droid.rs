@@ -11,7 +11,10 @@ use workspace_utils::msg_store::MsgStore;
 
 use crate::{
     command::CommandParts,
-    executors::{AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
+    env::ExecutionEnv,
+    executors::{
+        AppendPrompt, ExecutorError, PromptAffixes, SpawnedChild, StandardCodingAgentExecutor,
+    },
     logs::utils::EntryIndexProvider,
 };
 
@@ -55,6 +58,8 @@ pub enum ReasoningEffortLevel {
 pub struct Droid {
     #[serde(default)]
     pub append_prompt: AppendPrompt,
+    #[serde(flatten)]
+    pub prompt_affixes: PromptAffixes,
 
     #[serde(default = "default_autonomy")]
     #[schemars(
@@ -108,6 +113,7 @@ async fn spawn(
     command_parts: CommandParts,
     prompt: &String,
     current_dir: &Path,
+    env: &ExecutionEnv,
 ) -> Result<SpawnedChild, ExecutorError> {
     let (program_path, args) = command_parts.into_resolved().await?;
 
@@ -119,6 +125,7 @@ async fn spawn(
         .stderr(Stdio::piped())
         .current_dir(current_dir)
         .args(args);
+    env.apply_to_command(&mut command);
 
     let mut child = command.group_spawn()?;
 
@@ -132,11 +139,18 @@ async fn spawn(
 
 #[async_trait]
 impl StandardCodingAgentExecutor for Droid {
-    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
         let droid_command = self.build_command_builder().build_initial()?;
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_with(&self.prompt_affixes, prompt);
 
-        spawn(droid_command, &combined_prompt, current_dir).await
+        spawn(droid_command, &combined_prompt, current_dir, env).await
     }
 
     async fn spawn_follow_up(
@@ -144,6 +158,7 @@ impl StandardCodingAgentExecutor for Droid {
         current_dir: &Path,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let forked_session_id = fork_session(session_id).map_err(|e| {
             ExecutorError::FollowUpNotSupported(format!(
@@ -153,9 +168,11 @@ impl StandardCodingAgentExecutor for Droid {
         let continue_cmd = self
             .build_command_builder()
             .build_follow_up(&["--session-id".to_string(), forked_session_id.clone()])?;
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_with(&self.prompt_affixes, prompt);
 
-        spawn(continue_cmd, &combined_prompt, current_dir).await
+        spawn(continue_cmd, &combined_prompt, current_dir, env).await
     }
 
     fn normalize_logs(&self, msg_store: Arc<MsgStore>, current_dir: &Path) {
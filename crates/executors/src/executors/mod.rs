@@ -1,10 +1,10 @@
-use std::{path::Path, sync::Arc};
+use std::{path::Path, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use command_group::AsyncGroupChild;
 use enum_dispatch::enum_dispatch;
 use futures_io::Error as FuturesIoError;
-use schemars::JsonSchema;
+use schemars::{JsonSchema, generate::SchemaSettings};
 use serde::{Deserialize, Serialize};
 use sqlx::Type;
 use strum_macros::{Display, EnumDiscriminants, EnumString, VariantNames};
@@ -16,6 +16,7 @@ use crate::{
     actions::ExecutorAction,
     approvals::ExecutorApprovalService,
     command::CommandBuildError,
+    env::ExecutionEnv,
     executors::{
         amp::Amp, claude::ClaudeCode, codex::Codex, copilot::Copilot, cursor::CursorAgent,
         droid::Droid, gemini::Gemini, opencode::Opencode, qwen::QwenCode,
@@ -107,20 +108,51 @@ impl BaseCodingAgent {
     pub fn compact_command(&self) -> Option<&'static str> {
         match self {
             Self::ClaudeCode => Some("/compact"),
-            // Gemini uses /compress but currently has no InputSender implementation
-            // TODO: Implement InputSender for ACP harness to support this
-            Self::Gemini => None,
+            // Gemini compresses its context with /compress, delivered through the ACP harness
+            Self::Gemini => Some("/compress"),
             // Codex uses compact_prompt parameter at conversation start, not interactive command
             Self::Codex => None,
+            // Amp accepts /compact as a streamed user message while the turn is running
+            Self::Amp => Some("/compact"),
+            // Droid has no compaction: `droid exec` takes no input after the prompt and
+            // has no option to condense a resumed session
+            Self::Droid => None,
             // Other agents - not yet known if they support compaction
-            Self::Amp
-            | Self::Opencode
-            | Self::CursorAgent
-            | Self::QwenCode
-            | Self::Copilot
-            | Self::Droid => None,
+            Self::Opencode | Self::CursorAgent | Self::QwenCode | Self::Copilot => None,
         }
     }
+
+    /// Whether the executor's configuration has a `model` setting
+    pub fn supports_model(&self) -> bool {
+        !matches!(self, Self::Amp | Self::QwenCode)
+    }
+
+    /// Draft-07 JSON schema for this executor's profile configuration, with
+    /// every subschema inlined so form generators can render it directly.
+    pub fn config_schema(&self) -> serde_json::Value {
+        match self {
+            Self::ClaudeCode => config_schema_for::<ClaudeCode>(),
+            Self::Amp => config_schema_for::<Amp>(),
+            Self::Gemini => config_schema_for::<Gemini>(),
+            Self::Codex => config_schema_for::<Codex>(),
+            Self::Opencode => config_schema_for::<Opencode>(),
+            Self::CursorAgent => config_schema_for::<CursorAgent>(),
+            Self::QwenCode => config_schema_for::<QwenCode>(),
+            Self::Copilot => config_schema_for::<Copilot>(),
+            Self::Droid => config_schema_for::<Droid>(),
+        }
+    }
+}
+
+fn config_schema_for<T: JsonSchema>() -> serde_json::Value {
+    let mut settings = SchemaSettings::draft07();
+    settings.inline_subschemas = true;
+    let mut schema = serde_json::Value::from(settings.into_generator().into_root_schema_for::<T>());
+    // Remove the title from root schema to prevent RJSF from creating an outer field container
+    if let Some(obj) = schema.as_object_mut() {
+        obj.remove("title");
+    }
+    schema
 }
 
 impl CodingAgent {
@@ -170,6 +202,25 @@ impl CodingAgent {
         }
     }
 
+    /// Use `model` unless the profile already picked one. Returns false if the
+    /// agent has no `model` setting.
+    pub fn apply_default_model(&mut self, model: &str) -> bool {
+        let slot = match self {
+            Self::ClaudeCode(agent) => &mut agent.model,
+            Self::Gemini(agent) => &mut agent.model,
+            Self::Codex(agent) => &mut agent.model,
+            Self::Opencode(agent) => &mut agent.model,
+            Self::CursorAgent(agent) => &mut agent.model,
+            Self::Copilot(agent) => &mut agent.model,
+            Self::Droid(agent) => &mut agent.model,
+            Self::Amp(_) | Self::QwenCode(_) => return false,
+        };
+        if slot.is_none() {
+            *slot = Some(model.to_string());
+        }
+        true
+    }
+
     pub fn supports_mcp(&self) -> bool {
         self.default_mcp_config_path().is_some()
     }
@@ -180,15 +231,29 @@ impl CodingAgent {
             | Self::Amp(_)
             | Self::Gemini(_)
             | Self::QwenCode(_)
-            | Self::Droid(_) => vec![BaseAgentCapability::SessionFork],
+            | Self::Droid(_)
+            | Self::Copilot(_) => vec![BaseAgentCapability::SessionFork],
             Self::Codex(_) => vec![
                 BaseAgentCapability::SessionFork,
                 BaseAgentCapability::SetupHelper,
             ],
             Self::CursorAgent(_) => vec![BaseAgentCapability::SetupHelper],
-            Self::Opencode(_) | Self::Copilot(_) => vec![],
+            Self::Opencode(_) => vec![],
         }
     }
+
+    /// Age of the agent's login if it is older than `max_age`. Only agents with a
+    /// setup helper are checked, since re-running setup is how their login is renewed.
+    pub fn stale_login_age(&self, max_age: Duration) -> Option<Duration> {
+        if !self
+            .capabilities()
+            .contains(&BaseAgentCapability::SetupHelper)
+        {
+            return None;
+        }
+        let now = chrono::Utc::now().timestamp();
+        self.get_availability_info().stale_login_age(now, max_age)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -207,6 +272,23 @@ impl AvailabilityInfo {
             AvailabilityInfo::LoginDetected { .. } | AvailabilityInfo::InstallationFound
         )
     }
+
+    /// Time since the detected login, measured from `now` (seconds since the epoch)
+    pub fn login_age(&self, now: i64) -> Option<Duration> {
+        match self {
+            AvailabilityInfo::LoginDetected {
+                last_auth_timestamp,
+            } => Some(Duration::from_secs(
+                now.saturating_sub(*last_auth_timestamp).max(0) as u64,
+            )),
+            _ => None,
+        }
+    }
+
+    /// The login's age at `now`, if it is older than `max_age`
+    pub fn stale_login_age(&self, now: i64, max_age: Duration) -> Option<Duration> {
+        self.login_age(now).filter(|age| *age > max_age)
+    }
 }
 
 #[async_trait]
@@ -217,12 +299,18 @@ pub trait StandardCodingAgentExecutor {
     /// Set orchestrator mode (enables orchestrator-specific features like vibe_kanban MCP)
     fn set_orchestrator_mode(&mut self, _is_orchestrator: bool) {}
 
-    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError>;
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError>;
     async fn spawn_follow_up(
         &self,
         current_dir: &Path,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError>;
     fn normalize_logs(&self, _raw_logs_event_store: Arc<MsgStore>, _worktree_path: &Path);
 
@@ -319,6 +407,57 @@ impl AppendPrompt {
             AppendPrompt(None) => prompt.to_string(),
         }
     }
+
+    /// `prompt` as sent to the agent: with the appended text, surrounded by the
+    /// profile's prefix and suffix. Executors build every prompt, initial and
+    /// follow-up, through this.
+    pub fn combine_with(&self, affixes: &PromptAffixes, prompt: &str) -> String {
+        affixes.wrap(&self.combine_prompt(prompt))
+    }
+}
+
+/// Standing instructions placed around every prompt, initial and follow-up, sent
+/// with a profile
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct PromptAffixes {
+    #[schemars(
+        title = "Prompt Prefix",
+        description = "Text placed before every prompt, including follow-ups",
+        extend("format" = "textarea")
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_prefix: Option<String>,
+    #[schemars(
+        title = "Prompt Suffix",
+        description = "Text placed after every prompt, including follow-ups",
+        extend("format" = "textarea")
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_suffix: Option<String>,
+}
+
+impl PromptAffixes {
+    /// Surround `prompt` with the prefix and suffix, each separated by a blank line
+    pub fn wrap(&self, prompt: &str) -> String {
+        let non_empty = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let mut combined = String::new();
+        if let Some(prefix) = non_empty(&self.prompt_prefix) {
+            combined.push_str(&prefix);
+            combined.push_str("\n\n");
+        }
+        combined.push_str(prompt);
+        if let Some(suffix) = non_empty(&self.prompt_suffix) {
+            combined.push_str("\n\n");
+            combined.push_str(&suffix);
+        }
+        combined
+    }
 }
 
 #[cfg(test)]
@@ -326,6 +465,7 @@ mod tests {
     use std::str::FromStr;
 
     use super::*;
+    use crate::{actions::ExecutorActionType, profile::ExecutorProfileId};
 
     #[test]
     fn test_cursor_agent_deserialization() {
@@ -352,4 +492,139 @@ mod tests {
         assert!(result.is_ok(), "CURSOR should deserialize via serde");
         assert_eq!(result.unwrap(), BaseCodingAgent::CursorAgent);
     }
+
+    #[test]
+    fn test_amp_compaction() {
+        assert_eq!(BaseCodingAgent::Amp.compact_command(), Some("/compact"));
+
+        let amp: CodingAgent = serde_json::from_value(serde_json::json!({ "AMP": {} })).unwrap();
+        assert!(
+            amp.capabilities()
+                .contains(&BaseAgentCapability::SessionFork)
+        );
+    }
+
+    #[test]
+    fn test_prompt_affixes_round_trip_and_wrap() {
+        let profile = serde_json::json!({
+            "GEMINI": {
+                "append_prompt": null,
+                "prompt_prefix": "Always write tests.",
+                "prompt_suffix": "  ",
+                "model": "gemini-2.5-flash"
+            }
+        });
+        let agent: CodingAgent = serde_json::from_value(profile).unwrap();
+        let CodingAgent::Gemini(gemini) = &agent else {
+            panic!("expected a Gemini executor");
+        };
+        assert_eq!(
+            gemini.prompt_affixes.wrap("Fix the bug."),
+            "Always write tests.\n\nFix the bug."
+        );
+
+        let serialized = serde_json::to_value(&agent).unwrap();
+        assert_eq!(serialized["GEMINI"]["prompt_prefix"], "Always write tests.");
+        assert_eq!(
+            serde_json::from_value::<CodingAgent>(serialized).unwrap(),
+            agent
+        );
+
+        let affixes = PromptAffixes {
+            prompt_prefix: None,
+            prompt_suffix: Some("Reply in English.".to_string()),
+        };
+        assert_eq!(affixes.wrap("Hi"), "Hi\n\nReply in English.");
+        assert_eq!(PromptAffixes::default().wrap("Hi"), "Hi");
+        assert_eq!(
+            AppendPrompt(Some(" Be brief.".to_string())).combine_with(&affixes, "Hi"),
+            "Hi Be brief.\n\nReply in English."
+        );
+    }
+
+    #[test]
+    fn test_login_age() {
+        let login = AvailabilityInfo::LoginDetected {
+            last_auth_timestamp: 1_000,
+        };
+        assert_eq!(login.login_age(4_600), Some(Duration::from_secs(3_600)));
+        // A login stamped in the future (clock skew) counts as brand new
+        assert_eq!(login.login_age(500), Some(Duration::ZERO));
+        assert_eq!(AvailabilityInfo::InstallationFound.login_age(4_600), None);
+        assert_eq!(AvailabilityInfo::NotFound.login_age(4_600), None);
+
+        let hour = Duration::from_secs(3_600);
+        assert_eq!(login.stale_login_age(4_600, hour), None);
+        assert_eq!(
+            login.stale_login_age(4_601, hour),
+            Some(hour + Duration::from_secs(1))
+        );
+        assert_eq!(
+            AvailabilityInfo::NotFound.stale_login_age(4_601, hour),
+            None
+        );
+    }
+
+    #[test]
+    fn test_stale_login_age_only_checks_agents_with_setup_helpers() {
+        let gemini: CodingAgent =
+            serde_json::from_value(serde_json::json!({ "GEMINI": {} })).unwrap();
+        assert_eq!(gemini.stale_login_age(Duration::ZERO), None);
+    }
+
+    #[test]
+    fn test_droid_follow_ups_resume_the_session() {
+        // Droid can't compact, so long sessions rely on forking alone
+        assert_eq!(BaseCodingAgent::Droid.compact_command(), None);
+
+        let droid: CodingAgent =
+            serde_json::from_value(serde_json::json!({ "DROID": {} })).unwrap();
+        assert!(
+            droid
+                .capabilities()
+                .contains(&BaseAgentCapability::SessionFork)
+        );
+
+        let follow_up = ExecutorActionType::coding_agent_request(
+            "Fix the failing test.".to_string(),
+            Some("droid-session".to_string()),
+            ExecutorProfileId::new(BaseCodingAgent::Droid),
+            false,
+        );
+        assert!(matches!(
+            follow_up,
+            ExecutorActionType::CodingAgentFollowUpRequest(ref request)
+                if request.session_id == "droid-session"
+        ));
+    }
+
+    #[test]
+    fn test_copilot_session_continuity() {
+        let copilot: CodingAgent =
+            serde_json::from_value(serde_json::json!({ "COPILOT": {} })).unwrap();
+        assert_eq!(
+            copilot.capabilities(),
+            vec![BaseAgentCapability::SessionFork]
+        );
+
+        let profile = ExecutorProfileId::new(BaseCodingAgent::Copilot);
+        let follow_up = ExecutorActionType::coding_agent_request(
+            "Continue".to_string(),
+            Some("0c9a5b2e-5f3c-4d3e-9a7b-1c2d3e4f5a6b".to_string()),
+            profile.clone(),
+            false,
+        );
+        assert!(matches!(
+            follow_up,
+            ExecutorActionType::CodingAgentFollowUpRequest(ref request)
+                if request.session_id == "0c9a5b2e-5f3c-4d3e-9a7b-1c2d3e4f5a6b"
+        ));
+
+        let initial =
+            ExecutorActionType::coding_agent_request("Start".to_string(), None, profile, false);
+        assert!(matches!(
+            initial,
+            ExecutorActionType::CodingAgentInitialRequest(_)
+        ));
+    }
 }
@@ -20,9 +20,10 @@ use workspace_utils::{msg_store::MsgStore, path::make_path_relative};
 
 use crate::{
     command::{CmdOverrides, CommandBuilder, apply_overrides},
+    env::ExecutionEnv,
     executors::{
-        AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
-        opencode::share_bridge::Bridge as ShareBridge,
+        AppendPrompt, AvailabilityInfo, ExecutorError, PromptAffixes, SpawnedChild,
+        StandardCodingAgentExecutor, opencode::share_bridge::Bridge as ShareBridge,
     },
     logs::{
         ActionType, FileChange, NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
@@ -98,6 +99,8 @@ struct OcToolState {
 pub struct Opencode {
     #[serde(default)]
     pub append_prompt: AppendPrompt,
+    #[serde(flatten)]
+    pub prompt_affixes: PromptAffixes,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -128,13 +131,20 @@ impl Opencode {
 
 #[async_trait]
 impl StandardCodingAgentExecutor for Opencode {
-    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
         // Start a dedicated local share bridge bound to this opencode process
         let bridge = ShareBridge::start().await.map_err(ExecutorError::Io)?;
         let command_parts = self.build_command_builder().build_initial()?;
         let (program_path, args) = command_parts.into_resolved().await?;
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_with(&self.prompt_affixes, prompt);
 
         let mut command = Command::new(program_path);
         command
@@ -147,6 +157,7 @@ impl StandardCodingAgentExecutor for Opencode {
             .env("NODE_NO_WARNINGS", "1")
             .env("OPENCODE_AUTO_SHARE", "1")
             .env("OPENCODE_API", bridge.base_url.clone());
+        env.apply_to_command(&mut command);
 
         let mut child = match command.group_spawn() {
             Ok(c) => c,
@@ -192,6 +203,7 @@ impl StandardCodingAgentExecutor for Opencode {
         current_dir: &Path,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         // Start a dedicated local share bridge bound to this opencode process
         let bridge = ShareBridge::start().await.map_err(ExecutorError::Io)?;
@@ -200,7 +212,9 @@ impl StandardCodingAgentExecutor for Opencode {
             .build_follow_up(&["--session".to_string(), session_id.to_string()])?;
         let (program_path, args) = command_parts.into_resolved().await?;
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_with(&self.prompt_affixes, prompt);
 
         let mut command = Command::new(program_path);
         command
@@ -213,6 +227,7 @@ impl StandardCodingAgentExecutor for Opencode {
             .env("NODE_NO_WARNINGS", "1")
             .env("OPENCODE_AUTO_SHARE", "1")
             .env("OPENCODE_API", bridge.base_url.clone());
+        env.apply_to_command(&mut command);
 
         let mut child = match command.group_spawn() {
             Ok(c) => c,
@@ -455,8 +470,16 @@ impl Opencode {
         };
 
         while let Some(line) = lines.next().await {
-            let Ok(env) = serde_json::from_str::<ShareSyncEnvelope>(&line) else {
-                continue;
+            let env = match serde_json::from_str::<ShareSyncEnvelope>(&line) {
+                Ok(env) => env,
+                Err(e) => {
+                    tracing::warn!("Failed to parse Opencode share event: {}", e);
+                    msg_store.push_patch(ConversationPatch::add_normalized_entry(
+                        entry_index_counter.next(),
+                        NormalizedEntry::parse_error(e, &line),
+                    ));
+                    continue;
+                }
             };
             // Record session id once from stream
             if !session_id_set {
@@ -818,7 +841,18 @@ impl Opencode {
                     let patch = upsert_by_part(entry, id);
                     msg_store.push_patch(patch);
                 }
-                Err(_) => {}
+                Err(e) => {
+                    // Part types we don't render (steps, snapshots, ...) are expected to
+                    // fail; only a malformed text or tool part is worth surfacing
+                    let part_type = env.content.get("type").and_then(|t| t.as_str());
+                    if matches!(part_type, Some("text" | "tool")) {
+                        tracing::warn!("Failed to parse Opencode {:?} part: {}", part_type, e);
+                        msg_store.push_patch(ConversationPatch::add_normalized_entry(
+                            entry_index_counter.next(),
+                            NormalizedEntry::parse_error(e, &env.content.to_string()),
+                        ));
+                    }
+                }
             }
         }
     }
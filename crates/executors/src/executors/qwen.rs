@@ -8,9 +8,10 @@ use workspace_utils::msg_store::MsgStore;
 
 use crate::{
     command::{CmdOverrides, CommandBuilder, apply_overrides},
+    env::ExecutionEnv,
     executors::{
-        AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
-        gemini::AcpAgentHarness,
+        AppendPrompt, AvailabilityInfo, ExecutorError, PromptAffixes, SpawnedChild,
+        StandardCodingAgentExecutor, gemini::AcpAgentHarness,
     },
 };
 
@@ -18,6 +19,8 @@ use crate::{
 pub struct QwenCode {
     #[serde(default)]
     pub append_prompt: AppendPrompt,
+    #[serde(flatten)]
+    pub prompt_affixes: PromptAffixes,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub yolo: Option<bool>,
     #[serde(flatten)]
@@ -38,12 +41,19 @@ impl QwenCode {
 
 #[async_trait]
 impl StandardCodingAgentExecutor for QwenCode {
-    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
         let qwen_command = self.build_command_builder().build_initial()?;
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_with(&self.prompt_affixes, prompt);
         let harness = AcpAgentHarness::with_session_namespace("qwen_sessions");
         harness
-            .spawn_with_command(current_dir, combined_prompt, qwen_command)
+            .spawn_with_command(current_dir, combined_prompt, qwen_command, env)
             .await
     }
 
@@ -52,12 +62,21 @@ impl StandardCodingAgentExecutor for QwenCode {
         current_dir: &Path,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let qwen_command = self.build_command_builder().build_follow_up(&[])?;
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self
+            .append_prompt
+            .combine_with(&self.prompt_affixes, prompt);
         let harness = AcpAgentHarness::with_session_namespace("qwen_sessions");
         harness
-            .spawn_follow_up_with_command(current_dir, combined_prompt, session_id, qwen_command)
+            .spawn_follow_up_with_command(
+                current_dir,
+                combined_prompt,
+                session_id,
+                qwen_command,
+                env,
+            )
             .await
     }
 
@@ -87,3 +106,153 @@ impl StandardCodingAgentExecutor for QwenCode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::io::{AsyncBufReadExt, BufReader};
+    use workspace_utils::log_msg::LogMsg;
+
+    use super::*;
+    use crate::executors::{
+        ExecutorExitResult,
+        acp::{AcpEvent, SessionManager},
+    };
+
+    fn qwen(base_command_override: Option<String>) -> QwenCode {
+        QwenCode {
+            append_prompt: AppendPrompt::default(),
+            prompt_affixes: PromptAffixes::default(),
+            yolo: None,
+            cmd: CmdOverrides {
+                base_command_override,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_id_is_captured() {
+        let msg_store = Arc::new(MsgStore::new());
+        qwen(None).normalize_logs(msg_store.clone(), Path::new("/tmp/qwen-repo"));
+        let session_id = "2f6c7a4e-9a43-4c55-8d1f-3b0e6f1c2a9d";
+        msg_store.push_stdout(format!(
+            "{}\n",
+            AcpEvent::SessionStart(session_id.to_string())
+        ));
+        msg_store.push_finished();
+
+        let captured = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let found = msg_store.get_history().into_iter().find_map(|m| match m {
+                    LogMsg::SessionId(id) => Some(id),
+                    _ => None,
+                });
+                if let Some(id) = found {
+                    break id;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("session id pushed to the msg store");
+        assert_eq!(captured, session_id);
+    }
+
+    /// An ACP agent that answers every request and appends it to `<script>.requests`
+    #[cfg(unix)]
+    const FAKE_ACP_AGENT: &str = r#"#!/bin/sh
+while IFS= read -r line; do
+  printf '%s\n' "$line" >> "$0.requests"
+  id=$(printf '%s' "$line" | grep -o '"id":[^,}]*' | head -n 1)
+  case "$line" in
+    *'"method":"initialize"'*) result='{"protocolVersion":1,"agentCapabilities":{},"authMethods":[]}' ;;
+    *'"method":"session/new"'*) result='{"sessionId":"acp-session"}' ;;
+    *'"method":"session/prompt"'*) result='{"stopReason":"end_turn"}' ;;
+    *) continue ;;
+  esac
+  printf '{"jsonrpc":"2.0",%s,"result":%s}\n' "$id" "$result"
+done
+"#;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_follow_up_resumes_the_session() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("qwen-follow-up-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let agent = dir.join("fake-acp-agent");
+        std::fs::write(&agent, FAKE_ACP_AGENT).unwrap();
+        std::fs::set_permissions(&agent, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        // The session a previous run left behind
+        let sessions = SessionManager::new("qwen_sessions").unwrap();
+        let previous_id = uuid::Uuid::new_v4().to_string();
+        for event in [
+            AcpEvent::User("Add a README".to_string()),
+            AcpEvent::User("Also add a license".to_string()),
+        ] {
+            sessions
+                .append_raw_line(&previous_id, &event.to_string())
+                .unwrap();
+        }
+
+        let mut spawned = qwen(Some(agent.to_string_lossy().into_owned()))
+            .spawn_follow_up(&dir, "Now fix the typo", &previous_id, &ExecutionEnv::new())
+            .await
+            .unwrap();
+        let stdout = spawned.child.inner().stdout.take().unwrap();
+        let exit =
+            tokio::time::timeout(Duration::from_secs(10), spawned.exit_signal.take().unwrap())
+                .await
+                .expect("the follow-up turn finishes")
+                .unwrap();
+        assert!(matches!(exit, ExecutorExitResult::Success));
+
+        let mut lines = BufReader::new(stdout).lines();
+        let session_id = tokio::time::timeout(Duration::from_secs(5), async {
+            while let Some(line) = lines.next_line().await.unwrap() {
+                if let Ok(AcpEvent::SessionStart(id)) = line.parse() {
+                    return id;
+                }
+            }
+            panic!("no session start in the logs");
+        })
+        .await
+        .unwrap();
+        let _ = spawned.child.kill().await;
+
+        // The agent got the previous conversation and the new prompt
+        let requests = std::fs::read_to_string(dir.join("fake-acp-agent.requests")).unwrap();
+        let new_session = requests
+            .lines()
+            .find(|line| line.contains(r#""method":"session/new""#))
+            .unwrap();
+        assert!(new_session.contains("Also add a license"));
+        let prompt = requests
+            .lines()
+            .find(|line| line.contains(r#""method":"session/prompt""#))
+            .unwrap();
+        assert!(prompt.contains("RESUME CONTEXT"));
+        assert!(prompt.contains("Add a README"));
+        assert!(prompt.contains("Now fix the typo"));
+
+        // The forked session records each prompt once, without the resume wrapper
+        assert_ne!(session_id, previous_id);
+        let history = sessions.read_session_raw(&session_id).unwrap();
+        assert_eq!(
+            history.lines().collect::<Vec<_>>(),
+            [
+                r#"{"user":"Add a README"}"#,
+                r#"{"user":"Also add a license"}"#,
+                r#"{"user":"Now fix the typo"}"#,
+            ]
+        );
+
+        sessions.delete_session(&previous_id).unwrap();
+        sessions.delete_session(&session_id).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
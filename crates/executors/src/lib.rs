@@ -2,9 +2,12 @@ pub mod actions;
 pub mod approvals;
 pub mod command;
 pub mod conversation_export;
+pub mod env;
 pub mod executors;
 pub mod logs;
+pub mod mcp_calls;
 pub mod mcp_config;
 pub mod profile;
 pub mod stdout_dup;
 pub mod token_tracker;
+pub mod usage;
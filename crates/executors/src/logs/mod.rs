@@ -106,6 +106,25 @@ pub struct ContextUsage {
     pub is_estimated: bool,
 }
 
+/// Tokens an agent run consumed, as reported by the agent CLI once it finishes.
+/// Agents that don't report usage leave every count at zero.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, Default, PartialEq, Eq)]
+#[ts(export)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Input tokens served from (or written to) the prompt cache
+    pub cached_tokens: u64,
+}
+
+impl std::ops::AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cached_tokens += other.cached_tokens;
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct NormalizedConversation {
     pub entries: Vec<NormalizedEntry>,
@@ -119,6 +138,10 @@ pub struct NormalizedConversation {
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum NormalizedEntryError {
     SetupRequired,
+    /// The agent's credentials are missing or expired and the user must sign in again
+    AuthRequired,
+    /// A line of agent output couldn't be parsed into a conversation entry
+    ParseError,
     Other,
 }
 
@@ -151,6 +174,10 @@ pub enum NormalizedEntryType {
     ContextUsage {
         usage: ContextUsage,
     },
+    /// Final token usage for the run
+    Usage {
+        usage: TokenUsage,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -163,6 +190,49 @@ pub struct NormalizedEntry {
 }
 
 impl NormalizedEntry {
+    /// Error entry telling the user the agent needs to be re-authenticated.
+    pub fn auth_required(content: String) -> Self {
+        Self {
+            timestamp: None,
+            entry_type: NormalizedEntryType::ErrorMessage {
+                error_type: NormalizedEntryError::AuthRequired,
+            },
+            content,
+            metadata: None,
+        }
+    }
+
+    /// Error entry for agent output that failed to parse, so it shows up
+    /// inline instead of silently disappearing from the conversation.
+    pub fn parse_error(error: impl std::fmt::Display, line: &str) -> Self {
+        Self {
+            timestamp: None,
+            entry_type: NormalizedEntryType::ErrorMessage {
+                error_type: NormalizedEntryError::ParseError,
+            },
+            content: format!("Log parse error: {error}\n{line}"),
+            metadata: None,
+        }
+    }
+
+    pub fn usage(usage: TokenUsage) -> Self {
+        Self {
+            timestamp: None,
+            entry_type: NormalizedEntryType::Usage { usage },
+            content: String::new(),
+            metadata: None,
+        }
+    }
+
+    pub fn is_auth_required(&self) -> bool {
+        matches!(
+            self.entry_type,
+            NormalizedEntryType::ErrorMessage {
+                error_type: NormalizedEntryError::AuthRequired
+            }
+        )
+    }
+
     pub fn with_tool_status(&self, status: ToolStatus) -> Option<Self> {
         if let NormalizedEntryType::ToolUse {
             tool_name,
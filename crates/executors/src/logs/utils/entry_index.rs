@@ -33,9 +33,11 @@ impl EntryIndexProvider {
     }
 
     /// Create a provider starting from the maximum existing normalized-entry index
-    /// observed in prior JSON patches in `MsgStore`.
+    /// observed in prior JSON patches in `MsgStore`. Providers for the same store
+    /// share one counter, so entries appended from outside the executor's
+    /// normalizer never reuse an index it has handed out.
     pub fn start_from(msg_store: &MsgStore) -> Self {
-        let provider = EntryIndexProvider::new();
+        let provider = EntryIndexProvider(msg_store.entry_index());
 
         let max_index: Option<usize> = msg_store
             .get_history()
@@ -58,7 +60,7 @@ impl EntryIndexProvider {
             .max();
 
         let start_at = max_index.map_or(0, |n| n.saturating_add(1));
-        provider.0.store(start_at, Ordering::Relaxed);
+        provider.0.fetch_max(start_at, Ordering::Relaxed);
         provider
     }
 }
@@ -99,6 +101,21 @@ mod tests {
         assert_eq!(provider1.next(), 2);
     }
 
+    #[test]
+    fn test_providers_for_the_same_store_share_indexes() {
+        let store = MsgStore::new();
+        let normalizer = EntryIndexProvider::start_from(&store);
+        assert_eq!(normalizer.next(), 0);
+
+        // Nothing has been pushed for index 0 yet, but it is already taken
+        let other = EntryIndexProvider::start_from(&store);
+        assert_eq!(other.next(), 1);
+        assert_eq!(normalizer.next(), 2);
+
+        let unrelated = EntryIndexProvider::start_from(&MsgStore::new());
+        assert_eq!(unrelated.next(), 0);
+    }
+
     #[test]
     fn test_current_index() {
         let provider = EntryIndexProvider::test_new();
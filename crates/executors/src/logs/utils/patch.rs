@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use json_patch::Patch;
 use serde::{Deserialize, Serialize};
@@ -142,6 +142,26 @@ pub fn extract_normalized_entry_from_patch(patch: &Patch) -> Option<(usize, Norm
     })
 }
 
+/// The latest version of each normalized entry in `patches`, in the order the
+/// entries were first added. Entries are replaced in place as they update.
+pub fn latest_normalized_entries<'a>(
+    patches: impl IntoIterator<Item = &'a Patch>,
+) -> Vec<NormalizedEntry> {
+    let mut order = Vec::new();
+    let mut latest: HashMap<usize, NormalizedEntry> = HashMap::new();
+    for patch in patches {
+        if let Some((index, entry)) = extract_normalized_entry_from_patch(patch)
+            && latest.insert(index, entry).is_none()
+        {
+            order.push(index);
+        }
+    }
+    order
+        .into_iter()
+        .filter_map(|index| latest.remove(&index))
+        .collect()
+}
+
 pub fn upsert_normalized_entry(
     msg_store: &Arc<MsgStore>,
     index: usize,
@@ -0,0 +1,152 @@
+//! Extraction of MCP tool invocations from a normalized conversation.
+//!
+//! Executors surface MCP tools as generic `ToolUse` entries whose tool name is
+//! labelled `mcp:{server}:{tool}`; this module picks those out of the stream.
+
+use json_patch::Patch;
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::logs::{
+    ActionType, NormalizedEntry, NormalizedEntryType, ToolResult, ToolStatus,
+    utils::patch::latest_normalized_entries,
+};
+
+/// A single MCP tool invocation made by the agent.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct McpToolCall {
+    pub server: String,
+    pub tool: String,
+    pub arguments: Option<serde_json::Value>,
+    pub result: Option<ToolResult>,
+    pub status: ToolStatus,
+}
+
+/// Split an `mcp:{server}:{tool}` label into its server and tool names.
+pub fn parse_mcp_tool_name(tool_name: &str) -> Option<(&str, &str)> {
+    let rest = tool_name.strip_prefix("mcp:")?;
+    let (server, tool) = rest.split_once(':')?;
+    (!server.is_empty() && !tool.is_empty()).then_some((server, tool))
+}
+
+/// MCP tool invocations among `entries`, in conversation order.
+pub fn extract_mcp_calls<'a>(
+    entries: impl IntoIterator<Item = &'a NormalizedEntry>,
+) -> Vec<McpToolCall> {
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let NormalizedEntryType::ToolUse {
+                action_type:
+                    ActionType::Tool {
+                        tool_name,
+                        arguments,
+                        result,
+                    },
+                status,
+                ..
+            } = &entry.entry_type
+            else {
+                return None;
+            };
+            let (server, tool) = parse_mcp_tool_name(tool_name)?;
+            Some(McpToolCall {
+                server: server.to_string(),
+                tool: tool.to_string(),
+                arguments: arguments.clone(),
+                result: result.clone(),
+                status: status.clone(),
+            })
+        })
+        .collect()
+}
+
+/// MCP tool invocations from a process's stored conversation patches. Entries are
+/// replaced in place as tools complete, so only the latest version of each counts.
+pub fn extract_mcp_calls_from_patches<'a>(
+    patches: impl IntoIterator<Item = &'a Patch>,
+) -> Vec<McpToolCall> {
+    extract_mcp_calls(&latest_normalized_entries(patches))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::logs::utils::ConversationPatch;
+
+    fn tool_entry(
+        tool_name: &str,
+        result: Option<ToolResult>,
+        status: ToolStatus,
+    ) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::ToolUse {
+                tool_name: tool_name.to_string(),
+                action_type: ActionType::Tool {
+                    tool_name: tool_name.to_string(),
+                    arguments: Some(json!({ "query": "open issues", "limit": 5 })),
+                    result,
+                },
+                status,
+            },
+            content: tool_name.to_string(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_extracts_mcp_call_with_arguments_from_transcript() {
+        let transcript = vec![
+            ConversationPatch::add_normalized_entry(
+                0,
+                NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::AssistantMessage,
+                    content: "Let me look that up.".to_string(),
+                    metadata: None,
+                },
+            ),
+            ConversationPatch::add_normalized_entry(
+                1,
+                tool_entry("mcp:github:search_issues", None, ToolStatus::Created),
+            ),
+            ConversationPatch::add_normalized_entry(
+                2,
+                tool_entry("NotebookEdit", None, ToolStatus::Success),
+            ),
+            ConversationPatch::replace(
+                1,
+                tool_entry(
+                    "mcp:github:search_issues",
+                    Some(ToolResult::markdown("3 issues found")),
+                    ToolStatus::Success,
+                ),
+            ),
+        ];
+
+        let calls = extract_mcp_calls_from_patches(&transcript);
+        assert_eq!(calls.len(), 1);
+        let call = &calls[0];
+        assert_eq!(call.server, "github");
+        assert_eq!(call.tool, "search_issues");
+        assert_eq!(
+            call.arguments,
+            Some(json!({ "query": "open issues", "limit": 5 }))
+        );
+        assert!(call.result.is_some());
+        assert!(matches!(call.status, ToolStatus::Success));
+    }
+
+    #[test]
+    fn test_parse_mcp_tool_name() {
+        assert_eq!(
+            parse_mcp_tool_name("mcp:vibe_kanban:list_tasks"),
+            Some(("vibe_kanban", "list_tasks"))
+        );
+        assert_eq!(parse_mcp_tool_name("mcp:missing"), None);
+        assert_eq!(parse_mcp_tool_name("Bash"), None);
+    }
+}
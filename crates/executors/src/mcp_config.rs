@@ -6,6 +6,7 @@ use std::{collections::HashMap, sync::LazyLock};
 
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use thiserror::Error;
 use tokio::fs;
 use ts_rs::TS;
 
@@ -45,6 +46,82 @@ impl McpConfig {
     }
 }
 
+/// A stdio MCP server a project adds to its agents' configs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct McpServerDefinition {
+    /// Key the server is stored under in the agent's config
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum McpServerDefinitionError {
+    #[error("MCP server name '{0}' must be non-empty and use only letters, digits, '-' and '_'")]
+    InvalidName(String),
+    #[error("MCP server name '{0}' is reserved")]
+    ReservedName(String),
+    #[error("MCP server '{0}' is defined more than once")]
+    DuplicateName(String),
+    #[error("MCP server '{0}' has no command")]
+    MissingCommand(String),
+}
+
+impl McpServerDefinition {
+    /// The server in the canonical (Claude-style) shape the adapters start from
+    fn to_canonical(&self) -> Value {
+        let mut server = Map::new();
+        server.insert("command".to_string(), Value::String(self.command.clone()));
+        server.insert(
+            "args".to_string(),
+            Value::Array(self.args.iter().cloned().map(Value::String).collect()),
+        );
+        if !self.env.is_empty() {
+            server.insert(
+                "env".to_string(),
+                Value::Object(
+                    self.env
+                        .iter()
+                        .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+                        .collect(),
+                ),
+            );
+        }
+        Value::Object(server)
+    }
+}
+
+/// Check a project's MCP server definitions before they are saved.
+pub fn validate_mcp_server_definitions(
+    servers: &[McpServerDefinition],
+) -> Result<(), McpServerDefinitionError> {
+    let mut seen = std::collections::HashSet::new();
+    for server in servers {
+        let name = server.name.as_str();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(McpServerDefinitionError::InvalidName(name.to_string()));
+        }
+        // `meta` holds the preconfigured servers' display metadata
+        if name == "meta" {
+            return Err(McpServerDefinitionError::ReservedName(name.to_string()));
+        }
+        if !seen.insert(name) {
+            return Err(McpServerDefinitionError::DuplicateName(name.to_string()));
+        }
+        if server.command.trim().is_empty() {
+            return Err(McpServerDefinitionError::MissingCommand(name.to_string()));
+        }
+    }
+    Ok(())
+}
+
 /// Read an agent's external config file (JSON or TOML) and normalize it to serde_json::Value.
 pub async fn read_agent_config(
     config_path: &std::path::Path,
@@ -242,6 +319,10 @@ fn adapt_opencode(servers: ServerMap, meta: Option<Value>) -> Value {
             new_map.insert("type".to_string(), Value::String("local".to_string()));
             new_map.insert("command".to_string(), Value::Array(cmd_vec));
             new_map.insert("enabled".to_string(), Value::Bool(true));
+            // OpenCode reads a local server's variables from `environment`
+            if let Some(env) = s.remove("env") {
+                new_map.insert("environment".to_string(), env);
+            }
             *s = new_map;
         }
     }
@@ -305,3 +386,101 @@ impl CodingAgent {
         apply_adapter(adapter, canonical)
     }
 }
+
+/// `servers` as the `{"mcpServers": {...}}` document Claude Code's
+/// `--mcp-config` flag takes
+pub fn claude_mcp_config(servers: &[McpServerDefinition]) -> Value {
+    let servers: Map<String, Value> = servers
+        .iter()
+        .map(|server| (server.name.clone(), server.to_canonical()))
+        .collect();
+    serde_json::json!({ "mcpServers": servers })
+}
+
+/// `servers` as Codex config overrides, one `mcp_servers.<name>` entry each
+pub fn codex_mcp_overrides(servers: &[McpServerDefinition]) -> HashMap<String, Value> {
+    servers
+        .iter()
+        .map(|server| {
+            (
+                format!("mcp_servers.{}", server.name),
+                server.to_canonical(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(name: &str, command: &str) -> McpServerDefinition {
+        McpServerDefinition {
+            name: name.to_string(),
+            command: command.to_string(),
+            args: vec!["--db".to_string(), "dev.sqlite".to_string()],
+            env: HashMap::from([("LOG".to_string(), "debug".to_string())]),
+        }
+    }
+
+    #[test]
+    fn test_validate_mcp_server_definitions() {
+        assert!(validate_mcp_server_definitions(&[server("db-introspect", "dbmcp")]).is_ok());
+        assert_eq!(
+            validate_mcp_server_definitions(&[server("db tools", "dbmcp")]),
+            Err(McpServerDefinitionError::InvalidName(
+                "db tools".to_string()
+            ))
+        );
+        assert_eq!(
+            validate_mcp_server_definitions(&[server("meta", "dbmcp")]),
+            Err(McpServerDefinitionError::ReservedName("meta".to_string()))
+        );
+        assert_eq!(
+            validate_mcp_server_definitions(&[server("db", "a"), server("db", "b")]),
+            Err(McpServerDefinitionError::DuplicateName("db".to_string()))
+        );
+        assert_eq!(
+            validate_mcp_server_definitions(&[server("db", " ")]),
+            Err(McpServerDefinitionError::MissingCommand("db".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_custom_servers_adapted_like_preconfigured() {
+        let canonical = Value::Object(Map::from_iter([(
+            "db".to_string(),
+            server("db", "dbmcp").to_canonical(),
+        )]));
+        let adapted = apply_adapter(Adapter::Opencode, canonical);
+        assert_eq!(
+            adapted["db"],
+            serde_json::json!({
+                "type": "local",
+                "command": ["dbmcp", "--db", "dev.sqlite"],
+                "enabled": true,
+                "environment": { "LOG": "debug" },
+            })
+        );
+    }
+
+    #[test]
+    fn test_project_servers_as_spawn_arguments() {
+        let servers = [server("db", "dbmcp")];
+        let expected = serde_json::json!({
+            "command": "dbmcp",
+            "args": ["--db", "dev.sqlite"],
+            "env": { "LOG": "debug" },
+        });
+
+        assert_eq!(
+            claude_mcp_config(&servers),
+            serde_json::json!({ "mcpServers": { "db": expected.clone() } })
+        );
+        assert_eq!(
+            codex_mcp_overrides(&servers),
+            HashMap::from([("mcp_servers.db".to_string(), expected)])
+        );
+        assert!(codex_mcp_overrides(&[]).is_empty());
+    }
+}
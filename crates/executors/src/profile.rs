@@ -45,6 +45,19 @@ pub enum ProfileError {
 
     #[error("No available executor profile")]
     NoAvailableExecutorProfile,
+
+    #[error("Executor '{executor}' has no configuration")]
+    UnknownExecutor { executor: BaseCodingAgent },
+
+    #[error(
+        "Unknown variant '{variant}' for executor '{executor}'. Valid variants: {}",
+        valid_variants.join(", ")
+    )]
+    UnknownVariant {
+        executor: BaseCodingAgent,
+        variant: String,
+        valid_variants: Vec<String>,
+    },
 }
 
 lazy_static! {
@@ -171,6 +184,41 @@ impl ExecutorConfig {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct ExecutorConfigs {
     pub executors: HashMap<BaseCodingAgent, ExecutorConfig>,
+    /// Model used by an executor's profiles that don't pick one themselves
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        deserialize_with = "de_default_models"
+    )]
+    pub default_models: HashMap<BaseCodingAgent, String>,
+}
+
+// Drop (with a warning) entries for unknown executors or ones without a model setting,
+// so a typo doesn't discard the rest of profiles.json
+fn de_default_models<'de, D>(de: D) -> Result<HashMap<BaseCodingAgent, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = HashMap::<String, String>::deserialize(de)?;
+    let mut models = HashMap::new();
+    for (key, model) in raw {
+        let model = model.trim();
+        match BaseCodingAgent::from_str(&key) {
+            Ok(executor) if !executor.supports_model() => {
+                tracing::warn!("Ignoring default model for '{key}': executor has no model setting");
+            }
+            Ok(_) if model.is_empty() => {
+                tracing::warn!("Ignoring empty default model for '{key}'");
+            }
+            Ok(executor) => {
+                models.insert(executor, model.to_string());
+            }
+            Err(_) => {
+                tracing::warn!("Ignoring default model for unknown executor '{key}'");
+            }
+        }
+    }
+    Ok(models)
 }
 
 impl ExecutorConfigs {
@@ -265,6 +313,7 @@ impl ExecutorConfigs {
 
     /// Deep merge defaults with user overrides
     fn merge_with_defaults(mut defaults: Self, overrides: Self) -> Self {
+        defaults.default_models.extend(overrides.default_models);
         for (executor_key, override_profile) in overrides.executors {
             match defaults.executors.get_mut(&executor_key) {
                 Some(default_profile) => {
@@ -286,6 +335,12 @@ impl ExecutorConfigs {
     fn compute_overrides(defaults: &Self, current: &Self) -> Result<Self, ProfileError> {
         let mut overrides = Self {
             executors: HashMap::new(),
+            default_models: current
+                .default_models
+                .iter()
+                .filter(|(executor, model)| defaults.default_models.get(executor) != Some(model))
+                .map(|(executor, model)| (*executor, model.clone()))
+                .collect(),
         };
 
         // Fast scan for any illegal deletions BEFORE allocating/cloning
@@ -397,6 +452,38 @@ impl ExecutorConfigs {
                 )
             })
             .cloned()
+            .map(|mut agent| {
+                if let Some(model) = self.default_models.get(&executor_profile_id.executor) {
+                    agent.apply_default_model(model);
+                }
+                agent
+            })
+    }
+
+    /// Check that the profile's executor is configured and its variant, if
+    /// given, exists, rather than letting spawn fall back to the default.
+    pub fn validate_profile(
+        &self,
+        executor_profile_id: &ExecutorProfileId,
+    ) -> Result<(), ProfileError> {
+        let executor = executor_profile_id.executor;
+        let config = self
+            .executors
+            .get(&executor)
+            .ok_or(ProfileError::UnknownExecutor { executor })?;
+        match &executor_profile_id.variant {
+            Some(variant) if config.get_variant(variant).is_none() => {
+                let mut valid_variants: Vec<String> =
+                    config.configurations.keys().cloned().collect();
+                valid_variants.sort();
+                Err(ProfileError::UnknownVariant {
+                    executor,
+                    variant: variant.clone(),
+                    valid_variants,
+                })
+            }
+            _ => Ok(()),
+        }
     }
 
     pub fn get_coding_agent_or_default(
@@ -480,3 +567,70 @@ pub fn to_default_variant(id: &ExecutorProfileId) -> ExecutorProfileId {
         variant: None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_profile_variants() {
+        let configs = ExecutorConfigs::from_defaults();
+        let executor = BaseCodingAgent::ClaudeCode;
+
+        assert!(
+            configs
+                .validate_profile(&ExecutorProfileId::new(executor))
+                .is_ok()
+        );
+        assert!(
+            configs
+                .validate_profile(&ExecutorProfileId::with_variant(
+                    executor,
+                    "DEFAULT".to_string()
+                ))
+                .is_ok()
+        );
+
+        match configs.validate_profile(&ExecutorProfileId::with_variant(
+            executor,
+            "NO_SUCH_VARIANT".to_string(),
+        )) {
+            Err(ProfileError::UnknownVariant { valid_variants, .. }) => {
+                assert!(valid_variants.contains(&"DEFAULT".to_string()));
+            }
+            other => panic!("expected UnknownVariant, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_default_models() {
+        let mut configs = ExecutorConfigs::from_defaults();
+        let parsed: ExecutorConfigs = serde_json::from_value(serde_json::json!({
+            "executors": {},
+            "default_models": {
+                "CLAUDE_CODE": "haiku",
+                "AMP": "fast",
+                "NOT_AN_AGENT": "x"
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            parsed.default_models,
+            HashMap::from([(BaseCodingAgent::ClaudeCode, "haiku".to_string())])
+        );
+        configs.default_models = parsed.default_models;
+
+        let model = |variant: Option<&str>| {
+            let profile_id = ExecutorProfileId {
+                executor: BaseCodingAgent::ClaudeCode,
+                variant: variant.map(str::to_string),
+            };
+            match configs.get_coding_agent_or_default(&profile_id) {
+                CodingAgent::ClaudeCode(claude) => claude.model,
+                other => panic!("expected ClaudeCode, got {other:?}"),
+            }
+        };
+        assert_eq!(model(None).as_deref(), Some("haiku"));
+        assert_eq!(model(Some("OPUS")).as_deref(), Some("opus"));
+    }
+}
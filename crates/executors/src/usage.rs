@@ -0,0 +1,86 @@
+//! Token usage totals from a normalized conversation.
+//!
+//! Executors that report usage emit `Usage` entries; ones that don't simply
+//! contribute nothing, so their totals stay at zero.
+
+use std::collections::HashMap;
+
+use json_patch::Patch;
+
+use crate::logs::{
+    NormalizedEntryType, TokenUsage, utils::patch::extract_normalized_entry_from_patch,
+};
+
+/// Total usage reported in a process's stored conversation patches. Usage entries
+/// may be replaced in place as running totals update, so only the latest version
+/// of each entry counts.
+pub fn usage_from_patches<'a>(patches: impl IntoIterator<Item = &'a Patch>) -> TokenUsage {
+    let mut latest: HashMap<usize, TokenUsage> = HashMap::new();
+    for patch in patches {
+        if let Some((index, entry)) = extract_normalized_entry_from_patch(patch) {
+            match entry.entry_type {
+                NormalizedEntryType::Usage { usage } => {
+                    latest.insert(index, usage);
+                }
+                _ => {
+                    latest.remove(&index);
+                }
+            }
+        }
+    }
+
+    let mut total = TokenUsage::default();
+    for usage in latest.into_values() {
+        total += usage;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::{NormalizedEntry, utils::ConversationPatch};
+
+    fn usage(input_tokens: u64, output_tokens: u64, cached_tokens: u64) -> TokenUsage {
+        TokenUsage {
+            input_tokens,
+            output_tokens,
+            cached_tokens,
+        }
+    }
+
+    #[test]
+    fn test_replaced_usage_counts_once() {
+        let transcript = vec![
+            ConversationPatch::add_normalized_entry(
+                0,
+                NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::AssistantMessage,
+                    content: "Done.".to_string(),
+                    metadata: None,
+                },
+            ),
+            ConversationPatch::add_normalized_entry(1, NormalizedEntry::usage(usage(10, 5, 0))),
+            ConversationPatch::replace(1, NormalizedEntry::usage(usage(40, 20, 8))),
+            ConversationPatch::add_normalized_entry(2, NormalizedEntry::usage(usage(2, 1, 1))),
+        ];
+
+        assert_eq!(usage_from_patches(&transcript), usage(42, 21, 9));
+    }
+
+    #[test]
+    fn test_no_usage_is_zero() {
+        let transcript = vec![ConversationPatch::add_normalized_entry(
+            0,
+            NormalizedEntry {
+                timestamp: None,
+                entry_type: NormalizedEntryType::AssistantMessage,
+                content: "Hi".to_string(),
+                metadata: None,
+            },
+        )];
+
+        assert_eq!(usage_from_patches(&transcript), TokenUsage::default());
+    }
+}
@@ -8,6 +8,21 @@ use services::services::container::ContainerError;
 #[cfg(unix)]
 use tokio::time::Duration;
 
+/// Send SIGINT to the child's process group so it can shut down cleanly, without
+/// waiting for it to exit. Does nothing on platforms without process group signals.
+pub fn interrupt_process_group(child: &mut AsyncGroupChild) -> Result<(), ContainerError> {
+    #[cfg(unix)]
+    if let Some(pid) = child.inner().id() {
+        let pgid = getpgid(Some(Pid::from_raw(pid as i32)))
+            .map_err(|e| ContainerError::KillFailed(std::io::Error::other(e)))?;
+        killpg(pgid, Signal::SIGINT)
+            .map_err(|e| ContainerError::KillFailed(std::io::Error::other(e)))?;
+    }
+    #[cfg(not(unix))]
+    let _ = child;
+    Ok(())
+}
+
 pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<(), ContainerError> {
     // hit the whole process group, not just the leader
     #[cfg(unix)]
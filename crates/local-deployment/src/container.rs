@@ -1,8 +1,11 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     io,
     path::{Path, PathBuf},
-    sync::{Arc, atomic::AtomicUsize},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
     time::Duration,
 };
 
@@ -25,12 +28,9 @@ use db::{
 };
 use deployment::{DeploymentError, RemoteClientNotConfigured};
 use executors::{
-    actions::{
-        Executable, ExecutorAction, ExecutorActionType,
-        coding_agent_follow_up::CodingAgentFollowUpRequest,
-        coding_agent_initial::CodingAgentInitialRequest,
-    },
+    actions::{Executable, ExecutorAction, ExecutorActionType},
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
+    env::ExecutionEnv,
     executors::{BaseCodingAgent, BoxedInputSender, ExecutorExitResult, ExecutorExitSignal},
     logs::{
         NormalizedEntryType,
@@ -41,32 +41,49 @@ use executors::{
     },
     profile::ExecutorProfileId,
 };
-use futures::{FutureExt, StreamExt, TryStreamExt, stream::select};
+use futures::{
+    FutureExt, StreamExt,
+    stream::{self, BoxStream, select},
+};
 use serde_json::json;
 use services::services::{
     analytics::AnalyticsContext,
     approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
-    config::Config,
-    container::{ContainerError, ContainerRef, ContainerService},
+    branch_status::BranchStatusCache,
+    config::{AutoCommitMessageMode, Config, ExecutionLimitsConfig, LogFileSinkConfig},
+    container::{
+        AttemptOverrides, ContainerError, ContainerRef, ContainerService, DeletedWorktree,
+        DeletedWorktreeFlags, auto_commit_execution, commits_on_exit, copy_project_file,
+        expand_copy_files, notify_execution_slot_freed,
+    },
+    dev_server_url::{parse_url_pattern, watch_for_dev_server_url},
     diff_stream::{self, DiffStreamHandle},
-    git::{Commit, DiffTarget, GitService},
+    events::EventService,
+    git::{Commit, CommitNote, DiffTarget, GitService},
+    idle_timeout::{push_idle_timeout_entry, wait_for_idle},
     image::ImageService,
-    queued_message::QueuedMessageService,
-    share::SharePublisher,
-    worktree_manager::{WorktreeCleanup, WorktreeManager},
+    output_abort::{abort_on_pattern, parse_abort_pattern},
+    queued_message::{QueuedMessageService, runs_queued_after},
+    share::{SharePublisher, update_task_status_and_share},
+    task_registry::TaskRegistry,
+    worktree_manager::{WorktreeCleanup, WorktreeManager, parse_sparse_patterns},
 };
 use tokio::{sync::RwLock, task::JoinHandle};
 use tokio_util::io::ReaderStream;
 use utils::{
+    diff::Diff,
     log_msg::LogMsg,
     msg_store::MsgStore,
     path::get_vibe_kanban_temp_dir,
-    text::{git_branch_id, short_uuid, truncate_to_char_boundary},
+    text::{SecretRedactor, git_branch_id, short_uuid, truncate_to_char_boundary},
 };
 use uuid::Uuid;
 
 use crate::command;
 
+/// Floor for the configured worktree cleanup interval, so a bad value can't spin the loop
+const MIN_WORKTREE_CLEANUP_INTERVAL_SECS: u64 = 60;
+
 #[derive(Clone)]
 pub struct LocalContainerService {
     db: DBService,
@@ -80,7 +97,23 @@ pub struct LocalContainerService {
     approvals: Approvals,
     queued_message_service: QueuedMessageService,
     publisher: Result<SharePublisher, RemoteClientNotConfigured>,
+    task_registry: TaskRegistry,
+    events: EventService,
     worktree_cleanup_shutdown: Arc<tokio::sync::watch::Sender<bool>>,
+    branch_status_cache: BranchStatusCache,
+    draining: Arc<AtomicBool>,
+    deleted_worktrees: DeletedWorktreeFlags,
+}
+
+enum AttemptDiffSource {
+    Merged {
+        repo_path: PathBuf,
+        merge_commit: String,
+    },
+    Live {
+        worktree_path: PathBuf,
+        base_commit: Commit,
+    },
 }
 
 impl LocalContainerService {
@@ -95,6 +128,8 @@ impl LocalContainerService {
         approvals: Approvals,
         queued_message_service: QueuedMessageService,
         publisher: Result<SharePublisher, RemoteClientNotConfigured>,
+        task_registry: TaskRegistry,
+        events: EventService,
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
         let input_senders = Arc::new(RwLock::new(HashMap::new()));
@@ -113,7 +148,12 @@ impl LocalContainerService {
             approvals,
             queued_message_service,
             publisher,
+            task_registry,
+            events,
             worktree_cleanup_shutdown: Arc::new(worktree_cleanup_shutdown_tx),
+            branch_status_cache: BranchStatusCache::new(),
+            draining: Arc::new(AtomicBool::new(false)),
+            deleted_worktrees: DeletedWorktreeFlags::default(),
         };
 
         container
@@ -128,6 +168,27 @@ impl LocalContainerService {
         let _ = self.worktree_cleanup_shutdown.send(true);
     }
 
+    /// Tag the commit just made with the task metadata, so it can be traced back
+    /// to its task once merged. Best-effort: the commit stands either way.
+    fn write_commit_note(&self, ctx: &ExecutionContext, worktree_path: &Path) {
+        let note = CommitNote {
+            task_id: ctx.task.id,
+            attempt_id: ctx.task_attempt.id,
+            executor: ctx.task_attempt.executor.clone(),
+        };
+        let result = self
+            .git()
+            .get_head_info(worktree_path)
+            .and_then(|head| self.git().write_note(worktree_path, &head.oid, &note));
+        if let Err(e) = result {
+            tracing::warn!(
+                "Failed to write commit note for task attempt {}: {}",
+                ctx.task_attempt.id,
+                e
+            );
+        }
+    }
+
     pub async fn get_child_from_store(&self, id: &Uuid) -> Option<Arc<RwLock<AsyncGroupChild>>> {
         let map = self.child_store.read().await;
         map.get(id).cloned()
@@ -158,8 +219,13 @@ impl LocalContainerService {
         map.remove(id);
     }
 
-    /// Defensively check for externally deleted worktrees and mark them as deleted in the database
-    async fn check_externally_deleted_worktrees(db: &DBService) -> Result<(), DeploymentError> {
+    /// Defensively check for externally deleted worktrees, mark them as deleted in the database
+    /// and announce each one on the event stream
+    async fn check_externally_deleted_worktrees(
+        db: &DBService,
+        events: &EventService,
+        deleted_worktrees: &DeletedWorktreeFlags,
+    ) -> Result<(), DeploymentError> {
         let active_attempts = TaskAttempt::find_by_worktree_deleted(&db.pool).await?;
         tracing::debug!(
             "Checking {} active worktrees for external deletion...",
@@ -181,6 +247,13 @@ impl LocalContainerService {
                         attempt_id,
                         worktree_path
                     );
+                    deleted_worktrees.set(
+                        events.msg_store(),
+                        DeletedWorktree {
+                            attempt_id,
+                            path: worktree_path,
+                        },
+                    );
                 }
             }
         }
@@ -188,14 +261,18 @@ impl LocalContainerService {
     }
 
     /// Find and delete orphaned worktrees that don't correspond to any task attempts
-    async fn cleanup_orphaned_worktrees(db: &DBService) {
-        // Check if orphan cleanup is disabled via environment variable
+    async fn cleanup_orphaned_worktrees(db: &DBService, config: &Arc<RwLock<Config>>) {
+        // The environment variable still overrides the config for backward compatibility
         if std::env::var("DISABLE_WORKTREE_ORPHAN_CLEANUP").is_ok() {
             tracing::debug!(
                 "Orphan worktree cleanup is disabled via DISABLE_WORKTREE_ORPHAN_CLEANUP environment variable"
             );
             return;
         }
+        if !config.read().await.worktree_orphan_cleanup_enabled {
+            tracing::debug!("Orphan worktree cleanup is disabled in config");
+            return;
+        }
         let worktree_base_dir = WorktreeManager::get_worktree_base_dir();
 
         // CRITICAL SAFETY CHECK: Ensure worktree base is in a temp directory
@@ -344,9 +421,14 @@ impl LocalContainerService {
         mut shutdown_rx: tokio::sync::watch::Receiver<bool>,
     ) {
         let db = self.db.clone();
-        let mut cleanup_interval = tokio::time::interval(tokio::time::Duration::from_secs(1800)); // 30 minutes
-        Self::cleanup_orphaned_worktrees(self.db()).await;
-        tokio::spawn(async move {
+        let config = self.config.clone();
+        let events = self.events.clone();
+        let deleted_worktrees = self.deleted_worktrees.clone();
+        Self::cleanup_orphaned_worktrees(self.db(), &config).await;
+        self.task_registry.spawn("worktree_cleanup", true, async move {
+            // The first pass runs right away; later ones wait for the configured
+            // interval, re-read every tick so changes apply without a restart
+            let mut next_delay = tokio::time::Duration::ZERO;
             loop {
                 tokio::select! {
                     _ = shutdown_rx.changed() => {
@@ -355,10 +437,17 @@ impl LocalContainerService {
                             break;
                         }
                     }
-                    _ = cleanup_interval.tick() => {
+                    _ = tokio::time::sleep(next_delay) => {
+                        next_delay = tokio::time::Duration::from_secs(
+                            config
+                                .read()
+                                .await
+                                .worktree_cleanup_interval_secs
+                                .max(MIN_WORKTREE_CLEANUP_INTERVAL_SECS),
+                        );
                         tracing::info!("Starting periodic worktree cleanup...");
-                        Self::cleanup_orphaned_worktrees(&db).await;
-                        Self::check_externally_deleted_worktrees(&db)
+                        Self::cleanup_orphaned_worktrees(&db, &config).await;
+                        Self::check_externally_deleted_worktrees(&db, &events, &deleted_worktrees)
                             .await
                             .unwrap_or_else(|e| {
                                 tracing::error!("Failed to check externally deleted worktrees: {}", e);
@@ -394,11 +483,25 @@ impl LocalContainerService {
 
         let mut process_exit_rx = self.spawn_os_exit_watcher(exec_id);
 
-        tokio::spawn(async move {
+        // Not cancellable: aborting mid-finalization would leave the process row running
+        self.task_registry.spawn(format!("exit_monitor:{exec_id}"), false, async move {
             let mut exit_signal_future = exit_signal
                 .map(|rx| rx.boxed()) // wait for result
                 .unwrap_or_else(|| std::future::pending().boxed()); // no signal, stall forever
 
+            // Dev servers are expected to sit quietly, so only other runs are watched for idleness
+            let idle_timeout = match ExecutionProcess::find_by_id(&db.pool, exec_id).await {
+                Ok(Some(process)) if process.run_reason != ExecutionProcessRunReason::DevServer => {
+                    config.read().await.execution_idle_timeout()
+                }
+                _ => None,
+            };
+            let mut idle_future = match (idle_timeout, msg_stores.read().await.get(&exec_id)) {
+                (Some(timeout), Some(store)) => wait_for_idle(store.get_receiver(), timeout).boxed(),
+                _ => std::future::pending().boxed(),
+            };
+            let mut timed_out = false;
+
             let status_result: std::io::Result<std::process::ExitStatus>;
 
             // Wait for process to exit, or exit signal from executor
@@ -426,9 +529,25 @@ impl LocalContainerService {
                 exit_status_result = &mut process_exit_rx => {
                     status_result = exit_status_result.unwrap_or_else(|e| Err(std::io::Error::other(e)));
                 }
+                // No output for the configured idle timeout: treat the agent as stuck
+                true = &mut idle_future => {
+                    tracing::warn!("Execution {} produced no output within the idle timeout, stopping it", exec_id);
+                    if let Some(child_lock) = child_store.read().await.get(&exec_id).cloned() {
+                        let mut child = child_lock.write().await;
+                        if let Err(err) = command::kill_process_group(&mut child).await {
+                            tracing::error!("Failed to kill process group after idle timeout: {} {}", exec_id, err);
+                        }
+                    }
+                    if let (Some(timeout), Some(store)) = (idle_timeout, msg_stores.read().await.get(&exec_id)) {
+                        push_idle_timeout_entry(store, timeout);
+                    }
+                    timed_out = true;
+                    status_result = Ok(failure_exit_status());
+                }
             }
 
             let (exit_code, status) = match status_result {
+                _ if timed_out => (None, ExecutionProcessStatus::TimedOut),
                 Ok(exit_status) => {
                     let code = exit_status.code().unwrap_or(-1) as i64;
                     let status = if exit_status.success() {
@@ -447,32 +566,42 @@ impl LocalContainerService {
             {
                 tracing::error!("Failed to update execution process completion: {}", e);
             }
+            notify_execution_slot_freed();
 
             if let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
+                // Once shutdown has begun, nothing new is started for this attempt
+                let draining = container.is_draining();
+
+                // The process may have left uncommitted changes behind without moving HEAD
+                container.branch_status_cache.invalidate(ctx.task_attempt.id);
+
                 // Update executor session summary if available
                 if let Err(e) = container.update_executor_session_summary(&exec_id).await {
                     tracing::warn!("Failed to update executor session summary: {}", e);
                 }
 
-                let success = matches!(
-                    ctx.execution_process.status,
-                    ExecutionProcessStatus::Completed
-                ) && exit_code == Some(0);
-
-                let cleanup_done = matches!(
-                    ctx.execution_process.run_reason,
-                    ExecutionProcessRunReason::CleanupScript
-                ) && !matches!(
-                    ctx.execution_process.status,
-                    ExecutionProcessStatus::Running
-                );
-
-                if success || cleanup_done {
+                if commits_on_exit(
+                    &ctx.execution_process.run_reason,
+                    &ctx.execution_process.status,
+                    exit_code,
+                    draining,
+                ) {
                     // Commit changes (if any) and get feedback about whether changes were made
                     let auto_commit_enabled = config.read().await.auto_commit_enabled;
                     let changes_committed = if auto_commit_enabled {
                         match container.try_commit_changes(&ctx).await {
-                            Ok(committed) => committed,
+                            Ok(committed) => {
+                                if committed {
+                                    // Push in the background so next actions are not held up
+                                    let label = format!("auto_push:{}", ctx.task_attempt.id);
+                                    let container_for_push = container.clone();
+                                    let ctx = ctx.clone();
+                                    container.task_registry.spawn(label, true, async move {
+                                        container_for_push.try_auto_push(&ctx).await;
+                                    });
+                                }
+                                committed
+                            }
                             Err(e) => {
                                 tracing::error!("Failed to commit changes after execution: {}", e);
                                 // Treat commit failures as if changes were made to be safe
@@ -504,7 +633,12 @@ impl LocalContainerService {
                         true
                     };
 
-                    if should_start_next {
+                    if draining {
+                        tracing::info!(
+                            "Shutting down; not starting the next action for task attempt {}",
+                            ctx.task_attempt.id
+                        );
+                    } else if should_start_next {
                         // If the process exited successfully, start the next action
                         if let Err(e) = container.try_start_next_action(&ctx).await {
                             tracing::error!("Failed to start next action after completion: {}", e);
@@ -522,56 +656,72 @@ impl LocalContainerService {
                     }
                 }
 
-                if container.should_finalize(&ctx) {
+                if draining {
+                    // Queued messages stay persisted and are restored on the next start,
+                    // which discards them if this run was interrupted or failed
+                    container
+                        .finalize_task(&config, publisher.as_ref().ok(), &ctx)
+                        .await;
+                } else if container.should_finalize(&ctx) {
                     // Only execute queued messages if the execution succeeded
                     // If it failed or was killed, just clear the queue and finalize
-                    let should_execute_queued = !matches!(
-                        ctx.execution_process.status,
-                        ExecutionProcessStatus::Failed | ExecutionProcessStatus::Killed
-                    );
-
-                    if let Some(queued_msg) = container
-                        .queued_message_service
-                        .take_queued(ctx.task_attempt.id)
+                    let should_execute_queued =
+                        runs_queued_after(&ctx.execution_process.status);
+                    let queue = &container.queued_message_service;
+                    let attempt_id = ctx.task_attempt.id;
+
+                    if !should_execute_queued && queue.has_queued(attempt_id) {
+                        // Execution failed or was killed - discard the whole queue and finalize
+                        match queue.cancel_queued(attempt_id).await {
+                            Ok(dropped) => tracing::info!(
+                                "Discarded {} queued message(s) for attempt {} due to execution status {:?}",
+                                dropped.len(),
+                                attempt_id,
+                                ctx.execution_process.status
+                            ),
+                            Err(e) => tracing::warn!(
+                                "Failed to discard queued messages for attempt {}: {}",
+                                attempt_id,
+                                e
+                            ),
+                        }
+                        container
+                            .finalize_task(&config, publisher.as_ref().ok(), &ctx)
+                            .await;
+                    } else if let Some(queued_msg) =
+                        queue.pop_queued(attempt_id).await.unwrap_or_else(|e| {
+                            tracing::warn!(
+                                "Failed to take queued message for attempt {}: {}",
+                                attempt_id,
+                                e
+                            );
+                            None
+                        })
                     {
-                        if should_execute_queued {
-                            tracing::info!(
-                                "Found queued message for attempt {}, starting follow-up execution",
-                                ctx.task_attempt.id
+                        tracing::info!(
+                            "Found queued message for attempt {}, starting follow-up execution",
+                            attempt_id
+                        );
+
+                        // The draft mirrors the latest queued message; drop it once the
+                        // queue has drained
+                        if !queue.has_queued(attempt_id)
+                            && let Err(e) =
+                                Scratch::delete(&db.pool, attempt_id, &ScratchType::DraftFollowUp)
+                                    .await
+                        {
+                            tracing::warn!(
+                                "Failed to delete scratch after consuming queued message: {}",
+                                e
                             );
+                        }
 
-                            // Delete the scratch since we're consuming the queued message
-                            if let Err(e) = Scratch::delete(
-                                &db.pool,
-                                ctx.task_attempt.id,
-                                &ScratchType::DraftFollowUp,
-                            )
+                        if let Err(e) = container
+                            .start_queued_follow_up(&ctx, &queued_msg.data)
                             .await
-                            {
-                                tracing::warn!(
-                                    "Failed to delete scratch after consuming queued message: {}",
-                                    e
-                                );
-                            }
-
-                            // Execute the queued follow-up
-                            if let Err(e) = container
-                                .start_queued_follow_up(&ctx, &queued_msg.data)
-                                .await
-                            {
-                                tracing::error!("Failed to start queued follow-up: {}", e);
-                                // Fall back to finalization if follow-up fails
-                                container
-                                    .finalize_task(&config, publisher.as_ref().ok(), &ctx)
-                                    .await;
-                            }
-                        } else {
-                            // Execution failed or was killed - discard the queued message and finalize
-                            tracing::info!(
-                                "Discarding queued message for attempt {} due to execution status {:?}",
-                                ctx.task_attempt.id,
-                                ctx.execution_process.status
-                            );
+                        {
+                            tracing::error!("Failed to start queued follow-up: {}", e);
+                            // Fall back to finalization if follow-up fails
                             container
                                 .finalize_task(&config, publisher.as_ref().ok(), &ctx)
                                 .await;
@@ -671,24 +821,128 @@ impl LocalContainerService {
         rx
     }
 
+    /// Stop the execution once the agent's output matches the project's abort pattern
+    async fn spawn_abort_pattern_watcher(
+        &self,
+        task_attempt: &TaskAttempt,
+        execution_process: &ExecutionProcess,
+    ) {
+        let project = match task_attempt.parent_task(&self.db.pool).await {
+            Ok(Some(task)) => task.parent_project(&self.db.pool).await.ok().flatten(),
+            _ => None,
+        };
+        let Some(raw_pattern) = project.and_then(|p| p.abort_on_output_pattern) else {
+            return;
+        };
+        let pattern = match parse_abort_pattern(&raw_pattern) {
+            Ok(pattern) => pattern,
+            Err(e) => {
+                tracing::warn!("Ignoring invalid abort pattern '{}': {}", raw_pattern, e);
+                return;
+            }
+        };
+        let Some(store) = self.get_msg_store_by_id(&execution_process.id).await else {
+            return;
+        };
+
+        let container = self.clone();
+        let execution_process = execution_process.clone();
+        let label = format!("abort_watch:{}", execution_process.id);
+        self.task_registry.spawn(label, true, async move {
+            abort_on_pattern(store, pattern, |matched| async move {
+                tracing::info!(
+                    "Stopping execution {} after output matched abort pattern: {}",
+                    execution_process.id,
+                    matched
+                );
+                if let Err(e) = container
+                    .stop_execution(&execution_process, ExecutionProcessStatus::Killed)
+                    .await
+                {
+                    tracing::error!(
+                        "Failed to stop execution {} on abort pattern: {}",
+                        execution_process.id,
+                        e
+                    );
+                }
+            })
+            .await;
+        });
+    }
+
+    /// Record the URL the dev server reports it is serving on, using the project's
+    /// URL pattern when it has one
+    async fn spawn_dev_server_url_watcher(
+        &self,
+        task_attempt: &TaskAttempt,
+        execution_process: &ExecutionProcess,
+    ) {
+        let project = match task_attempt.parent_task(&self.db.pool).await {
+            Ok(Some(task)) => task.parent_project(&self.db.pool).await.ok().flatten(),
+            _ => None,
+        };
+        let pattern = match project.and_then(|p| p.dev_server_url_pattern) {
+            Some(raw_pattern) => match parse_url_pattern(&raw_pattern) {
+                Ok(pattern) => Some(pattern),
+                Err(e) => {
+                    tracing::warn!(
+                        "Ignoring invalid dev server URL pattern '{}': {}",
+                        raw_pattern,
+                        e
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+        let Some(store) = self.get_msg_store_by_id(&execution_process.id).await else {
+            return;
+        };
+
+        let db = self.db.clone();
+        let exec_id = execution_process.id;
+        let label = format!("dev_server_url_watch:{exec_id}");
+        self.task_registry.spawn(label, true, async move {
+            let Some(detected) = watch_for_dev_server_url(store, pattern).await else {
+                tracing::debug!("No URL detected in output of dev server {}", exec_id);
+                return;
+            };
+            tracing::info!("Dev server {} is serving on {}", exec_id, detected.url);
+            if let Err(e) = ExecutionProcess::set_dev_server_url(
+                &db.pool,
+                exec_id,
+                &detected.url,
+                detected.port.map(i64::from),
+            )
+            .await
+            {
+                tracing::error!("Failed to store URL of dev server {}: {}", exec_id, e);
+            }
+        });
+    }
+
     pub fn dir_name_from_task_attempt(attempt_id: &Uuid, task_title: &str) -> String {
         let task_title_id = git_branch_id(task_title);
         format!("{}-{}", short_uuid(attempt_id), task_title_id)
     }
 
-    async fn track_child_msgs_in_store(&self, id: Uuid, child: &mut AsyncGroupChild) {
+    /// Forward the child's output into a new msg store, masking any of `secrets`.
+    async fn track_child_msgs_in_store(
+        &self,
+        id: Uuid,
+        child: &mut AsyncGroupChild,
+        secrets: Vec<String>,
+    ) {
         let store = Arc::new(MsgStore::new());
 
         let out = child.inner().stdout.take().expect("no stdout");
         let err = child.inner().stderr.take().expect("no stderr");
 
         // Map stdout bytes -> LogMsg::Stdout
-        let out = ReaderStream::new(out)
-            .map_ok(|chunk| LogMsg::Stdout(String::from_utf8_lossy(&chunk).into_owned()));
+        let out = redacted_output(out, &secrets, LogMsg::Stdout);
 
         // Map stderr bytes -> LogMsg::Stderr
-        let err = ReaderStream::new(err)
-            .map_ok(|chunk| LogMsg::Stderr(String::from_utf8_lossy(&chunk).into_owned()));
+        let err = redacted_output(err, &secrets, LogMsg::Stderr);
 
         // If you have a JSON Patch source, map it to LogMsg::JsonPatch too, then select all three.
 
@@ -717,6 +971,98 @@ impl LocalContainerService {
         Ok(project_repo_path)
     }
 
+    /// Build the extra environment for a process spawned in a task attempt,
+    /// resolving `${HOST_VAR}` references in the project's env template, adding
+    /// the configured secrets scoped to `executor_action` and finally the
+    /// attempt's own overrides. The project's MCP servers ride along for agents.
+    async fn execution_env(
+        &self,
+        task_attempt: &TaskAttempt,
+        executor_action: &ExecutorAction,
+    ) -> Result<ExecutionEnv, ContainerError> {
+        let project = task_attempt
+            .parent_task(&self.db.pool)
+            .await?
+            .ok_or(ContainerError::Other(anyhow!("Parent task not found")))?
+            .parent_project(&self.db.pool)
+            .await?
+            .ok_or(ContainerError::Other(anyhow!("Parent project not found")))?;
+
+        let (mut env, unresolved) =
+            ExecutionEnv::from_template(&project.env_template, |name| std::env::var(name).ok());
+        if !unresolved.is_empty() {
+            tracing::warn!(
+                "Unresolved host variables in env template for project {}: {}",
+                project.id,
+                unresolved.join(", ")
+            );
+        }
+        env.insert_scoped_secrets(&self.config.read().await.secrets, executor_action.typ());
+        if let Some(overrides) = &task_attempt.env_overrides {
+            for (key, value) in &overrides.0.0 {
+                env.insert(key, value);
+            }
+        }
+        env.mcp_servers = project.mcp_servers.0;
+        Ok(env)
+    }
+
+    /// Where an attempt's diff comes from: its merge commit once merged with a
+    /// clean worktree and nothing new on the branch, otherwise the live worktree.
+    async fn attempt_diff_source(
+        &self,
+        task_attempt: &TaskAttempt,
+    ) -> Result<AttemptDiffSource, ContainerError> {
+        let project_repo_path = self.get_project_repo_path(task_attempt).await?;
+        let latest_merge =
+            Merge::find_latest_by_task_attempt_id(&self.db.pool, task_attempt.id).await?;
+
+        let is_ahead = if let Ok((ahead, _)) = self.git().get_branch_status(
+            &project_repo_path,
+            &task_attempt.branch,
+            &task_attempt.target_branch,
+        ) {
+            ahead > 0
+        } else {
+            false
+        };
+
+        if let Some(merge) = &latest_merge
+            && let Some(commit) = merge.merge_commit()
+            && self.is_container_clean(task_attempt).await?
+            && !is_ahead
+        {
+            return Ok(AttemptDiffSource::Merged {
+                repo_path: project_repo_path,
+                merge_commit: commit,
+            });
+        }
+
+        // For orchestrator tasks, use container_ref directly (it's the main repo, not a worktree)
+        let worktree_path = if task_attempt.is_orchestrator {
+            task_attempt
+                .container_ref
+                .as_ref()
+                .map(PathBuf::from)
+                .ok_or_else(|| {
+                    ContainerError::Other(anyhow!("Orchestrator attempt missing container_ref"))
+                })?
+        } else {
+            let container_ref = self.ensure_container_exists(task_attempt).await?;
+            PathBuf::from(container_ref)
+        };
+        let base_commit = self.git().get_base_commit(
+            &project_repo_path,
+            &task_attempt.branch,
+            &task_attempt.target_branch,
+        )?;
+
+        Ok(AttemptDiffSource::Live {
+            worktree_path,
+            base_commit,
+        })
+    }
+
     /// Create a diff log stream for merged attempts (never changes) for WebSocket
     fn create_merged_diff_stream(
         &self,
@@ -855,22 +1201,30 @@ impl LocalContainerService {
             .await?
             .ok_or_else(|| ContainerError::Other(anyhow!("Project not found")))?;
 
-        let cleanup_action = self.cleanup_action(project.cleanup_script);
+        let script_language = project.effective_script_language();
+        let cleanup_action = self.cleanup_action(
+            ctx.task_attempt
+                .effective_cleanup_script(project.cleanup_script),
+            script_language,
+        );
 
-        let action_type = if let Some(session_id) = latest_session_id {
-            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
-                prompt: queued_data.message.clone(),
-                session_id,
-                executor_profile_id: executor_profile_id.clone(),
-                is_orchestrator: ctx.task_attempt.is_orchestrator,
-            })
-        } else {
-            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
-                prompt: queued_data.message.clone(),
-                executor_profile_id: executor_profile_id.clone(),
-                is_orchestrator: ctx.task_attempt.is_orchestrator,
-            })
-        };
+        let worktree_path = PathBuf::from(self.ensure_container_exists(&ctx.task_attempt).await?);
+        if !queued_data.image_ids.is_empty()
+            && let Err(e) = self
+                .image_service
+                .attach_images_to_task(&worktree_path, ctx.task.id, &queued_data.image_ids)
+                .await
+        {
+            tracing::warn!("Failed to attach queued follow-up images: {}", e);
+        }
+        let prompt = ImageService::canonicalise_image_paths(&queued_data.message, &worktree_path);
+
+        let action_type = ExecutorActionType::coding_agent_request(
+            prompt,
+            latest_session_id,
+            executor_profile_id,
+            ctx.task_attempt.is_orchestrator,
+        );
 
         let action = ExecutorAction::new(action_type, cleanup_action);
 
@@ -883,6 +1237,38 @@ impl LocalContainerService {
     }
 }
 
+/// Output of `reader` as `wrap`ped messages with `secrets` masked, including
+/// secrets that straddle two reads.
+fn redacted_output<R>(
+    reader: R,
+    secrets: &[String],
+    wrap: fn(String) -> LogMsg,
+) -> BoxStream<'static, std::io::Result<LogMsg>>
+where
+    R: tokio::io::AsyncRead + Send + Unpin + 'static,
+{
+    let state = Some((ReaderStream::new(reader), SecretRedactor::new(secrets)));
+    stream::unfold(state, move |state| async move {
+        let (mut chunks, mut redactor) = state?;
+        loop {
+            match chunks.next().await {
+                Some(Ok(chunk)) => {
+                    let ready = redactor.push(&String::from_utf8_lossy(&chunk));
+                    if !ready.is_empty() {
+                        return Some((Ok(wrap(ready)), Some((chunks, redactor))));
+                    }
+                }
+                Some(Err(e)) => return Some((Err(e), Some((chunks, redactor)))),
+                None => {
+                    let rest = redactor.finish();
+                    return (!rest.is_empty()).then(|| (Ok(wrap(rest)), None));
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
 fn failure_exit_status() -> std::process::ExitStatus {
     #[cfg(unix)]
     {
@@ -910,14 +1296,129 @@ impl ContainerService for LocalContainerService {
         &self.git
     }
 
+    fn branch_status_cache(&self) -> &BranchStatusCache {
+        &self.branch_status_cache
+    }
+
     fn share_publisher(&self) -> Option<&SharePublisher> {
         self.publisher.as_ref().ok()
     }
 
+    async fn running_child_count(&self) -> usize {
+        self.child_store.read().await.len()
+    }
+
+    async fn resume_queued_follow_ups(&self) -> Result<(), ContainerError> {
+        let queue = &self.queued_message_service;
+        let busy: HashSet<Uuid> = ExecutionProcess::find_running(&self.db.pool)
+            .await?
+            .into_iter()
+            .filter(|p| p.run_reason != ExecutionProcessRunReason::DevServer)
+            .map(|p| p.task_attempt_id)
+            .collect();
+
+        for attempt_id in queue.queued_attempt_ids() {
+            if busy.contains(&attempt_id) {
+                continue;
+            }
+            let Some(latest) = ExecutionProcess::find_latest_by_task_attempt_and_run_reason(
+                &self.db.pool,
+                attempt_id,
+                &ExecutionProcessRunReason::CodingAgent,
+            )
+            .await?
+            else {
+                continue;
+            };
+            if !runs_queued_after(&latest.status) {
+                // Same rule as on exit: a failed or killed run discards its queue
+                match queue.cancel_queued(attempt_id).await {
+                    Ok(dropped) => tracing::info!(
+                        "Discarded {} queued message(s) for attempt {} due to execution status {:?}",
+                        dropped.len(),
+                        attempt_id,
+                        latest.status
+                    ),
+                    Err(e) => tracing::warn!(
+                        "Failed to discard queued messages for attempt {}: {}",
+                        attempt_id,
+                        e
+                    ),
+                }
+                continue;
+            }
+            let ctx = ExecutionProcess::load_context(&self.db.pool, latest.id).await?;
+            let queued_msg = match queue.pop_queued(attempt_id).await {
+                Ok(Some(queued_msg)) => queued_msg,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to take queued message for attempt {}: {}",
+                        attempt_id,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            tracing::info!(
+                "Resuming queued follow-ups for idle attempt {} after restart",
+                attempt_id
+            );
+            // The draft mirrors the latest queued message; drop it once the queue has drained
+            if !queue.has_queued(attempt_id)
+                && let Err(e) =
+                    Scratch::delete(&self.db.pool, attempt_id, &ScratchType::DraftFollowUp).await
+            {
+                tracing::warn!(
+                    "Failed to delete scratch after consuming queued message: {}",
+                    e
+                );
+            }
+            if let Err(e) = self.start_queued_follow_up(&ctx, &queued_msg.data).await {
+                tracing::error!(
+                    "Failed to start queued follow-up for attempt {}: {}",
+                    attempt_id,
+                    e
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn interrupt_running_processes(&self) {
+        for (id, child) in self.child_store.read().await.iter() {
+            let mut child = child.write().await;
+            if let Err(e) = command::interrupt_process_group(&mut child) {
+                tracing::warn!("Failed to interrupt execution process {}: {}", id, e);
+            }
+        }
+    }
+
+    fn begin_draining(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
     async fn git_branch_prefix(&self) -> String {
         self.config.read().await.git_branch_prefix.clone()
     }
 
+    async fn log_file_sink_config(&self) -> Option<LogFileSinkConfig> {
+        self.config.read().await.log_file_sink.clone()
+    }
+
+    async fn auto_commit_message_mode(&self) -> AutoCommitMessageMode {
+        self.config.read().await.auto_commit_message_mode
+    }
+
+    async fn execution_limits(&self) -> ExecutionLimitsConfig {
+        self.config.read().await.execution_limits.clone()
+    }
+
     fn task_attempt_to_current_dir(&self, task_attempt: &TaskAttempt) -> PathBuf {
         PathBuf::from(task_attempt.container_ref.clone().unwrap_or_default())
     }
@@ -976,6 +1477,7 @@ impl ContainerService for LocalContainerService {
                 &new_worktree_path,
                 &task_attempt.target_branch,
                 !using_existing_branch, // create_new_branch
+                &parse_sparse_patterns(project.worktree_sparse_patterns.as_deref()),
             )
             .await?;
 
@@ -1021,6 +1523,7 @@ impl ContainerService for LocalContainerService {
         custom_branch: Option<String>,
         use_existing_branch: bool,
         conversation_history: Option<String>,
+        overrides: AttemptOverrides,
     ) -> Result<TaskAttempt, ContainerError> {
         let attempt_id = Uuid::new_v4();
         let git_branch_name = if let Some(custom_branch) = custom_branch {
@@ -1028,11 +1531,10 @@ impl ContainerService for LocalContainerService {
         } else if use_existing_branch {
             base_branch.to_string()
         } else {
-            self.git_branch_from_task_attempt(&attempt_id, &task.title)
-                .await
+            self.git_branch_from_task_attempt(&attempt_id, task).await
         };
 
-        let task_attempt = TaskAttempt::create(
+        let mut task_attempt = TaskAttempt::create(
             &self.db.pool,
             &db::models::task_attempt::CreateTaskAttempt {
                 executor: executor_profile_id.executor,
@@ -1045,6 +1547,16 @@ impl ContainerService for LocalContainerService {
         )
         .await?;
 
+        if let Some(script) = overrides.cleanup_script.filter(|s| !s.trim().is_empty()) {
+            TaskAttempt::set_cleanup_script_override(&self.db.pool, attempt_id, Some(&script))
+                .await?;
+            task_attempt.cleanup_script_override = Some(script);
+        }
+        if let Some(env) = overrides.env.filter(|env| !env.0.is_empty()) {
+            TaskAttempt::set_env_overrides(&self.db.pool, attempt_id, Some(&env)).await?;
+            task_attempt.env_overrides = Some(sqlx::types::Json(env));
+        }
+
         let start_result = self
             .start_attempt_with_prompt(
                 &task_attempt,
@@ -1066,6 +1578,74 @@ impl ContainerService for LocalContainerService {
         Ok(task_attempt)
     }
 
+    async fn adopt_branch(
+        &self,
+        task: &Task,
+        executor: BaseCodingAgent,
+        branch: &str,
+        base_branch: &str,
+    ) -> Result<TaskAttempt, ContainerError> {
+        let project = task
+            .parent_project(&self.db.pool)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let attempt_id = Uuid::new_v4();
+        TaskAttempt::create(
+            &self.db.pool,
+            &db::models::task_attempt::CreateTaskAttempt {
+                executor,
+                base_branch: base_branch.to_string(),
+                branch: branch.to_string(),
+                is_orchestrator: false,
+            },
+            attempt_id,
+            task.id,
+        )
+        .await?;
+
+        let worktree_path = WorktreeManager::get_worktree_base_dir().join(
+            LocalContainerService::dir_name_from_task_attempt(&attempt_id, &task.title),
+        );
+        // The branch already exists, so only a worktree is checked out for it
+        if let Err(err) = WorktreeManager::create_worktree(
+            &project.git_repo_path,
+            branch,
+            &worktree_path,
+            base_branch,
+            false,
+            &parse_sparse_patterns(project.worktree_sparse_patterns.as_deref()),
+        )
+        .await
+        {
+            if let Err(e) = TaskAttempt::delete(&self.db.pool, attempt_id).await {
+                tracing::error!("Failed to delete task attempt after adoption error: {}", e);
+            }
+            return Err(err.into());
+        }
+
+        if let Some(copy_files) = &project.copy_files
+            && !copy_files.trim().is_empty()
+        {
+            self.copy_project_files(&project.git_repo_path, &worktree_path, copy_files)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to copy project files: {}", e);
+                });
+        }
+
+        TaskAttempt::update_container_ref(
+            &self.db.pool,
+            attempt_id,
+            &worktree_path.to_string_lossy(),
+        )
+        .await?;
+
+        Ok(TaskAttempt::find_by_id(&self.db.pool, attempt_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?)
+    }
+
     async fn delete_inner(&self, task_attempt: &TaskAttempt) -> Result<(), ContainerError> {
         // Orchestrator attempts don't have worktrees to clean up
         if task_attempt.is_orchestrator {
@@ -1161,8 +1741,14 @@ impl ContainerService for LocalContainerService {
             &project.git_repo_path,
             &task_attempt.branch,
             &worktree_path,
+            &parse_sparse_patterns(project.worktree_sparse_patterns.as_deref()),
         )
         .await?;
+        if task_attempt.worktree_deleted {
+            TaskAttempt::mark_worktree_restored(&self.db.pool, task_attempt.id).await?;
+        }
+        self.deleted_worktrees
+            .clear(self.events.msg_store(), task_attempt.id);
 
         Ok(container_ref.to_string())
     }
@@ -1208,10 +1794,27 @@ impl ContainerService for LocalContainerService {
                 _ => Arc::new(NoopExecutorApprovalService {}),
             };
 
+        let env = self.execution_env(task_attempt, executor_action).await?;
+        // Names only: values may be secrets
+        tracing::debug!(
+            "Spawning execution process {} with env vars: {}",
+            execution_process.id,
+            env.var_names().join(", ")
+        );
+        // Every configured secret is masked, whether or not this process received it
+        let secrets = self
+            .config
+            .read()
+            .await
+            .secrets
+            .iter()
+            .map(|secret| secret.value.clone())
+            .collect();
+
         // Create the child and stream, add to execution tracker with timeout
         let mut spawned = tokio::time::timeout(
             Duration::from_secs(30),
-            executor_action.spawn(&current_dir, approvals_service),
+            executor_action.spawn(&current_dir, approvals_service, &env),
         )
         .await
         .map_err(|_| {
@@ -1220,7 +1823,7 @@ impl ContainerService for LocalContainerService {
             ))
         })??;
 
-        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
+        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child, secrets)
             .await;
 
         self.add_child_to_store(execution_process.id, spawned.child)
@@ -1235,6 +1838,15 @@ impl ContainerService for LocalContainerService {
         // Spawn unified exit monitor: watches OS exit and optional executor signal
         let _hn = self.spawn_exit_monitor(&execution_process.id, spawned.exit_signal);
 
+        if executor_action.base_executor().is_some() {
+            self.spawn_abort_pattern_watcher(task_attempt, execution_process)
+                .await;
+        }
+        if execution_process.run_reason == ExecutionProcessRunReason::DevServer {
+            self.spawn_dev_server_url_watcher(task_attempt, execution_process)
+                .await;
+        }
+
         Ok(())
     }
 
@@ -1285,21 +1897,15 @@ impl ContainerService for LocalContainerService {
                 ExecutionProcessRunReason::DevServer
             )
         {
-            match Task::update_status(&self.db.pool, ctx.task.id, TaskStatus::InReview).await {
-                Ok(_) => {
-                    if let Some(publisher) = self.share_publisher()
-                        && let Err(err) = publisher.update_shared_task_by_id(ctx.task.id).await
-                    {
-                        tracing::warn!(
-                            ?err,
-                            "Failed to propagate shared task update for {}",
-                            ctx.task.id
-                        );
-                    }
-                }
-                Err(e) => {
-                    tracing::error!("Failed to update task status to InReview: {e}");
-                }
+            if let Err(e) = update_task_status_and_share(
+                &self.db.pool,
+                self.share_publisher().cloned(),
+                ctx.task.id,
+                TaskStatus::InReview,
+            )
+            .await
+            {
+                tracing::error!("Failed to update task status to InReview: {e}");
             }
         }
 
@@ -1330,120 +1936,81 @@ impl ContainerService for LocalContainerService {
         stats_only: bool,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, ContainerError>
     {
-        let project_repo_path = self.get_project_repo_path(task_attempt).await?;
-        let latest_merge =
-            Merge::find_latest_by_task_attempt_id(&self.db.pool, task_attempt.id).await?;
-
-        let is_ahead = if let Ok((ahead, _)) = self.git().get_branch_status(
-            &project_repo_path,
-            &task_attempt.branch,
-            &task_attempt.target_branch,
-        ) {
-            ahead > 0
-        } else {
-            false
+        let wrapper = match self.attempt_diff_source(task_attempt).await? {
+            AttemptDiffSource::Merged {
+                repo_path,
+                merge_commit,
+            } => self.create_merged_diff_stream(&repo_path, &merge_commit, stats_only)?,
+            AttemptDiffSource::Live {
+                worktree_path,
+                base_commit,
+            } => {
+                self.create_live_diff_stream(&worktree_path, &base_commit, stats_only)
+                    .await?
+            }
         };
+        Ok(Box::pin(wrapper))
+    }
 
-        if let Some(merge) = &latest_merge
-            && let Some(commit) = merge.merge_commit()
-            && self.is_container_clean(task_attempt).await?
-            && !is_ahead
-        {
-            let wrapper =
-                self.create_merged_diff_stream(&project_repo_path, &commit, stats_only)?;
-            return Ok(Box::pin(wrapper));
-        }
-
-        // For orchestrator tasks, use container_ref directly (it's the main repo, not a worktree)
-        let worktree_path = if task_attempt.is_orchestrator {
-            task_attempt
-                .container_ref
-                .as_ref()
-                .map(PathBuf::from)
-                .ok_or_else(|| {
-                    ContainerError::Other(anyhow!("Orchestrator attempt missing container_ref"))
-                })?
-        } else {
-            let container_ref = self.ensure_container_exists(task_attempt).await?;
-            PathBuf::from(container_ref)
+    async fn collect_diff(
+        &self,
+        task_attempt: &TaskAttempt,
+        stats_only: bool,
+    ) -> Result<Vec<Diff>, ContainerError> {
+        let diffs = match self.attempt_diff_source(task_attempt).await? {
+            AttemptDiffSource::Merged {
+                repo_path,
+                merge_commit,
+            } => self.git().get_diffs(
+                DiffTarget::Commit {
+                    repo_path: &repo_path,
+                    commit_sha: &merge_commit,
+                },
+                None,
+            )?,
+            AttemptDiffSource::Live {
+                worktree_path,
+                base_commit,
+            } => self.git().get_diffs(
+                DiffTarget::Worktree {
+                    worktree_path: &worktree_path,
+                    base_commit: &base_commit,
+                },
+                None,
+            )?,
         };
-        let base_commit = self.git().get_base_commit(
-            &project_repo_path,
-            &task_attempt.branch,
-            &task_attempt.target_branch,
-        )?;
 
-        let wrapper = self
-            .create_live_diff_stream(&worktree_path, &base_commit, stats_only)
-            .await?;
-        Ok(Box::pin(wrapper))
+        let cum = Arc::new(AtomicUsize::new(0));
+        Ok(diffs
+            .into_iter()
+            .map(|mut d| {
+                diff_stream::apply_stream_omit_policy(&mut d, &cum, stats_only);
+                d
+            })
+            .collect())
     }
 
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
-        if !matches!(
-            ctx.execution_process.run_reason,
-            ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::CleanupScript,
-        ) {
-            return Ok(false);
-        }
-
-        let message = match ctx.execution_process.run_reason {
-            ExecutionProcessRunReason::CodingAgent => {
-                // Try to retrieve the task summary from the executor session
-                // otherwise fallback to default message
-                match ExecutorSession::find_by_execution_process_id(
-                    &self.db().pool,
-                    ctx.execution_process.id,
-                )
-                .await
-                {
-                    Ok(Some(session)) if session.summary.is_some() => session.summary.unwrap(),
-                    Ok(_) => {
-                        tracing::debug!(
-                            "No summary found for execution process {}, using default message",
-                            ctx.execution_process.id
-                        );
-                        format!(
-                            "Commit changes from coding agent for task attempt {}",
-                            ctx.task_attempt.id
-                        )
-                    }
-                    Err(e) => {
-                        tracing::debug!(
-                            "Failed to retrieve summary for execution process {}: {}",
-                            ctx.execution_process.id,
-                            e
-                        );
-                        format!(
-                            "Commit changes from coding agent for task attempt {}",
-                            ctx.task_attempt.id
-                        )
-                    }
-                }
-            }
-            ExecutionProcessRunReason::CleanupScript => {
-                format!(
-                    "Cleanup script changes for task attempt {}",
-                    ctx.task_attempt.id
-                )
-            }
-            _ => Err(ContainerError::Other(anyhow::anyhow!(
-                "Invalid run reason for commit"
-            )))?,
+        let (allow_empty_cleanup, line_endings, mode) = {
+            let config = self.config.read().await;
+            (
+                config.allow_empty_cleanup_commit,
+                config.line_endings,
+                config.auto_commit_message_mode,
+            )
         };
-
-        let container_ref = ctx.task_attempt.container_ref.as_ref().ok_or_else(|| {
-            ContainerError::Other(anyhow::anyhow!("Container reference not found"))
-        })?;
-
-        tracing::debug!(
-            "Committing changes for task attempt {} at path {:?}: '{}'",
-            ctx.task_attempt.id,
-            &container_ref,
-            message
-        );
-
-        let changes_committed = self.git().commit(Path::new(container_ref), &message)?;
+        let changes_committed = auto_commit_execution(
+            &self.db.pool,
+            self.git(),
+            ctx,
+            mode,
+            allow_empty_cleanup,
+            line_endings,
+        )
+        .await?;
+        if changes_committed && let Some(container_ref) = &ctx.task_attempt.container_ref {
+            self.write_commit_note(ctx, Path::new(container_ref));
+        }
         Ok(changes_committed)
     }
 
@@ -1454,15 +2021,12 @@ impl ContainerService for LocalContainerService {
         target_dir: &Path,
         copy_files: &str,
     ) -> Result<(), ContainerError> {
-        let files: Vec<&str> = copy_files
-            .split(',')
-            .map(|s| s.trim())
-            .filter(|s| !s.is_empty())
-            .collect();
+        let files = expand_copy_files(source_dir, copy_files)?;
+        let line_endings = self.config.read().await.line_endings;
 
         for file_path in files {
-            let source_file = source_dir.join(file_path);
-            let target_file = target_dir.join(file_path);
+            let source_file = source_dir.join(&file_path);
+            let target_file = target_dir.join(&file_path);
 
             // Create parent directories if needed
             if let Some(parent) = target_file.parent()
@@ -1473,19 +2037,12 @@ impl ContainerService for LocalContainerService {
                 })?;
             }
 
-            // Copy the file
-            if source_file.exists() {
-                std::fs::copy(&source_file, &target_file).map_err(|e| {
-                    ContainerError::Other(anyhow!(
-                        "Failed to copy file {source_file:?} to {target_file:?}: {e}"
-                    ))
-                })?;
-                tracing::info!("Copied file {:?} to worktree", file_path);
-            } else {
-                return Err(ContainerError::Other(anyhow!(
-                    "File {source_file:?} does not exist in the project directory"
-                )));
-            }
+            copy_project_file(&source_file, &target_file, line_endings).map_err(|e| {
+                ContainerError::Other(anyhow!(
+                    "Failed to copy file {source_file:?} to {target_file:?}: {e}"
+                ))
+            })?;
+            tracing::info!("Copied file {:?} to worktree", file_path);
         }
         Ok(())
     }
@@ -5,6 +5,7 @@ use db::DBService;
 use deployment::{Deployment, DeploymentError, RemoteClientNotConfigured};
 use executors::profile::ExecutorConfigs;
 use services::services::{
+    agent_login::AgentLoginMonitor,
     analytics::{AnalyticsConfig, AnalyticsContext, AnalyticsService, generate_user_id},
     approvals::Approvals,
     auth::AuthContext,
@@ -19,8 +20,10 @@ use services::services::{
     queued_message::QueuedMessageService,
     remote_client::{RemoteClient, RemoteClientError},
     share::{RemoteSyncHandle, ShareConfig, SharePublisher},
+    task_registry::TaskRegistry,
+    webhook::WebhookService,
 };
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{Mutex, RwLock, mpsc};
 use utils::{
     api::oauth::LoginStatus,
     assets::{config_path, credentials_path},
@@ -46,6 +49,7 @@ pub struct LocalDeployment {
     file_search_cache: Arc<FileSearchCache>,
     approvals: Approvals,
     queued_message_service: QueuedMessageService,
+    task_registry: TaskRegistry,
     share_publisher: Result<SharePublisher, RemoteClientNotConfigured>,
     share_sync_handle: Arc<Mutex<Option<RemoteSyncHandle>>>,
     share_config: Option<ShareConfig>,
@@ -99,19 +103,28 @@ impl Deployment for LocalDeployment {
         let events_entry_count = Arc::new(RwLock::new(0));
 
         // Create DB with event hooks
+        let (task_status_tx, task_status_rx) = mpsc::unbounded_channel();
         let db = {
             let hook = EventService::create_hook(
                 events_msg_store.clone(),
                 events_entry_count.clone(),
                 DBService::new().await?, // Temporary DB service for the hook
+                task_status_tx,
             );
             DBService::new_with_after_connect(hook).await?
         };
 
+        let task_registry = TaskRegistry::new();
+        task_registry.spawn(
+            "webhooks",
+            false,
+            WebhookService::new(db.clone(), config.clone(), task_registry.clone())
+                .run(task_status_rx),
+        );
         let image = ImageService::new(db.clone().pool)?;
         {
             let image_service = image.clone();
-            tokio::spawn(async move {
+            task_registry.spawn("orphaned_image_cleanup", true, async move {
                 tracing::info!("Starting orphaned image cleanup...");
                 if let Err(e) = image_service.delete_orphaned_images().await {
                     tracing::error!("Failed to clean up orphaned images: {}", e);
@@ -120,7 +133,12 @@ impl Deployment for LocalDeployment {
         }
 
         let approvals = Approvals::new(msg_stores.clone());
-        let queued_message_service = QueuedMessageService::new();
+        let queued_message_service = QueuedMessageService::new(db.pool.clone());
+        match queued_message_service.restore().await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Restored queued follow-ups for {} attempts", count),
+            Err(e) => tracing::warn!("Failed to restore queued follow-ups: {}", e),
+        }
 
         let share_config = ShareConfig::from_env();
 
@@ -174,6 +192,7 @@ impl Deployment for LocalDeployment {
             user_id: user_id.clone(),
             analytics_service: s.clone(),
         });
+        let events = EventService::new(db.clone(), events_msg_store, events_entry_count);
         let container = LocalContainerService::new(
             db.clone(),
             msg_stores.clone(),
@@ -184,10 +203,16 @@ impl Deployment for LocalDeployment {
             approvals.clone(),
             queued_message_service.clone(),
             share_publisher.clone(),
+            task_registry.clone(),
+            events.clone(),
         )
         .await;
 
-        let events = EventService::new(db.clone(), events_msg_store, events_entry_count);
+        task_registry.spawn(
+            "agent_login_monitor",
+            true,
+            AgentLoginMonitor::new(config.clone(), events.clone()).run(),
+        );
 
         let file_search_cache = Arc::new(FileSearchCache::new());
 
@@ -204,6 +229,7 @@ impl Deployment for LocalDeployment {
             file_search_cache,
             approvals,
             queued_message_service,
+            task_registry,
             share_publisher,
             share_sync_handle: share_sync_handle.clone(),
             share_config: share_config.clone(),
@@ -267,6 +293,10 @@ impl Deployment for LocalDeployment {
         &self.queued_message_service
     }
 
+    fn task_registry(&self) -> &TaskRegistry {
+        &self.task_registry
+    }
+
     fn share_publisher(&self) -> Result<SharePublisher, RemoteClientNotConfigured> {
         self.share_publisher.clone()
     }
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, env, fs, path::Path};
 
-use schemars::{JsonSchema, Schema, SchemaGenerator, generate::SchemaSettings};
+use executors::executors::BaseCodingAgent;
 use ts_rs::TS;
 
 fn generate_types_content() -> String {
@@ -12,16 +12,36 @@ fn generate_types_content() -> String {
     let decls: Vec<String> = vec![
         services::services::filesystem::DirectoryEntry::decl(),
         services::services::filesystem::DirectoryListResponse::decl(),
+        db::models::project::MergeStrategy::decl(),
         db::models::project::Project::decl(),
         db::models::project::ProjectWithTaskCounts::decl(),
         db::models::project::CreateProject::decl(),
         db::models::project::UpdateProject::decl(),
         db::models::project::SearchResult::decl(),
         db::models::project::SearchMatchType::decl(),
+        services::services::file_search_cache::IndexStatus::decl(),
+        server::routes::file_search::WarmTarget::decl(),
+        server::routes::file_search::WarmFileSearchRequest::decl(),
+        server::routes::file_search::ProjectIndexStatus::decl(),
+        server::routes::file_search::WarmProgress::decl(),
         server::routes::projects::CreateRemoteProjectRequest::decl(),
         server::routes::projects::LinkToExistingRequest::decl(),
         server::routes::projects::BranchWorktreeStatus::decl(),
+        server::routes::projects::ExportProjectBundleQuery::decl(),
+        server::routes::projects::ImportProjectBundleRequest::decl(),
+        server::routes::projects::AttemptWorktreeSize::decl(),
+        server::routes::projects::ProjectWorktreeSize::decl(),
+        server::routes::projects::StopAllProcessesResponse::decl(),
+        db::models::activity::ActivityKind::decl(),
+        db::models::activity::Activity::decl(),
+        server::routes::projects::ProjectActivityPage::decl(),
+        server::routes::projects::UpdateTaskLabelRequest::decl(),
+        services::services::project_bundle::ProjectBundle::decl(),
+        services::services::project_bundle::BundledProjectSettings::decl(),
+        services::services::project_bundle::BundledTask::decl(),
+        services::services::project_bundle::BundledAttempt::decl(),
         executors::actions::ExecutorAction::decl(),
+        executors::mcp_config::McpServerDefinition::decl(),
         executors::mcp_config::McpConfig::decl(),
         executors::actions::ExecutorActionType::decl(),
         executors::actions::script::ScriptContext::decl(),
@@ -33,14 +53,23 @@ fn generate_types_content() -> String {
         db::models::tag::CreateTag::decl(),
         db::models::tag::UpdateTag::decl(),
         server::routes::tags::TagSearchParams::decl(),
+        db::models::task_template::TaskTemplate::decl(),
+        db::models::task_template::CreateTaskTemplate::decl(),
+        db::models::task_template::UpdateTaskTemplate::decl(),
+        server::routes::task_templates::TaskTemplateQuery::decl(),
+        server::routes::task_templates::InstantiateTaskTemplateRequest::decl(),
+        server::routes::task_templates::InstantiateTaskTemplateResponse::decl(),
         db::models::task::TaskStatus::decl(),
         db::models::task::Task::decl(),
         db::models::task::TaskWithAttemptStatus::decl(),
         db::models::task::TaskRelationships::decl(),
+        db::models::task::TaskDependencies::decl(),
+        db::models::task::TaskLabel::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
         db::models::shared_task::SharedTask::decl(),
         db::models::scratch::DraftFollowUpData::decl(),
+        db::models::scratch::QueuedFollowUpData::decl(),
         db::models::scratch::ScratchPayload::decl(),
         db::models::scratch::ScratchType::decl(),
         db::models::scratch::Scratch::decl(),
@@ -48,6 +77,12 @@ fn generate_types_content() -> String {
         db::models::scratch::UpdateScratch::decl(),
         services::services::queued_message::QueuedMessage::decl(),
         services::services::queued_message::QueueStatus::decl(),
+        services::services::task_registry::BackgroundTaskStatus::decl(),
+        services::services::task_registry::BackgroundTaskInfo::decl(),
+        services::services::github::GitHubRateLimit::decl(),
+        services::services::pr_monitor::PrSyncStatus::decl(),
+        services::services::agent_login::AgentLoginStatus::decl(),
+        services::services::container::DeletedWorktree::decl(),
         db::models::image::Image::decl(),
         db::models::image::CreateImage::decl(),
         utils::response::ApiResponse::<()>::decl(),
@@ -91,15 +126,24 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::CreateFollowUpAttempt::decl(),
         server::routes::task_attempts::ChangeTargetBranchRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchResponse::decl(),
+        server::routes::task_attempts::AheadBehindRequest::decl(),
+        server::routes::task_attempts::BranchAheadBehind::decl(),
         server::routes::task_attempts::RenameBranchRequest::decl(),
         server::routes::task_attempts::RenameBranchResponse::decl(),
         server::routes::task_attempts::CommitCompareResult::decl(),
+        server::routes::task_attempts::WorktreeSize::decl(),
         server::routes::task_attempts::OpenEditorRequest::decl(),
         server::routes::task_attempts::OpenEditorResponse::decl(),
         server::routes::shared_tasks::AssignSharedTaskRequest::decl(),
         server::routes::shared_tasks::AssignSharedTaskResponse::decl(),
+        services::services::share::RemoteSyncState::decl(),
         server::routes::tasks::ShareTaskResponse::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
+        server::routes::tasks::AdoptBranchRequest::decl(),
+        server::routes::tasks::AdoptBranchResponse::decl(),
+        server::routes::tasks::AddTaskDependencyRequest::decl(),
+        server::routes::tasks::AddTaskLabelRequest::decl(),
+        server::routes::tasks::TaskSearchQuery::decl(),
         server::routes::task_attempts::CreateGitHubPrRequest::decl(),
         server::routes::images::ImageResponse::decl(),
         server::routes::images::ImageMetadata::decl(),
@@ -110,10 +154,23 @@ fn generate_types_content() -> String {
         services::services::config::EditorType::decl(),
         services::services::config::EditorOpenError::decl(),
         services::services::config::GitHubConfig::decl(),
+        services::services::config::LogFileSinkConfig::decl(),
+        services::services::config::LineEndings::decl(),
+        services::services::config::AutoCommitMessageMode::decl(),
+        services::services::config::WebhooksConfig::decl(),
+        services::services::config::ExecutionLimitsConfig::decl(),
+        services::services::container::ExecutionSlots::decl(),
+        executors::env::ScopedSecret::decl(),
+        executors::env::SecretScope::decl(),
+        executors::env::EnvOverrides::decl(),
         services::services::config::SoundFile::decl(),
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
+        services::services::config::ConfigVersionInfo::decl(),
         services::services::git::GitBranch::decl(),
+        services::services::git::LargeFileEntry::decl(),
+        services::services::git::CommitNote::decl(),
+        services::services::git::CommitInfo::decl(),
         utils::diff::Diff::decl(),
         utils::diff::DiffChangeKind::decl(),
         executors::command::CommandBuilder::decl(),
@@ -141,21 +198,48 @@ fn generate_types_content() -> String {
         executors::actions::coding_agent_initial::CodingAgentInitialRequest::decl(),
         executors::actions::coding_agent_follow_up::CodingAgentFollowUpRequest::decl(),
         server::routes::task_attempts::CreateTaskAttemptBody::decl(),
+        server::routes::task_attempts::CreateTaskAttemptBatchBody::decl(),
+        server::routes::task_attempts::TaskAttemptBatchResult::decl(),
+        server::routes::task_attempts::CloneTaskAttemptBody::decl(),
+        server::routes::task_attempts::DevServerInfo::decl(),
         server::routes::task_attempts::RunAgentSetupRequest::decl(),
         server::routes::task_attempts::RunAgentSetupResponse::decl(),
         server::routes::task_attempts::gh_cli_setup::GhCliSetupError::decl(),
         server::routes::task_attempts::RebaseTaskAttemptRequest::decl(),
+        server::routes::task_attempts::MergeTaskAttemptRequest::decl(),
         server::routes::task_attempts::GitOperationError::decl(),
+        server::routes::task_attempts::PushTaskAttemptRequest::decl(),
         server::routes::task_attempts::PushError::decl(),
         server::routes::task_attempts::CreatePrError::decl(),
-        server::routes::task_attempts::BranchStatus::decl(),
+        services::services::branch_status::BranchStatus::decl(),
         server::routes::task_attempts::CommitChangesRequest::decl(),
+        server::routes::task_attempts::CommitChangesError::decl(),
         server::routes::task_attempts::WorktreeStatusResponse::decl(),
         server::routes::task_attempts::FileStatusEntry::decl(),
         server::routes::task_attempts::GenerateCommitMessageResponse::decl(),
         server::routes::task_attempts::GenerateCommitMessageError::decl(),
+        server::routes::task_attempts::CommitMessagePreviewResponse::decl(),
+        server::routes::task_attempts::SetLastViewedRequest::decl(),
+        server::routes::task_attempts::LastViewedResponse::decl(),
+        server::routes::task_attempts::FileDiff::decl(),
+        server::routes::task_attempts::DiffSinceLastResponse::decl(),
+        server::routes::task_attempts::LargeFilesResponse::decl(),
+        server::routes::task_attempts::ResetToCommitRequest::decl(),
+        server::routes::task_attempts::ResetToCommitResponse::decl(),
+        server::routes::task_attempts::ConversationExportFormat::decl(),
+        server::routes::task_attempts::ConversationExport::decl(),
+        server::routes::task_attempts::ResolveConflictRequest::decl(),
+        server::routes::task_attempts::ResolveConflictResponse::decl(),
+        server::routes::execution_processes::RawLogStream::decl(),
+        server::routes::execution_processes::RawLogEntry::decl(),
+        server::routes::execution_processes::RawLogPage::decl(),
+        server::routes::execution_processes::SendInputRequest::decl(),
         executors::conversation_export::ExportResult::decl(),
+        executors::conversation_export::JsonExportResult::decl(),
+        executors::mcp_calls::McpToolCall::decl(),
         services::services::git::ConflictOp::decl(),
+        services::services::git::ResetMode::decl(),
+        services::services::git::ConflictSide::decl(),
         db::models::task_attempt::TaskAttempt::decl(),
         db::models::execution_process::ExecutionProcess::decl(),
         db::models::execution_process::ExecutionProcessStatus::decl(),
@@ -164,11 +248,13 @@ fn generate_types_content() -> String {
         db::models::merge::DirectMerge::decl(),
         db::models::merge::PrMerge::decl(),
         db::models::merge::MergeStatus::decl(),
+        db::models::merge::GitProvider::decl(),
         db::models::merge::PullRequestInfo::decl(),
         executors::logs::CommandExitStatus::decl(),
         executors::logs::CommandRunResult::decl(),
         executors::logs::ContextWarningLevel::decl(),
         executors::logs::ContextUsage::decl(),
+        executors::logs::TokenUsage::decl(),
         executors::logs::NormalizedEntry::decl(),
         executors::logs::NormalizedEntryType::decl(),
         executors::logs::FileChange::decl(),
@@ -201,64 +287,32 @@ fn generate_types_content() -> String {
     format!("{HEADER}\n\n{body}")
 }
 
-fn generate_json_schema<T: JsonSchema>() -> Result<String, serde_json::Error> {
-    // Draft-07, inline everything (no $defs)
-    let mut settings = SchemaSettings::draft07();
-    settings.inline_subschemas = true;
-
-    let generator: SchemaGenerator = settings.into_generator();
-    let schema: Schema = generator.into_root_schema_for::<T>();
-
-    // Convert to JSON value to manipulate it
-    let mut schema_value: serde_json::Value = serde_json::to_value(&schema)?;
-    // Remove the title from root schema to prevent RJSF from creating an outer field container
-    if let Some(obj) = schema_value.as_object_mut() {
-        obj.remove("title");
-    }
-    let formatted = serde_json::to_string_pretty(&schema_value)?;
-    Ok(formatted)
+fn generate_json_schema(agent: BaseCodingAgent) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(&agent.config_schema())
 }
 
 fn generate_schemas() -> Result<HashMap<&'static str, String>, serde_json::Error> {
     // // Generate schemas for all executor types
     println!("Generating JSON schemas…");
     let schemas: HashMap<&str, String> = HashMap::from([
-        (
-            "amp",
-            generate_json_schema::<executors::executors::amp::Amp>()?,
-        ),
+        ("amp", generate_json_schema(BaseCodingAgent::Amp)?),
         (
             "claude_code",
-            generate_json_schema::<executors::executors::claude::ClaudeCode>()?,
-        ),
-        (
-            "gemini",
-            generate_json_schema::<executors::executors::gemini::Gemini>()?,
-        ),
-        (
-            "codex",
-            generate_json_schema::<executors::executors::codex::Codex>()?,
+            generate_json_schema(BaseCodingAgent::ClaudeCode)?,
         ),
+        ("gemini", generate_json_schema(BaseCodingAgent::Gemini)?),
+        ("codex", generate_json_schema(BaseCodingAgent::Codex)?),
         (
             "cursor_agent",
-            generate_json_schema::<executors::executors::cursor::CursorAgent>()?,
-        ),
-        (
-            "opencode",
-            generate_json_schema::<executors::executors::opencode::Opencode>()?,
+            generate_json_schema(BaseCodingAgent::CursorAgent)?,
         ),
+        ("opencode", generate_json_schema(BaseCodingAgent::Opencode)?),
         (
             "qwen_code",
-            generate_json_schema::<executors::executors::qwen::QwenCode>()?,
-        ),
-        (
-            "copilot",
-            generate_json_schema::<executors::executors::copilot::Copilot>()?,
-        ),
-        (
-            "droid",
-            generate_json_schema::<executors::executors::droid::Droid>()?,
+            generate_json_schema(BaseCodingAgent::QwenCode)?,
         ),
+        ("copilot", generate_json_schema(BaseCodingAgent::Copilot)?),
+        ("droid", generate_json_schema(BaseCodingAgent::Droid)?),
     ]);
     println!(
         "✅ JSON schemas generated. {} schemas created.",
@@ -16,9 +16,12 @@ use services::services::{
     container::ContainerError,
     git::GitServiceError,
     github::GitHubServiceError,
+    gitlab::GitLabServiceError,
     image::ImageError,
+    project_bundle::ProjectBundleError,
     remote_client::RemoteClientError,
     share::ShareError,
+    task_registry::TaskRegistryError,
     worktree_manager::WorktreeError,
 };
 use thiserror::Error;
@@ -40,6 +43,8 @@ pub enum ApiError {
     #[error(transparent)]
     GitHubService(#[from] GitHubServiceError),
     #[error(transparent)]
+    GitLabService(#[from] GitLabServiceError),
+    #[error(transparent)]
     Deployment(#[from] DeploymentError),
     #[error(transparent)]
     Container(#[from] ContainerError),
@@ -61,10 +66,16 @@ pub enum ApiError {
     EditorOpen(#[from] EditorOpenError),
     #[error(transparent)]
     RemoteClient(#[from] RemoteClientError),
+    #[error(transparent)]
+    TaskRegistry(#[from] TaskRegistryError),
+    #[error(transparent)]
+    ProjectBundle(#[from] ProjectBundleError),
     #[error("Unauthorized")]
     Unauthorized,
     #[error("Bad request: {0}")]
     BadRequest(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
     #[error("Conflict: {0}")]
     Conflict(String),
     #[error("Forbidden: {0}")]
@@ -111,10 +122,23 @@ impl IntoResponse for ApiError {
                 services::services::git::GitServiceError::RebaseInProgress => {
                     (StatusCode::CONFLICT, "GitServiceError")
                 }
+                services::services::git::GitServiceError::NoRebaseInProgress => {
+                    (StatusCode::CONFLICT, "GitServiceError")
+                }
+                services::services::git::GitServiceError::CommitNotOnBranch(_) => {
+                    (StatusCode::BAD_REQUEST, "GitServiceError")
+                }
+                services::services::git::GitServiceError::FileNotConflicted(_) => {
+                    (StatusCode::BAD_REQUEST, "GitServiceError")
+                }
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "GitServiceError"),
             },
             ApiError::GitHubService(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitHubServiceError"),
+            ApiError::GitLabService(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitLabServiceError"),
             ApiError::Deployment(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DeploymentError"),
+            ApiError::Container(ContainerError::ConcurrencyLimitReached { .. }) => {
+                (StatusCode::CONFLICT, "ContainerError")
+            }
             ApiError::Container(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError"),
             ApiError::Executor(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutorError"),
             ApiError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DatabaseError"),
@@ -166,8 +190,24 @@ impl IntoResponse for ApiError {
                     (StatusCode::BAD_REQUEST, "RemoteClientError")
                 }
             },
+            ApiError::TaskRegistry(err) => match err {
+                TaskRegistryError::NotFound(_) => (StatusCode::NOT_FOUND, "TaskRegistryError"),
+                TaskRegistryError::NotCancellable(_) => (StatusCode::CONFLICT, "TaskRegistryError"),
+            },
+            ApiError::ProjectBundle(err) => match err {
+                ProjectBundleError::ProjectNotFound => {
+                    (StatusCode::NOT_FOUND, "ProjectBundleError")
+                }
+                ProjectBundleError::UnsupportedVersion(_) | ProjectBundleError::Invalid(_) => {
+                    (StatusCode::BAD_REQUEST, "ProjectBundleError")
+                }
+                ProjectBundleError::Database(_) | ProjectBundleError::TaskAttempt(_) => {
+                    (StatusCode::INTERNAL_SERVER_ERROR, "ProjectBundleError")
+                }
+            },
             ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized"),
             ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "BadRequest"),
+            ApiError::NotFound(_) => (StatusCode::NOT_FOUND, "NotFoundError"),
             ApiError::Conflict(_) => (StatusCode::CONFLICT, "ConflictError"),
             ApiError::Forbidden(_) => (StatusCode::FORBIDDEN, "ForbiddenError"),
             ApiError::Timeout(_) => (StatusCode::REQUEST_TIMEOUT, "TimeoutError"),
@@ -191,6 +231,9 @@ impl IntoResponse for ApiError {
                 services::services::git::GitServiceError::RebaseInProgress => {
                     "A rebase is already in progress. Resolve conflicts or abort the rebase, then retry.".to_string()
                 }
+                services::services::git::GitServiceError::NoRebaseInProgress => {
+                    "There is no rebase in progress to abort.".to_string()
+                }
                 _ => format!("{}: {}", error_type, self),
             },
             ApiError::Multipart(_) => "Failed to upload file. Please ensure the file is valid and try again.".to_string(),
@@ -243,8 +286,11 @@ impl IntoResponse for ApiError {
                 RemoteClientError::Serde(_) => "Unexpected response from remote service.".to_string(),
                 RemoteClientError::Url(_) => "Remote service URL is invalid.".to_string(),
             },
+            ApiError::TaskRegistry(err) => err.to_string(),
+            ApiError::ProjectBundle(err) => err.to_string(),
             ApiError::Unauthorized => "Unauthorized. Please sign in again.".to_string(),
             ApiError::BadRequest(msg) => msg.clone(),
+            ApiError::NotFound(msg) => msg.clone(),
             ApiError::Conflict(msg) => msg.clone(),
             ApiError::Forbidden(msg) => msg.clone(),
             ApiError::Timeout(msg) => msg.clone(),
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use anyhow::{self, Error as AnyhowError};
 use deployment::{Deployment, DeploymentError};
 use server::{DeploymentImpl, routes};
@@ -13,6 +15,8 @@ use utils::{
     sentry::{self as sentry_utils, SentrySource, sentry_layer},
 };
 
+const DEFAULT_SHUTDOWN_GRACE_SECS: u64 = 5;
+
 #[derive(Debug, Error)]
 pub enum VibeKanbanError {
     #[error(transparent)]
@@ -57,21 +61,27 @@ async fn main() -> Result<(), VibeKanbanError> {
         .backfill_before_head_commits()
         .await
         .map_err(DeploymentError::from)?;
+    // Queues restored from the last run only advance when an execution finishes
+    if let Err(e) = deployment.container().resume_queued_follow_ups().await {
+        tracing::warn!("Failed to resume queued follow-ups: {}", e);
+    }
     let pr_monitor_handle = deployment.spawn_pr_monitor_service().await;
     deployment
         .track_if_analytics_allowed("session_start", serde_json::json!({}))
         .await;
     // Pre-warm file search cache for most active projects
     let deployment_for_cache = deployment.clone();
-    tokio::spawn(async move {
-        if let Err(e) = deployment_for_cache
-            .file_search_cache()
-            .warm_most_active(&deployment_for_cache.db().pool, 3)
-            .await
-        {
-            tracing::warn!("Failed to warm file search cache: {}", e);
-        }
-    });
+    deployment
+        .task_registry()
+        .spawn("file_search_cache_warm", true, async move {
+            if let Err(e) = deployment_for_cache
+                .file_search_cache()
+                .warm_most_active(&deployment_for_cache.db().pool, 3)
+                .await
+            {
+                tracing::warn!("Failed to warm file search cache: {}", e);
+            }
+        });
 
     let app_router = routes::router(deployment.clone());
 
@@ -100,9 +110,12 @@ async fn main() -> Result<(), VibeKanbanError> {
 
     tracing::info!("Server running on http://{host}:{actual_port}");
 
-    if !cfg!(debug_assertions) {
+    // DISABLE_BROWSER_OPEN suppresses this regardless of config, e.g. on a remote box
+    let open_browser_on_startup = deployment.config().read().await.open_browser_on_startup
+        && std::env::var("DISABLE_BROWSER_OPEN").is_err();
+    if !cfg!(debug_assertions) && open_browser_on_startup {
         tracing::info!("Opening browser...");
-        tokio::spawn(async move {
+        deployment.task_registry().spawn("open_browser", true, async move {
             if let Err(e) = open_browser(&format!("http://127.0.0.1:{actual_port}")).await {
                 tracing::warn!(
                     "Failed to open browser automatically: {}. Please open http://127.0.0.1:{} manually.",
@@ -158,6 +171,16 @@ pub async fn shutdown_signal() {
     }
 }
 
+/// How long running executions get to finish on shutdown before they are killed.
+/// Set with `VK_SHUTDOWN_GRACE_SECS`; 0 kills them immediately.
+fn shutdown_grace_period() -> Duration {
+    let secs = std::env::var("VK_SHUTDOWN_GRACE_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_SHUTDOWN_GRACE_SECS);
+    Duration::from_secs(secs)
+}
+
 pub async fn perform_cleanup_actions(
     deployment: &DeploymentImpl,
     pr_monitor_handle: services::services::pr_monitor::PrMonitorHandle,
@@ -170,10 +193,10 @@ pub async fn perform_cleanup_actions(
     // Shutdown PR monitor service
     pr_monitor_handle.shutdown().await;
 
-    // Kill all running execution processes
+    // Let running execution processes finish, then kill whatever is left
     deployment
         .container()
-        .kill_all_running_processes()
+        .drain_running_processes(shutdown_grace_period())
         .await
         .expect("Failed to cleanly kill running execution processes");
 
@@ -580,11 +580,13 @@ impl TaskServer {
 
         let payload = CreateTaskAttemptBody {
             task_id,
-            executor_profile_id,
+            executor_profile_id: Some(executor_profile_id),
             base_branch,
             use_existing_branch: false,
             custom_branch: None,
             conversation_history: None,
+            cleanup_script_override: None,
+            env_overrides: None,
         };
 
         let url = self.url("/api/task-attempts");
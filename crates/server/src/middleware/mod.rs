@@ -1,3 +1,5 @@
 pub mod model_loaders;
+pub mod timeout;
 
 pub use model_loaders::*;
+pub use timeout::*;
@@ -6,7 +6,7 @@ use axum::{
 };
 use db::models::{
     execution_process::ExecutionProcess, project::Project, tag::Tag, task::Task,
-    task_attempt::TaskAttempt,
+    task_attempt::TaskAttempt, task_template::TaskTemplate,
 };
 use deployment::Deployment;
 use uuid::Uuid;
@@ -147,3 +147,26 @@ pub async fn load_tag_middleware(
     // Continue with the next middleware/handler
     Ok(next.run(request).await)
 }
+
+// Middleware that loads and injects TaskTemplate based on the template_id path parameter
+pub async fn load_task_template_middleware(
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<Uuid>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let template = match TaskTemplate::find_by_id(&deployment.db().pool, template_id).await {
+        Ok(Some(template)) => template,
+        Ok(None) => {
+            tracing::warn!("Task template {} not found", template_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch task template {}: {}", template_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    request.extensions_mut().insert(template);
+    Ok(next.run(request).await)
+}
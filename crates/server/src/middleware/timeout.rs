@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderMap, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use futures_util::{StreamExt, stream};
+
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 300;
+const DEFAULT_IDLE_TIMEOUT_SECS: u64 = 30;
+
+/// Limits applied to every HTTP request except WebSocket/SSE streams and long-polls
+#[derive(Debug, Clone, Copy)]
+pub struct HttpTimeouts {
+    /// Upper bound for producing a response, including reading the request body
+    pub request: Option<Duration>,
+    /// Longest pause allowed between chunks of a request body
+    pub idle: Option<Duration>,
+}
+
+impl HttpTimeouts {
+    /// Read `VK_HTTP_REQUEST_TIMEOUT_SECS` and `VK_HTTP_IDLE_TIMEOUT_SECS`; `0` disables a limit.
+    pub fn from_env() -> Self {
+        fn secs(var: &str, default: u64) -> Option<Duration> {
+            let secs = std::env::var(var)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .unwrap_or(default);
+            (secs > 0).then(|| Duration::from_secs(secs))
+        }
+        Self {
+            request: secs("VK_HTTP_REQUEST_TIMEOUT_SECS", DEFAULT_REQUEST_TIMEOUT_SECS),
+            idle: secs("VK_HTTP_IDLE_TIMEOUT_SECS", DEFAULT_IDLE_TIMEOUT_SECS),
+        }
+    }
+}
+
+/// Long-poll routes that bound their own wait, e.g. `?timeout=` on task waits
+fn is_long_poll(path: &str) -> bool {
+    path.strip_prefix("/api/tasks/")
+        .and_then(|rest| rest.strip_suffix("/wait"))
+        .is_some_and(|task_id| !task_id.is_empty() && !task_id.contains('/'))
+}
+
+fn is_long_lived(path: &str, headers: &HeaderMap) -> bool {
+    let upgrade = headers.contains_key(header::UPGRADE);
+    let event_stream = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/event-stream"));
+    upgrade || event_stream || is_long_poll(path)
+}
+
+/// Fail the body stream if the client stops sending for longer than `idle`.
+fn with_idle_timeout(body: Body, idle: Duration) -> Body {
+    let data = Some(body.into_data_stream());
+    Body::from_stream(stream::unfold(data, move |data| async move {
+        let mut data = data?;
+        match tokio::time::timeout(idle, data.next()).await {
+            Ok(Some(chunk)) => Some((chunk, Some(data))),
+            Ok(None) => None,
+            Err(_) => Some((
+                Err(axum::Error::new(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "request body idle timeout",
+                ))),
+                None,
+            )),
+        }
+    }))
+}
+
+// Middleware that drops requests which take too long or stall while sending their body
+pub async fn enforce_http_timeouts(
+    State(timeouts): State<HttpTimeouts>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if is_long_lived(request.uri().path(), request.headers()) {
+        return next.run(request).await;
+    }
+
+    let request = match timeouts.idle {
+        Some(idle) => request.map(|body| with_idle_timeout(body, idle)),
+        None => request,
+    };
+    let Some(limit) = timeouts.request else {
+        return next.run(request).await;
+    };
+
+    match tokio::time::timeout(limit, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!("Request timed out after {:?}", limit);
+            (StatusCode::REQUEST_TIMEOUT, [(header::CONNECTION, "close")]).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Router,
+        middleware::from_fn_with_state,
+        routing::{get, post},
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+
+    use super::*;
+
+    async fn spawn_server(timeouts: HttpTimeouts) -> std::net::SocketAddr {
+        let app = Router::new()
+            .route("/echo", post(|body: String| async move { body }))
+            .route(
+                "/api/tasks/{id}/wait",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_millis(800)).await;
+                    "done"
+                }),
+            )
+            .layer(from_fn_with_state(timeouts, enforce_http_timeouts));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        addr
+    }
+
+    async fn send(addr: std::net::SocketAddr, raw: &str) -> String {
+        let mut conn = TcpStream::connect(addr).await.unwrap();
+        conn.write_all(raw.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        tokio::time::timeout(Duration::from_secs(5), conn.read_to_string(&mut response))
+            .await
+            .expect("server should answer or close the connection")
+            .unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_stalled_request_is_dropped_while_normal_request_succeeds() {
+        let addr = spawn_server(HttpTimeouts {
+            request: Some(Duration::from_millis(500)),
+            idle: Some(Duration::from_millis(200)),
+        })
+        .await;
+
+        let ok = send(
+            addr,
+            "POST /echo HTTP/1.1\r\nHost: test\r\nConnection: close\r\nContent-Length: 5\r\n\r\nhello",
+        )
+        .await;
+        assert!(ok.starts_with("HTTP/1.1 200"), "{ok}");
+        assert!(ok.ends_with("hello"));
+
+        // Promise a body, send a fragment, then go quiet
+        let started = std::time::Instant::now();
+        let stalled = send(
+            addr,
+            "POST /echo HTTP/1.1\r\nHost: test\r\nConnection: close\r\nContent-Length: 100\r\n\r\nhel",
+        )
+        .await;
+        assert!(!stalled.starts_with("HTTP/1.1 200"), "{stalled}");
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_task_wait_long_poll_is_not_cut_off_by_request_timeout() {
+        let addr = spawn_server(HttpTimeouts {
+            request: Some(Duration::from_millis(300)),
+            idle: Some(Duration::from_millis(200)),
+        })
+        .await;
+
+        let waited = send(
+            addr,
+            "GET /api/tasks/abc/wait HTTP/1.1\r\nHost: test\r\nConnection: close\r\n\r\n",
+        )
+        .await;
+        assert!(waited.starts_with("HTTP/1.1 200"), "{waited}");
+        assert!(waited.ends_with("done"));
+    }
+
+    #[test]
+    fn test_only_task_wait_routes_are_long_polls() {
+        assert!(is_long_poll("/api/tasks/abc/wait"));
+        assert!(!is_long_poll("/api/tasks//wait"));
+        assert!(!is_long_poll("/api/tasks/abc/attempts/wait"));
+        assert!(!is_long_poll("/api/tasks/abc"));
+    }
+}
@@ -0,0 +1,36 @@
+use axum::{
+    Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use deployment::Deployment;
+use services::services::task_registry::BackgroundTaskInfo;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn list_background_tasks(
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<Vec<BackgroundTaskInfo>>> {
+    ResponseJson(ApiResponse::success(deployment.task_registry().list()))
+}
+
+pub async fn cancel_background_task(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<BackgroundTaskInfo>>, ApiError> {
+    let task = deployment.task_registry().cancel(task_id)?;
+    tracing::info!("Cancelled background task '{}' ({})", task.label, task.id);
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/admin/tasks", get(list_background_tasks))
+        .route(
+            "/admin/tasks/{task_id}/cancel",
+            post(cancel_background_task),
+        )
+}
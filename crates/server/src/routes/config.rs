@@ -18,10 +18,13 @@ use executors::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use services::services::config::{
-    Config, ConfigError, SoundFile,
-    editor::{EditorConfig, EditorType},
-    save_config_to_file,
+use services::services::{
+    config::{
+        Config, ConfigError, ConfigVersionInfo, SoundFile,
+        editor::{EditorConfig, EditorType},
+        inspect_config_file, save_config_to_file,
+    },
+    webhook::invalid_webhook_url,
 };
 use tokio::fs;
 use ts_rs::TS;
@@ -33,6 +36,7 @@ pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/info", get(get_user_system_info))
         .route("/config", put(update_config))
+        .route("/config/version", get(get_config_version))
         .route("/sounds/{sound}", get(get_sound))
         .route("/mcp-config", get(get_mcp_servers).post(update_mcp_servers))
         .route("/profiles", get(get_profiles).put(update_profiles))
@@ -41,6 +45,8 @@ pub fn router() -> Router<DeploymentImpl> {
             get(check_editor_availability),
         )
         .route("/agents/check-availability", get(check_agent_availability))
+        .route("/executors/availability", get(get_executors_availability))
+        .route("/executors/{agent}/schema", get(get_executor_schema))
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -90,7 +96,7 @@ async fn get_user_system_info(
     let login_status = deployment.get_login_status().await;
 
     let user_system_info = UserSystemInfo {
-        config: config.clone(),
+        config: config.redacted(),
         analytics_user_id: deployment.user_id().to_string(),
         login_status,
         profiles: ExecutorConfigs::get_cached(),
@@ -110,9 +116,17 @@ async fn get_user_system_info(
     ResponseJson(ApiResponse::success(user_system_info))
 }
 
+/// Report the config file's schema version and what migrating it would produce,
+/// without writing the result back
+async fn get_config_version() -> ResponseJson<ApiResponse<ConfigVersionInfo>> {
+    let mut info = inspect_config_file(&config_path()).await;
+    info.migrated_config = info.migrated_config.redacted();
+    ResponseJson(ApiResponse::success(info))
+}
+
 async fn update_config(
     State(deployment): State<DeploymentImpl>,
-    Json(new_config): Json<Config>,
+    Json(mut new_config): Json<Config>,
 ) -> ResponseJson<ApiResponse<Config>> {
     let config_path = config_path();
 
@@ -122,9 +136,21 @@ async fn update_config(
             "Invalid git branch prefix. Must be a valid git branch name component without slashes.",
         ));
     }
+    if let Some(url) = invalid_webhook_url(&new_config.webhooks) {
+        return ResponseJson(ApiResponse::error(&format!(
+            "Invalid webhook URL '{url}'. Must be an absolute http or https URL."
+        )));
+    }
+    if new_config.execution_limits.max_concurrent == Some(0) {
+        return ResponseJson(ApiResponse::error(
+            "The concurrent execution limit must be at least 1.",
+        ));
+    }
 
     // Get old config state before updating
     let old_config = deployment.config().read().await.clone();
+    // Responses mask secret values, so clients send the mask back for unchanged ones
+    new_config.restore_redacted_secrets(&old_config);
 
     match save_config_to_file(&new_config, &config_path).await {
         Ok(_) => {
@@ -135,7 +161,7 @@ async fn update_config(
             // Track config events when fields transition from false → true and run side effects
             handle_config_events(&deployment, &old_config, &new_config).await;
 
-            ResponseJson(ApiResponse::success(new_config))
+            ResponseJson(ApiResponse::success(new_config.redacted()))
         }
         Err(e) => ResponseJson(ApiResponse::error(&format!("Failed to save config: {}", e))),
     }
@@ -477,3 +503,85 @@ async fn check_agent_availability(
 
     ResponseJson(ApiResponse::success(info))
 }
+
+/// Availability of every configured coding agent.
+async fn get_executors_availability()
+-> ResponseJson<ApiResponse<HashMap<BaseCodingAgent, AvailabilityInfo>>> {
+    let availability = executors_availability(&ExecutorConfigs::get_cached()).await;
+    ResponseJson(ApiResponse::success(availability))
+}
+
+/// Checks run concurrently on the blocking pool so one slow filesystem probe
+/// doesn't hold up the others. Executors without a default profile are reported
+/// as not found.
+async fn executors_availability(
+    profiles: &ExecutorConfigs,
+) -> HashMap<BaseCodingAgent, AvailabilityInfo> {
+    let checks = profiles.executors.keys().map(|executor| {
+        let executor = *executor;
+        let agent = profiles.get_coding_agent(&ExecutorProfileId::new(executor));
+        async move {
+            let info = match agent {
+                Some(agent) => tokio::task::spawn_blocking(move || agent.get_availability_info())
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Availability check for {} failed: {}", executor, e);
+                        AvailabilityInfo::NotFound
+                    }),
+                None => AvailabilityInfo::NotFound,
+            };
+            (executor, info)
+        }
+    });
+
+    futures_util::future::join_all(checks)
+        .await
+        .into_iter()
+        .collect()
+}
+
+/// JSON schema for an executor's profile configuration, used to render its
+/// settings form without hardcoding fields in the frontend.
+async fn get_executor_schema(
+    Path(agent): Path<BaseCodingAgent>,
+) -> ResponseJson<ApiResponse<Value>> {
+    ResponseJson(ApiResponse::success(agent.config_schema()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_executors_availability_reports_every_executor() {
+        let ResponseJson(response) = get_executors_availability().await;
+        let availability = response.into_data().unwrap();
+        assert_eq!(
+            availability.keys().collect::<HashSet<_>>(),
+            ExecutorConfigs::get_cached()
+                .executors
+                .keys()
+                .collect::<HashSet<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_executors_without_a_default_profile_are_not_found() {
+        let mut profiles = ExecutorConfigs::get_cached();
+        let gemini = profiles
+            .executors
+            .get_mut(&BaseCodingAgent::Gemini)
+            .unwrap();
+        let default = gemini.configurations.remove("DEFAULT").unwrap();
+        gemini.configurations.insert("FAST".to_string(), default);
+
+        let availability = executors_availability(&profiles).await;
+        assert!(matches!(
+            availability[&BaseCodingAgent::Gemini],
+            AvailabilityInfo::NotFound
+        ));
+        assert_eq!(availability.len(), profiles.executors.len());
+    }
+}
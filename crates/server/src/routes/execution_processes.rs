@@ -1,25 +1,40 @@
 use anyhow;
 use axum::{
-    Extension, Router,
+    Extension, Json, Router,
     extract::{
         Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
+    response::{
+        IntoResponse, Json as ResponseJson, Sse,
+        sse::{Event, KeepAlive, KeepAliveStream},
+    },
     routing::{get, post},
 };
-use db::models::execution_process::{
-    ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus,
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus},
+    project::{Project, ProjectError},
 };
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
-use serde::Deserialize;
-use services::services::container::ContainerService;
-use utils::{log_msg::LogMsg, response::ApiResponse};
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::{ContainerService, ExecutionSlots},
+    git::{Commit, DiffTarget},
+};
+use ts_rs::TS;
+use utils::{
+    diff::Diff,
+    log_msg::{LogMsg, sse_until_finished},
+    response::ApiResponse,
+};
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_execution_process_middleware};
+use crate::{
+    DeploymentImpl, error::ApiError, middleware::load_execution_process_middleware,
+    routes::task_attempts::util::ensure_worktree_path,
+};
 
 #[derive(Debug, Deserialize)]
 pub struct ExecutionProcessQuery {
@@ -36,65 +51,81 @@ pub async fn get_execution_process_by_id(
     Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct RawLogsStreamQuery {
+    /// Resume after this sequence number, skipping entries the client already has
+    #[serde(default)]
+    pub after_seq: Option<u64>,
+}
+
 pub async fn stream_raw_logs_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
     Path(exec_id): Path<Uuid>,
+    Query(query): Query<RawLogsStreamQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
     // Check if the stream exists before upgrading the WebSocket
     let _stream = deployment
         .container()
-        .stream_raw_logs(&exec_id)
+        .stream_raw_logs_sequenced(&exec_id)
         .await
         .ok_or_else(|| {
             ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound)
         })?;
 
     Ok(ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_raw_logs_ws(socket, deployment, exec_id).await {
+        if let Err(e) = handle_raw_logs_ws(socket, deployment, exec_id, query.after_seq).await {
             tracing::warn!("raw logs WS closed: {}", e);
         }
     }))
 }
 
+/// Convert a sequenced raw Stdout/Stderr stream into JSON patches on-the-fly.
+/// Every raw entry takes a patch index so resumed streams keep the same indices,
+/// but entries with a sequence number at or below `after_seq` are not sent.
+fn raw_logs_as_patches(
+    raw_stream: futures_util::stream::BoxStream<'static, Result<(u64, LogMsg), std::io::Error>>,
+    after_seq: Option<u64>,
+) -> impl futures_util::Stream<Item = Result<LogMsg, std::io::Error>> + Send + 'static {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use executors::logs::utils::patch::ConversationPatch;
+
+    let counter = AtomicUsize::new(0);
+    raw_stream
+        .map_ok(move |(seq, m)| {
+            let already_sent = after_seq.is_some_and(|after| seq <= after);
+            let patch = match m {
+                LogMsg::Stdout(content) => {
+                    let index = counter.fetch_add(1, Ordering::SeqCst);
+                    LogMsg::JsonPatch(ConversationPatch::add_stdout(index, content))
+                }
+                LogMsg::Stderr(content) => {
+                    let index = counter.fetch_add(1, Ordering::SeqCst);
+                    LogMsg::JsonPatch(ConversationPatch::add_stderr(index, content))
+                }
+                LogMsg::Finished => return Some(LogMsg::Finished),
+                _ => unreachable!("Raw stream should only have Stdout/Stderr/Finished"),
+            };
+            (!already_sent).then_some(patch)
+        })
+        .try_filter_map(|m| futures_util::future::ready(Ok(m)))
+}
+
 async fn handle_raw_logs_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
     exec_id: Uuid,
+    after_seq: Option<u64>,
 ) -> anyhow::Result<()> {
-    use std::sync::{
-        Arc,
-        atomic::{AtomicUsize, Ordering},
-    };
-
-    use executors::logs::utils::patch::ConversationPatch;
-    use utils::log_msg::LogMsg;
-
-    // Get the raw stream and convert to JSON patches on-the-fly
     let raw_stream = deployment
         .container()
-        .stream_raw_logs(&exec_id)
+        .stream_raw_logs_sequenced(&exec_id)
         .await
         .ok_or_else(|| anyhow::anyhow!("Execution process not found"))?;
 
-    let counter = Arc::new(AtomicUsize::new(0));
-    let mut stream = raw_stream.map_ok({
-        let counter = counter.clone();
-        move |m| match m {
-            LogMsg::Stdout(content) => {
-                let index = counter.fetch_add(1, Ordering::SeqCst);
-                let patch = ConversationPatch::add_stdout(index, content);
-                LogMsg::JsonPatch(patch).to_ws_message_unchecked()
-            }
-            LogMsg::Stderr(content) => {
-                let index = counter.fetch_add(1, Ordering::SeqCst);
-                let patch = ConversationPatch::add_stderr(index, content);
-                LogMsg::JsonPatch(patch).to_ws_message_unchecked()
-            }
-            LogMsg::Finished => LogMsg::Finished.to_ws_message_unchecked(),
-            _ => unreachable!("Raw stream should only have Stdout/Stderr/Finished"),
-        }
-    });
+    let mut stream =
+        raw_logs_as_patches(raw_stream, after_seq).map_ok(|m| m.to_ws_message_unchecked());
 
     // Split socket into sender and receiver
     let (mut sender, mut receiver) = socket.split();
@@ -119,6 +150,85 @@ async fn handle_raw_logs_ws(
     Ok(())
 }
 
+const DEFAULT_RAW_LOG_PAGE_SIZE: usize = 500;
+const MAX_RAW_LOG_PAGE_SIZE: usize = 5000;
+
+#[derive(Debug, Deserialize)]
+pub struct RawLogPageQuery {
+    /// Return entries with a sequence number greater than this cursor
+    #[serde(default)]
+    pub after_seq: Option<u64>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum RawLogStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct RawLogEntry {
+    pub seq: u64,
+    pub stream: RawLogStream,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct RawLogPage {
+    pub entries: Vec<RawLogEntry>,
+    /// Pass as `after_seq` to fetch the next page; unchanged when nothing new was returned
+    pub next_cursor: Option<u64>,
+    pub has_more: bool,
+}
+
+/// Page through an execution's stored raw logs, so clients can load large
+/// outputs incrementally and resume the raw logs stream from `next_cursor`.
+pub async fn get_raw_log_page(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<RawLogPageQuery>,
+) -> Result<ResponseJson<ApiResponse<RawLogPage>>, ApiError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_RAW_LOG_PAGE_SIZE)
+        .clamp(1, MAX_RAW_LOG_PAGE_SIZE);
+
+    let history = deployment
+        .container()
+        .raw_log_history(&execution_process.id, query.after_seq)
+        .await
+        .unwrap_or_default();
+
+    let has_more = history.len() > limit;
+    let entries: Vec<RawLogEntry> = history
+        .into_iter()
+        .take(limit)
+        .filter_map(|(seq, msg)| match msg {
+            LogMsg::Stdout(content) => Some(RawLogEntry {
+                seq,
+                stream: RawLogStream::Stdout,
+                content,
+            }),
+            LogMsg::Stderr(content) => Some(RawLogEntry {
+                seq,
+                stream: RawLogStream::Stderr,
+                content,
+            }),
+            _ => None,
+        })
+        .collect();
+    let next_cursor = entries.last().map(|e| e.seq).or(query.after_seq);
+
+    Ok(ResponseJson(ApiResponse::success(RawLogPage {
+        entries,
+        next_cursor,
+        has_more,
+    })))
+}
+
 pub async fn stream_normalized_logs_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -165,6 +275,47 @@ async fn handle_normalized_logs_ws(
     Ok(())
 }
 
+pub type LogSse =
+    Sse<KeepAliveStream<futures_util::stream::BoxStream<'static, Result<Event, std::io::Error>>>>;
+
+/// Stream an execution's raw (as patches) or normalized logs over SSE.
+/// The stream closes after `Finished`.
+pub async fn log_sse_for_execution(
+    deployment: &DeploymentImpl,
+    exec_id: Uuid,
+    raw: bool,
+) -> Result<LogSse, ApiError> {
+    let stream = if raw {
+        deployment
+            .container()
+            .stream_raw_logs_sequenced(&exec_id)
+            .await
+            .map(|s| raw_logs_as_patches(s, None).boxed())
+    } else {
+        deployment
+            .container()
+            .stream_normalized_logs(&exec_id)
+            .await
+    }
+    .ok_or_else(|| ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound))?;
+
+    Ok(Sse::new(sse_until_finished(stream).boxed()).keep_alive(KeepAlive::default()))
+}
+
+pub async fn stream_raw_logs_sse(
+    State(deployment): State<DeploymentImpl>,
+    Path(exec_id): Path<Uuid>,
+) -> Result<LogSse, ApiError> {
+    log_sse_for_execution(&deployment, exec_id, true).await
+}
+
+pub async fn stream_normalized_logs_sse(
+    State(deployment): State<DeploymentImpl>,
+    Path(exec_id): Path<Uuid>,
+) -> Result<LogSse, ApiError> {
+    log_sse_for_execution(&deployment, exec_id, false).await
+}
+
 pub async fn stop_execution_process(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(deployment): State<DeploymentImpl>,
@@ -201,6 +352,133 @@ pub async fn compact_execution_process(
     Ok(ResponseJson(ApiResponse::success(sent)))
 }
 
+/// Commits an execution process's changes are read between
+#[derive(Debug, PartialEq)]
+enum DiffSource<'a> {
+    /// Both ends were recorded
+    Range { from: &'a str, to: &'a str },
+    /// Still running, so compared against the live worktree
+    Worktree { from: &'a str },
+}
+
+/// Only a running process may be diffed against the worktree; once it has stopped,
+/// the worktree can hold later processes' changes too.
+fn diff_source<'a>(
+    before: Option<&'a str>,
+    after: Option<&'a str>,
+    status: &ExecutionProcessStatus,
+) -> Result<DiffSource<'a>, ApiError> {
+    let Some(from) = before else {
+        return Err(ApiError::BadRequest(
+            "Execution process has no recorded starting commit".to_string(),
+        ));
+    };
+    match (after, status) {
+        (Some(to), _) => Ok(DiffSource::Range { from, to }),
+        (None, ExecutionProcessStatus::Running) => Ok(DiffSource::Worktree { from }),
+        (None, _) => Err(ApiError::BadRequest(
+            "Execution process stopped without recording its final commit".to_string(),
+        )),
+    }
+}
+
+/// What this process changed: `before_head_commit..after_head_commit`, or
+/// `before_head_commit` against the live worktree while it is still running.
+pub async fn get_execution_process_diff(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Diff>>>, ApiError> {
+    let source = diff_source(
+        execution_process.before_head_commit.as_deref(),
+        execution_process.after_head_commit.as_deref(),
+        &execution_process.status,
+    )?;
+
+    let pool = &deployment.db().pool;
+    let ctx = ExecutionProcess::load_context(pool, execution_process.id).await?;
+
+    let diffs = match source {
+        DiffSource::Range { from, to } => {
+            let project = Project::find_by_id(pool, ctx.task.project_id)
+                .await?
+                .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
+            deployment.git().get_diffs(
+                DiffTarget::Range {
+                    repo_path: &project.git_repo_path,
+                    from_sha: from,
+                    to_sha: to,
+                },
+                None,
+            )?
+        }
+        DiffSource::Worktree { from } => {
+            let worktree_path = ensure_worktree_path(&deployment, &ctx.task_attempt).await?;
+            let before_oid = git2::Oid::from_str(from)
+                .map_err(|_| ApiError::BadRequest(format!("Invalid starting commit: {from}")))?;
+            deployment.git().get_diffs(
+                DiffTarget::Worktree {
+                    worktree_path: &worktree_path,
+                    base_commit: &Commit::new(before_oid),
+                },
+                None,
+            )?
+        }
+    };
+
+    Ok(ResponseJson(ApiResponse::success(diffs)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct SendInputRequest {
+    pub input: String,
+}
+
+/// Forward text to a running process, e.g. to answer a prompt the agent is waiting on.
+pub async fn send_execution_process_input(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SendInputRequest>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let sent = deployment
+        .container()
+        .send_input_to_process(execution_process.id, payload.input)
+        .await?;
+    if !sent {
+        return Err(ApiError::Conflict(
+            "Execution process is not accepting input".to_string(),
+        ));
+    }
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExecutionSlotsQuery {
+    /// Also report the slots used within this project
+    pub project_id: Option<Uuid>,
+}
+
+/// Running coding agent executions against the concurrency caps
+pub async fn get_execution_slots(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ExecutionSlotsQuery>,
+) -> Result<ResponseJson<ApiResponse<ExecutionSlots>>, ApiError> {
+    let project = match query.project_id {
+        Some(project_id) => Some(
+            Project::find_by_id(&deployment.db().pool, project_id)
+                .await?
+                .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?,
+        ),
+        None => None,
+    };
+    let slots = deployment
+        .container()
+        .execution_slots(project.as_ref())
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(slots)))
+}
+
 pub async fn stream_execution_processes_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -261,8 +539,13 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/", get(get_execution_process_by_id))
         .route("/stop", post(stop_execution_process))
         .route("/compact", post(compact_execution_process))
+        .route("/diff", get(get_execution_process_diff))
+        .route("/input", post(send_execution_process_input))
+        .route("/logs", get(get_raw_log_page))
         .route("/raw-logs/ws", get(stream_raw_logs_ws))
+        .route("/raw-logs/sse", get(stream_raw_logs_sse))
         .route("/normalized-logs/ws", get(stream_normalized_logs_ws))
+        .route("/normalized-logs/sse", get(stream_normalized_logs_sse))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_execution_process_middleware,
@@ -270,7 +553,43 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let task_attempts_router = Router::new()
         .route("/stream/ws", get(stream_execution_processes_ws))
+        .route("/slots", get(get_execution_slots))
         .nest("/{id}", task_attempt_id_router);
 
     Router::new().nest("/execution-processes", task_attempts_router)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_running_processes_are_diffed_against_the_worktree() {
+        assert_eq!(
+            diff_source(Some("a1"), Some("b2"), &ExecutionProcessStatus::Failed).unwrap(),
+            DiffSource::Range {
+                from: "a1",
+                to: "b2"
+            }
+        );
+        assert_eq!(
+            diff_source(Some("a1"), None, &ExecutionProcessStatus::Running).unwrap(),
+            DiffSource::Worktree { from: "a1" }
+        );
+        for status in [
+            ExecutionProcessStatus::Completed,
+            ExecutionProcessStatus::Failed,
+            ExecutionProcessStatus::Killed,
+            ExecutionProcessStatus::TimedOut,
+        ] {
+            assert!(matches!(
+                diff_source(Some("a1"), None, &status),
+                Err(ApiError::BadRequest(_))
+            ));
+        }
+        assert!(matches!(
+            diff_source(None, Some("b2"), &ExecutionProcessStatus::Completed),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,169 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use axum::{
+    Json, Router,
+    extract::State,
+    response::{
+        Json as ResponseJson, Sse,
+        sse::{Event, KeepAlive},
+    },
+    routing::{get, post},
+};
+use db::models::project::Project;
+use deployment::Deployment;
+use futures_util::{Stream, StreamExt, stream};
+use serde::{Deserialize, Serialize};
+use services::services::file_search_cache::IndexStatus;
+use tokio::sync::broadcast::error::RecvError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Which projects to index: `"all"` or `{ "projects": [...] }`
+#[derive(Debug, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum WarmTarget {
+    All,
+    Projects(Vec<Uuid>),
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct WarmFileSearchRequest {
+    pub target: WarmTarget,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ProjectIndexStatus {
+    pub project_id: Uuid,
+    pub status: IndexStatus,
+}
+
+/// Sent as a `progress` event each time a warmed project's status changes
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct WarmProgress {
+    pub project_id: Uuid,
+    pub status: IndexStatus,
+    /// Projects whose indexing has finished, successfully or not
+    pub finished: usize,
+    pub total: usize,
+}
+
+/// Index the given projects for file search, streaming progress over SSE until
+/// every one of them is done or has failed.
+pub async fn warm_file_search(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<WarmFileSearchRequest>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let projects = match payload.target {
+        WarmTarget::All => Project::find_all(pool).await?,
+        WarmTarget::Projects(ids) => {
+            let mut projects = Vec::with_capacity(ids.len());
+            for id in ids {
+                let project = Project::find_by_id(pool, id)
+                    .await?
+                    .ok_or_else(|| ApiError::BadRequest(format!("Project {id} not found")))?;
+                projects.push(project);
+            }
+            projects
+        }
+    };
+
+    // Projects can share a repository, which is only indexed once
+    let mut waiting: HashMap<PathBuf, Vec<Uuid>> = HashMap::new();
+    for project in &projects {
+        waiting
+            .entry(project.git_repo_path.clone())
+            .or_default()
+            .push(project.id);
+    }
+    let total = projects.len();
+    let repo_paths: Vec<PathBuf> = waiting.keys().cloned().collect();
+
+    // Subscribe before queueing so no status change is missed
+    let cache = deployment.file_search_cache();
+    let updates = cache.subscribe_index_status();
+    cache
+        .warm_and_watch(&repo_paths)
+        .await
+        .map_err(|e| ApiError::Io(std::io::Error::other(e)))?;
+
+    let progress = stream::unfold(
+        (updates, waiting, 0usize),
+        move |(mut updates, mut waiting, mut finished)| async move {
+            loop {
+                if waiting.is_empty() {
+                    return None;
+                }
+                let update = match updates.recv().await {
+                    Ok(update) => update,
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("File search warm progress skipped {skipped} updates");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return None,
+                };
+                let project_ids = if update.status.is_finished() {
+                    match waiting.remove(&update.repo_path) {
+                        Some(ids) => {
+                            finished += ids.len();
+                            ids
+                        }
+                        None => continue,
+                    }
+                } else {
+                    match waiting.get(&update.repo_path) {
+                        Some(ids) => ids.clone(),
+                        None => continue,
+                    }
+                };
+
+                let events: Vec<_> = project_ids
+                    .into_iter()
+                    .map(|project_id| {
+                        Event::default().event("progress").json_data(WarmProgress {
+                            project_id,
+                            status: update.status.clone(),
+                            finished,
+                            total,
+                        })
+                    })
+                    .collect();
+                return Some((stream::iter(events), (updates, waiting, finished)));
+            }
+        },
+    )
+    .flatten();
+
+    Ok(Sse::new(progress).keep_alive(KeepAlive::default()))
+}
+
+/// Indexing status of every project whose repository has been queued for indexing
+pub async fn get_file_search_status(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectIndexStatus>>>, ApiError> {
+    let statuses = deployment.file_search_cache().index_statuses();
+    let projects = Project::find_all(&deployment.db().pool).await?;
+
+    let response = projects
+        .into_iter()
+        .filter_map(|project| {
+            statuses
+                .get(&project.git_repo_path)
+                .map(|status| ProjectIndexStatus {
+                    project_id: project.id,
+                    status: status.clone(),
+                })
+        })
+        .collect();
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/file-search/warm", post(warm_file_search))
+        .route("/file-search/status", get(get_file_search_status))
+}
@@ -0,0 +1,200 @@
+use std::fmt::Write;
+
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use db::models::{execution_process::ExecutionProcess, task_attempt::TaskAttempt};
+use deployment::Deployment;
+use services::services::{container::ContainerService, pr_monitor::pr_poll_stats};
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+#[derive(Debug, Clone, Copy)]
+enum MetricKind {
+    /// Only ever increases while the server runs
+    Counter,
+    /// Can go up and down
+    Gauge,
+}
+
+impl MetricKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+        }
+    }
+}
+
+/// One metric family in the Prometheus text exposition format
+struct Metric {
+    name: &'static str,
+    help: &'static str,
+    kind: MetricKind,
+    samples: Vec<(Option<(&'static str, String)>, f64)>,
+}
+
+impl Metric {
+    fn counter(name: &'static str, help: &'static str, value: f64) -> Self {
+        Self {
+            name,
+            help,
+            kind: MetricKind::Counter,
+            samples: vec![(None, value)],
+        }
+    }
+
+    fn gauge(name: &'static str, help: &'static str, value: f64) -> Self {
+        Self {
+            name,
+            help,
+            kind: MetricKind::Gauge,
+            samples: vec![(None, value)],
+        }
+    }
+
+    /// A gauge with one sample per value of `label`
+    fn labelled_gauge(
+        name: &'static str,
+        help: &'static str,
+        label: &'static str,
+        values: impl IntoIterator<Item = (String, f64)>,
+    ) -> Self {
+        Self {
+            name,
+            help,
+            kind: MetricKind::Gauge,
+            samples: values
+                .into_iter()
+                .map(|(value, sample)| (Some((label, value)), sample))
+                .collect(),
+        }
+    }
+
+    fn render(&self, out: &mut String) {
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} {}", self.name, self.kind.as_str());
+        for (label, value) in &self.samples {
+            match label {
+                Some((key, label_value)) => {
+                    let _ = writeln!(
+                        out,
+                        "{}{{{}=\"{}\"}} {}",
+                        self.name,
+                        key,
+                        escape_label_value(label_value),
+                        value
+                    );
+                }
+                None => {
+                    let _ = writeln!(out, "{} {}", self.name, value);
+                }
+            }
+        }
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn render(metrics: &[Metric]) -> String {
+    let mut out = String::new();
+    for metric in metrics {
+        metric.render(&mut out);
+    }
+    out
+}
+
+pub async fn metrics(State(deployment): State<DeploymentImpl>) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let executions_by_status = ExecutionProcess::count_by_status(pool).await?;
+    let worktrees = TaskAttempt::find_by_worktree_deleted(pool).await?.len();
+    let running_children = deployment.container().running_child_count().await;
+    let msg_stores = deployment.container().msg_stores().read().await.len();
+    let queued_messages = deployment.queued_message_service().total_queued();
+    let pr_polls = pr_poll_stats();
+
+    let metrics = [
+        Metric::gauge(
+            "vibe_kanban_running_executions",
+            "Execution processes with a live child process",
+            running_children as f64,
+        ),
+        Metric::labelled_gauge(
+            "vibe_kanban_executions",
+            "Execution processes recorded in the database, by status",
+            "status",
+            executions_by_status
+                .into_iter()
+                .map(|(status, count)| (status, count as f64)),
+        ),
+        Metric::gauge(
+            "vibe_kanban_worktrees",
+            "Task attempt worktrees that have not been cleaned up",
+            worktrees as f64,
+        ),
+        Metric::gauge(
+            "vibe_kanban_msg_stores",
+            "Execution log stores held in memory",
+            msg_stores as f64,
+        ),
+        Metric::gauge(
+            "vibe_kanban_queued_messages",
+            "Follow-up messages waiting for their attempt's execution to finish",
+            queued_messages as f64,
+        ),
+        Metric::counter(
+            "vibe_kanban_pr_monitor_polls_total",
+            "PR monitor polls run since startup",
+            pr_polls.polls as f64,
+        ),
+        Metric::counter(
+            "vibe_kanban_pr_monitor_poll_seconds_total",
+            "Time spent in PR monitor polls since startup",
+            pr_polls.total.as_secs_f64(),
+        ),
+        Metric::gauge(
+            "vibe_kanban_pr_monitor_last_poll_seconds",
+            "Duration of the most recent PR monitor poll",
+            pr_polls.last.unwrap_or_default().as_secs_f64(),
+        ),
+    ];
+
+    Ok(([(header::CONTENT_TYPE, CONTENT_TYPE)], render(&metrics)).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_prometheus_text_format() {
+        let out = render(&[
+            Metric::counter("polls_total", "Polls run", 3.0),
+            Metric::labelled_gauge(
+                "executions",
+                "Executions by status",
+                "status",
+                [("run\"ning".to_string(), 2.0), ("failed".to_string(), 0.5)],
+            ),
+        ]);
+        assert_eq!(
+            out,
+            "# HELP polls_total Polls run\n\
+             # TYPE polls_total counter\n\
+             polls_total 3\n\
+             # HELP executions Executions by status\n\
+             # TYPE executions gauge\n\
+             executions{status=\"run\\\"ning\"} 2\n\
+             executions{status=\"failed\"} 0.5\n"
+        );
+    }
+}
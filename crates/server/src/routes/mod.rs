@@ -1,13 +1,19 @@
 use axum::{
     Router,
+    middleware::from_fn_with_state,
     routing::{IntoMakeService, get},
 };
 
-use crate::DeploymentImpl;
+use crate::{
+    DeploymentImpl,
+    middleware::{HttpTimeouts, enforce_http_timeouts},
+};
 
+pub mod admin;
 pub mod approvals;
 pub mod config;
 pub mod containers;
+pub mod file_search;
 pub mod filesystem;
 // pub mod github;
 pub mod events;
@@ -15,6 +21,7 @@ pub mod execution_processes;
 pub mod frontend;
 pub mod health;
 pub mod images;
+pub mod metrics;
 pub mod oauth;
 pub mod orchestrator;
 pub mod organizations;
@@ -23,10 +30,14 @@ pub mod scratch;
 pub mod shared_tasks;
 pub mod tags;
 pub mod task_attempts;
+pub mod task_templates;
 pub mod tasks;
 pub mod tools;
 
 pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
+    // Prometheus scrapes `/metrics` at the root, outside the API
+    let metrics_route = get(metrics::metrics).with_state(deployment.clone());
+
     // Create routers with different middleware layers
     let base_routes = Router::new()
         .route("/health", get(health::health_check))
@@ -38,20 +49,28 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(task_attempts::router(&deployment))
         .merge(execution_processes::router(&deployment))
         .merge(tags::router(&deployment))
+        .merge(task_templates::router(&deployment))
         .merge(oauth::router())
         .merge(organizations::router())
         .merge(filesystem::router())
+        .merge(file_search::router())
         .merge(events::router(&deployment))
         .merge(approvals::router())
         .merge(scratch::router(&deployment))
         .merge(orchestrator::router(&deployment))
         .merge(tools::router())
+        .merge(admin::router())
         .nest("/images", images::routes())
         .with_state(deployment);
 
     Router::new()
         .route("/", get(frontend::serve_frontend_root))
+        .route("/metrics", metrics_route)
         .route("/{*path}", get(frontend::serve_frontend))
         .nest("/api", base_routes)
+        .layer(from_fn_with_state(
+            HttpTimeouts::from_env(),
+            enforce_http_timeouts,
+        ))
         .into_make_service()
 }
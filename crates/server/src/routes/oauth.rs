@@ -8,7 +8,9 @@ use axum::{
 use deployment::Deployment;
 use rand::{Rng, distributions::Alphanumeric};
 use serde::{Deserialize, Serialize};
-use services::services::{config::save_config_to_file, oauth_credentials::Credentials};
+use services::services::{
+    config::save_config_to_file, oauth_credentials::Credentials, share::RemoteSyncState,
+};
 use sha2::{Digest, Sha256};
 use utils::{
     api::oauth::{HandoffInitRequest, HandoffRedeemRequest, StatusResponse},
@@ -190,7 +192,14 @@ async fn handoff_complete(
 
     // Start remote sync if not already running
     {
-        let handle_guard = deployment.share_sync_handle().lock().await;
+        let mut handle_guard = deployment.share_sync_handle().lock().await;
+        // A sync that gave up retrying is replaced by a fresh one
+        if handle_guard
+            .as_ref()
+            .is_some_and(|handle| matches!(handle.state(), RemoteSyncState::Failed { .. }))
+        {
+            handle_guard.take();
+        }
         let should_start = handle_guard.is_none();
         drop(handle_guard);
 
@@ -17,10 +17,7 @@ use db::models::{
 };
 use deployment::Deployment;
 use executors::{
-    actions::{
-        ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
-        coding_agent_initial::CodingAgentInitialRequest,
-    },
+    actions::{ExecutorAction, ExecutorActionType},
     executors::BaseCodingAgent,
     profile::ExecutorProfileId,
 };
@@ -231,22 +228,13 @@ pub async fn orchestrator_send(
         }
     };
 
-    let action_type = if let Some(session_id) = latest_session_id {
-        // Resume existing session
-        ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
-            prompt,
-            session_id,
-            executor_profile_id: executor_profile_id.clone(),
-            is_orchestrator: true,
-        })
-    } else {
-        // Start new session
-        ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
-            prompt,
-            executor_profile_id: executor_profile_id.clone(),
-            is_orchestrator: true,
-        })
-    };
+    // Resume the existing session, or start a new one
+    let action_type = ExecutorActionType::coding_agent_request(
+        prompt,
+        latest_session_id,
+        executor_profile_id,
+        true,
+    );
 
     // No cleanup action for orchestrator - it operates directly on main
     let action = ExecutorAction::new(action_type, None);
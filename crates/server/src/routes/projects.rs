@@ -6,24 +6,34 @@ use axum::{
     http::StatusCode,
     middleware::from_fn_with_state,
     response::Json as ResponseJson,
-    routing::{get, post},
+    routing::{get, post, put},
 };
 use db::models::{
+    activity::Activity,
     project::{
         CreateProject, Project, ProjectError, ProjectWithTaskCounts, SearchMatchType, SearchResult,
         UpdateProject,
     },
-    task::Task,
+    task::{Task, TaskLabel},
+    task_attempt::TaskAttempt,
 };
 use deployment::Deployment;
+use executors::mcp_config::validate_mcp_server_definitions;
 use ignore::WalkBuilder;
 use serde::{Deserialize, Serialize};
 use services::services::{
+    branch_name::validate_branch_template,
+    commit_message::validate_commit_message_template,
+    container::ContainerService,
+    dev_server_url::parse_url_pattern,
     file_ranker::FileRanker,
-    file_search_cache::{CacheError, SearchMode, SearchQuery},
+    file_search_cache::{CacheError, SearchFilter, SearchMode, SearchQuery},
     git::GitBranch,
+    output_abort::parse_abort_pattern,
+    project_bundle::ProjectBundle,
     remote_client::CreateRemoteProjectPayload,
     share::link_shared_tasks_to_project,
+    worktree_manager::{WorktreeManager, validate_sparse_patterns},
 };
 use ts_rs::TS;
 use utils::{
@@ -33,7 +43,7 @@ use utils::{
 };
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware, routes::tasks};
 
 #[derive(Deserialize, TS)]
 pub struct LinkToExistingRequest {
@@ -60,10 +70,18 @@ pub struct CreateRemoteProjectRequest {
     pub name: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ProjectsQuery {
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
 pub async fn get_projects(
     State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ProjectsQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<ProjectWithTaskCounts>>>, ApiError> {
-    let projects = Project::find_all_with_task_counts(&deployment.db().pool).await?;
+    let projects =
+        Project::find_all_with_task_counts(&deployment.db().pool, query.include_archived).await?;
     Ok(ResponseJson(ApiResponse::success(projects)))
 }
 
@@ -239,9 +257,59 @@ pub async fn create_project(
         cleanup_script,
         copy_files,
         use_existing_repo,
+        env_template,
+        merge_strategy,
+        auto_push,
+        abort_on_output_pattern,
+        default_executor_profile_id,
+        commit_message_template,
+        branch_template,
+        mcp_servers,
+        worktree_sparse_patterns,
+        max_concurrent_executions,
+        dev_server_url_pattern,
+        script_language,
     } = payload;
+    if max_concurrent_executions.is_some_and(|max| max < 1) {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Max concurrent executions must be at least 1",
+        )));
+    }
     tracing::debug!("Creating project '{}'", name);
 
+    if let Some(pattern) = abort_on_output_pattern.as_deref()
+        && let Err(e) = parse_abort_pattern(pattern)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&format!(
+            "Invalid abort pattern: {e}"
+        ))));
+    }
+    if let Some(pattern) = dev_server_url_pattern.as_deref()
+        && let Err(e) = parse_url_pattern(pattern)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&format!(
+            "Invalid dev server URL pattern: {e}"
+        ))));
+    }
+    if let Some(template) = commit_message_template.as_deref()
+        && let Err(e) = validate_commit_message_template(template)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&e.to_string())));
+    }
+    if let Some(template) = branch_template.as_deref()
+        && let Err(e) = validate_branch_template(template)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&e.to_string())));
+    }
+    if let Err(e) = validate_mcp_server_definitions(&mcp_servers) {
+        return Ok(ResponseJson(ApiResponse::error(&e.to_string())));
+    }
+    if let Some(patterns) = worktree_sparse_patterns.as_deref()
+        && let Err(e) = validate_sparse_patterns(patterns)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&e.to_string())));
+    }
+
     // Validate and setup git repository
     let path = std::path::absolute(expand_tilde(&git_repo_path))?;
     // Check if git repo path is already used by another project
@@ -325,6 +393,18 @@ pub async fn create_project(
             dev_script,
             cleanup_script,
             copy_files,
+            env_template,
+            merge_strategy,
+            auto_push,
+            abort_on_output_pattern,
+            default_executor_profile_id,
+            commit_message_template,
+            branch_template,
+            mcp_servers,
+            worktree_sparse_patterns,
+            max_concurrent_executions,
+            dev_server_url_pattern,
+            script_language,
         },
         id,
     )
@@ -366,7 +446,58 @@ pub async fn update_project(
         dev_script,
         cleanup_script,
         copy_files,
+        env_template,
+        merge_strategy,
+        auto_push,
+        abort_on_output_pattern,
+        default_executor_profile_id,
+        commit_message_template,
+        branch_template,
+        mcp_servers,
+        worktree_sparse_patterns,
+        max_concurrent_executions,
+        dev_server_url_pattern,
+        script_language,
     } = payload;
+    if max_concurrent_executions.is_some_and(|max| max < 1) {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Max concurrent executions must be at least 1",
+        )));
+    }
+    if let Some(pattern) = abort_on_output_pattern.as_deref()
+        && let Err(e) = parse_abort_pattern(pattern)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&format!(
+            "Invalid abort pattern: {e}"
+        ))));
+    }
+    if let Some(pattern) = dev_server_url_pattern.as_deref()
+        && let Err(e) = parse_url_pattern(pattern)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&format!(
+            "Invalid dev server URL pattern: {e}"
+        ))));
+    }
+    if let Some(template) = commit_message_template.as_deref()
+        && let Err(e) = validate_commit_message_template(template)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&e.to_string())));
+    }
+    if let Some(template) = branch_template.as_deref()
+        && let Err(e) = validate_branch_template(template)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&e.to_string())));
+    }
+    if let Some(servers) = mcp_servers.as_deref()
+        && let Err(e) = validate_mcp_server_definitions(servers)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&e.to_string())));
+    }
+    if let Some(Some(patterns)) = worktree_sparse_patterns.as_ref()
+        && let Err(e) = validate_sparse_patterns(patterns)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&e.to_string())));
+    }
     // If git_repo_path is being changed, check if the new path is already used by another project
     let git_repo_path = if let Some(new_git_repo_path) = git_repo_path.map(|s| expand_tilde(&s))
         && new_git_repo_path != existing_project.git_repo_path
@@ -402,6 +533,18 @@ pub async fn update_project(
         dev_script,
         cleanup_script,
         copy_files,
+        env_template.unwrap_or(existing_project.env_template.0),
+        merge_strategy.unwrap_or(existing_project.merge_strategy),
+        auto_push.unwrap_or(existing_project.auto_push),
+        abort_on_output_pattern,
+        default_executor_profile_id,
+        commit_message_template,
+        branch_template,
+        mcp_servers.unwrap_or(existing_project.mcp_servers.0),
+        worktree_sparse_patterns.unwrap_or(existing_project.worktree_sparse_patterns),
+        max_concurrent_executions,
+        dev_server_url_pattern,
+        script_language,
     )
     .await
     {
@@ -501,6 +644,7 @@ pub async fn search_project_files(
     Query(search_query): Query<SearchQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<SearchResult>>>, StatusCode> {
     let query = search_query.q.trim();
+    let filter = search_query.filter();
     let mode = search_query.mode;
 
     if query.is_empty() {
@@ -514,7 +658,7 @@ pub async fn search_project_files(
 
     // Try cache first
     match file_search_cache
-        .search(repo_path, query, mode.clone())
+        .search(repo_path, query, mode.clone(), &filter)
         .await
     {
         Ok(results) => {
@@ -534,7 +678,13 @@ pub async fn search_project_files(
                 query,
                 mode
             );
-            match search_files_in_repo(&project.git_repo_path.to_string_lossy(), query, mode).await
+            match search_files_in_repo(
+                &project.git_repo_path.to_string_lossy(),
+                query,
+                mode,
+                &filter,
+            )
+            .await
             {
                 Ok(results) => Ok(ResponseJson(ApiResponse::success(results))),
                 Err(e) => {
@@ -546,7 +696,13 @@ pub async fn search_project_files(
         Err(CacheError::BuildError(e)) => {
             tracing::error!("Cache build error for repo {:?}: {}", repo_path, e);
             // Fall back to filesystem search
-            match search_files_in_repo(&project.git_repo_path.to_string_lossy(), query, mode).await
+            match search_files_in_repo(
+                &project.git_repo_path.to_string_lossy(),
+                query,
+                mode,
+                &filter,
+            )
+            .await
             {
                 Ok(results) => Ok(ResponseJson(ApiResponse::success(results))),
                 Err(e) => {
@@ -562,6 +718,7 @@ async fn search_files_in_repo(
     repo_path: &str,
     query: &str,
     mode: SearchMode,
+    filter: &SearchFilter,
 ) -> Result<Vec<SearchResult>, Box<dyn std::error::Error + Send + Sync>> {
     let repo_path = StdPath::new(repo_path);
 
@@ -619,6 +776,13 @@ async fn search_files_in_repo(
         let relative_path = path.strip_prefix(repo_path)?;
         let relative_path_str = relative_path.to_string_lossy().to_lowercase();
 
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase());
+        if !filter.matches(&relative_path_str, extension.as_deref(), path.is_file()) {
+            continue;
+        }
+
         let file_name = path
             .file_name()
             .map(|name| name.to_string_lossy().to_lowercase())
@@ -686,6 +850,277 @@ async fn search_files_in_repo(
     Ok(results)
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct ExportProjectBundleQuery {
+    /// Include attempt metadata (branches and executors, never worktrees)
+    #[serde(default)]
+    pub include_attempts: bool,
+}
+
+pub async fn export_project_bundle(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ExportProjectBundleQuery>,
+) -> Result<ResponseJson<ApiResponse<ProjectBundle>>, ApiError> {
+    let bundle =
+        ProjectBundle::export(&deployment.db().pool, project.id, query.include_attempts).await?;
+    Ok(ResponseJson(ApiResponse::success(bundle)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ImportProjectBundleRequest {
+    /// Existing git repository the imported project will point at
+    pub git_repo_path: String,
+    pub bundle: ProjectBundle,
+}
+
+pub async fn import_project_bundle(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ImportProjectBundleRequest>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let path = std::path::absolute(expand_tilde(&payload.git_repo_path))?;
+    if !path.join(".git").exists() {
+        return Err(ApiError::BadRequest(
+            "The specified directory is not a git repository".to_string(),
+        ));
+    }
+    if Project::find_by_git_repo_path(&deployment.db().pool, path.to_string_lossy().as_ref())
+        .await?
+        .is_some()
+    {
+        return Err(ApiError::Conflict(
+            "A project with this git repository path already exists".to_string(),
+        ));
+    }
+
+    let project = payload
+        .bundle
+        .import(&deployment.db().pool, path.to_string_lossy().to_string())
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_bundle_imported",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "task_count": payload.bundle.tasks.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct AttemptWorktreeSize {
+    pub attempt_id: Uuid,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ProjectWorktreeSize {
+    pub total_bytes: u64,
+    pub attempts: Vec<AttemptWorktreeSize>,
+}
+
+/// Disk space used by the worktrees of the project's attempts. Attempts whose
+/// worktree is missing or outside the managed directory are left out.
+pub async fn get_project_worktree_size(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectWorktreeSize>>, ApiError> {
+    let worktrees =
+        TaskAttempt::find_worktrees_by_project_id(&deployment.db().pool, project.id).await?;
+
+    let mut attempts = Vec::with_capacity(worktrees.len());
+    for (attempt_id, container_ref) in worktrees {
+        match WorktreeManager::worktree_disk_usage(StdPath::new(&container_ref)).await {
+            Ok(bytes) => attempts.push(AttemptWorktreeSize { attempt_id, bytes }),
+            Err(e) => tracing::debug!("Skipping worktree size for attempt {}: {}", attempt_id, e),
+        }
+    }
+    let total_bytes = attempts.iter().map(|a| a.bytes).sum();
+
+    Ok(ResponseJson(ApiResponse::success(ProjectWorktreeSize {
+        total_bytes,
+        attempts,
+    })))
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct StopAllProcessesResponse {
+    /// Number of execution processes that were killed
+    pub stopped: usize,
+}
+
+/// Kill every running execution process in the project, dev servers included.
+/// Tasks whose agent or script was stopped move to in review.
+pub async fn stop_all_project_processes(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<StopAllProcessesResponse>>, ApiError> {
+    let stopped = deployment
+        .container()
+        .stop_project_processes(project.id)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_processes_stopped",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "stopped": stopped,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        StopAllProcessesResponse { stopped },
+    )))
+}
+
+const DEFAULT_ACTIVITY_PAGE_SIZE: usize = 50;
+const MAX_ACTIVITY_PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectActivityQuery {
+    /// Return entries older than the activity with this id
+    #[serde(default)]
+    pub before: Option<Uuid>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ProjectActivityPage {
+    pub entries: Vec<Activity>,
+    /// Pass as `before` to fetch the next, older page
+    pub next_cursor: Option<Uuid>,
+    pub has_more: bool,
+}
+
+/// The project's activity feed, newest first.
+pub async fn get_project_activity(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ProjectActivityQuery>,
+) -> Result<ResponseJson<ApiResponse<ProjectActivityPage>>, ApiError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_ACTIVITY_PAGE_SIZE)
+        .clamp(1, MAX_ACTIVITY_PAGE_SIZE);
+    let pool = &deployment.db().pool;
+    if let Some(before) = query.before
+        && !Activity::find_by_id(pool, before)
+            .await?
+            .is_some_and(|activity| activity.project_id == project.id)
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Unknown activity cursor {before}"
+        )));
+    }
+
+    // Fetch one extra row to tell whether another page follows
+    let mut entries =
+        Activity::find_by_project_id(pool, project.id, query.before, limit as i64 + 1).await?;
+    let has_more = entries.len() > limit;
+    entries.truncate(limit);
+    let next_cursor = if has_more {
+        entries.last().map(|a| a.id)
+    } else {
+        None
+    };
+
+    Ok(ResponseJson(ApiResponse::success(ProjectActivityPage {
+        entries,
+        next_cursor,
+        has_more,
+    })))
+}
+
+async fn set_project_archived(
+    deployment: &DeploymentImpl,
+    mut project: Project,
+    archived: bool,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    Project::set_archived(&deployment.db().pool, project.id, archived).await?;
+    project.archived = archived;
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// Hide the project from the default list and stop cleaning up its worktrees.
+/// Its tasks and history are kept and it can still be opened by id.
+pub async fn archive_project(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    set_project_archived(&deployment, project, true).await
+}
+
+pub async fn unarchive_project(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    set_project_archived(&deployment, project, false).await
+}
+
+/// Labels defined in the project, for filtering and grouping its tasks.
+pub async fn get_project_labels(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskLabel>>>, ApiError> {
+    let labels = Task::find_project_labels(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateTaskLabelRequest {
+    /// New name, unique within the project ignoring case
+    pub name: Option<String>,
+    /// New hex color such as `#ef4444`
+    pub color: Option<String>,
+}
+
+/// Rename and/or recolor a project label; every task carrying it sees the change.
+pub async fn update_project_label(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, label_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateTaskLabelRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskLabel>>, ApiError> {
+    let name = payload
+        .name
+        .as_deref()
+        .map(tasks::validate_label_name)
+        .transpose()?;
+    let color = tasks::validate_label_color(payload.color.as_deref())?;
+
+    match Task::update_label(&deployment.db().pool, project_id, label_id, name, color).await {
+        Ok(Some(label)) => Ok(ResponseJson(ApiResponse::success(label))),
+        Ok(None) => Err(ApiError::NotFound("Label not found".to_string())),
+        Err(e)
+            if e.as_database_error()
+                .is_some_and(|e| e.is_unique_violation()) =>
+        {
+            Err(ApiError::Conflict(format!(
+                "The project already has a label named '{}'",
+                name.unwrap_or_default()
+            )))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Delete a project label, removing it from every task that carries it.
+pub async fn delete_project_label(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, label_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    if Task::delete_label(&deployment.db().pool, project_id, label_id).await? == 0 {
+        return Err(ApiError::NotFound("Label not found".to_string()));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let project_id_router = Router::new()
         .route(
@@ -702,6 +1137,13 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             post(link_project_to_existing_remote).delete(unlink_project),
         )
         .route("/link/create", post(create_and_link_remote_project))
+        .route("/export-bundle", get(export_project_bundle))
+        .route("/worktree-size", get(get_project_worktree_size))
+        .route("/stop-all", post(stop_all_project_processes))
+        .route("/activity", get(get_project_activity))
+        .route("/labels", get(get_project_labels))
+        .route("/archive", post(archive_project))
+        .route("/unarchive", post(unarchive_project))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_project_middleware,
@@ -709,7 +1151,12 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let projects_router = Router::new()
         .route("/", get(get_projects).post(create_project))
-        .nest("/{id}", project_id_router);
+        .route("/import-bundle", post(import_project_bundle))
+        .nest("/{id}", project_id_router)
+        .route(
+            "/{id}/labels/{label_id}",
+            put(update_project_label).delete(delete_project_label),
+        );
 
     Router::new().nest("/projects", projects_router).route(
         "/remote-projects/{remote_project_id}",
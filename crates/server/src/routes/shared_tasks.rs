@@ -2,12 +2,12 @@ use axum::{
     Json, Router,
     extract::{Path, State},
     response::Json as ResponseJson,
-    routing::{delete, post},
+    routing::{delete, get, post},
 };
 use db::models::shared_task::SharedTask;
 use deployment::Deployment;
 use serde::{Deserialize, Serialize};
-use services::services::share::ShareError;
+use services::services::share::{RemoteSyncState, ShareError};
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
@@ -29,6 +29,7 @@ pub struct AssignSharedTaskResponse {
 
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
+        .route("/shared-tasks/sync-status", get(get_sync_status))
         .route(
             "/shared-tasks/{shared_task_id}/assign",
             post(assign_shared_task),
@@ -36,6 +37,19 @@ pub fn router() -> Router<DeploymentImpl> {
         .route("/shared-tasks/{shared_task_id}", delete(delete_shared_task))
 }
 
+/// Connection state of the shared task sync, or `None` when it is not running
+pub async fn get_sync_status(
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<Option<RemoteSyncState>>> {
+    let state = deployment
+        .share_sync_handle()
+        .lock()
+        .await
+        .as_ref()
+        .map(|handle| handle.state());
+    ResponseJson(ApiResponse::success(state))
+}
+
 pub async fn assign_shared_task(
     Path(shared_task_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
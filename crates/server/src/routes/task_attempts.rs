@@ -5,7 +5,10 @@ pub mod images;
 pub mod queue;
 pub mod util;
 
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 use axum::{
     Extension, Json, Router,
@@ -13,16 +16,19 @@ use axum::{
         Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
-    http::StatusCode,
+    http::{StatusCode, header},
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
-    routing::{get, post},
+    routing::{get, post, put},
 };
 use db::models::{
-    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    activity::{ActivityKind, CreateActivity},
+    execution_process::{
+        ExecutionProcess, ExecutionProcessError, ExecutionProcessRunReason, ExecutionProcessStatus,
+    },
     execution_process_logs::ExecutionProcessLogs,
     merge::{Merge, MergeStatus},
-    project::{Project, ProjectError},
+    project::{MergeStrategy, Project, ProjectError},
     scratch::{Scratch, ScratchType},
     task::{Task, TaskRelationships, TaskStatus},
     task_attempt::{TaskAttempt, TaskAttemptError},
@@ -31,33 +37,55 @@ use deployment::Deployment;
 use executors::{
     actions::{
         ExecutorAction, ExecutorActionType,
-        coding_agent_follow_up::CodingAgentFollowUpRequest,
-        script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+        script::{ScriptContext, ScriptRequest},
     },
-    conversation_export::{self, ExportResult},
+    conversation_export::{self, ExportResult, JsonExportResult, RawProcessLogs},
+    env::EnvOverrides,
     executors::{CodingAgent, ExecutorError},
-    logs::utils::patch::extract_normalized_entry_from_patch,
+    logs::{NormalizedEntry, TokenUsage, utils::patch::latest_normalized_entries},
+    mcp_calls::{self, McpToolCall},
     profile::{ExecutorConfigs, ExecutorProfileId},
+    usage,
 };
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
-    commit_message::{self, CommitMessageError},
-    container::{ContainerError, ContainerService},
-    git::{ConflictOp, GitCliError, GitServiceError, WorktreeResetOptions},
+    branch_status::{BranchStatus, CommitGraphStatus},
+    commit_message::{self, CommitMessageContext, CommitMessageError},
+    container::{
+        AttemptOverrides, ContainerError, ContainerService, cleanup_script_action,
+        setup_script_action,
+    },
+    git::{
+        CommitInfo, CommitNote, ConflictOp, ConflictSide, DiffTarget, GitCliError, GitService,
+        GitServiceError, LargeFileEntry, ResetMode, WorktreeResetOptions,
+    },
+    git_provider::{GitProvider, RepoInfo},
     github::{CreatePrRequest, GitHubService, GitHubServiceError},
-    worktree_manager::WorktreeError,
+    gitlab::{GitLabService, GitLabServiceError},
+    image::ImageService,
+    notification::NotificationService,
+    share::update_task_status_and_share,
+    worktree_manager::{WorktreeError, WorktreeManager},
 };
-use sqlx::Error as SqlxError;
+use sqlx::{Error as SqlxError, SqlitePool};
 use ts_rs::TS;
-use utils::{log_msg::LogMsg, response::ApiResponse};
+use utils::{
+    diff::{Diff, DiffChangeKind},
+    log_msg::LogMsg,
+    response::ApiResponse,
+};
 use uuid::Uuid;
 
 use crate::{
     DeploymentImpl,
     error::ApiError,
     middleware::load_task_attempt_middleware,
-    routes::task_attempts::{gh_cli_setup::GhCliSetupError, util::ensure_worktree_path},
+    routes::{
+        execution_processes::{LogSse, log_sse_for_execution},
+        task_attempts::{gh_cli_setup::GhCliSetupError, util::ensure_worktree_path},
+        tasks::ensure_task_unblocked,
+    },
 };
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -79,6 +107,9 @@ pub struct CreateGitHubPrRequest {
     pub title: String,
     pub body: Option<String>,
     pub target_branch: Option<String>,
+    /// Fill the body from the attempt's conversation export when `body` isn't given
+    #[serde(default)]
+    pub body_from_conversation: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -87,6 +118,19 @@ pub struct CommitChangesRequest {
     pub files: Vec<String>,
     /// Commit message.
     pub message: String,
+    /// Commit even if a rebase is in progress or files are still conflicted.
+    #[serde(default)]
+    pub force_when_dirty: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum CommitChangesError {
+    CommitBlockedByConflict {
+        rebase_in_progress: bool,
+        conflicted_files: Vec<String>,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -138,8 +182,9 @@ pub async fn get_task_attempt(
 #[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
 pub struct CreateTaskAttemptBody {
     pub task_id: Uuid,
-    /// Executor profile specification
-    pub executor_profile_id: ExecutorProfileId,
+    /// Executor profile specification; falls back to the project's default when omitted
+    #[serde(default)]
+    pub executor_profile_id: Option<ExecutorProfileId>,
     pub base_branch: String,
     /// If true, use base_branch as the working branch instead of creating a new one
     #[serde(default)]
@@ -150,12 +195,19 @@ pub struct CreateTaskAttemptBody {
     /// Conversation history from a previous attempt to prepend to the prompt.
     /// Used when continuing a task with a different agent.
     pub conversation_history: Option<String>,
+    /// Cleanup script to run for this attempt instead of the project's
+    pub cleanup_script_override: Option<String>,
+    /// Environment variables for every process of this attempt, over the project's
+    #[serde(default)]
+    pub env_overrides: Option<HashMap<String, String>>,
 }
 
 impl CreateTaskAttemptBody {
-    /// Get the executor profile ID
-    pub fn get_executor_profile_id(&self) -> ExecutorProfileId {
-        self.executor_profile_id.clone()
+    /// Get the executor profile ID, falling back to the project's default
+    pub fn get_executor_profile_id(&self, project: &Project) -> Option<ExecutorProfileId> {
+        self.executor_profile_id
+            .clone()
+            .or_else(|| project.default_executor_profile_id.clone().map(|p| p.0))
     }
 }
 
@@ -172,10 +224,23 @@ pub async fn create_task_attempt(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTaskAttemptBody>,
 ) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
-    let executor_profile_id = payload.get_executor_profile_id();
     let task = Task::find_by_id(&deployment.db().pool, payload.task_id)
         .await?
         .ok_or(SqlxError::RowNotFound)?;
+    let project = task
+        .parent_project(&deployment.db().pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let executor_profile_id = payload.get_executor_profile_id(&project).ok_or_else(|| {
+        ApiError::BadRequest(
+            "No executor_profile_id given and the project has no default executor profile"
+                .to_string(),
+        )
+    })?;
+    ExecutorConfigs::get_cached()
+        .validate_profile(&executor_profile_id)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    ensure_task_unblocked(&deployment, task.id).await?;
 
     let task_attempt_result = deployment
         .container()
@@ -186,6 +251,10 @@ pub async fn create_task_attempt(
             payload.custom_branch,
             payload.use_existing_branch,
             payload.conversation_history,
+            AttemptOverrides {
+                cleanup_script: payload.cleanup_script_override,
+                env: payload.env_overrides.map(EnvOverrides),
+            },
         )
         .await;
 
@@ -214,12 +283,215 @@ pub async fn create_task_attempt(
             }),
         )
         .await;
+    deployment
+        .record_activity(CreateActivity::for_attempt(
+            ActivityKind::AttemptStarted,
+            &task,
+            task_attempt.id,
+        ))
+        .await;
 
     tracing::info!("Created attempt for task {}", task.id);
 
     Ok(ResponseJson(ApiResponse::success(task_attempt)))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateTaskAttemptBatchBody {
+    pub task_id: Uuid,
+    pub base_branch: String,
+    /// One attempt is started per profile, each on its own auto-generated branch
+    pub executor_profile_ids: Vec<ExecutorProfileId>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct TaskAttemptBatchResult {
+    pub executor_profile_id: ExecutorProfileId,
+    pub attempt: Option<TaskAttempt>,
+    /// Why this profile's attempt could not be started
+    pub error: Option<String>,
+}
+
+/// Start the same task with several executor profiles at once. Profiles that
+/// fail to start are reported individually without aborting the rest.
+pub async fn create_task_attempt_batch(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskAttemptBatchBody>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskAttemptBatchResult>>>, ApiError> {
+    if payload.executor_profile_ids.is_empty() {
+        return Err(ApiError::BadRequest(
+            "executor_profile_ids must not be empty".to_string(),
+        ));
+    }
+    let task = Task::find_by_id(&deployment.db().pool, payload.task_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    ensure_task_unblocked(&deployment, task.id).await?;
+
+    let profiles = ExecutorConfigs::get_cached();
+    for executor_profile_id in &payload.executor_profile_ids {
+        profiles
+            .validate_profile(executor_profile_id)
+            .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    }
+
+    // Started one after another so worktree creation doesn't contend on the repo
+    let mut results = Vec::with_capacity(payload.executor_profile_ids.len());
+    for executor_profile_id in payload.executor_profile_ids {
+        let result = deployment
+            .container()
+            .create_and_start_task_attempt(
+                &task,
+                executor_profile_id.clone(),
+                &payload.base_branch,
+                None,
+                false,
+                None,
+                AttemptOverrides::default(),
+            )
+            .await;
+
+        match result {
+            Ok(task_attempt) => {
+                deployment
+                    .track_if_analytics_allowed(
+                        "task_attempt_started",
+                        serde_json::json!({
+                            "task_id": task_attempt.task_id.to_string(),
+                            "variant": &executor_profile_id.variant,
+                            "executor": &executor_profile_id.executor,
+                            "attempt_id": task_attempt.id.to_string(),
+                            "batch": true,
+                        }),
+                    )
+                    .await;
+                deployment
+                    .record_activity(CreateActivity::for_attempt(
+                        ActivityKind::AttemptStarted,
+                        &task,
+                        task_attempt.id,
+                    ))
+                    .await;
+                results.push(TaskAttemptBatchResult {
+                    executor_profile_id,
+                    attempt: Some(task_attempt),
+                    error: None,
+                });
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "Failed to start {} attempt for task {}: {}",
+                    executor_profile_id,
+                    task.id,
+                    err
+                );
+                results.push(TaskAttemptBatchResult {
+                    executor_profile_id,
+                    attempt: None,
+                    error: Some(err.to_string()),
+                });
+            }
+        }
+    }
+
+    tracing::info!(
+        "Created {} of {} batch attempts for task {}",
+        results.iter().filter(|r| r.attempt.is_some()).count(),
+        results.len(),
+        task.id
+    );
+
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CloneTaskAttemptBody {
+    pub executor_profile_id: ExecutorProfileId,
+    /// Prepend the source attempt's conversation, exported as markdown, to the prompt
+    #[serde(default)]
+    pub include_conversation_history: bool,
+}
+
+/// Start a new attempt of the same task on a fresh branch forked from this
+/// attempt's branch, typically with a different executor. Only committed work is
+/// carried over; the cleanup script and environment overrides are copied.
+pub async fn clone_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CloneTaskAttemptBody>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let executor_profile_id = payload.executor_profile_id;
+    ExecutorConfigs::get_cached()
+        .validate_profile(&executor_profile_id)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    ensure_task_unblocked(&deployment, task.id).await?;
+
+    let conversation_history = if payload.include_conversation_history {
+        let processes = coding_agent_processes(pool, task_attempt.id).await?;
+        if processes.is_empty() {
+            None
+        } else {
+            let (entries, _) = load_conversation(&deployment, &processes, false).await?;
+            let export =
+                conversation_export::export_to_markdown(&entries, &task_attempt.executor, None);
+            Some(export.markdown)
+        }
+    } else {
+        None
+    };
+
+    let clone = deployment
+        .container()
+        .create_and_start_task_attempt(
+            &task,
+            executor_profile_id.clone(),
+            &task_attempt.branch,
+            None,
+            false,
+            conversation_history,
+            AttemptOverrides {
+                cleanup_script: task_attempt.cleanup_script_override.clone(),
+                env: task_attempt.env_overrides.clone().map(|env| env.0),
+            },
+        )
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_cloned",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "variant": &executor_profile_id.variant,
+                "executor": &executor_profile_id.executor,
+                "source_attempt_id": task_attempt.id.to_string(),
+                "attempt_id": clone.id.to_string(),
+                "include_conversation_history": payload.include_conversation_history,
+            }),
+        )
+        .await;
+    deployment
+        .record_activity(CreateActivity::for_attempt(
+            ActivityKind::AttemptStarted,
+            &task,
+            clone.id,
+        ))
+        .await;
+
+    tracing::info!(
+        "Cloned attempt {} of task {} as {}",
+        task_attempt.id,
+        task.id,
+        clone.id
+    );
+
+    Ok(ResponseJson(ApiResponse::success(clone)))
+}
+
 #[axum::debug_handler]
 pub async fn run_agent_setup(
     Extension(task_attempt): Extension<TaskAttempt>,
@@ -259,17 +531,21 @@ pub struct CreateFollowUpAttempt {
     pub retry_process_id: Option<Uuid>,
     pub force_when_dirty: Option<bool>,
     pub perform_git_reset: Option<bool>,
+    /// Replaces the attempt's cleanup script override; an empty string restores the project's
+    pub cleanup_script: Option<String>,
+    /// Images to attach; ids with no stored image are skipped
+    pub image_ids: Option<Vec<Uuid>>,
 }
 
 pub async fn follow_up(
-    Extension(task_attempt): Extension<TaskAttempt>,
+    Extension(mut task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateFollowUpAttempt>,
 ) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
     tracing::info!("{:?}", task_attempt);
 
     // Ensure worktree exists (recreate if needed for cold task support)
-    let _ = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
 
     // Get executor profile data from the latest CodingAgent process
     let initial_executor_profile_id = ExecutionProcess::latest_executor_profile_for_attempt(
@@ -282,6 +558,9 @@ pub async fn follow_up(
         executor: initial_executor_profile_id.executor,
         variant: payload.variant,
     };
+    ExecutorConfigs::get_cached()
+        .validate_profile(&executor_profile_id)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
 
     // Get parent task
     let task = task_attempt
@@ -289,6 +568,8 @@ pub async fn follow_up(
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
+    ensure_task_unblocked(&deployment, task.id).await?;
+
     // Get parent project
     let project = task
         .parent_project(&deployment.db().pool)
@@ -357,28 +638,38 @@ pub async fn follow_up(
     )
     .await?;
 
-    let prompt = payload.prompt;
-
-    let cleanup_action = deployment
-        .container()
-        .cleanup_action(project.cleanup_script);
-
-    let action_type = if let Some(session_id) = latest_session_id {
-        ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
-            prompt: prompt.clone(),
-            session_id,
-            executor_profile_id: executor_profile_id.clone(),
-            is_orchestrator: task_attempt.is_orchestrator,
-        })
-    } else {
-        ExecutorActionType::CodingAgentInitialRequest(
-            executors::actions::coding_agent_initial::CodingAgentInitialRequest {
-                prompt,
-                executor_profile_id: executor_profile_id.clone(),
-                is_orchestrator: task_attempt.is_orchestrator,
-            },
+    if let Some(script) = payload.cleanup_script {
+        let script = Some(script).filter(|s| !s.trim().is_empty());
+        TaskAttempt::set_cleanup_script_override(
+            &deployment.db().pool,
+            task_attempt.id,
+            script.as_deref(),
         )
-    };
+        .await?;
+        task_attempt.cleanup_script_override = script;
+    }
+
+    if let Some(image_ids) = &payload.image_ids {
+        deployment
+            .image()
+            .attach_images_to_task(&worktree_path, task.id, image_ids)
+            .await?;
+    }
+
+    let prompt = ImageService::canonicalise_image_paths(&payload.prompt, &worktree_path);
+
+    let script_language = project.effective_script_language();
+    let cleanup_action = deployment.container().cleanup_action(
+        task_attempt.effective_cleanup_script(project.cleanup_script),
+        script_language,
+    );
+
+    let action_type = ExecutorActionType::coding_agent_request(
+        prompt,
+        latest_session_id,
+        executor_profile_id,
+        task_attempt.is_orchestrator,
+    );
 
     let action = ExecutorAction::new(action_type, cleanup_action);
 
@@ -474,6 +765,134 @@ async fn handle_task_attempt_diff_ws(
     Ok(())
 }
 
+/// One changed file, as returned by `GET /task-attempts/{id}/diff`
+#[derive(Debug, Serialize, TS)]
+pub struct FileDiff {
+    /// Path after the change, or before it for deletions
+    pub path: String,
+    /// Previous path of a renamed or copied file
+    pub old_path: Option<String>,
+    pub change: DiffChangeKind,
+    pub additions: usize,
+    pub deletions: usize,
+    /// Unified diff hunks; empty when contents were omitted
+    pub hunks: Vec<String>,
+    /// True when contents were left out because of `stats_only` or the size limit
+    pub content_omitted: bool,
+}
+
+impl From<Diff> for FileDiff {
+    fn from(diff: Diff) -> Self {
+        let path = diff
+            .new_path
+            .clone()
+            .or_else(|| diff.old_path.clone())
+            .unwrap_or_default();
+        let old_path = match diff.change {
+            DiffChangeKind::Renamed | DiffChangeKind::Copied => diff.old_path,
+            _ => None,
+        };
+        let old = diff.old_content.as_deref().unwrap_or_default();
+        let new = diff.new_content.as_deref().unwrap_or_default();
+        let (hunks, (additions, deletions)) = if diff.content_omitted {
+            (
+                Vec::new(),
+                (
+                    diff.additions.unwrap_or_default(),
+                    diff.deletions.unwrap_or_default(),
+                ),
+            )
+        } else {
+            (
+                utils::diff::create_unified_diff_hunks(old, new),
+                utils::diff::compute_line_change_counts(old, new),
+            )
+        };
+
+        Self {
+            path,
+            old_path,
+            change: diff.change,
+            additions,
+            deletions,
+            hunks,
+            content_omitted: diff.content_omitted,
+        }
+    }
+}
+
+/// The attempt's full diff as a plain list of files, for clients that don't
+/// want to follow the incremental `/diff/ws` stream.
+pub async fn get_task_attempt_diff(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(params): Query<DiffStreamQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<FileDiff>>>, ApiError> {
+    let diffs = deployment
+        .container()
+        .collect_diff(&task_attempt, params.stats_only)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(
+        diffs.into_iter().map(FileDiff::from).collect(),
+    )))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompareAttemptsQuery {
+    pub a: Uuid,
+    pub b: Uuid,
+}
+
+/// Load an attempt and its project id, recreating its worktree if it was
+/// cleaned up so the attempt's branch is available to diff.
+async fn load_attempt_for_compare(
+    deployment: &DeploymentImpl,
+    id: Uuid,
+) -> Result<(TaskAttempt, Uuid), ApiError> {
+    let pool = &deployment.db().pool;
+    let attempt = TaskAttempt::find_by_id(pool, id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest(format!("Task attempt {id} not found")))?;
+    ensure_worktree_path(deployment, &attempt).await?;
+    let task = attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    Ok((attempt, task.project_id))
+}
+
+/// Diff attempt `b`'s branch against attempt `a`'s branch, for comparing two
+/// attempts raced on the same task.
+pub async fn compare_task_attempts(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<CompareAttemptsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<FileDiff>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let (attempt_a, project_a) = load_attempt_for_compare(&deployment, query.a).await?;
+    let (attempt_b, project_b) = load_attempt_for_compare(&deployment, query.b).await?;
+
+    if project_a != project_b {
+        return Err(ApiError::BadRequest(
+            "Attempts to compare must belong to the same project".to_string(),
+        ));
+    }
+    let project = Project::find_by_id(pool, project_a)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::ProjectNotFound))?;
+
+    let diffs = deployment.git().diff_between_branches(
+        &project.git_repo_path,
+        &attempt_a.branch,
+        &attempt_b.branch,
+        None,
+    )?;
+
+    Ok(ResponseJson(ApiResponse::success(
+        diffs.into_iter().map(FileDiff::from).collect(),
+    )))
+}
+
 #[derive(Debug, Serialize, TS)]
 pub struct CommitCompareResult {
     pub subject: String,
@@ -513,142 +932,451 @@ pub async fn compare_commit_to_head(
     })))
 }
 
-#[axum::debug_handler]
-pub async fn merge_task_attempt(
+#[derive(Debug, Serialize, TS)]
+pub struct WorktreeSize {
+    pub bytes: u64,
+}
+
+/// Disk space used by the attempt's worktree.
+pub async fn get_worktree_size(
+    Extension(task_attempt): Extension<TaskAttempt>,
+) -> Result<ResponseJson<ApiResponse<WorktreeSize>>, ApiError> {
+    let container_ref = task_attempt
+        .container_ref
+        .as_deref()
+        .filter(|_| !task_attempt.worktree_deleted)
+        .ok_or_else(|| ApiError::BadRequest("Attempt has no worktree".to_string()))?;
+
+    let bytes = match WorktreeManager::worktree_disk_usage(Path::new(container_ref)).await {
+        Ok(bytes) => bytes,
+        Err(WorktreeError::UnsafePath(_)) => {
+            return Err(ApiError::BadRequest(
+                "Attempt does not use a managed worktree".to_string(),
+            ));
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(ResponseJson(ApiResponse::success(WorktreeSize { bytes })))
+}
+
+/// Commits the attempt branch has on top of its base commit, newest first.
+pub async fn list_attempt_commits(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
-) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<Vec<CommitInfo>>>, ApiError> {
     let pool = &deployment.db().pool;
-
     let task = task_attempt
         .parent_task(pool)
         .await?
         .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
     let ctx = TaskAttempt::load_context(pool, task_attempt.id, task.id, task.project_id).await?;
+    let base_commit = deployment.git().get_base_commit(
+        &ctx.project.git_repo_path,
+        &task_attempt.branch,
+        &task_attempt.target_branch,
+    )?;
 
-    let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
-    let worktree_path = worktree_path_buf.as_path();
-
-    let task_uuid_str = task.id.to_string();
-    let first_uuid_section = task_uuid_str.split('-').next().unwrap_or(&task_uuid_str);
-
-    // Create commit message with task title and description
-    let mut commit_message = format!("{} (vibe-kanban {})", ctx.task.title, first_uuid_section);
-
-    // Add description on next line if it exists
-    if let Some(description) = &ctx.task.description
-        && !description.trim().is_empty()
-    {
-        commit_message.push_str("\n\n");
-        commit_message.push_str(description);
-    }
-
-    let merge_commit_id = deployment.git().merge_changes(
+    let commits = deployment.git().list_commits_between(
         &ctx.project.git_repo_path,
-        worktree_path,
-        &ctx.task_attempt.branch,
-        &ctx.task_attempt.target_branch,
-        &commit_message,
+        &base_commit,
+        &task_attempt.branch,
     )?;
 
-    Merge::create_direct(
-        pool,
-        task_attempt.id,
-        &ctx.task_attempt.target_branch,
-        &merge_commit_id,
-    )
-    .await?;
-    Task::update_status(pool, ctx.task.id, TaskStatus::Done).await?;
+    Ok(ResponseJson(ApiResponse::success(commits)))
+}
 
-    // Stop any running dev servers for this task attempt
-    let dev_servers =
-        ExecutionProcess::find_running_dev_servers_by_task_attempt(pool, task_attempt.id).await?;
+#[derive(Debug, Deserialize, TS)]
+pub struct SetLastViewedRequest {
+    /// Defaults to the attempt's current HEAD
+    pub oid: Option<String>,
+}
 
-    for dev_server in dev_servers {
-        tracing::info!(
-            "Stopping dev server {} for completed task attempt {}",
-            dev_server.id,
-            task_attempt.id
-        );
+#[derive(Debug, Serialize, TS)]
+pub struct LastViewedResponse {
+    pub oid: String,
+}
 
-        if let Err(e) = deployment
-            .container()
-            .stop_execution(&dev_server, ExecutionProcessStatus::Killed)
-            .await
-        {
-            tracing::error!(
-                "Failed to stop dev server {} for task attempt {}: {}",
-                dev_server.id,
-                task_attempt.id,
-                e
-            );
-        }
-    }
+/// Record the commit the user has reviewed up to, for `diff/since-last`.
+pub async fn set_last_viewed(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetLastViewedRequest>,
+) -> Result<ResponseJson<ApiResponse<LastViewedResponse>>, ApiError> {
+    let wt_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let wt = wt_buf.as_path();
 
-    // Try broadcast update to other users in organization
-    if let Ok(publisher) = deployment.share_publisher() {
-        if let Err(err) = publisher.update_shared_task_by_id(ctx.task.id).await {
-            tracing::warn!(
-                ?err,
-                "Failed to propagate shared task update for {}",
-                ctx.task.id
-            );
+    let oid = match payload.oid {
+        Some(oid) => {
+            // Make sure the commit exists in this repository
+            deployment.git().get_commit_subject(wt, &oid)?;
+            oid
         }
-    } else {
-        tracing::debug!(
-            "Share publisher unavailable; skipping remote update for {}",
-            ctx.task.id
-        );
-    }
+        None => deployment.git().get_head_info(wt)?.oid,
+    };
 
-    deployment
-        .track_if_analytics_allowed(
-            "task_attempt_merged",
-            serde_json::json!({
-                "task_id": ctx.task.id.to_string(),
-                "project_id": ctx.project.id.to_string(),
-                "attempt_id": task_attempt.id.to_string(),
-            }),
-        )
-        .await;
+    TaskAttempt::update_last_viewed_oid(&deployment.db().pool, task_attempt.id, &oid).await?;
 
-    Ok(ResponseJson(ApiResponse::success(())))
+    Ok(ResponseJson(ApiResponse::success(LastViewedResponse {
+        oid,
+    })))
 }
 
-pub async fn push_task_attempt_branch(
+#[derive(Debug, Serialize, TS)]
+pub struct DiffSinceLastResponse {
+    pub from_oid: String,
+    pub to_oid: String,
+    /// True when no marker was set and the diff starts at the attempt's base commit
+    pub from_base: bool,
+    pub diffs: Vec<Diff>,
+}
+
+/// Diff from the last viewed marker (or the base commit) to the attempt's HEAD.
+pub async fn get_diff_since_last_view(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
-) -> Result<ResponseJson<ApiResponse<(), PushError>>, ApiError> {
-    let github_service = GitHubService::new()?;
-    github_service.check_token().await?;
+) -> Result<ResponseJson<ApiResponse<DiffSinceLastResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let wt_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let wt = wt_buf.as_path();
+    let head_oid = deployment.git().get_head_info(wt)?.oid;
+
+    let marker = TaskAttempt::find_last_viewed_oid(pool, task_attempt.id).await?;
+    let (from_oid, from_base) = match marker {
+        Some(oid) => (oid, false),
+        None => {
+            let task = task_attempt
+                .parent_task(pool)
+                .await?
+                .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+            let ctx =
+                TaskAttempt::load_context(pool, task_attempt.id, task.id, task.project_id).await?;
+            let base = deployment.git().get_base_commit(
+                &ctx.project.git_repo_path,
+                &task_attempt.branch,
+                &task_attempt.target_branch,
+            )?;
+            (base.to_string(), true)
+        }
+    };
 
-    let ws_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let diffs = deployment.git().get_diffs(
+        DiffTarget::Range {
+            repo_path: wt,
+            from_sha: &from_oid,
+            to_sha: &head_oid,
+        },
+        None,
+    )?;
 
-    match deployment
+    Ok(ResponseJson(ApiResponse::success(DiffSinceLastResponse {
+        from_oid,
+        to_oid: head_oid,
+        from_base,
+        diffs,
+    })))
+}
+
+const DEFAULT_LARGE_FILE_THRESHOLD_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct LargeFilesQuery {
+    /// Files bigger than this are reported; defaults to 1 MiB
+    pub threshold_bytes: Option<u64>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct LargeFilesResponse {
+    #[ts(type = "number")]
+    pub threshold_bytes: u64,
+    pub files: Vec<LargeFileEntry>,
+}
+
+/// Changed files in the attempt that are binary or exceed the size threshold.
+pub async fn get_large_files(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<LargeFilesQuery>,
+) -> Result<ResponseJson<ApiResponse<LargeFilesResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let threshold_bytes = query
+        .threshold_bytes
+        .unwrap_or(DEFAULT_LARGE_FILE_THRESHOLD_BYTES);
+    let wt_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let ctx = TaskAttempt::load_context(pool, task_attempt.id, task.id, task.project_id).await?;
+    let base_commit = deployment.git().get_base_commit(
+        &ctx.project.git_repo_path,
+        &task_attempt.branch,
+        &task_attempt.target_branch,
+    )?;
+
+    let files = deployment
         .git()
-        .push_to_github(&ws_path, &task_attempt.branch, false)
+        .find_large_files(&wt_buf, &base_commit, threshold_bytes)?;
+
+    Ok(ResponseJson(ApiResponse::success(LargeFilesResponse {
+        threshold_bytes,
+        files,
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergeTaskAttemptQuery {
+    /// Only report whether the merge would conflict; nothing is written
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct MergeTaskAttemptRequest {
+    /// Strategy to merge with (`squash` collapses the attempt into one commit);
+    /// must match the project's configured strategy
+    pub strategy: Option<MergeStrategy>,
+}
+
+#[axum::debug_handler]
+pub async fn merge_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<MergeTaskAttemptQuery>,
+    payload: Option<Json<MergeTaskAttemptRequest>>,
+) -> Result<ResponseJson<ApiResponse<(), GitOperationError>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let ctx = TaskAttempt::load_context(pool, task_attempt.id, task.id, task.project_id).await?;
+
+    let strategy = ctx.project.merge_strategy;
+    if let Some(requested) = payload.and_then(|Json(body)| body.strategy)
+        && requested != strategy
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Merge strategy {requested:?} is not permitted for this project (configured: {strategy:?})"
+        )));
+    }
+
+    if query.dry_run {
+        let conflicts = deployment.git().merge_changes_dry_run(
+            &ctx.project.git_repo_path,
+            &ctx.task_attempt.branch,
+            &ctx.task_attempt.target_branch,
+            strategy,
+        )?;
+        if conflicts.is_empty() {
+            return Ok(ResponseJson(ApiResponse::success(())));
+        }
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            GitOperationError::MergeConflicts {
+                message: format!(
+                    "Merging into '{}' would conflict in: {}",
+                    ctx.task_attempt.target_branch,
+                    conflicts.join(", ")
+                ),
+                op: ConflictOp::Merge,
+            },
+        )));
+    }
+
+    let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let worktree_path = worktree_path_buf.as_path();
+
+    let commit_message = commit_message::merge_commit_message(
+        ctx.project.commit_message_template.as_deref(),
+        &CommitMessageContext {
+            title: &ctx.task.title,
+            description: ctx.task.description.as_deref(),
+            task_id: ctx.task.id,
+            branch: &ctx.task_attempt.branch,
+        },
+    );
+
+    let merge_commit_id = deployment.git().merge_changes_with_strategy(
+        &ctx.project.git_repo_path,
+        worktree_path,
+        &ctx.task_attempt.branch,
+        &ctx.task_attempt.target_branch,
+        &commit_message,
+        strategy,
+    )?;
+    // Squash and merge commits are new on the target, so tag them for tracing
+    // back to the task too. Best-effort: the merge stands either way.
+    let note = CommitNote {
+        task_id: ctx.task.id,
+        attempt_id: task_attempt.id,
+        executor: ctx.task_attempt.executor.clone(),
+    };
+    if let Err(e) = deployment
+        .git()
+        .write_note(&ctx.project.git_repo_path, &merge_commit_id, &note)
     {
+        tracing::warn!(
+            "Failed to write commit note on merge commit {}: {}",
+            merge_commit_id,
+            e
+        );
+    }
+
+    Merge::create_direct(
+        pool,
+        task_attempt.id,
+        &ctx.task_attempt.target_branch,
+        &merge_commit_id,
+    )
+    .await?;
+    // The target moved, so every attempt on it is now further behind
+    deployment
+        .container()
+        .branch_status_cache()
+        .invalidate_target(ctx.project.id, &ctx.task_attempt.target_branch);
+    // Broadcast the update to other users in the organization
+    update_task_status_and_share(
+        pool,
+        deployment.share_publisher().ok(),
+        ctx.task.id,
+        TaskStatus::Done,
+    )
+    .await?;
+
+    // Stop any running dev servers for this task attempt
+    let dev_servers =
+        ExecutionProcess::find_running_dev_servers_by_task_attempt(pool, task_attempt.id).await?;
+
+    for dev_server in dev_servers {
+        tracing::info!(
+            "Stopping dev server {} for completed task attempt {}",
+            dev_server.id,
+            task_attempt.id
+        );
+
+        if let Err(e) = deployment
+            .container()
+            .stop_execution(&dev_server, ExecutionProcessStatus::Killed)
+            .await
+        {
+            tracing::error!(
+                "Failed to stop dev server {} for task attempt {}: {}",
+                dev_server.id,
+                task_attempt.id,
+                e
+            );
+        }
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_merged",
+            serde_json::json!({
+                "task_id": ctx.task.id.to_string(),
+                "project_id": ctx.project.id.to_string(),
+                "attempt_id": task_attempt.id.to_string(),
+            }),
+        )
+        .await;
+    deployment
+        .record_activity(CreateActivity::for_attempt(
+            ActivityKind::AttemptMerged,
+            &ctx.task,
+            task_attempt.id,
+        ))
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Check the hosting provider's CLI is authenticated before pushing. Remotes
+/// not recognised as GitLab keep the GitHub check.
+/// Check the token for the provider hosting the remote the branch will be pushed to
+async fn check_push_auth(
+    deployment: &DeploymentImpl,
+    ws_path: &Path,
+    branch: &str,
+    remote: Option<&str>,
+) -> Result<(), ApiError> {
+    let remote_name = deployment.git().push_remote_name(ws_path, branch, remote)?;
+    match deployment.git().get_remote_repo_info(ws_path, &remote_name) {
+        Ok(RepoInfo::GitLab(_)) => GitLabService::new()?.check_token().await?,
+        _ => GitHubService::new()?.check_token().await?,
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, TS)]
+pub struct PushTaskAttemptRequest {
+    /// Remote to push to; defaults to the remote the branch already tracks
+    pub remote: Option<String>,
+}
+
+fn push_attempt_branch(
+    deployment: &DeploymentImpl,
+    ws_path: &Path,
+    task_attempt: &TaskAttempt,
+    request: PushTaskAttemptRequest,
+    force: bool,
+) -> Result<ResponseJson<ApiResponse<(), PushError>>, ApiError> {
+    let result = deployment.git().push_to_github(
+        ws_path,
+        &task_attempt.branch,
+        request.remote.as_deref(),
+        force,
+    );
+    deployment
+        .container()
+        .branch_status_cache()
+        .invalidate(task_attempt.id);
+    match result {
         Ok(_) => Ok(ResponseJson(ApiResponse::success(()))),
-        Err(GitServiceError::GitCLI(GitCliError::PushRejected(_))) => Ok(ResponseJson(
+        Err(GitServiceError::GitCLI(GitCliError::PushRejected(_))) if !force => Ok(ResponseJson(
             ApiResponse::error_with_data(PushError::ForcePushRequired),
         )),
+        Err(GitServiceError::RemoteNotFound(remote)) => Ok(ResponseJson(
+            ApiResponse::error_with_data(PushError::RemoteNotFound { remote }),
+        )),
         Err(e) => Err(ApiError::GitService(e)),
     }
 }
 
-pub async fn force_push_task_attempt_branch(
+pub async fn push_task_attempt_branch(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
+    payload: Option<Json<PushTaskAttemptRequest>>,
 ) -> Result<ResponseJson<ApiResponse<(), PushError>>, ApiError> {
-    let github_service = GitHubService::new()?;
-    github_service.check_token().await?;
+    let request = payload.map(|Json(body)| body).unwrap_or_default();
+    let ws_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    check_push_auth(
+        &deployment,
+        &ws_path,
+        &task_attempt.branch,
+        request.remote.as_deref(),
+    )
+    .await?;
 
+    push_attempt_branch(&deployment, &ws_path, &task_attempt, request, false)
+}
+
+pub async fn force_push_task_attempt_branch(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    payload: Option<Json<PushTaskAttemptRequest>>,
+) -> Result<ResponseJson<ApiResponse<(), PushError>>, ApiError> {
+    let request = payload.map(|Json(body)| body).unwrap_or_default();
     let ws_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    check_push_auth(
+        &deployment,
+        &ws_path,
+        &task_attempt.branch,
+        request.remote.as_deref(),
+    )
+    .await?;
 
-    deployment
-        .git()
-        .push_to_github(&ws_path, &task_attempt.branch, true)?;
-    Ok(ResponseJson(ApiResponse::success(())))
+    push_attempt_branch(&deployment, &ws_path, &task_attempt, request, true)
 }
 
 pub async fn get_worktree_status(
@@ -676,13 +1404,42 @@ pub async fn get_worktree_status(
     })))
 }
 
+/// Raw `git status --porcelain=v2` for tools that need more than the structured status
+pub async fn get_worktree_status_porcelain(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<impl IntoResponse, ApiError> {
+    let ws_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+
+    let status = deployment.git().get_worktree_status_porcelain(&ws_path)?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        status,
+    ))
+}
+
 pub async fn commit_changes(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<CommitChangesRequest>,
-) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<(), CommitChangesError>>, ApiError> {
     let ws_path = ensure_worktree_path(&deployment, &task_attempt).await?;
 
+    // Committing mid-rebase or with unresolved conflicts produces a broken commit
+    if !request.force_when_dirty {
+        let rebase_in_progress = deployment.git().is_rebase_in_progress(&ws_path)?;
+        let conflicted_files = deployment.git().get_conflicted_files(&ws_path)?;
+        if rebase_in_progress || !conflicted_files.is_empty() {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                CommitChangesError::CommitBlockedByConflict {
+                    rebase_in_progress,
+                    conflicted_files,
+                },
+            )));
+        }
+    }
+
     // Stage files
     if request.files.is_empty() {
         // Stage all changes
@@ -694,6 +1451,10 @@ pub async fn commit_changes(
 
     // Commit
     deployment.git().commit_staged(&ws_path, &request.message)?;
+    deployment
+        .container()
+        .branch_status_cache()
+        .invalidate(task_attempt.id);
 
     Ok(ResponseJson(ApiResponse::success(())))
 }
@@ -703,6 +1464,7 @@ pub async fn commit_changes(
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum PushError {
     ForcePushRequired,
+    RemoteNotFound { remote: String },
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -713,9 +1475,26 @@ pub enum CreatePrError {
     GithubCliNotLoggedIn,
     GitCliNotLoggedIn,
     GitCliNotInstalled,
+    GlabCliNotInstalled,
+    GlabCliNotLoggedIn,
     TargetBranchNotFound { branch: String },
 }
 
+/// PR body generated from the attempt's conversation, or None when no coding
+/// agent has run yet.
+async fn conversation_pr_body(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+) -> Result<Option<String>, ApiError> {
+    let processes = coding_agent_processes(&deployment.db().pool, task_attempt.id).await?;
+    if processes.is_empty() {
+        return Ok(None);
+    }
+    let (entries, _) = load_conversation(deployment, &processes, false).await?;
+    let export = conversation_export::export_to_markdown(&entries, &task_attempt.executor, None);
+    Ok(Some(conversation_export::to_pr_body(&export)))
+}
+
 pub async fn create_github_pr(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
@@ -773,10 +1552,15 @@ pub async fn create_github_pr(
     }
 
     // Push the branch to GitHub first
-    if let Err(e) = deployment
-        .git()
-        .push_to_github(&workspace_path, &task_attempt.branch, false)
-    {
+    let push_result =
+        deployment
+            .git()
+            .push_to_github(&workspace_path, &task_attempt.branch, None, false);
+    deployment
+        .container()
+        .branch_status_cache()
+        .invalidate(task_attempt.id);
+    if let Err(e) = push_result {
         tracing::error!("Failed to push branch to GitHub: {}", e);
         match e {
             GitServiceError::GitCLI(GitCliError::AuthFailed(_)) => {
@@ -812,69 +1596,117 @@ pub async fn create_github_pr(
     } else {
         target_branch
     };
+    let body = match request.body {
+        Some(body) => Some(body),
+        None if request.body_from_conversation => {
+            conversation_pr_body(&deployment, &task_attempt).await?
+        }
+        None => None,
+    };
     // Create the PR using GitHub service
     let pr_request = CreatePrRequest {
         title: request.title.clone(),
-        body: request.body.clone(),
+        body,
         head_branch: task_attempt.branch.clone(),
         base_branch: norm_target_branch_name.clone(),
     };
-    // Use GitService to get the remote URL, then create GitHubRepoInfo
-    let repo_info = deployment
-        .git()
-        .get_github_repo_info(&project.git_repo_path)?;
-
-    // Use GitHubService to create the PR
-    let github_service = GitHubService::new()?;
-    match github_service.create_pr(&repo_info, &pr_request).await {
-        Ok(pr_info) => {
-            // Update the task attempt with PR information
-            if let Err(e) = Merge::create_pr(
-                pool,
-                task_attempt.id,
-                &norm_target_branch_name,
-                pr_info.number,
-                &pr_info.url,
-            )
-            .await
+    // The remote decides whether this opens a GitHub PR or a GitLab MR
+    let repo_info = deployment.git().get_repo_info(&project.git_repo_path)?;
+    let provider = repo_info.provider();
+    let pr_info = match &repo_info {
+        RepoInfo::GitHub(github_repo) => {
+            match GitHubService::new()?
+                .create_pr(github_repo, &pr_request)
+                .await
             {
-                tracing::error!("Failed to update task attempt PR status: {}", e);
-            }
-
-            // Auto-open PR in browser
-            if let Err(e) = utils::browser::open_browser(&pr_info.url).await {
-                tracing::warn!("Failed to open PR in browser: {}", e);
+                Ok(pr_info) => pr_info,
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to create GitHub PR for attempt {}: {}",
+                        task_attempt.id,
+                        e
+                    );
+                    return match &e {
+                        GitHubServiceError::GhCliNotInstalled(_) => Ok(ResponseJson(
+                            ApiResponse::error_with_data(CreatePrError::GithubCliNotInstalled),
+                        )),
+                        GitHubServiceError::AuthFailed(_) => Ok(ResponseJson(
+                            ApiResponse::error_with_data(CreatePrError::GithubCliNotLoggedIn),
+                        )),
+                        _ => Err(ApiError::GitHubService(e)),
+                    };
+                }
             }
-            deployment
-                .track_if_analytics_allowed(
-                    "github_pr_created",
-                    serde_json::json!({
-                        "task_id": task.id.to_string(),
-                        "project_id": project.id.to_string(),
-                        "attempt_id": task_attempt.id.to_string(),
-                    }),
-                )
-                .await;
-
-            Ok(ResponseJson(ApiResponse::success(pr_info.url)))
         }
-        Err(e) => {
-            tracing::error!(
-                "Failed to create GitHub PR for attempt {}: {}",
-                task_attempt.id,
-                e
-            );
-            match &e {
-                GitHubServiceError::GhCliNotInstalled(_) => Ok(ResponseJson(
-                    ApiResponse::error_with_data(CreatePrError::GithubCliNotInstalled),
-                )),
-                GitHubServiceError::AuthFailed(_) => Ok(ResponseJson(
-                    ApiResponse::error_with_data(CreatePrError::GithubCliNotLoggedIn),
-                )),
-                _ => Err(ApiError::GitHubService(e)),
+        RepoInfo::GitLab(gitlab_repo) => {
+            match GitLabService::new()?
+                .create_pr(gitlab_repo, &pr_request)
+                .await
+            {
+                Ok(pr_info) => pr_info,
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to create GitLab MR for attempt {}: {}",
+                        task_attempt.id,
+                        e
+                    );
+                    return match &e {
+                        GitLabServiceError::GlabCliNotInstalled(_) => Ok(ResponseJson(
+                            ApiResponse::error_with_data(CreatePrError::GlabCliNotInstalled),
+                        )),
+                        GitLabServiceError::AuthFailed(_) => Ok(ResponseJson(
+                            ApiResponse::error_with_data(CreatePrError::GlabCliNotLoggedIn),
+                        )),
+                        _ => Err(ApiError::GitLabService(e)),
+                    };
+                }
             }
         }
+    };
+
+    // Update the task attempt with PR information
+    if let Err(e) = Merge::create_pr(
+        pool,
+        task_attempt.id,
+        &norm_target_branch_name,
+        pr_info.number,
+        &pr_info.url,
+        provider,
+    )
+    .await
+    {
+        tracing::error!("Failed to update task attempt PR status: {}", e);
     }
+
+    // Auto-open PR in browser
+    if deployment.config().read().await.auto_open_pr_in_browser
+        && let Err(e) = utils::browser::open_browser(&pr_info.url).await
+    {
+        tracing::warn!("Failed to open PR in browser: {}", e);
+    }
+    let event_name = match provider {
+        GitProvider::GitHub => "github_pr_created",
+        GitProvider::GitLab => "gitlab_mr_created",
+    };
+    deployment
+        .track_if_analytics_allowed(
+            event_name,
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": project.id.to_string(),
+                "attempt_id": task_attempt.id.to_string(),
+            }),
+        )
+        .await;
+    deployment
+        .record_activity(CreateActivity::for_attempt(
+            ActivityKind::PrOpened,
+            &task,
+            task_attempt.id,
+        ))
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(pr_info.url)))
 }
 
 #[derive(serde::Deserialize, TS)]
@@ -945,128 +1777,11 @@ pub async fn open_task_attempt_in_editor(
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
-pub struct BranchStatus {
-    pub commits_behind: Option<usize>,
-    pub commits_ahead: Option<usize>,
-    pub has_uncommitted_changes: Option<bool>,
-    pub head_oid: Option<String>,
-    pub uncommitted_count: Option<usize>,
-    pub untracked_count: Option<usize>,
-    pub target_branch_name: String,
-    pub remote_commits_behind: Option<usize>,
-    pub remote_commits_ahead: Option<usize>,
-    pub merges: Vec<Merge>,
-    /// True if a `git rebase` is currently in progress in this worktree
-    pub is_rebase_in_progress: bool,
-    /// Current conflict operation if any
-    pub conflict_op: Option<ConflictOp>,
-    /// List of files currently in conflicted (unmerged) state
-    pub conflicted_files: Vec<String>,
-}
-
 pub async fn get_task_attempt_branch_status(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<BranchStatus>>, ApiError> {
-    let pool = &deployment.db().pool;
-
-    let task = task_attempt
-        .parent_task(pool)
-        .await?
-        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
-    let ctx = TaskAttempt::load_context(pool, task_attempt.id, task.id, task.project_id).await?;
-
-    // For orchestrator tasks, use container_ref directly (it's the main repo, not a worktree)
-    // This avoids unnecessary ensure_worktree_path calls on every poll
-    let wt_buf = if task_attempt.is_orchestrator {
-        task_attempt
-            .container_ref
-            .as_ref()
-            .map(PathBuf::from)
-            .ok_or_else(|| {
-                ApiError::TaskAttempt(TaskAttemptError::ValidationError(
-                    "Orchestrator attempt missing container_ref".to_string(),
-                ))
-            })?
-    } else {
-        ensure_worktree_path(&deployment, &task_attempt).await?
-    };
-    let wt = wt_buf.as_path();
-
-    let has_uncommitted_changes = deployment
-        .container()
-        .is_container_clean(&task_attempt)
-        .await
-        .ok()
-        .map(|is_clean| !is_clean);
-    let head_oid = deployment.git().get_head_info(wt).ok().map(|h| h.oid);
-    // Detect conflicts and operation in progress (best-effort)
-    let is_rebase_in_progress = deployment.git().is_rebase_in_progress(wt).unwrap_or(false);
-    let conflicted_files = deployment
-        .git()
-        .get_conflicted_files(wt)
-        .unwrap_or_default();
-    let conflict_op = if conflicted_files.is_empty() {
-        None
-    } else {
-        deployment.git().detect_conflict_op(wt).unwrap_or(None)
-    };
-    let (uncommitted_count, untracked_count) = match deployment.git().get_worktree_change_counts(wt)
-    {
-        Ok((a, b)) => (Some(a), Some(b)),
-        Err(_) => (None, None),
-    };
-
-    let target_branch_type = deployment
-        .git()
-        .find_branch_type(&ctx.project.git_repo_path, &task_attempt.target_branch)?;
-
-    let (commits_ahead, commits_behind) = match target_branch_type {
-        BranchType::Local => {
-            let (a, b) = deployment.git().get_branch_status(
-                &ctx.project.git_repo_path,
-                &task_attempt.branch,
-                &task_attempt.target_branch,
-            )?;
-            (Some(a), Some(b))
-        }
-        BranchType::Remote => {
-            let (remote_commits_ahead, remote_commits_behind) =
-                deployment.git().get_remote_branch_status(
-                    &ctx.project.git_repo_path,
-                    &task_attempt.branch,
-                    Some(&task_attempt.target_branch),
-                )?;
-            (Some(remote_commits_ahead), Some(remote_commits_behind))
-        }
-    };
-    // Fetch merges for this task attempt and add to branch status
-    let merges = Merge::find_by_task_attempt_id(pool, task_attempt.id).await?;
-
-    // Always check remote status to show if local commits are not pushed to origin
-    // This is used by the arrow-up indicator in the UI
-    let (remote_ahead, remote_behind) = deployment
-        .git()
-        .get_remote_branch_status(&ctx.project.git_repo_path, &task_attempt.branch, None)
-        .map(|(ahead, behind)| (Some(ahead), Some(behind)))
-        .unwrap_or((None, None));
-
-    let branch_status = BranchStatus {
-        commits_ahead,
-        commits_behind,
-        has_uncommitted_changes,
-        head_oid,
-        uncommitted_count,
-        untracked_count,
-        remote_commits_ahead: remote_ahead,
-        remote_commits_behind: remote_behind,
-        merges,
-        target_branch_name: task_attempt.target_branch,
-        is_rebase_in_progress,
-        conflict_op,
-        conflicted_files,
-    };
+    let branch_status = get_branch_status_for_attempt(&deployment, &task_attempt).await?;
     Ok(ResponseJson(ApiResponse::success(branch_status)))
 }
 
@@ -1076,6 +1791,20 @@ pub struct BatchBranchStatusRequest {
     pub attempt_ids: Vec<Uuid>,
 }
 
+/// Commits `branch` is ahead of and behind `base_branch`. A remote base branch is
+/// fetched first so the counts reflect the remote.
+fn branch_ahead_behind(
+    git: &GitService,
+    repo_path: &Path,
+    branch: &str,
+    base_branch: &str,
+) -> Result<(usize, usize), GitServiceError> {
+    match git.find_branch_type(repo_path, base_branch)? {
+        BranchType::Local => git.get_branch_status(repo_path, branch, base_branch),
+        BranchType::Remote => git.get_remote_branch_status(repo_path, branch, Some(base_branch)),
+    }
+}
+
 /// Helper function to get branch status for a single task attempt
 async fn get_branch_status_for_attempt(
     deployment: &DeploymentImpl,
@@ -1105,13 +1834,13 @@ async fn get_branch_status_for_attempt(
     };
     let wt = wt_buf.as_path();
 
+    let head_oid = deployment.git().get_head_info(wt).ok().map(|h| h.oid);
     let has_uncommitted_changes = deployment
         .container()
         .is_container_clean(task_attempt)
         .await
         .ok()
         .map(|is_clean| !is_clean);
-    let head_oid = deployment.git().get_head_info(wt).ok().map(|h| h.oid);
     let is_rebase_in_progress = deployment.git().is_rebase_in_progress(wt).unwrap_or(false);
     let conflicted_files = deployment
         .git()
@@ -1128,46 +1857,56 @@ async fn get_branch_status_for_attempt(
         Err(_) => (None, None),
     };
 
-    let target_branch_type = deployment
-        .git()
-        .find_branch_type(&ctx.project.git_repo_path, &task_attempt.target_branch)?;
-
-    let (commits_ahead, commits_behind) = match target_branch_type {
-        BranchType::Local => {
-            let (a, b) = deployment.git().get_branch_status(
+    // The ahead/behind counts don't change until HEAD moves, unless the cache was
+    // invalidated
+    let cache = deployment.container().branch_status_cache();
+    let cached = head_oid
+        .as_deref()
+        .and_then(|oid| cache.get(task_attempt.id, oid, &task_attempt.target_branch));
+    let graph = match cached {
+        Some(graph) => graph,
+        None => {
+            let (commits_ahead, commits_behind) = branch_ahead_behind(
+                deployment.git(),
                 &ctx.project.git_repo_path,
                 &task_attempt.branch,
                 &task_attempt.target_branch,
-            )?;
-            (Some(a), Some(b))
-        }
-        BranchType::Remote => {
-            let (remote_commits_ahead, remote_commits_behind) =
-                deployment.git().get_remote_branch_status(
-                    &ctx.project.git_repo_path,
-                    &task_attempt.branch,
-                    Some(&task_attempt.target_branch),
-                )?;
-            (Some(remote_commits_ahead), Some(remote_commits_behind))
+            )
+            .map(|(ahead, behind)| (Some(ahead), Some(behind)))?;
+            let (remote_commits_ahead, remote_commits_behind) = deployment
+                .git()
+                .get_remote_branch_status(&ctx.project.git_repo_path, &task_attempt.branch, None)
+                .map(|(ahead, behind)| (Some(ahead), Some(behind)))
+                .unwrap_or((None, None));
+            let graph = CommitGraphStatus {
+                commits_ahead,
+                commits_behind,
+                remote_commits_ahead,
+                remote_commits_behind,
+            };
+            if let Some(oid) = &head_oid {
+                cache.insert(
+                    task_attempt.id,
+                    ctx.project.id,
+                    oid.clone(),
+                    task_attempt.target_branch.clone(),
+                    graph.clone(),
+                );
+            }
+            graph
         }
     };
     let merges = Merge::find_by_task_attempt_id(pool, task_attempt.id).await?;
 
-    let (remote_ahead, remote_behind) = deployment
-        .git()
-        .get_remote_branch_status(&ctx.project.git_repo_path, &task_attempt.branch, None)
-        .map(|(ahead, behind)| (Some(ahead), Some(behind)))
-        .unwrap_or((None, None));
-
     Ok(BranchStatus {
-        commits_ahead,
-        commits_behind,
+        commits_ahead: graph.commits_ahead,
+        commits_behind: graph.commits_behind,
         has_uncommitted_changes,
         head_oid,
         uncommitted_count,
         untracked_count,
-        remote_commits_ahead: remote_ahead,
-        remote_commits_behind: remote_behind,
+        remote_commits_ahead: graph.remote_commits_ahead,
+        remote_commits_behind: graph.remote_commits_behind,
         merges,
         target_branch_name: task_attempt.target_branch.clone(),
         is_rebase_in_progress,
@@ -1215,15 +1954,95 @@ pub async fn get_batch_branch_status(
     Ok(ResponseJson(ApiResponse::success(results)))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct AheadBehindRequest {
+    /// Branches to compare the attempt's branch against, e.g. `main` and `release/1.x`
+    pub branches: Vec<String>,
+}
+
+/// How far the attempt's branch is from one base branch
+#[derive(Debug, Serialize, TS)]
+pub struct BranchAheadBehind {
+    pub branch: String,
+    pub commits_ahead: Option<usize>,
+    pub commits_behind: Option<usize>,
+    /// Why the comparison failed, e.g. the branch does not exist
+    pub error: Option<String>,
+}
+
+/// Ahead/behind counts of the attempt's branch against each requested branch, in
+/// request order. A branch that can't be compared reports an error without failing
+/// the others.
+pub async fn get_ahead_behind(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<AheadBehindRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<BranchAheadBehind>>>, ApiError> {
+    if payload.branches.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one branch is required".to_string(),
+        ));
+    }
+    let pool = &deployment.db().pool;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let project = Project::find_by_id(pool, task.project_id)
+        .await?
+        .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
+
+    let mut seen = std::collections::HashSet::new();
+    let results = payload
+        .branches
+        .into_iter()
+        .filter(|branch| seen.insert(branch.clone()))
+        .map(|branch| {
+            match branch_ahead_behind(
+                deployment.git(),
+                &project.git_repo_path,
+                &task_attempt.branch,
+                &branch,
+            ) {
+                Ok((ahead, behind)) => BranchAheadBehind {
+                    branch,
+                    commits_ahead: Some(ahead),
+                    commits_behind: Some(behind),
+                    error: None,
+                },
+                Err(e) => BranchAheadBehind {
+                    branch,
+                    commits_ahead: None,
+                    commits_behind: None,
+                    error: Some(e.to_string()),
+                },
+            }
+        })
+        .collect();
+
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
 #[derive(serde::Deserialize, Debug, TS)]
 pub struct ChangeTargetBranchRequest {
     pub new_target_branch: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ChangeTargetBranchQuery {
+    /// Report status and conflicts against the new target without saving it
+    #[serde(default)]
+    pub preview: bool,
+}
+
 #[derive(serde::Serialize, Debug, TS)]
 pub struct ChangeTargetBranchResponse {
     pub new_target_branch: String,
     pub status: (usize, usize),
+    /// Files that would conflict when rebasing onto the new target
+    pub conflicting_files: Vec<String>,
+    /// True when nothing was saved
+    pub preview: bool,
 }
 
 #[derive(serde::Deserialize, Debug, TS)]
@@ -1240,6 +2059,7 @@ pub struct RenameBranchResponse {
 pub async fn change_target_branch(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ChangeTargetBranchQuery>,
     Json(payload): Json<ChangeTargetBranchRequest>,
 ) -> Result<ResponseJson<ApiResponse<ChangeTargetBranchResponse>>, ApiError> {
     // Extract new base branch from request body if provided
@@ -1251,33 +2071,42 @@ pub async fn change_target_branch(
     let project = Project::find_by_id(&deployment.db().pool, task.project_id)
         .await?
         .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
-    match deployment
+    if !deployment
         .git()
         .check_branch_exists(&project.git_repo_path, &new_target_branch)?
     {
-        true => {
-            TaskAttempt::update_target_branch(
-                &deployment.db().pool,
-                task_attempt.id,
-                &new_target_branch,
+        return Ok(ResponseJson(ApiResponse::error(
+            format!(
+                "Branch '{}' does not exist in the repository",
+                new_target_branch
             )
-            .await?;
-        }
-        false => {
-            return Ok(ResponseJson(ApiResponse::error(
-                format!(
-                    "Branch '{}' does not exist in the repository",
-                    new_target_branch
-                )
-                .as_str(),
-            )));
-        }
+            .as_str(),
+        )));
     }
     let status = deployment.git().get_branch_status(
         &project.git_repo_path,
         &task_attempt.branch,
         &new_target_branch,
     )?;
+    let conflicting_files = deployment.git().conflicting_paths(
+        &project.git_repo_path,
+        &task_attempt.branch,
+        &new_target_branch,
+    )?;
+
+    if query.preview {
+        return Ok(ResponseJson(ApiResponse::success(
+            ChangeTargetBranchResponse {
+                new_target_branch,
+                status,
+                conflicting_files,
+                preview: true,
+            },
+        )));
+    }
+
+    TaskAttempt::update_target_branch(&deployment.db().pool, task_attempt.id, &new_target_branch)
+        .await?;
 
     deployment
         .track_if_analytics_allowed(
@@ -1292,6 +2121,8 @@ pub async fn change_target_branch(
         ChangeTargetBranchResponse {
             new_target_branch,
             status,
+            conflicting_files,
+            preview: false,
         },
     )))
 }
@@ -1397,6 +2228,64 @@ pub async fn rename_branch(
     })))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct ResetToCommitRequest {
+    pub oid: String,
+    pub mode: ResetMode,
+    /// Allow a hard reset to discard uncommitted changes
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ResetToCommitResponse {
+    pub head_oid: String,
+}
+
+#[axum::debug_handler]
+pub async fn reset_to_commit(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ResetToCommitRequest>,
+) -> Result<ResponseJson<ApiResponse<ResetToCommitResponse>>, ApiError> {
+    let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let worktree_path = worktree_path_buf.as_path();
+
+    if deployment.git().is_rebase_in_progress(worktree_path)? {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Cannot reset while a rebase is in progress. Please complete or abort the rebase first.",
+        )));
+    }
+
+    // Don't move the branch out from under a running agent
+    deployment.container().try_stop(&task_attempt).await;
+
+    let head_oid = deployment.git().reset_branch_to_commit(
+        worktree_path,
+        payload.oid.trim(),
+        payload.mode,
+        payload.force,
+    )?;
+    deployment
+        .container()
+        .branch_status_cache()
+        .invalidate(task_attempt.id);
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_reset_to_commit",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "mode": payload.mode,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(ResetToCommitResponse {
+        head_oid,
+    })))
+}
+
 #[axum::debug_handler]
 pub async fn rebase_task_attempt(
     Extension(task_attempt): Extension<TaskAttempt>,
@@ -1450,18 +2339,34 @@ pub async fn rebase_task_attempt(
         &old_base_branch,
         &task_attempt.branch.clone(),
     );
+    deployment
+        .container()
+        .branch_status_cache()
+        .invalidate(task_attempt.id);
     if let Err(e) = result {
         use services::services::git::GitServiceError;
         return match e {
-            GitServiceError::MergeConflicts(msg) => Ok(ResponseJson(ApiResponse::<
-                (),
-                GitOperationError,
-            >::error_with_data(
-                GitOperationError::MergeConflicts {
-                    message: msg,
-                    op: ConflictOp::Rebase,
-                },
-            ))),
+            GitServiceError::MergeConflicts(msg) => {
+                let notify_cfg = deployment.config().read().await.notifications.clone();
+                // Fire and forget so the response isn't held up by the notifier
+                tokio::spawn(async move {
+                    NotificationService::notify_merge_conflict(
+                        notify_cfg,
+                        &ctx.task,
+                        &ctx.task_attempt,
+                        &ConflictOp::Rebase,
+                    )
+                    .await;
+                });
+                Ok(ResponseJson(
+                    ApiResponse::<(), GitOperationError>::error_with_data(
+                        GitOperationError::MergeConflicts {
+                            message: msg,
+                            op: ConflictOp::Rebase,
+                        },
+                    ),
+                ))
+            }
             GitServiceError::RebaseInProgress => Ok(ResponseJson(ApiResponse::<
                 (),
                 GitOperationError,
@@ -1486,6 +2391,33 @@ pub async fn rebase_task_attempt(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Abort an in-progress rebase, conflicted or not, returning the branch to its
+/// pre-rebase state.
+#[axum::debug_handler]
+pub async fn abort_rebase_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+
+    deployment.git().abort_rebase(&worktree_path)?;
+    deployment
+        .container()
+        .branch_status_cache()
+        .invalidate(task_attempt.id);
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_rebase_aborted",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 #[axum::debug_handler]
 pub async fn abort_conflicts_task_attempt(
     Extension(task_attempt): Extension<TaskAttempt>,
@@ -1496,89 +2428,311 @@ pub async fn abort_conflicts_task_attempt(
     let worktree_path = worktree_path_buf.as_path();
 
     deployment.git().abort_conflicts(worktree_path)?;
+    deployment
+        .container()
+        .branch_status_cache()
+        .invalidate(task_attempt.id);
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ResolveConflictRequest {
+    pub file_path: String,
+    pub strategy: ConflictSide,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ResolveConflictResponse {
+    /// Files still conflicted after this one was resolved
+    pub conflicted_files: Vec<String>,
+}
+
+/// Resolve one conflicted file by keeping the attempt's (`ours`) or the incoming
+/// (`theirs`) version and staging it, without leaving the rebase or merge.
+#[axum::debug_handler]
+pub async fn resolve_conflict_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ResolveConflictRequest>,
+) -> Result<ResponseJson<ApiResponse<ResolveConflictResponse>>, ApiError> {
+    let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let conflicted_files = deployment.git().resolve_conflict(
+        &worktree_path_buf,
+        &payload.file_path,
+        payload.strategy,
+    )?;
+    deployment
+        .container()
+        .branch_status_cache()
+        .invalidate(task_attempt.id);
+
+    Ok(ResponseJson(ApiResponse::success(
+        ResolveConflictResponse { conflicted_files },
+    )))
+}
+
+#[axum::debug_handler]
+pub async fn start_dev_server(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    // Get parent task
+    let task = task_attempt
+        .parent_task(&deployment.db().pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    // Get parent project
+    let project = task
+        .parent_project(&deployment.db().pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    // Stop any existing dev servers for this project
+    let existing_dev_servers =
+        match ExecutionProcess::find_running_dev_servers_by_project(pool, project.id).await {
+            Ok(servers) => servers,
+            Err(e) => {
+                tracing::error!(
+                    "Failed to find running dev servers for project {}: {}",
+                    project.id,
+                    e
+                );
+                return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+                    e.to_string(),
+                )));
+            }
+        };
+
+    for dev_server in existing_dev_servers {
+        tracing::info!(
+            "Stopping existing dev server {} for project {}",
+            dev_server.id,
+            project.id
+        );
+
+        if let Err(e) = deployment
+            .container()
+            .stop_execution(&dev_server, ExecutionProcessStatus::Killed)
+            .await
+        {
+            tracing::error!("Failed to stop dev server {}: {}", dev_server.id, e);
+        }
+    }
+
+    let script_language = project.effective_script_language();
+    if let Some(dev_server) = project.dev_script {
+        let executor_action = ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script: dev_server,
+                language: script_language,
+                context: ScriptContext::DevServer,
+            }),
+            None,
+        );
+
+        deployment
+            .container()
+            .start_execution(
+                &task_attempt,
+                &executor_action,
+                &ExecutionProcessRunReason::DevServer,
+            )
+            .await?
+    } else {
+        return Ok(ResponseJson(ApiResponse::error(
+            "No dev server script configured for this project",
+        )));
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "dev_server_started",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": project.id.to_string(),
+                "attempt_id": task_attempt.id.to_string(),
+            }),
+        )
+        .await;
+    deployment
+        .record_activity(CreateActivity::for_attempt(
+            ActivityKind::DevServerStarted,
+            &task,
+            task_attempt.id,
+        ))
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Reject running a script on an attempt that already has a running process.
+/// Dev servers may keep running; anything else would race with the script.
+async fn ensure_no_running_script_conflict(
+    pool: &SqlitePool,
+    task_attempt_id: Uuid,
+) -> Result<(), ApiError> {
+    let busy = ExecutionProcess::find_by_task_attempt_id(pool, task_attempt_id, false)
+        .await?
+        .into_iter()
+        .any(|process| {
+            process.status == ExecutionProcessStatus::Running
+                && process.run_reason != ExecutionProcessRunReason::DevServer
+        });
+    if busy {
+        return Err(ApiError::Conflict(
+            "Attempt has a running execution process. Wait for it to finish or stop it first."
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub async fn run_setup_script(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    ensure_task_unblocked(&deployment, task.id).await?;
+
+    let script_language = project.effective_script_language();
+    let Some(setup_script) = project.setup_script else {
+        return Ok(ResponseJson(ApiResponse::error(
+            "No setup script configured for this project",
+        )));
+    };
+
+    ensure_no_running_script_conflict(pool, task_attempt.id).await?;
+
+    deployment
+        .container()
+        .ensure_container_exists(&task_attempt)
+        .await?;
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let execution_process = deployment
+        .container()
+        .start_execution(
+            &task_attempt,
+            &setup_script_action(setup_script, script_language),
+            &ExecutionProcessRunReason::SetupScript,
+        )
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "setup_script_rerun",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": project.id.to_string(),
+                "attempt_id": task_attempt.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct DevServerInfo {
+    pub execution_process_id: Uuid,
+    pub status: ExecutionProcessStatus,
+    /// Detected from the dev server's output; null until it prints one
+    pub url: Option<String>,
+    #[ts(type = "number | null")]
+    pub port: Option<i64>,
+}
 
-    Ok(ResponseJson(ApiResponse::success(())))
+/// The attempt's most recent dev server and the URL it is serving on, or null if
+/// no dev server has been started for it.
+pub async fn get_dev_server(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<DevServerInfo>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some(process) = ExecutionProcess::find_latest_by_task_attempt_and_run_reason(
+        pool,
+        task_attempt.id,
+        &ExecutionProcessRunReason::DevServer,
+    )
+    .await?
+    else {
+        return Ok(ResponseJson(ApiResponse::success(None)));
+    };
+    let detected = ExecutionProcess::find_dev_server_url(pool, process.id).await?;
+
+    Ok(ResponseJson(ApiResponse::success(Some(DevServerInfo {
+        execution_process_id: process.id,
+        status: process.status,
+        url: detected.as_ref().map(|d| d.url.clone()),
+        port: detected.and_then(|d| d.port),
+    }))))
 }
 
-#[axum::debug_handler]
-pub async fn start_dev_server(
+/// Run the attempt's cleanup script on its own, e.g. to retry one that failed
+/// after the coding agent finished
+pub async fn run_cleanup_script(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
-) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
     let pool = &deployment.db().pool;
 
-    // Get parent task
     let task = task_attempt
-        .parent_task(&deployment.db().pool)
+        .parent_task(pool)
         .await?
         .ok_or(SqlxError::RowNotFound)?;
-
-    // Get parent project
     let project = task
-        .parent_project(&deployment.db().pool)
+        .parent_project(pool)
         .await?
         .ok_or(SqlxError::RowNotFound)?;
+    ensure_task_unblocked(&deployment, task.id).await?;
+
+    let script_language = project.effective_script_language();
+    let Some(cleanup_action) = cleanup_script_action(
+        task_attempt.effective_cleanup_script(project.cleanup_script),
+        script_language,
+    ) else {
+        return Err(ApiError::BadRequest(
+            "No cleanup script configured for this attempt or its project".to_string(),
+        ));
+    };
 
-    // Stop any existing dev servers for this project
-    let existing_dev_servers =
-        match ExecutionProcess::find_running_dev_servers_by_project(pool, project.id).await {
-            Ok(servers) => servers,
-            Err(e) => {
-                tracing::error!(
-                    "Failed to find running dev servers for project {}: {}",
-                    project.id,
-                    e
-                );
-                return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
-                    e.to_string(),
-                )));
-            }
-        };
-
-    for dev_server in existing_dev_servers {
-        tracing::info!(
-            "Stopping existing dev server {} for project {}",
-            dev_server.id,
-            project.id
-        );
-
-        if let Err(e) = deployment
-            .container()
-            .stop_execution(&dev_server, ExecutionProcessStatus::Killed)
-            .await
-        {
-            tracing::error!("Failed to stop dev server {}: {}", dev_server.id, e);
-        }
-    }
+    ensure_no_running_script_conflict(pool, task_attempt.id).await?;
 
-    if let Some(dev_server) = project.dev_script {
-        // TODO: Derive script language from system config
-        let executor_action = ExecutorAction::new(
-            ExecutorActionType::ScriptRequest(ScriptRequest {
-                script: dev_server,
-                language: ScriptRequestLanguage::Bash,
-                context: ScriptContext::DevServer,
-            }),
-            None,
-        );
+    deployment
+        .container()
+        .ensure_container_exists(&task_attempt)
+        .await?;
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
 
-        deployment
-            .container()
-            .start_execution(
-                &task_attempt,
-                &executor_action,
-                &ExecutionProcessRunReason::DevServer,
-            )
-            .await?
-    } else {
-        return Ok(ResponseJson(ApiResponse::error(
-            "No dev server script configured for this project",
-        )));
-    };
+    let execution_process = deployment
+        .container()
+        .start_execution(
+            &task_attempt,
+            &cleanup_action,
+            &ExecutionProcessRunReason::CleanupScript,
+        )
+        .await?;
 
     deployment
         .track_if_analytics_allowed(
-            "dev_server_started",
+            "cleanup_script_rerun",
             serde_json::json!({
                 "task_id": task.id.to_string(),
                 "project_id": project.id.to_string(),
@@ -1587,7 +2741,7 @@ pub async fn start_dev_server(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(())))
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
 
 pub async fn get_task_attempt_children(
@@ -1620,6 +2774,35 @@ pub async fn get_task_attempt_children(
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AttemptLogsSseQuery {
+    /// Stream a specific execution instead of the attempt's latest one
+    pub execution_process_id: Option<Uuid>,
+    /// Raw stdout/stderr instead of normalized entries
+    #[serde(default)]
+    pub raw: bool,
+}
+
+/// SSE alternative to the execution process log WebSockets, scoped to an attempt.
+pub async fn stream_task_attempt_logs_sse(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<AttemptLogsSseQuery>,
+) -> Result<LogSse, ApiError> {
+    let pool = &deployment.db().pool;
+    let process = match query.execution_process_id {
+        Some(id) => ExecutionProcess::find_by_id(pool, id)
+            .await?
+            .filter(|p| p.task_attempt_id == task_attempt.id),
+        None => ExecutionProcess::find_latest_by_task_attempt(pool, task_attempt.id).await?,
+    }
+    .ok_or(ApiError::ExecutionProcess(
+        ExecutionProcessError::ExecutionProcessNotFound,
+    ))?;
+
+    log_sse_for_execution(&deployment, process.id, query.raw).await
+}
+
 pub async fn stop_task_attempt_execution(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
@@ -1638,6 +2821,79 @@ pub async fn stop_task_attempt_execution(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Pause or resume PR monitor polling for the attempt's latest PR
+async fn set_pr_monitoring_paused(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+    paused: bool,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some(Merge::Pr(pr_merge)) =
+        Merge::find_latest_by_task_attempt_id(pool, task_attempt.id).await?
+    else {
+        return Ok(ResponseJson(ApiResponse::error(
+            "No pull request is attached to this task attempt",
+        )));
+    };
+
+    Merge::set_monitoring_paused(pool, pr_merge.id, paused).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "pr_monitoring_paused_changed",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "paused": paused,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Stop the PR monitor from polling this attempt's PR, e.g. while it is a draft
+pub async fn pause_pr_monitoring(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    set_pr_monitoring_paused(&deployment, &task_attempt, true).await
+}
+
+/// Resume polling; the PR is included again from the monitor's next cycle
+pub async fn resume_pr_monitoring(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    set_pr_monitoring_paused(&deployment, &task_attempt, false).await
+}
+
+async fn set_worktree_pinned(
+    deployment: &DeploymentImpl,
+    mut task_attempt: TaskAttempt,
+    pinned: bool,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    TaskAttempt::set_pin_worktree(&deployment.db().pool, task_attempt.id, pinned).await?;
+    task_attempt.pin_worktree = pinned;
+    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+}
+
+/// Keep this attempt's worktree past expiry cleanup, e.g. to run the dev server
+/// after merging. Pinned worktrees stay on disk until unpinned or removed by hand.
+pub async fn pin_task_attempt_worktree(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    set_worktree_pinned(&deployment, task_attempt, true).await
+}
+
+/// Make the worktree eligible for expiry cleanup again
+pub async fn unpin_task_attempt_worktree(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    set_worktree_pinned(&deployment, task_attempt, false).await
+}
+
 #[derive(Debug, Serialize, TS)]
 pub struct AttachPrResponse {
     pub pr_attached: bool,
@@ -1672,15 +2928,21 @@ pub async fn attach_existing_pr(
         return Err(ApiError::Project(ProjectError::ProjectNotFound));
     };
 
-    let github_service = GitHubService::new()?;
-    let repo_info = deployment
-        .git()
-        .get_github_repo_info(&project.git_repo_path)?;
+    let repo_info = deployment.git().get_repo_info(&project.git_repo_path)?;
 
     // List all PRs for branch (open, closed, and merged)
-    let prs = github_service
-        .list_all_prs_for_branch(&repo_info, &task_attempt.branch)
-        .await?;
+    let prs = match &repo_info {
+        RepoInfo::GitHub(github_repo) => {
+            GitHubService::new()?
+                .list_all_prs_for_branch(github_repo, &task_attempt.branch)
+                .await?
+        }
+        RepoInfo::GitLab(gitlab_repo) => {
+            GitLabService::new()?
+                .list_all_prs_for_branch(gitlab_repo, &task_attempt.branch)
+                .await?
+        }
+    };
 
     // Take the first PR (prefer open, but also accept merged/closed)
     if let Some(pr_info) = prs.into_iter().next() {
@@ -1691,8 +2953,13 @@ pub async fn attach_existing_pr(
             &task_attempt.target_branch,
             pr_info.number,
             &pr_info.url,
+            repo_info.provider(),
         )
         .await?;
+        deployment
+            .container()
+            .branch_status_cache()
+            .invalidate(task_attempt.id);
 
         // Update status if not open
         if !matches!(pr_info.status, MergeStatus::Open) {
@@ -1707,23 +2974,14 @@ pub async fn attach_existing_pr(
 
         // If PR is merged, mark task as done
         if matches!(pr_info.status, MergeStatus::Merged) {
-            Task::update_status(pool, task.id, TaskStatus::Done).await?;
-
-            // Try broadcast update to other users in organization
-            if let Ok(publisher) = deployment.share_publisher() {
-                if let Err(err) = publisher.update_shared_task_by_id(task.id).await {
-                    tracing::warn!(
-                        ?err,
-                        "Failed to propagate shared task update for {}",
-                        task.id
-                    );
-                }
-            } else {
-                tracing::debug!(
-                    "Share publisher unavailable; skipping remote update for {}",
-                    task.id
-                );
-            }
+            // Broadcast the update to other users in the organization
+            update_task_status_and_share(
+                pool,
+                deployment.share_publisher().ok(),
+                task.id,
+                TaskStatus::Done,
+            )
+            .await?;
         }
 
         Ok(ResponseJson(ApiResponse::success(AttachPrResponse {
@@ -1779,38 +3037,71 @@ pub async fn gh_cli_setup_handler(
     }
 }
 
-/// Export the conversation history from a task attempt as markdown.
-/// This is useful for passing context to a different agent.
-#[axum::debug_handler]
-pub async fn export_conversation(
-    Extension(task_attempt): Extension<TaskAttempt>,
-    State(deployment): State<DeploymentImpl>,
-) -> Result<ResponseJson<ApiResponse<ExportResult>>, ApiError> {
-    let pool = &deployment.db().pool;
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum ConversationExportFormat {
+    #[default]
+    Markdown,
+    Json,
+}
 
-    // Get all non-dropped execution processes for this attempt that are CodingAgent type
-    let processes = ExecutionProcess::find_by_task_attempt_id(pool, task_attempt.id, false)
-        .await?
-        .into_iter()
-        .filter(|p| matches!(p.run_reason, ExecutionProcessRunReason::CodingAgent))
-        .collect::<Vec<_>>();
+#[derive(Debug, Deserialize)]
+pub struct ExportConversationQuery {
+    #[serde(default)]
+    pub format: ConversationExportFormat,
+    /// JSON only: keep just the most recent entries
+    pub max_entries: Option<usize>,
+    /// Markdown only: append the stdout/stderr tail of each run under "Raw logs"
+    #[serde(default)]
+    pub include_raw_logs: bool,
+    /// Size of the stdout/stderr tail kept per run, in KB; defaults to 8
+    pub raw_log_tail_kb: Option<usize>,
+    /// Markdown only: length limit for the conversation, in characters; defaults
+    /// to 50,000 and is capped at 2,000,000
+    pub max_chars: Option<usize>,
+}
 
-    if processes.is_empty() {
-        return Ok(ResponseJson(ApiResponse::success(ExportResult {
-            markdown: "No conversation history available.".to_string(),
-            message_count: 0,
-            truncated: false,
-        })));
-    }
+#[derive(Debug, Serialize, TS)]
+#[serde(untagged)]
+pub enum ConversationExport {
+    Markdown(ExportResult),
+    Json(JsonExportResult),
+}
 
-    // Collect all normalized entries from all processes
+/// All non-dropped coding agent execution processes of the attempt
+async fn coding_agent_processes(
+    pool: &SqlitePool,
+    task_attempt_id: Uuid,
+) -> Result<Vec<ExecutionProcess>, ApiError> {
+    Ok(
+        ExecutionProcess::find_by_task_attempt_id(pool, task_attempt_id, false)
+            .await?
+            .into_iter()
+            .filter(|p| matches!(p.run_reason, ExecutionProcessRunReason::CodingAgent))
+            .collect(),
+    )
+}
+
+/// Normalized conversation entries of the given processes, plus their raw
+/// stdout/stderr when `include_raw_logs` is set.
+async fn load_conversation(
+    deployment: &DeploymentImpl,
+    processes: &[ExecutionProcess],
+    include_raw_logs: bool,
+) -> Result<(Vec<NormalizedEntry>, Vec<RawProcessLogs>), ApiError> {
     let mut all_entries = Vec::new();
+    let mut raw_logs = Vec::new();
 
-    for process in &processes {
-        // Load logs for this process
-        let log_records = ExecutionProcessLogs::find_by_execution_id(pool, process.id).await?;
+    for process in processes {
+        // Only raw output is persisted, so finished processes are normalized again
+        let patches = deployment.container().normalized_log_patches(process).await;
+        all_entries.extend(latest_normalized_entries(&patches));
 
-        // Parse the JSONL logs
+        if !include_raw_logs {
+            continue;
+        }
+        let log_records =
+            ExecutionProcessLogs::find_by_execution_id(&deployment.db().pool, process.id).await?;
         let messages = match ExecutionProcessLogs::parse_logs(&log_records) {
             Ok(msgs) => msgs,
             Err(e) => {
@@ -1818,30 +3109,94 @@ pub async fn export_conversation(
                 continue;
             }
         };
-
-        // Extract NormalizedEntry from JsonPatch messages
+        let mut raw = RawProcessLogs {
+            process_id: process.id,
+            stdout: String::new(),
+            stderr: String::new(),
+        };
         for msg in messages {
-            if let LogMsg::JsonPatch(patch) = msg {
-                if let Some((_idx, entry)) = extract_normalized_entry_from_patch(&patch) {
-                    all_entries.push(entry);
-                }
+            match msg {
+                LogMsg::Stdout(out) => raw.stdout.push_str(&out),
+                LogMsg::Stderr(err) => raw.stderr.push_str(&err),
+                _ => {}
             }
         }
+        raw_logs.push(raw);
+    }
+
+    Ok((all_entries, raw_logs))
+}
+
+/// Export the conversation history from a task attempt as markdown, or as
+/// structured JSON with `?format=json`.
+/// This is useful for passing context to a different agent.
+#[axum::debug_handler]
+pub async fn export_conversation(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ExportConversationQuery>,
+) -> Result<ResponseJson<ApiResponse<ConversationExport>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let processes = coding_agent_processes(pool, task_attempt.id).await?;
+
+    if processes.is_empty() && matches!(query.format, ConversationExportFormat::Markdown) {
+        return Ok(ResponseJson(ApiResponse::success(
+            ConversationExport::Markdown(ExportResult {
+                markdown: "No conversation history available.".to_string(),
+                message_count: 0,
+                truncated: false,
+                max_chars: conversation_export::effective_max_chars(query.max_chars),
+            }),
+        )));
     }
 
+    // Collect all normalized entries from all processes
+    let (all_entries, raw_logs) =
+        load_conversation(&deployment, &processes, query.include_raw_logs).await?;
+
     // Get the executor name for the header
     let executor_name = task_attempt.executor.to_string();
 
-    // Export to markdown
-    let result = conversation_export::export_to_markdown(&all_entries, &executor_name);
+    let result = match query.format {
+        ConversationExportFormat::Markdown => {
+            let mut result = conversation_export::export_to_markdown(
+                &all_entries,
+                &executor_name,
+                query.max_chars,
+            );
+            if query.include_raw_logs {
+                let tail_bytes = query
+                    .raw_log_tail_kb
+                    .map(|kb| kb.saturating_mul(1024))
+                    .unwrap_or(conversation_export::DEFAULT_RAW_LOG_TAIL_BYTES);
+                conversation_export::append_raw_logs(&mut result, &raw_logs, tail_bytes);
+            }
+            ConversationExport::Markdown(result)
+        }
+        ConversationExportFormat::Json => {
+            ConversationExport::Json(conversation_export::export_to_json(
+                &all_entries,
+                &executor_name,
+                task_attempt.id,
+                query.max_entries,
+            ))
+        }
+    };
+    let (message_count, truncated) = match &result {
+        ConversationExport::Markdown(r) => (r.message_count, r.truncated),
+        ConversationExport::Json(r) => (r.message_count, r.truncated),
+    };
 
     deployment
         .track_if_analytics_allowed(
             "conversation_exported",
             serde_json::json!({
                 "attempt_id": task_attempt.id.to_string(),
-                "message_count": result.message_count,
-                "truncated": result.truncated,
+                "format": query.format,
+                "message_count": message_count,
+                "truncated": truncated,
+                "include_raw_logs": query.include_raw_logs,
             }),
         )
         .await;
@@ -1849,6 +3204,44 @@ pub async fn export_conversation(
     Ok(ResponseJson(ApiResponse::success(result)))
 }
 
+/// List the MCP tool invocations the agent made across this attempt's coding agent runs.
+pub async fn get_mcp_calls(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<McpToolCall>>>, ApiError> {
+    let processes = coding_agent_processes(&deployment.db().pool, task_attempt.id).await?;
+
+    let mut calls = Vec::new();
+    for process in processes {
+        let patches = deployment
+            .container()
+            .normalized_log_patches(&process)
+            .await;
+        calls.extend(mcp_calls::extract_mcp_calls_from_patches(&patches));
+    }
+
+    Ok(ResponseJson(ApiResponse::success(calls)))
+}
+
+/// Total tokens consumed across this attempt's coding agent runs.
+pub async fn get_usage(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TokenUsage>>, ApiError> {
+    let processes = coding_agent_processes(&deployment.db().pool, task_attempt.id).await?;
+
+    let mut total = TokenUsage::default();
+    for process in processes {
+        let patches = deployment
+            .container()
+            .normalized_log_patches(&process)
+            .await;
+        total += usage::usage_from_patches(&patches);
+    }
+
+    Ok(ResponseJson(ApiResponse::success(total)))
+}
+
 #[derive(Debug, Serialize, TS)]
 pub struct GenerateCommitMessageResponse {
     pub message: String,
@@ -1914,32 +3307,112 @@ pub async fn generate_commit_message(
     )))
 }
 
+#[derive(Debug, Serialize, TS)]
+pub struct CommitMessagePreviewResponse {
+    /// `None` when no execution in this attempt would be auto-committed
+    pub message: Option<String>,
+    pub execution_process_id: Option<Uuid>,
+}
+
+/// Preview the message auto-commit would use for the latest coding agent or
+/// cleanup script run of this attempt.
+pub async fn preview_commit_message(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<CommitMessagePreviewResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let latest = match ExecutionProcess::find_latest_by_task_attempt(pool, task_attempt.id).await? {
+        Some(process)
+            if matches!(
+                process.run_reason,
+                ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::CleanupScript
+            ) =>
+        {
+            Some(process)
+        }
+        _ => {
+            ExecutionProcess::find_latest_by_task_attempt_and_run_reason(
+                pool,
+                task_attempt.id,
+                &ExecutionProcessRunReason::CodingAgent,
+            )
+            .await?
+        }
+    };
+
+    let Some(process) = latest else {
+        return Ok(ResponseJson(ApiResponse::success(
+            CommitMessagePreviewResponse {
+                message: None,
+                execution_process_id: None,
+            },
+        )));
+    };
+
+    let ctx = ExecutionProcess::load_context(pool, process.id).await?;
+    let message = deployment.container().auto_commit_message(&ctx).await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        CommitMessagePreviewResponse {
+            message,
+            execution_process_id: Some(process.id),
+        },
+    )))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_attempt_id_router = Router::new()
         .route("/", get(get_task_attempt))
         .route("/follow-up", post(follow_up))
         .route("/run-agent-setup", post(run_agent_setup))
+        .route("/clone", post(clone_task_attempt))
         .route("/gh-cli-setup", post(gh_cli_setup_handler))
         .route("/commit-compare", get(compare_commit_to_head))
+        .route("/commits", get(list_attempt_commits))
+        .route("/worktree-size", get(get_worktree_size))
+        .route("/pin", post(pin_task_attempt_worktree))
+        .route("/unpin", post(unpin_task_attempt_worktree))
         .route("/start-dev-server", post(start_dev_server))
+        .route("/dev-server", get(get_dev_server))
+        .route("/run-setup", post(run_setup_script))
+        .route("/run-cleanup", post(run_cleanup_script))
         .route("/branch-status", get(get_task_attempt_branch_status))
+        .route("/ahead-behind", post(get_ahead_behind))
+        .route("/diff", get(get_task_attempt_diff))
         .route("/diff/ws", get(stream_task_attempt_diff_ws))
+        .route("/logs/sse", get(stream_task_attempt_logs_sse))
+        .route("/diff/last-viewed", put(set_last_viewed))
+        .route("/diff/since-last", get(get_diff_since_last_view))
+        .route("/diff/large-files", get(get_large_files))
         .route("/merge", post(merge_task_attempt))
         .route("/push", post(push_task_attempt_branch))
         .route("/push/force", post(force_push_task_attempt_branch))
         .route("/worktree-status", get(get_worktree_status))
+        .route(
+            "/worktree-status/porcelain",
+            get(get_worktree_status_porcelain),
+        )
         .route("/commit", post(commit_changes))
         .route("/generate-commit-message", post(generate_commit_message))
+        .route("/commit-message/preview", get(preview_commit_message))
         .route("/rebase", post(rebase_task_attempt))
+        .route("/rebase/abort", post(abort_rebase_task_attempt))
+        .route("/reset-to-commit", post(reset_to_commit))
         .route("/conflicts/abort", post(abort_conflicts_task_attempt))
+        .route("/conflicts/resolve", post(resolve_conflict_task_attempt))
         .route("/pr", post(create_github_pr))
         .route("/pr/attach", post(attach_existing_pr))
+        .route("/pr/pause", post(pause_pr_monitoring))
+        .route("/pr/resume", post(resume_pr_monitoring))
         .route("/open-editor", post(open_task_attempt_in_editor))
         .route("/children", get(get_task_attempt_children))
         .route("/stop", post(stop_task_attempt_execution))
         .route("/change-target-branch", post(change_target_branch))
         .route("/rename-branch", post(rename_branch))
         .route("/export-conversation", get(export_conversation))
+        .route("/mcp-calls", get(get_mcp_calls))
+        .route("/usage", get(get_usage))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_task_attempt_middleware,
@@ -1947,7 +3420,9 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let task_attempts_router = Router::new()
         .route("/", get(get_task_attempts).post(create_task_attempt))
+        .route("/batch", post(create_task_attempt_batch))
         .route("/batch-status", post(get_batch_branch_status))
+        .route("/compare", get(compare_task_attempts))
         .nest("/{id}", task_attempt_id_router)
         .nest("/{id}/images", images::router(deployment))
         .nest("/{id}/queue", queue::router(deployment));
@@ -65,7 +65,7 @@ async fn get_setup_helper_action(codex: &Codex) -> Result<ExecutorAction, ApiErr
     let login_script = format!("{} {}", program_path.to_string_lossy(), args.join(" "));
     let login_request = ScriptRequest {
         script: login_script,
-        language: ScriptRequestLanguage::Bash,
+        language: ScriptRequestLanguage::default(),
         context: ScriptContext::ToolInstallScript,
     };
 
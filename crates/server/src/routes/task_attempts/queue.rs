@@ -8,30 +8,41 @@ use serde::Deserialize;
 use services::services::queued_message::QueueStatus;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_task_attempt_middleware};
+use crate::{
+    DeploymentImpl, error::ApiError, middleware::load_task_attempt_middleware,
+    routes::tasks::ensure_task_unblocked,
+};
 
 /// Request body for queueing a follow-up message
 #[derive(Debug, Deserialize, TS)]
 pub struct QueueMessageRequest {
     pub message: String,
     pub variant: Option<String>,
+    /// Images to attach when the message runs
+    #[serde(default)]
+    pub image_ids: Vec<Uuid>,
 }
 
-/// Queue a follow-up message to be executed when the current execution finishes
+/// Append a follow-up message to the queue; queued messages run one at a time as executions finish
 pub async fn queue_message(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<QueueMessageRequest>,
 ) -> Result<ResponseJson<ApiResponse<QueueStatus>>, ApiError> {
+    ensure_task_unblocked(&deployment, task_attempt.task_id).await?;
+
     let data = DraftFollowUpData {
         message: payload.message,
         variant: payload.variant,
+        image_ids: payload.image_ids,
     };
 
-    let queued = deployment
+    deployment
         .queued_message_service()
-        .queue_message(task_attempt.id, data);
+        .queue_message(task_attempt.id, data)
+        .await?;
 
     deployment
         .track_if_analytics_allowed(
@@ -42,19 +53,21 @@ pub async fn queue_message(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(QueueStatus::Queued {
-        message: queued,
-    })))
+    let status = deployment
+        .queued_message_service()
+        .get_status(task_attempt.id);
+    Ok(ResponseJson(ApiResponse::success(status)))
 }
 
-/// Cancel a queued follow-up message
+/// Clear every queued follow-up message for the attempt
 pub async fn cancel_queued_message(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<QueueStatus>>, ApiError> {
     deployment
         .queued_message_service()
-        .cancel_queued(task_attempt.id);
+        .cancel_queued(task_attempt.id)
+        .await?;
 
     deployment
         .track_if_analytics_allowed(
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, Query, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post, put},
+};
+use db::models::{
+    activity::{ActivityKind, CreateActivity},
+    project::Project,
+    task::Task,
+    task_template::{CreateTaskTemplate, TaskTemplate, UpdateTaskTemplate},
+};
+use deployment::Deployment;
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_task_template_middleware};
+
+#[derive(Deserialize, TS)]
+pub struct TaskTemplateQuery {
+    /// Limit results to global templates and those scoped to this project
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct InstantiateTaskTemplateRequest {
+    pub project_id: Uuid,
+    /// Values for the template's `{placeholder}`s, keyed by placeholder name
+    #[serde(default)]
+    pub values: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct InstantiateTaskTemplateResponse {
+    pub task: Task,
+    /// Profile to start the task with, taken from the template's default
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    /// Base branch to start the task's attempt from, taken from the template's default
+    pub base_branch: Option<String>,
+}
+
+pub async fn get_task_templates(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskTemplateQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskTemplate>>>, ApiError> {
+    let templates = TaskTemplate::find_all(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(templates)))
+}
+
+pub async fn create_task_template(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskTemplate>,
+) -> Result<ResponseJson<ApiResponse<TaskTemplate>>, ApiError> {
+    insert_task_template(&deployment, payload).await
+}
+
+async fn insert_task_template(
+    deployment: &DeploymentImpl,
+    payload: CreateTaskTemplate,
+) -> Result<ResponseJson<ApiResponse<TaskTemplate>>, ApiError> {
+    if payload.name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Template name must not be empty".to_string(),
+        ));
+    }
+
+    let template = TaskTemplate::create(&deployment.db().pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_template_created",
+            serde_json::json!({
+                "task_template_id": template.id.to_string(),
+                "project_id": template.project_id,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+pub async fn update_task_template(
+    Extension(template): Extension<TaskTemplate>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateTaskTemplate>,
+) -> Result<ResponseJson<ApiResponse<TaskTemplate>>, ApiError> {
+    let updated = TaskTemplate::update(&deployment.db().pool, template.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_task_template(
+    Extension(template): Extension<TaskTemplate>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = TaskTemplate::delete(&deployment.db().pool, template.id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+pub async fn instantiate_task_template(
+    Extension(template): Extension<TaskTemplate>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<InstantiateTaskTemplateRequest>,
+) -> Result<ResponseJson<ApiResponse<InstantiateTaskTemplateResponse>>, ApiError> {
+    let project = find_project(&deployment, payload.project_id).await?;
+    let (create_task, executor_profile_id) = template
+        .instantiate(project.id, &payload.values)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let task = Task::create(&deployment.db().pool, &create_task, Uuid::new_v4()).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_created",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id,
+                "has_description": task.description.is_some(),
+                "task_template_id": template.id.to_string(),
+            }),
+        )
+        .await;
+    deployment
+        .record_activity(CreateActivity::for_task(ActivityKind::TaskCreated, &task))
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        InstantiateTaskTemplateResponse {
+            task,
+            executor_profile_id,
+            base_branch: template.default_base_branch.clone(),
+        },
+    )))
+}
+
+async fn find_project(deployment: &DeploymentImpl, project_id: Uuid) -> Result<Project, ApiError> {
+    Project::find_by_id(&deployment.db().pool, project_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Project not found".to_string()))
+}
+
+/// A template owned by `project_id`; global templates are only editable through
+/// `/task-templates`.
+async fn find_project_template(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    template_id: Uuid,
+) -> Result<TaskTemplate, ApiError> {
+    TaskTemplate::find_by_id(&deployment.db().pool, template_id)
+        .await?
+        .filter(|template| template.project_id == Some(project_id))
+        .ok_or_else(|| ApiError::NotFound("Task template not found".to_string()))
+}
+
+/// The project's own templates together with the global ones.
+pub async fn get_project_task_templates(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskTemplate>>>, ApiError> {
+    let project = find_project(&deployment, project_id).await?;
+    let templates = TaskTemplate::find_all(&deployment.db().pool, Some(project.id)).await?;
+    Ok(ResponseJson(ApiResponse::success(templates)))
+}
+
+pub async fn create_project_task_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(mut payload): Json<CreateTaskTemplate>,
+) -> Result<ResponseJson<ApiResponse<TaskTemplate>>, ApiError> {
+    let project = find_project(&deployment, project_id).await?;
+    payload.project_id = Some(project.id);
+    insert_task_template(&deployment, payload).await
+}
+
+pub async fn update_project_task_template(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, template_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateTaskTemplate>,
+) -> Result<ResponseJson<ApiResponse<TaskTemplate>>, ApiError> {
+    let template = find_project_template(&deployment, project_id, template_id).await?;
+    update_task_template(Extension(template), State(deployment), Json(payload)).await
+}
+
+pub async fn delete_project_task_template(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, template_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let template = find_project_template(&deployment, project_id, template_id).await?;
+    delete_task_template(Extension(template), State(deployment)).await
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let template_router = Router::new()
+        .route("/", put(update_task_template).delete(delete_task_template))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_task_template_middleware,
+        ));
+
+    let inner = Router::new()
+        .route("/", get(get_task_templates).post(create_task_template))
+        .nest("/{template_id}", template_router);
+
+    let project_router = Router::new()
+        .route(
+            "/",
+            get(get_project_task_templates).post(create_project_task_template),
+        )
+        .route(
+            "/{template_id}",
+            put(update_project_task_template).delete(delete_project_task_template),
+        );
+
+    let from_template_router = Router::new()
+        .route("/", post(instantiate_task_template))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_task_template_middleware,
+        ));
+
+    Router::new()
+        .nest("/task-templates", inner)
+        .nest("/projects/{id}/task-templates", project_router)
+        .nest("/tasks/from-template/{template_id}", from_template_router)
+}
@@ -4,17 +4,21 @@ use anyhow;
 use axum::{
     Extension, Json, Router,
     extract::{
-        Query, State,
+        Path, Query, Request, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
     http::StatusCode,
-    middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
+    middleware::{Next, from_fn_with_state},
+    response::{IntoResponse, Json as ResponseJson, Response},
     routing::{delete, get, post, put},
 };
 use db::models::{
+    activity::{ActivityKind, CreateActivity},
     image::TaskImage,
-    task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
+    task::{
+        CreateTask, Task, TaskDependencies, TaskLabel, TaskStatus, TaskWithAttemptStatus,
+        UpdateTask,
+    },
     task_attempt::TaskAttempt,
 };
 use deployment::Deployment;
@@ -22,11 +26,11 @@ use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use services::services::{
-    container::{ContainerError, ContainerService},
+    container::{AttemptOverrides, ContainerError, ContainerService},
     share::ShareError,
     worktree_manager::{WorktreeCleanup, WorktreeError, WorktreeManager},
 };
-use sqlx::Error as SqlxError;
+use sqlx::{Error as SqlxError, SqlitePool};
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
@@ -36,15 +40,68 @@ use crate::{DeploymentImpl, error::ApiError, middleware::load_task_middleware};
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TaskQuery {
     pub project_id: Uuid,
+    /// Comma-separated label names; only tasks carrying all of them are returned
+    pub labels: Option<String>,
 }
 
 pub async fn get_tasks(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<TaskQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<TaskWithAttemptStatus>>>, ApiError> {
-    let tasks =
-        Task::find_by_project_id_with_attempt_status(&deployment.db().pool, query.project_id)
-            .await?;
+    let pool = &deployment.db().pool;
+    let mut tasks = Task::find_by_project_id_with_attempt_status(pool, query.project_id).await?;
+
+    let labels = parse_label_filter(query.labels.as_deref());
+    if !labels.is_empty() {
+        let matching = Task::find_ids_with_labels(pool, query.project_id, &labels).await?;
+        tasks.retain(|task| matching.contains(&task.id));
+    }
+
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+/// Label names in a comma-separated `?labels=` filter
+fn parse_label_filter(labels: Option<&str>) -> Vec<String> {
+    labels
+        .into_iter()
+        .flat_map(|labels| labels.split(','))
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Most results `GET /tasks/search` returns when no limit is given
+const DEFAULT_SEARCH_LIMIT: u32 = 50;
+
+#[derive(Debug, Deserialize, TS)]
+pub struct TaskSearchQuery {
+    /// Text to find in task titles and descriptions
+    pub q: String,
+    pub project_id: Option<Uuid>,
+    pub status: Option<TaskStatus>,
+    pub limit: Option<u32>,
+}
+
+pub async fn search_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskSearchQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let text = query.q.trim();
+    if text.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Search query must not be empty".to_string(),
+        ));
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, 500);
+    let tasks = Task::search(
+        &deployment.db().pool,
+        text,
+        query.project_id,
+        query.status,
+        limit.into(),
+    )
+    .await?;
 
     Ok(ResponseJson(ApiResponse::success(tasks)))
 }
@@ -132,6 +189,9 @@ pub async fn create_task(
             }),
         )
         .await;
+    deployment
+        .record_activity(CreateActivity::for_task(ActivityKind::TaskCreated, &task))
+        .await;
 
     Ok(ResponseJson(ApiResponse::success(task)))
 }
@@ -171,6 +231,9 @@ pub async fn create_task_and_start(
             }),
         )
         .await;
+    deployment
+        .record_activity(CreateActivity::for_task(ActivityKind::TaskCreated, &task))
+        .await;
 
     let task_attempt_result = deployment
         .container()
@@ -181,6 +244,7 @@ pub async fn create_task_and_start(
             payload.custom_branch,
             payload.use_existing_branch,
             None, // conversation_history for a new task is always None
+            AttemptOverrides::default(),
         )
         .await;
 
@@ -197,6 +261,13 @@ pub async fn create_task_and_start(
                     }),
                 )
                 .await;
+            deployment
+                .record_activity(CreateActivity::for_attempt(
+                    ActivityKind::AttemptStarted,
+                    &task,
+                    task_attempt.id,
+                ))
+                .await;
 
             tracing::info!("Started attempt for task {}", task.id);
 
@@ -207,6 +278,7 @@ pub async fn create_task_and_start(
                 last_attempt_failed: false,
                 executor: task_attempt.executor.to_string(),
                 latest_task_attempt_id: Some(task_attempt.id),
+                labels: vec![],
             }
         }
         Err(err) => {
@@ -231,6 +303,7 @@ pub async fn create_task_and_start(
                 last_attempt_failed: true,
                 executor: "".to_string(),
                 latest_task_attempt_id: None,
+                labels: vec![],
             }
         }
     };
@@ -252,6 +325,7 @@ pub async fn start_task_attempt(
     Json(payload): Json<StartTaskAttemptRequest>,
 ) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
     let executor_profile_id = ExecutorProfileId::new(payload.executor);
+    ensure_task_unblocked(&deployment, task.id).await?;
 
     let task_attempt_result = deployment
         .container()
@@ -262,6 +336,7 @@ pub async fn start_task_attempt(
             payload.branch,
             false, // use_existing_branch
             None,  // conversation_history
+            AttemptOverrides::default(),
         )
         .await;
 
@@ -290,12 +365,108 @@ pub async fn start_task_attempt(
             }),
         )
         .await;
+    deployment
+        .record_activity(CreateActivity::for_attempt(
+            ActivityKind::AttemptStarted,
+            &task,
+            task_attempt.id,
+        ))
+        .await;
 
     tracing::info!("Created attempt for task {}", task.id);
 
     Ok(ResponseJson(ApiResponse::success(task_attempt)))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct AdoptBranchRequest {
+    /// Existing local branch to adopt
+    pub branch: String,
+    /// Branch the work was started from; the attempt diffs against their merge base
+    pub base: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct AdoptBranchResponse {
+    pub attempt: TaskAttempt,
+    pub base_commit: String,
+    pub head_commit: String,
+    pub commits_ahead: usize,
+}
+
+pub async fn adopt_branch(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<AdoptBranchRequest>,
+) -> Result<ResponseJson<ApiResponse<AdoptBranchResponse>>, ApiError> {
+    let branch = payload.branch.trim();
+    let base = payload.base.trim();
+    if branch.is_empty() || base.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Both branch and base are required".to_string(),
+        ));
+    }
+    if branch == base {
+        return Err(ApiError::BadRequest(
+            "Cannot adopt the base branch itself; pick the branch holding the work".to_string(),
+        ));
+    }
+
+    let project = task
+        .parent_project(&deployment.db().pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let (base_commit, head_commit) =
+        deployment
+            .git()
+            .branch_commit_range(&project.git_repo_path, branch, base)?;
+
+    let executor = deployment.config().read().await.executor_profile.executor;
+    let attempt = match deployment
+        .container()
+        .adopt_branch(&task, executor, branch, base)
+        .await
+    {
+        Ok(attempt) => attempt,
+        Err(ContainerError::Worktree(WorktreeError::BranchAlreadyCheckedOut(branch))) => {
+            return Err(ApiError::Conflict(format!(
+                "Cannot adopt branch '{branch}' because it is already checked out in the main repository. \
+                Switch the main repository to another branch and retry."
+            )));
+        }
+        Err(err) => return Err(ApiError::Container(err)),
+    };
+    let (commits_ahead, _) =
+        deployment
+            .git()
+            .get_branch_status(&project.git_repo_path, branch, base)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_branch_adopted",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "attempt_id": attempt.id.to_string(),
+                "commits_ahead": commits_ahead,
+            }),
+        )
+        .await;
+
+    tracing::info!(
+        "Adopted branch '{}' as attempt {} for task {}",
+        branch,
+        attempt.id,
+        task.id
+    );
+
+    Ok(ResponseJson(ApiResponse::success(AdoptBranchResponse {
+        attempt,
+        base_commit: base_commit.to_string(),
+        head_commit: head_commit.to_string(),
+        commits_ahead,
+    })))
+}
+
 #[derive(Debug, Deserialize)]
 pub struct WaitForTaskQuery {
     /// Polling interval in seconds (default: 2.0)
@@ -365,6 +536,11 @@ pub async fn update_task(
         Some(s) => Some(s),                     // Non-empty string = update description
         None => existing_task.description,      // Field omitted = keep existing
     };
+    if payload.status == Some(TaskStatus::InProgress)
+        && existing_task.status != TaskStatus::InProgress
+    {
+        ensure_task_unblocked(&deployment, existing_task.id).await?;
+    }
     let status = payload.status.unwrap_or(existing_task.status);
     let parent_task_attempt = payload
         .parent_task_attempt
@@ -483,6 +659,9 @@ pub async fn delete_task(
             }),
         )
         .await;
+    deployment
+        .record_activity(CreateActivity::for_task(ActivityKind::TaskDeleted, &task))
+        .await;
 
     // Spawn background worktree cleanup task
     let task_id = task.id;
@@ -543,25 +722,291 @@ pub async fn share_task(
     })))
 }
 
+/// Reject starting work on a task whose blockers aren't done yet.
+pub(crate) async fn ensure_task_unblocked(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+) -> Result<(), ApiError> {
+    ensure_unblocked(&deployment.db().pool, task_id).await
+}
+
+async fn ensure_unblocked(pool: &SqlitePool, task_id: Uuid) -> Result<(), ApiError> {
+    let blockers = Task::find_unfinished_blockers(pool, task_id).await?;
+    if blockers.is_empty() {
+        return Ok(());
+    }
+    let titles = blockers
+        .iter()
+        .map(|t| format!("'{}'", t.title))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(ApiError::Conflict(format!(
+        "Task is blocked by unfinished tasks: {titles}"
+    )))
+}
+
+pub async fn get_task_dependencies(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskDependencies>>, ApiError> {
+    let dependencies = Task::find_dependencies(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(dependencies)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct AddTaskDependencyRequest {
+    pub blocked_by_task_id: Uuid,
+}
+
+/// Mark the task as blocked by another task in the same project.
+pub async fn add_task_dependency(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<AddTaskDependencyRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskDependencies>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let blocker_id = payload.blocked_by_task_id;
+    if blocker_id == task.id {
+        return Err(ApiError::BadRequest(
+            "A task cannot be blocked by itself".to_string(),
+        ));
+    }
+    if !Task::exists(pool, blocker_id, task.project_id).await? {
+        return Err(ApiError::BadRequest(format!(
+            "Task {blocker_id} not found in this project"
+        )));
+    }
+    if Task::is_transitively_blocked_by(pool, blocker_id, task.id).await? {
+        return Err(ApiError::Conflict(
+            "Adding this dependency would create a cycle".to_string(),
+        ));
+    }
+
+    Task::add_dependency(pool, task.id, blocker_id).await?;
+
+    let dependencies = Task::find_dependencies(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(dependencies)))
+}
+
+pub async fn remove_task_dependency(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_task_id, blocked_by_task_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<TaskDependencies>>, ApiError> {
+    let pool = &deployment.db().pool;
+    Task::remove_dependency(pool, task.id, blocked_by_task_id).await?;
+
+    let dependencies = Task::find_dependencies(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(dependencies)))
+}
+
+/// Longest label name accepted
+const MAX_LABEL_NAME_LEN: usize = 50;
+
+pub async fn get_task_labels(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskLabel>>>, ApiError> {
+    let labels = Task::find_labels(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct AddTaskLabelRequest {
+    /// Label name; an existing project label with this name (ignoring case) is reused
+    pub name: String,
+    /// Hex color such as `#ef4444`. Recolors the label if it already exists.
+    pub color: Option<String>,
+}
+
+/// Attach a label to the task, creating it in the task's project if needed.
+pub async fn add_task_label(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<AddTaskLabelRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskLabel>>>, ApiError> {
+    let name = validate_label_name(&payload.name)?;
+    let color = validate_label_color(payload.color.as_deref())?;
+
+    let pool = &deployment.db().pool;
+    Task::add_label(pool, task.id, task.project_id, name, color).await?;
+
+    let labels = Task::find_labels(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+pub async fn remove_task_label(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_task_id, label_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskLabel>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    Task::remove_label(pool, task.id, label_id).await?;
+
+    let labels = Task::find_labels(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+/// The trimmed label name, if it can be used
+pub(crate) fn validate_label_name(name: &str) -> Result<&str, ApiError> {
+    let name = name.trim();
+    if name.is_empty() || name.contains(',') {
+        return Err(ApiError::BadRequest(
+            "Label name must be non-empty and cannot contain commas".to_string(),
+        ));
+    }
+    if name.chars().count() > MAX_LABEL_NAME_LEN {
+        return Err(ApiError::BadRequest(format!(
+            "Label name must be at most {MAX_LABEL_NAME_LEN} characters"
+        )));
+    }
+    Ok(name)
+}
+
+/// The trimmed label color, if it is `#rgb` or `#rrggbb`
+pub(crate) fn validate_label_color(color: Option<&str>) -> Result<Option<&str>, ApiError> {
+    let color = color.map(str::trim);
+    if let Some(color) = color
+        && !color.strip_prefix('#').is_some_and(|hex| {
+            matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit())
+        })
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid label color '{color}', expected a hex color like #ef4444"
+        )));
+    }
+    Ok(color)
+}
+
+/// Middleware to load the Task for routes with a second path param, which
+/// `load_task_middleware`'s single-param extractor rejects.
+async fn load_task_with_item(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, _item_id)): Path<(Uuid, Uuid)>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let task = match Task::find_by_id(&deployment.db().pool, task_id).await {
+        Ok(Some(task)) => task,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    request.extensions_mut().insert(task);
+    Ok(next.run(request).await)
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_actions_router = Router::new()
         .route("/", put(update_task))
         .route("/", delete(delete_task))
         .route("/share", post(share_task))
         .route("/attempts", post(start_task_attempt))
-        .route("/wait", get(wait_for_task));
+        .route("/adopt-branch", post(adopt_branch))
+        .route("/wait", get(wait_for_task))
+        .route(
+            "/dependencies",
+            get(get_task_dependencies).post(add_task_dependency),
+        )
+        .route("/labels", get(get_task_labels).post(add_task_label));
+
+    let task_item_router = Router::new()
+        .route(
+            "/dependencies/{blocked_by_task_id}",
+            delete(remove_task_dependency),
+        )
+        .route("/labels/{label_id}", delete(remove_task_label))
+        .layer(from_fn_with_state(deployment.clone(), load_task_with_item));
 
     let task_id_router = Router::new()
         .route("/", get(get_task))
         .merge(task_actions_router)
-        .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
+        .layer(from_fn_with_state(deployment.clone(), load_task_middleware))
+        .merge(task_item_router);
 
     let inner = Router::new()
         .route("/", get(get_tasks).post(create_task))
         .route("/stream/ws", get(stream_tasks_ws))
+        .route("/search", get(search_tasks))
         .route("/create-and-start", post(create_task_and_start))
         .nest("/{task_id}", task_id_router);
 
     // mount under /projects/:project_id/tasks
     Router::new().nest("/tasks", inner)
 }
+
+#[cfg(test)]
+mod tests {
+    use db::{
+        DBService,
+        models::project::{CreateProject, Project},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_parse_label_filter() {
+        assert!(parse_label_filter(None).is_empty());
+        assert!(parse_label_filter(Some(" , ")).is_empty());
+        assert_eq!(
+            parse_label_filter(Some("bug, Frontend,,ui ")),
+            ["bug", "Frontend", "ui"]
+        );
+    }
+
+    #[test]
+    fn test_validate_label() {
+        assert_eq!(validate_label_name("  bug ").unwrap(), "bug");
+        assert!(validate_label_name("  ").is_err());
+        assert!(validate_label_name("bug,ui").is_err());
+        assert!(validate_label_name(&"x".repeat(MAX_LABEL_NAME_LEN + 1)).is_err());
+
+        assert_eq!(validate_label_color(None).unwrap(), None);
+        assert_eq!(
+            validate_label_color(Some(" #EF4444")).unwrap(),
+            Some("#EF4444")
+        );
+        assert_eq!(validate_label_color(Some("#abc")).unwrap(), Some("#abc"));
+        assert!(validate_label_color(Some("ef4444")).is_err());
+        assert!(validate_label_color(Some("#ef44")).is_err());
+        assert!(validate_label_color(Some("#gggggg")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ensure_unblocked_until_blockers_are_done() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pool = &db.pool;
+        let project = Project::create(
+            pool,
+            &CreateProject {
+                name: "blockers".to_string(),
+                git_repo_path: "/tmp/blockers-repo".to_string(),
+                ..Default::default()
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let create =
+            |title: &str| CreateTask::from_title_description(project.id, title.to_string(), None);
+        let task = Task::create(pool, &create("Ship"), Uuid::new_v4())
+            .await
+            .unwrap();
+        let blocker = Task::create(pool, &create("Review"), Uuid::new_v4())
+            .await
+            .unwrap();
+        assert!(ensure_unblocked(pool, task.id).await.is_ok());
+
+        Task::add_dependency(pool, task.id, blocker.id)
+            .await
+            .unwrap();
+        match ensure_unblocked(pool, task.id).await {
+            Err(ApiError::Conflict(message)) => assert!(message.contains("'Review'")),
+            other => panic!("expected a conflict, got {other:?}"),
+        }
+
+        Task::update_status(pool, blocker.id, TaskStatus::Done)
+            .await
+            .unwrap();
+        assert!(ensure_unblocked(pool, task.id).await.is_ok());
+    }
+}
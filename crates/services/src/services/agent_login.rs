@@ -0,0 +1,102 @@
+//! Periodic check for agent logins old enough that the agent's setup should be
+//! re-run before the credential expires mid-run.
+
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use executors::{
+    executors::BaseCodingAgent,
+    profile::{ExecutorConfigs, ExecutorProfileId},
+};
+use serde::Serialize;
+use tokio::{sync::RwLock, time::sleep};
+use ts_rs::TS;
+
+use crate::services::{
+    config::Config,
+    events::{EventService, agent_login_patch},
+};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// An agent login due for renewal, published on the event stream at `/agent_logins/{agent}`
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct AgentLoginStatus {
+    pub agent: BaseCodingAgent,
+    #[ts(type = "Date")]
+    pub last_auth_at: DateTime<Utc>,
+    /// Age of the login in seconds
+    #[ts(type = "number")]
+    pub age_secs: u64,
+}
+
+pub struct AgentLoginMonitor {
+    config: Arc<RwLock<Config>>,
+    events: EventService,
+    /// Agents currently flagged on the event stream
+    flagged: HashSet<BaseCodingAgent>,
+}
+
+impl AgentLoginMonitor {
+    pub fn new(config: Arc<RwLock<Config>>, events: EventService) -> Self {
+        Self {
+            config,
+            events,
+            flagged: HashSet::new(),
+        }
+    }
+
+    pub async fn run(mut self) {
+        loop {
+            self.check_logins().await;
+            sleep(CHECK_INTERVAL).await;
+        }
+    }
+
+    async fn check_logins(&mut self) {
+        let max_age_days = self.config.read().await.agent_login_max_age_days;
+        let max_age = Duration::from_secs(u64::from(max_age_days) * 24 * 60 * 60);
+
+        let profiles = ExecutorConfigs::get_cached();
+        for &agent in profiles.executors.keys() {
+            let Some(coding_agent) = profiles.get_coding_agent(&ExecutorProfileId::new(agent))
+            else {
+                continue;
+            };
+            let stale_age =
+                tokio::task::spawn_blocking(move || coding_agent.stale_login_age(max_age))
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Login check for {} failed: {}", agent, e);
+                        None
+                    });
+
+            match stale_age {
+                Some(age) => {
+                    tracing::info!(
+                        "{} login is {} days old; re-run its setup to renew it",
+                        agent,
+                        age.as_secs() / (24 * 60 * 60)
+                    );
+                    let last_auth_at = Utc::now()
+                        - chrono::Duration::from_std(age).unwrap_or(chrono::Duration::zero());
+                    self.events
+                        .msg_store()
+                        .push_patch(agent_login_patch::set(&AgentLoginStatus {
+                            agent,
+                            last_auth_at,
+                            age_secs: age.as_secs(),
+                        }));
+                    self.flagged.insert(agent);
+                }
+                None => {
+                    if self.flagged.remove(&agent) {
+                        self.events
+                            .msg_store()
+                            .push_patch(agent_login_patch::clear(agent));
+                    }
+                }
+            }
+        }
+    }
+}
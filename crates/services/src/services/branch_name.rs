@@ -0,0 +1,156 @@
+//! Branch names for new task attempts, optionally rendered from a project template.
+
+use std::collections::HashMap;
+
+use utils::text::{git_branch_id, render_placeholders, short_uuid};
+use uuid::Uuid;
+
+use crate::services::config::ConfigError;
+
+/// Placeholders a project's branch template may use
+pub const BRANCH_TEMPLATE_PLACEHOLDERS: &[&str] = &["prefix", "short_id", "title", "task_id"];
+
+/// Values substituted into a branch template
+pub struct BranchNameContext<'a> {
+    /// The configured git branch prefix, possibly empty
+    pub prefix: &'a str,
+    pub attempt_id: Uuid,
+    pub task_id: Uuid,
+    pub title: &'a str,
+}
+
+impl BranchNameContext<'_> {
+    fn values(&self) -> HashMap<String, String> {
+        HashMap::from([
+            ("prefix".to_string(), self.prefix.to_string()),
+            ("short_id".to_string(), short_uuid(&self.attempt_id)),
+            ("title".to_string(), git_branch_id(self.title)),
+            ("task_id".to_string(), self.task_id.to_string()),
+        ])
+    }
+}
+
+/// The built-in branch name: `{prefix}/{short_id}-{title}`, without the
+/// leading `{prefix}/` when no prefix is configured.
+pub fn default_branch_name(ctx: &BranchNameContext) -> String {
+    let task_title_id = git_branch_id(ctx.title);
+    if ctx.prefix.is_empty() {
+        format!("{}-{}", short_uuid(&ctx.attempt_id), task_title_id)
+    } else {
+        format!(
+            "{}/{}-{}",
+            ctx.prefix,
+            short_uuid(&ctx.attempt_id),
+            task_title_id
+        )
+    }
+}
+
+/// Drop the empty path segments left behind by empty values, e.g. an unset
+/// `{prefix}` in `{prefix}/{short_id}`.
+fn tidy_branch_name(name: &str) -> String {
+    name.split('/')
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn is_valid_branch_name(name: &str) -> bool {
+    !name.is_empty() && git2::Branch::name_is_valid(name).unwrap_or(false)
+}
+
+/// Check a project's branch template before it is saved.
+pub fn validate_branch_template(template: &str) -> Result<(), ConfigError> {
+    if !template.contains("{short_id}") {
+        return Err(ConfigError::ValidationError(
+            "Branch template must include {short_id} so every attempt gets its own branch"
+                .to_string(),
+        ));
+    }
+
+    let sample = BranchNameContext {
+        prefix: "vk",
+        attempt_id: Uuid::nil(),
+        task_id: Uuid::nil(),
+        title: "Example task",
+    };
+    let rendered = render_placeholders(template, &sample.values()).map_err(|unknown| {
+        ConfigError::ValidationError(format!(
+            "Unknown branch template placeholder(s) {}; expected one of {}",
+            unknown
+                .iter()
+                .map(|p| format!("{{{p}}}"))
+                .collect::<Vec<_>>()
+                .join(", "),
+            BRANCH_TEMPLATE_PLACEHOLDERS
+                .iter()
+                .map(|p| format!("{{{p}}}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    })?;
+
+    if !is_valid_branch_name(&tidy_branch_name(&rendered)) {
+        return Err(ConfigError::ValidationError(format!(
+            "Branch template renders an invalid git branch name: '{rendered}'"
+        )));
+    }
+    Ok(())
+}
+
+/// Render a branch template. Templates that don't produce a valid branch name
+/// (which validation keeps out of the database) render as `None`.
+pub fn render_branch_template(template: &str, ctx: &BranchNameContext) -> Option<String> {
+    let rendered = render_placeholders(template, &ctx.values()).ok()?;
+    let name = tidy_branch_name(&rendered);
+    is_valid_branch_name(&name).then_some(name)
+}
+
+/// Branch name for a new attempt, rendered from the project's template when set.
+pub fn branch_name(template: Option<&str>, ctx: &BranchNameContext) -> String {
+    template
+        .and_then(|template| render_branch_template(template, ctx))
+        .unwrap_or_else(|| default_branch_name(ctx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_branch_template() {
+        let attempt_id = Uuid::new_v4();
+        let short_id = short_uuid(&attempt_id);
+        let ctx = BranchNameContext {
+            prefix: "vk",
+            attempt_id,
+            task_id: Uuid::nil(),
+            title: "Add Login Page!",
+        };
+
+        assert_eq!(
+            branch_name(None, &ctx),
+            format!("vk/{short_id}-add-login-page")
+        );
+        assert_eq!(
+            branch_name(Some("feature/{short_id}-{title}"), &ctx),
+            format!("feature/{short_id}-add-login-page")
+        );
+
+        // An empty prefix doesn't leave a leading slash behind
+        let no_prefix = BranchNameContext { prefix: "", ..ctx };
+        assert_eq!(
+            branch_name(Some("{prefix}/{short_id}"), &no_prefix),
+            short_id
+        );
+
+        assert!(validate_branch_template("{prefix}/{task_id}-{short_id}").is_ok());
+        assert!(matches!(
+            validate_branch_template("{title}"),
+            Err(ConfigError::ValidationError(_))
+        ));
+        assert!(validate_branch_template("{short_id}-{ticket}").is_err());
+        assert!(validate_branch_template("bad..name/{short_id}").is_err());
+    }
+}
@@ -0,0 +1,184 @@
+//! Per-attempt cache of the commit-graph part of branch status, so frequent
+//! polling doesn't rerun the ahead/behind walks while nothing has been committed.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use db::models::merge::Merge;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::git::ConflictOp;
+
+/// Upper bound on how long cached counts are served, which bounds how stale a
+/// remote that moved outside the app can get. Older entries are pruned.
+const MAX_CACHE_AGE: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BranchStatus {
+    pub commits_behind: Option<usize>,
+    pub commits_ahead: Option<usize>,
+    pub has_uncommitted_changes: Option<bool>,
+    pub head_oid: Option<String>,
+    pub uncommitted_count: Option<usize>,
+    pub untracked_count: Option<usize>,
+    pub target_branch_name: String,
+    pub remote_commits_behind: Option<usize>,
+    pub remote_commits_ahead: Option<usize>,
+    pub merges: Vec<Merge>,
+    /// True if a `git rebase` is currently in progress in this worktree
+    pub is_rebase_in_progress: bool,
+    /// Current conflict operation if any
+    pub conflict_op: Option<ConflictOp>,
+    /// List of files currently in conflicted (unmerged) state
+    pub conflicted_files: Vec<String>,
+}
+
+/// The commit-graph part of a [`BranchStatus`]. Worktree state (uncommitted
+/// changes, conflicts, rebases) changes without commits and is never cached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitGraphStatus {
+    pub commits_ahead: Option<usize>,
+    pub commits_behind: Option<usize>,
+    pub remote_commits_ahead: Option<usize>,
+    pub remote_commits_behind: Option<usize>,
+}
+
+struct CachedCommitGraph {
+    project_id: Uuid,
+    head_oid: String,
+    target_branch: String,
+    graph: CommitGraphStatus,
+    computed_at: Instant,
+}
+
+/// Commit-graph status per task attempt, keyed by the worktree HEAD it was computed
+/// at. Anything that moves the target or remote without moving HEAD (merges, pushes,
+/// target branch changes) must invalidate the affected attempts.
+#[derive(Clone, Default)]
+pub struct BranchStatusCache {
+    entries: Arc<RwLock<HashMap<Uuid, CachedCommitGraph>>>,
+}
+
+impl BranchStatusCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The cached counts for an attempt, if they were computed at `head_oid` for
+    /// `target_branch` and haven't expired.
+    pub fn get(
+        &self,
+        attempt_id: Uuid,
+        head_oid: &str,
+        target_branch: &str,
+    ) -> Option<CommitGraphStatus> {
+        let entries = self.entries.read().unwrap();
+        let cached = entries.get(&attempt_id)?;
+        let fresh = cached.head_oid == head_oid
+            && cached.target_branch == target_branch
+            && cached.computed_at.elapsed() < MAX_CACHE_AGE;
+        fresh.then(|| cached.graph.clone())
+    }
+
+    /// Cache freshly computed counts, pruning expired entries.
+    pub fn insert(
+        &self,
+        attempt_id: Uuid,
+        project_id: Uuid,
+        head_oid: String,
+        target_branch: String,
+        graph: CommitGraphStatus,
+    ) {
+        let mut entries = self.entries.write().unwrap();
+        entries.retain(|_, cached| cached.computed_at.elapsed() < MAX_CACHE_AGE);
+        entries.insert(
+            attempt_id,
+            CachedCommitGraph {
+                project_id,
+                head_oid,
+                target_branch,
+                graph,
+                computed_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Drop the cached counts for an attempt so the next poll recomputes them.
+    pub fn invalidate(&self, attempt_id: Uuid) {
+        self.entries.write().unwrap().remove(&attempt_id);
+    }
+
+    /// Drop the cached counts of every attempt in the project targeting
+    /// `target_branch`, e.g. after a merge moved it.
+    pub fn invalidate_target(&self, project_id: Uuid, target_branch: &str) {
+        self.entries.write().unwrap().retain(|_, cached| {
+            cached.project_id != project_id || cached.target_branch != target_branch
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> CommitGraphStatus {
+        CommitGraphStatus {
+            commits_ahead: Some(1),
+            commits_behind: Some(0),
+            remote_commits_ahead: None,
+            remote_commits_behind: None,
+        }
+    }
+
+    #[test]
+    fn test_cache_keyed_by_head_and_target() {
+        let cache = BranchStatusCache::new();
+        let (attempt_id, project_id) = (Uuid::new_v4(), Uuid::new_v4());
+        cache.insert(
+            attempt_id,
+            project_id,
+            "abc".to_string(),
+            "main".to_string(),
+            graph(),
+        );
+
+        assert_eq!(cache.get(attempt_id, "abc", "main"), Some(graph()));
+        assert!(cache.get(attempt_id, "def", "main").is_none());
+        assert!(cache.get(attempt_id, "abc", "develop").is_none());
+
+        cache.invalidate(attempt_id);
+        assert!(cache.get(attempt_id, "abc", "main").is_none());
+    }
+
+    #[test]
+    fn test_merge_invalidates_siblings_on_the_same_target() {
+        let cache = BranchStatusCache::new();
+        let (project_id, other_project) = (Uuid::new_v4(), Uuid::new_v4());
+        let sibling = Uuid::new_v4();
+        let other_target = Uuid::new_v4();
+        let other_repo = Uuid::new_v4();
+        for (attempt_id, project_id, target) in [
+            (sibling, project_id, "main"),
+            (other_target, project_id, "develop"),
+            (other_repo, other_project, "main"),
+        ] {
+            cache.insert(
+                attempt_id,
+                project_id,
+                "abc".to_string(),
+                target.to_string(),
+                graph(),
+            );
+        }
+
+        cache.invalidate_target(project_id, "main");
+        assert!(cache.get(sibling, "abc", "main").is_none());
+        assert!(cache.get(other_target, "abc", "develop").is_some());
+        assert!(cache.get(other_repo, "abc", "main").is_some());
+    }
+}
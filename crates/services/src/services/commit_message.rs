@@ -1,9 +1,18 @@
 //! Service for generating commit messages using Claude Code CLI.
 
-use std::path::Path;
-use std::process::{Command, Stdio};
+use std::{
+    collections::HashMap,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use db::models::execution_process::ExecutionProcessRunReason;
 use thiserror::Error;
 use tokio::io::AsyncWriteExt as _;
+use utils::text::render_placeholders;
+use uuid::Uuid;
+
+use crate::services::config::{AutoCommitMessageMode, ConfigError};
 
 #[derive(Debug, Error)]
 pub enum CommitMessageError {
@@ -27,6 +36,123 @@ Rules:
 Diff:
 "#;
 
+/// Placeholders a project's commit message template may use
+pub const COMMIT_TEMPLATE_PLACEHOLDERS: &[&str] = &["title", "description", "task_id", "branch"];
+
+/// Values substituted into a commit message template
+pub struct CommitMessageContext<'a> {
+    pub title: &'a str,
+    pub description: Option<&'a str>,
+    pub task_id: Uuid,
+    pub branch: &'a str,
+}
+
+impl CommitMessageContext<'_> {
+    fn values(&self) -> HashMap<String, String> {
+        HashMap::from([
+            ("title".to_string(), self.title.to_string()),
+            (
+                "description".to_string(),
+                self.description.unwrap_or_default().trim().to_string(),
+            ),
+            ("task_id".to_string(), self.task_id.to_string()),
+            ("branch".to_string(), self.branch.to_string()),
+        ])
+    }
+}
+
+/// Check a project's commit message template before it is saved.
+pub fn validate_commit_message_template(template: &str) -> Result<(), ConfigError> {
+    let sample = CommitMessageContext {
+        title: "Example task",
+        description: None,
+        task_id: Uuid::nil(),
+        branch: "vk/example",
+    };
+    render_placeholders(template, &sample.values()).map_err(|unknown| {
+        ConfigError::ValidationError(format!(
+            "Unknown commit message placeholder(s) {}; expected one of {}",
+            unknown
+                .iter()
+                .map(|p| format!("{{{p}}}"))
+                .collect::<Vec<_>>()
+                .join(", "),
+            COMMIT_TEMPLATE_PLACEHOLDERS
+                .iter()
+                .map(|p| format!("{{{p}}}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    })?;
+    Ok(())
+}
+
+/// Render a commit message template. Invalid templates (which validation keeps
+/// out of the database) render as `None` so callers fall back to the default.
+pub fn render_commit_message_template(
+    template: &str,
+    ctx: &CommitMessageContext,
+) -> Option<String> {
+    let message = render_placeholders(template, &ctx.values()).ok()?;
+    let message = message.trim_end().to_string();
+    (!message.trim().is_empty()).then_some(message)
+}
+
+/// The merge commit message used when the project has no template:
+/// `"{title} (vibe-kanban {short id})"`, followed by the description.
+pub fn default_merge_commit_message(ctx: &CommitMessageContext) -> String {
+    let task_uuid_str = ctx.task_id.to_string();
+    let first_uuid_section = task_uuid_str.split('-').next().unwrap_or(&task_uuid_str);
+
+    let mut message = format!("{} (vibe-kanban {})", ctx.title, first_uuid_section);
+    if let Some(description) = ctx.description
+        && !description.trim().is_empty()
+    {
+        message.push_str("\n\n");
+        message.push_str(description);
+    }
+    message
+}
+
+/// Merge commit message for a task, rendered from the project's template when set.
+pub fn merge_commit_message(template: Option<&str>, ctx: &CommitMessageContext) -> String {
+    template
+        .and_then(|template| render_commit_message_template(template, ctx))
+        .unwrap_or_else(|| default_merge_commit_message(ctx))
+}
+
+/// The message auto-commit uses after an execution process finishes.
+///
+/// Coding agent runs take their message from `mode`: the session summary when
+/// one was captured, the task title, or a default message naming the attempt,
+/// which is also the fallback. Returns `None` for run reasons that never auto-commit.
+pub fn auto_commit_message(
+    run_reason: &ExecutionProcessRunReason,
+    mode: AutoCommitMessageMode,
+    session_summary: Option<&str>,
+    task_title: &str,
+    task_attempt_id: Uuid,
+) -> Option<String> {
+    match run_reason {
+        ExecutionProcessRunReason::CodingAgent => {
+            let message = match mode {
+                AutoCommitMessageMode::SessionSummary => session_summary,
+                AutoCommitMessageMode::TaskTitle => {
+                    Some(task_title).filter(|title| !title.trim().is_empty())
+                }
+                AutoCommitMessageMode::Generic => None,
+            };
+            Some(message.map(|m| m.to_string()).unwrap_or_else(|| {
+                format!("Commit changes from coding agent for task attempt {task_attempt_id}")
+            }))
+        }
+        ExecutionProcessRunReason::CleanupScript => Some(format!(
+            "Cleanup script changes for task attempt {task_attempt_id}"
+        )),
+        _ => None,
+    }
+}
+
 /// Get the staged diff from the worktree, falling back to unstaged changes if nothing is staged.
 pub fn get_diff_for_commit(worktree_path: &Path) -> Result<String, CommitMessageError> {
     // First try to get staged changes
@@ -157,4 +283,97 @@ mod tests {
             assert!(truncated.contains("5000"));
         }
     }
+
+    #[test]
+    fn test_auto_commit_message_sources() {
+        let id = Uuid::new_v4();
+        let agent = ExecutionProcessRunReason::CodingAgent;
+        let generic = format!("Commit changes from coding agent for task attempt {id}");
+        assert_eq!(
+            auto_commit_message(
+                &agent,
+                AutoCommitMessageMode::SessionSummary,
+                Some("Add login"),
+                "Login page",
+                id
+            )
+            .as_deref(),
+            Some("Add login")
+        );
+        assert_eq!(
+            auto_commit_message(
+                &agent,
+                AutoCommitMessageMode::SessionSummary,
+                None,
+                "Login page",
+                id
+            ),
+            Some(generic.clone())
+        );
+        assert_eq!(
+            auto_commit_message(
+                &agent,
+                AutoCommitMessageMode::TaskTitle,
+                Some("Add login"),
+                "Login page",
+                id
+            )
+            .as_deref(),
+            Some("Login page")
+        );
+        assert_eq!(
+            auto_commit_message(
+                &agent,
+                AutoCommitMessageMode::Generic,
+                Some("Add login"),
+                "Login page",
+                id
+            ),
+            Some(generic)
+        );
+        assert!(
+            auto_commit_message(
+                &ExecutionProcessRunReason::DevServer,
+                AutoCommitMessageMode::SessionSummary,
+                None,
+                "Login page",
+                id
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_commit_message_template() {
+        let task_id = Uuid::new_v4();
+        let ctx = CommitMessageContext {
+            title: "Add login",
+            description: None,
+            task_id,
+            branch: "vk/1234-add-login",
+        };
+
+        assert_eq!(
+            merge_commit_message(Some("{title} [{branch}]\n\n{description}"), &ctx),
+            "Add login [vk/1234-add-login]"
+        );
+        assert_eq!(
+            merge_commit_message(None, &ctx),
+            format!(
+                "Add login (vibe-kanban {})",
+                task_id.to_string().split('-').next().unwrap()
+            )
+        );
+        assert!(validate_commit_message_template("{title} ({task_id})").is_ok());
+        assert!(matches!(
+            validate_commit_message_template("{title} {author}"),
+            Err(ConfigError::ValidationError(_))
+        ));
+        // Braces that don't wrap a placeholder name are kept as literal text
+        assert!(validate_commit_message_template("{title").is_ok());
+        assert_eq!(
+            merge_commit_message(Some("{title} {not a placeholder}"), &ctx),
+            "Add login {not a placeholder}"
+        );
+    }
 }
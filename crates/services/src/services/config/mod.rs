@@ -1,6 +1,8 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use serde::Serialize;
 use thiserror::Error;
+use ts_rs::TS;
 
 pub mod editor;
 mod versions;
@@ -24,6 +26,11 @@ pub type ThemeMode = versions::v9::ThemeMode;
 pub type SoundFile = versions::v9::SoundFile;
 pub type EditorType = versions::v9::EditorType;
 pub type GitHubConfig = versions::v9::GitHubConfig;
+pub type LogFileSinkConfig = versions::v9::LogFileSinkConfig;
+pub type LineEndings = versions::v9::LineEndings;
+pub type AutoCommitMessageMode = versions::v9::AutoCommitMessageMode;
+pub type WebhooksConfig = versions::v9::WebhooksConfig;
+pub type ExecutionLimitsConfig = versions::v9::ExecutionLimitsConfig;
 pub type UiLanguage = versions::v9::UiLanguage;
 pub type ShowcaseState = versions::v9::ShowcaseState;
 
@@ -38,6 +45,56 @@ pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
     }
 }
 
+/// What loading the config file would do, worked out without saving anything
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ConfigVersionInfo {
+    pub file_exists: bool,
+    /// `config_version` recorded in the file; null when there is no file, it isn't
+    /// valid JSON, or it predates versioning (v1)
+    pub detected_version: Option<String>,
+    pub current_version: String,
+    /// Loading the file would upgrade it (or reset it to defaults if no schema fits)
+    pub migration_needed: bool,
+    /// The config loading the file would produce; the API masks its secret values
+    pub migrated_config: Config,
+}
+
+/// Dry run of [`load_config_from_file`]: reports the on-disk version and the
+/// config it migrates to, leaving the file untouched
+pub async fn inspect_config_file(config_path: &Path) -> ConfigVersionInfo {
+    let current_version = Config::default().config_version;
+    let Ok(raw_config) = std::fs::read_to_string(config_path) else {
+        return ConfigVersionInfo {
+            file_exists: false,
+            detected_version: None,
+            current_version,
+            migration_needed: false,
+            migrated_config: Config::default(),
+        };
+    };
+
+    let detected_version = serde_json::from_str::<serde_json::Value>(&raw_config)
+        .ok()
+        .and_then(|value| {
+            value
+                .get("config_version")
+                .and_then(|version| version.as_str())
+                .map(str::to_string)
+        });
+    let (migration_needed, migrated_config) = match Config::from_current_version(&raw_config) {
+        Some(config) => (false, config),
+        None => (true, Config::from(raw_config)),
+    };
+
+    ConfigVersionInfo {
+        file_exists: true,
+        detected_version,
+        current_version,
+        migration_needed,
+        migrated_config,
+    }
+}
+
 /// Saves the config to the given path
 pub async fn save_config_to_file(
     config: &Config,
@@ -1,5 +1,10 @@
 use anyhow::Error;
-use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use db::models::task::TaskStatus;
+use executors::{
+    env::{ScopedSecret, redact_secret_values, restore_redacted_secret_values},
+    executors::BaseCodingAgent,
+    profile::ExecutorProfileId,
+};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 pub use v8::{
@@ -17,6 +22,106 @@ fn default_auto_commit_enabled() -> bool {
     false
 }
 
+fn default_worktree_cleanup_interval_secs() -> u64 {
+    30 * 60
+}
+
+fn default_worktree_orphan_cleanup_enabled() -> bool {
+    true
+}
+
+fn default_agent_login_max_age_days() -> u32 {
+    25
+}
+
+fn default_execution_slot_wait_secs() -> u32 {
+    60
+}
+
+fn default_open_in_browser() -> bool {
+    true
+}
+
+fn default_log_file_max_bytes() -> u64 {
+    10 * 1024 * 1024
+}
+
+/// Optional file-backed sink for raw execution logs, written alongside the database.
+///
+/// `path_template` may contain `{execution_id}` and `{task_attempt_id}` placeholders.
+/// Relative paths are resolved against the asset directory.
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct LogFileSinkConfig {
+    pub path_template: String,
+    #[serde(default = "default_log_file_max_bytes")]
+    pub max_file_bytes: u64,
+    /// Maximum number of rotated files to keep per process (unbounded when unset)
+    #[serde(default)]
+    pub max_files: Option<u32>,
+}
+
+/// Line-ending normalization applied to copied project files and auto-commits
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LineEndings {
+    #[default]
+    Preserve, // Leave line endings untouched
+    Lf,   // Normalize to LF
+    Crlf, // Normalize to CRLF in copied files; commits still store LF
+}
+
+impl LineEndings {
+    /// `core.autocrlf` value used when staging auto-commits, if normalizing
+    pub fn autocrlf(self) -> Option<&'static str> {
+        match self {
+            Self::Preserve => None,
+            Self::Lf => Some("input"),
+            Self::Crlf => Some("true"),
+        }
+    }
+}
+
+/// Where auto-commits after an agent run take their message from
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoCommitMessageMode {
+    #[default]
+    SessionSummary, // The agent's last assistant message, if one was captured
+    TaskTitle, // The task title
+    Generic,   // A fixed message naming the attempt
+}
+
+/// HTTP endpoints that are POSTed to when a task changes status
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+pub struct WebhooksConfig {
+    #[serde(default)]
+    pub urls: Vec<String>,
+    /// Statuses that trigger a webhook; empty sends every status change
+    #[serde(default)]
+    pub statuses: Vec<TaskStatus>,
+}
+
+/// Caps on coding agent executions running at once. Starts beyond a cap are
+/// rejected; an agent chained after its setup script waits for a slot instead.
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct ExecutionLimitsConfig {
+    /// Across all projects; unlimited when unset
+    #[serde(default)]
+    pub max_concurrent: Option<u32>,
+    /// Seconds a chained agent start waits for a free slot before giving up
+    #[serde(default = "default_execution_slot_wait_secs")]
+    pub slot_wait_secs: u32,
+}
+
+impl Default for ExecutionLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: None,
+            slot_wait_secs: default_execution_slot_wait_secs(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, TS)]
 pub struct Config {
     pub config_version: String,
@@ -39,9 +144,63 @@ pub struct Config {
     pub showcases: ShowcaseState,
     #[serde(default = "default_auto_commit_enabled")]
     pub auto_commit_enabled: bool,
+    #[serde(default)]
+    pub log_file_sink: Option<LogFileSinkConfig>,
+    /// Record an empty commit when a cleanup script leaves no changes
+    #[serde(default)]
+    pub allow_empty_cleanup_commit: bool,
+    #[serde(default)]
+    pub line_endings: LineEndings,
+    /// Secrets injected into attempt processes according to their scope; values are redacted from logs
+    #[serde(default)]
+    pub secrets: Vec<ScopedSecret>,
+    /// Seconds between periodic worktree cleanup passes
+    #[serde(default = "default_worktree_cleanup_interval_secs")]
+    pub worktree_cleanup_interval_secs: u64,
+    /// Delete worktree directories that no longer belong to any task attempt
+    #[serde(default = "default_worktree_orphan_cleanup_enabled")]
+    pub worktree_orphan_cleanup_enabled: bool,
+    #[serde(default)]
+    pub auto_commit_message_mode: AutoCommitMessageMode,
+    #[serde(default)]
+    pub webhooks: WebhooksConfig,
+    /// Days after which an agent login is flagged for renewal by re-running setup
+    #[serde(default = "default_agent_login_max_age_days")]
+    pub agent_login_max_age_days: u32,
+    #[serde(default)]
+    pub execution_limits: ExecutionLimitsConfig,
+    /// Stop an execution that produces no output for this many seconds; zero or unset disables it
+    #[serde(default)]
+    pub execution_idle_timeout_secs: Option<u32>,
+    /// Open newly created pull requests in the browser
+    #[serde(default = "default_open_in_browser")]
+    pub auto_open_pr_in_browser: bool,
+    /// Open the app in the browser when the server starts (release builds only)
+    #[serde(default = "default_open_in_browser")]
+    pub open_browser_on_startup: bool,
 }
 
 impl Config {
+    /// How long a running execution may stay silent before it is stopped
+    pub fn execution_idle_timeout(&self) -> Option<std::time::Duration> {
+        self.execution_idle_timeout_secs
+            .filter(|secs| *secs > 0)
+            .map(|secs| std::time::Duration::from_secs(secs.into()))
+    }
+
+    /// Copy safe to return from the API, with secret values masked
+    pub fn redacted(&self) -> Self {
+        Self {
+            secrets: redact_secret_values(&self.secrets),
+            ..self.clone()
+        }
+    }
+
+    /// Keep the `stored` value of any secret a client sent back masked
+    pub fn restore_redacted_secrets(&mut self, stored: &Config) {
+        restore_redacted_secret_values(&mut self.secrets, &stored.secrets);
+    }
+
     fn from_v8_config(old_config: v8::Config) -> Self {
         Self {
             config_version: "v9".to_string(),
@@ -60,6 +219,19 @@ impl Config {
             git_branch_prefix: old_config.git_branch_prefix,
             showcases: old_config.showcases,
             auto_commit_enabled: default_auto_commit_enabled(),
+            log_file_sink: None,
+            allow_empty_cleanup_commit: false,
+            line_endings: LineEndings::default(),
+            secrets: Vec::new(),
+            worktree_cleanup_interval_secs: default_worktree_cleanup_interval_secs(),
+            worktree_orphan_cleanup_enabled: default_worktree_orphan_cleanup_enabled(),
+            auto_commit_message_mode: AutoCommitMessageMode::default(),
+            webhooks: WebhooksConfig::default(),
+            agent_login_max_age_days: default_agent_login_max_age_days(),
+            execution_limits: ExecutionLimitsConfig::default(),
+            execution_idle_timeout_secs: None,
+            auto_open_pr_in_browser: default_open_in_browser(),
+            open_browser_on_startup: default_open_in_browser(),
         }
     }
 
@@ -67,13 +239,18 @@ impl Config {
         let old_config = v8::Config::from(raw_config.to_string());
         Ok(Self::from_v8_config(old_config))
     }
+
+    /// Parse `raw_config` only if it is already a v9 config, without migrating it
+    pub fn from_current_version(raw_config: &str) -> Option<Self> {
+        serde_json::from_str::<Config>(raw_config)
+            .ok()
+            .filter(|config| config.config_version == "v9")
+    }
 }
 
 impl From<String> for Config {
     fn from(raw_config: String) -> Self {
-        if let Ok(config) = serde_json::from_str::<Config>(&raw_config)
-            && config.config_version == "v9"
-        {
+        if let Some(config) = Self::from_current_version(&raw_config) {
             return config;
         }
 
@@ -109,6 +286,19 @@ impl Default for Config {
             git_branch_prefix: default_git_branch_prefix(),
             showcases: ShowcaseState::default(),
             auto_commit_enabled: default_auto_commit_enabled(),
+            log_file_sink: None,
+            allow_empty_cleanup_commit: false,
+            line_endings: LineEndings::default(),
+            secrets: Vec::new(),
+            worktree_cleanup_interval_secs: default_worktree_cleanup_interval_secs(),
+            worktree_orphan_cleanup_enabled: default_worktree_orphan_cleanup_enabled(),
+            auto_commit_message_mode: AutoCommitMessageMode::default(),
+            webhooks: WebhooksConfig::default(),
+            agent_login_max_age_days: default_agent_login_max_age_days(),
+            execution_limits: ExecutionLimitsConfig::default(),
+            execution_idle_timeout_secs: None,
+            auto_open_pr_in_browser: default_open_in_browser(),
+            open_browser_on_startup: default_open_in_browser(),
         }
     }
 }
@@ -1,7 +1,8 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, LazyLock},
+    time::{Duration, Instant},
 };
 
 use anyhow::Error as AnyhowError;
@@ -15,6 +16,7 @@ use db::{
         },
         execution_process_logs::ExecutionProcessLogs,
         executor_session::{CreateExecutorSession, ExecutorSession},
+        project::Project,
         task::{Task, TaskStatus},
         task_attempt::{TaskAttempt, TaskAttemptError},
     },
@@ -25,30 +27,50 @@ use executors::{
         coding_agent_initial::CodingAgentInitialRequest,
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
-    executors::{ExecutorError, StandardCodingAgentExecutor},
+    env::EnvOverrides,
+    executors::{BaseCodingAgent, ExecutorError, StandardCodingAgentExecutor},
     logs::{NormalizedEntry, NormalizedEntryError, NormalizedEntryType, utils::ConversationPatch},
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
-use futures::{StreamExt, future};
-use sqlx::Error as SqlxError;
+use futures::{StreamExt, TryStreamExt, future};
+use ignore::{WalkBuilder, overrides::OverrideBuilder};
+use json_patch::Patch;
+use serde::Serialize;
+use sqlx::{Error as SqlxError, SqlitePool};
 use thiserror::Error;
-use tokio::{sync::RwLock, task::JoinHandle};
-use utils::{
-    log_msg::LogMsg,
-    msg_store::MsgStore,
-    text::{git_branch_id, short_uuid},
+use tokio::{
+    sync::{Mutex, MutexGuard, Notify, RwLock},
+    task::JoinHandle,
 };
+use ts_rs::TS;
+use utils::{diff::Diff, log_msg::LogMsg, msg_store::MsgStore, text::normalize_line_endings};
 use uuid::Uuid;
 
 use crate::services::{
-    config::Config,
-    git::{GitService, GitServiceError},
+    branch_name,
+    branch_status::BranchStatusCache,
+    commit_message,
+    config::{
+        AutoCommitMessageMode, Config, ExecutionLimitsConfig, LineEndings, LogFileSinkConfig,
+    },
+    events::deleted_worktree_patch,
+    git::{GitCliError, GitService, GitServiceError},
+    log_file_sink::LogFileSink,
     notification::NotificationService,
-    share::SharePublisher,
+    share::{SharePublisher, update_task_status_and_share},
     worktree_manager::WorktreeError,
 };
 pub type ContainerRef = String;
 
+/// Per-attempt settings given when an attempt is created
+#[derive(Debug, Clone, Default)]
+pub struct AttemptOverrides {
+    /// Cleanup script to run instead of the project's
+    pub cleanup_script: Option<String>,
+    /// Environment variables set on every process of the attempt
+    pub env: Option<EnvOverrides>,
+}
+
 #[derive(Debug, Error)]
 pub enum ContainerError {
     #[error(transparent)]
@@ -65,10 +87,487 @@ pub enum ContainerError {
     KillFailed(std::io::Error),
     #[error(transparent)]
     TaskAttemptError(#[from] TaskAttemptError),
+    #[error("Concurrency limit reached: {running} of {limit} execution slots in use")]
+    ConcurrencyLimitReached { running: i64, limit: i64 },
     #[error(transparent)]
     Other(#[from] AnyhowError), // Catches any unclassified errors
 }
 
+/// Serializes slot checks so two starts can't both take the last free slot
+static EXECUTION_SLOT_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+/// Woken whenever an execution finishes, for chained starts waiting on a slot
+static EXECUTION_SLOT_FREED: LazyLock<Notify> = LazyLock::new(Notify::new);
+
+/// Wake chained starts waiting for a slot; call once an execution has finished.
+pub fn notify_execution_slot_freed() {
+    EXECUTION_SLOT_FREED.notify_waiters();
+}
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Running coding agent executions against the configured caps
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ExecutionSlots {
+    #[ts(type = "number")]
+    pub running: i64,
+    /// Global cap; unlimited when null
+    #[ts(type = "number | null")]
+    pub max: Option<i64>,
+    /// Running within the requested project, if any
+    #[ts(type = "number | null")]
+    pub project_running: Option<i64>,
+    #[ts(type = "number | null")]
+    pub project_max: Option<i64>,
+}
+
+impl ExecutionSlots {
+    /// The cap that is currently exhausted, as `(running, limit)`
+    pub fn exhausted(&self) -> Option<(i64, i64)> {
+        if let Some(max) = self.max
+            && self.running >= max
+        {
+            return Some((self.running, max));
+        }
+        if let (Some(running), Some(max)) = (self.project_running, self.project_max)
+            && running >= max
+        {
+            return Some((running, max));
+        }
+        None
+    }
+}
+
+/// An attempt whose worktree was removed outside vibe-kanban, published on the
+/// event stream at `/deleted_worktrees/{attempt_id}`
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct DeletedWorktree {
+    pub attempt_id: Uuid,
+    pub path: String,
+}
+
+/// Attempts flagged on the event stream as having lost their worktree. Only flags
+/// that were set get cleared, so clients never receive a `remove` for a missing path.
+#[derive(Clone, Default)]
+pub struct DeletedWorktreeFlags(Arc<std::sync::Mutex<HashSet<Uuid>>>);
+
+impl DeletedWorktreeFlags {
+    pub fn set(&self, msg_store: &MsgStore, worktree: DeletedWorktree) {
+        if self.0.lock().unwrap().insert(worktree.attempt_id) {
+            msg_store.push_patch(deleted_worktree_patch::set(&worktree));
+        }
+    }
+
+    pub fn clear(&self, msg_store: &MsgStore, attempt_id: Uuid) {
+        if self.0.lock().unwrap().remove(&attempt_id) {
+            msg_store.push_patch(deleted_worktree_patch::clear(attempt_id));
+        }
+    }
+}
+
+/// Normalized error entry for start failures the user can act on, such as a
+/// missing executable or expired agent credentials.
+pub fn start_error_entry(error: &ContainerError) -> Option<NormalizedEntry> {
+    match error {
+        ContainerError::ExecutorError(ExecutorError::ExecutableNotFound { program }) => {
+            Some(NormalizedEntry {
+                timestamp: None,
+                entry_type: NormalizedEntryType::ErrorMessage {
+                    error_type: NormalizedEntryError::SetupRequired,
+                },
+                content: format!("The required executable `{program}` is not installed."),
+                metadata: None,
+            })
+        }
+        ContainerError::ExecutorError(ExecutorError::AuthRequired(message)) => Some(
+            NormalizedEntry::auth_required(format!("{message}. Sign in again and retry.")),
+        ),
+        _ => None,
+    }
+}
+
+/// Push `branch` to its remote after an auto-commit. Best-effort: failures are
+/// logged, never returned. Returns whether the push went through.
+pub fn push_after_auto_commit(git: &GitService, worktree_path: &Path, branch: &str) -> bool {
+    match git.push_to_github(worktree_path, branch, None, false) {
+        Ok(()) => {
+            tracing::debug!("Auto-pushed branch {} after commit", branch);
+            true
+        }
+        Err(GitServiceError::GitCLI(GitCliError::PushRejected(_))) => {
+            tracing::warn!(
+                "Auto-push of branch {} rejected; the remote has diverged and needs a force push",
+                branch
+            );
+            false
+        }
+        Err(e) => {
+            tracing::warn!("Auto-push of branch {} failed: {}", branch, e);
+            false
+        }
+    }
+}
+
+/// Action that runs a project's setup script on its own, with no coding agent
+/// chained after it.
+pub fn setup_script_action(script: String, language: ScriptRequestLanguage) -> ExecutorAction {
+    ExecutorAction::new(
+        ExecutorActionType::ScriptRequest(ScriptRequest {
+            script,
+            language,
+            context: ScriptContext::SetupScript,
+        }),
+        None,
+    )
+}
+
+/// Action running `script` as a cleanup script, if there is one.
+pub fn cleanup_script_action(
+    script: Option<String>,
+    language: ScriptRequestLanguage,
+) -> Option<Box<ExecutorAction>> {
+    script.map(|script| {
+        Box::new(ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script,
+                language,
+                context: ScriptContext::CleanupScript,
+            }),
+            None,
+        ))
+    })
+}
+
+/// The message auto-commit uses for the execution in `ctx`: the built-in
+/// message for `mode`, replaced by the project's template when one is set.
+pub async fn resolve_auto_commit_message(
+    pool: &SqlitePool,
+    mode: AutoCommitMessageMode,
+    ctx: &ExecutionContext,
+) -> Option<String> {
+    let summary = match ctx.execution_process.run_reason {
+        ExecutionProcessRunReason::CodingAgent if mode == AutoCommitMessageMode::SessionSummary => {
+            match ExecutorSession::find_by_execution_process_id(pool, ctx.execution_process.id)
+                .await
+            {
+                Ok(session) => session.and_then(|s| s.summary),
+                Err(e) => {
+                    tracing::debug!(
+                        "Failed to retrieve summary for execution process {}: {}",
+                        ctx.execution_process.id,
+                        e
+                    );
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+
+    let message = commit_message::auto_commit_message(
+        &ctx.execution_process.run_reason,
+        mode,
+        summary.as_deref(),
+        &ctx.task.title,
+        ctx.task_attempt.id,
+    )?;
+
+    // A project template, when set, replaces the built-in message
+    let template = match Project::find_by_id(pool, ctx.task.project_id).await {
+        Ok(project) => project.and_then(|p| p.commit_message_template),
+        Err(e) => {
+            tracing::warn!("Failed to load project for commit message template: {}", e);
+            None
+        }
+    };
+    let rendered = template.and_then(|template| {
+        commit_message::render_commit_message_template(
+            &template,
+            &commit_message::CommitMessageContext {
+                title: &ctx.task.title,
+                description: ctx.task.description.as_deref(),
+                task_id: ctx.task.id,
+                branch: &ctx.task_attempt.branch,
+            },
+        )
+    });
+    Some(rendered.unwrap_or(message))
+}
+
+/// Whether the work an exited execution left behind gets committed: after a
+/// clean exit, once a cleanup script has finished, or whenever it stopped during
+/// shutdown, since interrupted agents exit with a failure.
+pub fn commits_on_exit(
+    run_reason: &ExecutionProcessRunReason,
+    status: &ExecutionProcessStatus,
+    exit_code: Option<i64>,
+    draining: bool,
+) -> bool {
+    let success = matches!(status, ExecutionProcessStatus::Completed) && exit_code == Some(0);
+    let cleanup_done = matches!(run_reason, ExecutionProcessRunReason::CleanupScript)
+        && !matches!(status, ExecutionProcessStatus::Running);
+    success || cleanup_done || draining
+}
+
+/// Commit what the execution in `ctx` left in its worktree, with the message
+/// `resolve_auto_commit_message` gives. Only coding agent and cleanup script
+/// runs commit; returns whether a commit was made.
+pub async fn auto_commit_execution(
+    pool: &SqlitePool,
+    git: &GitService,
+    ctx: &ExecutionContext,
+    mode: AutoCommitMessageMode,
+    allow_empty_cleanup: bool,
+    line_endings: LineEndings,
+) -> Result<bool, ContainerError> {
+    if !matches!(
+        ctx.execution_process.run_reason,
+        ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::CleanupScript,
+    ) {
+        return Ok(false);
+    }
+
+    let Some(message) = resolve_auto_commit_message(pool, mode, ctx).await else {
+        return Err(ContainerError::Other(anyhow::anyhow!(
+            "Invalid run reason for commit"
+        )));
+    };
+
+    let container_ref =
+        ctx.task_attempt.container_ref.as_ref().ok_or_else(|| {
+            ContainerError::Other(anyhow::anyhow!("Container reference not found"))
+        })?;
+
+    tracing::debug!(
+        "Committing changes for task attempt {} at path {:?}: '{}'",
+        ctx.task_attempt.id,
+        &container_ref,
+        message
+    );
+
+    // Optionally mark cleanup runs in history even when they changed nothing
+    let allow_empty = matches!(
+        ctx.execution_process.run_reason,
+        ExecutionProcessRunReason::CleanupScript
+    ) && allow_empty_cleanup;
+
+    Ok(commit_execution_changes(
+        git,
+        Path::new(container_ref),
+        &message,
+        allow_empty,
+        line_endings,
+    )?)
+}
+
+/// Commit pending changes in `worktree_path`, normalizing text files per `line_endings`.
+/// With `allow_empty`, an empty commit is recorded when there is nothing to commit.
+/// Returns whether a commit was made.
+pub fn commit_execution_changes(
+    git: &GitService,
+    worktree_path: &Path,
+    message: &str,
+    allow_empty: bool,
+    line_endings: LineEndings,
+) -> Result<bool, GitServiceError> {
+    if git.commit_with_autocrlf(worktree_path, message, line_endings.autocrlf())? {
+        return Ok(true);
+    }
+    if !allow_empty {
+        return Ok(false);
+    }
+    git.commit_empty(worktree_path, message)?;
+    Ok(true)
+}
+
+/// Expand a project's comma-separated `copy_files` list into the files to copy,
+/// as paths relative to `source_dir`.
+///
+/// Directories are copied recursively, including files git ignores. Entries that
+/// aren't existing paths and contain `*`, `?` or `[` are globs in `.gitignore`
+/// syntax, anchored at the project root: `*.env` matches only top-level files, so
+/// use `**/*.env` to search subdirectories and `\[` for a literal bracket. Globs
+/// that match nothing are skipped with a warning, while a missing exact path is
+/// an error.
+pub fn expand_copy_files(
+    source_dir: &Path,
+    copy_files: &str,
+) -> Result<Vec<PathBuf>, ContainerError> {
+    let walk_files = |root: &Path| {
+        WalkBuilder::new(root)
+            .standard_filters(false)
+            .filter_entry(|entry| entry.file_name() != ".git")
+            .build()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+            .filter_map(|entry| {
+                entry
+                    .path()
+                    .strip_prefix(source_dir)
+                    .ok()
+                    .map(Path::to_path_buf)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut files = Vec::new();
+    for entry in copy_files
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        let source = source_dir.join(entry);
+        if source.is_dir() {
+            files.extend(walk_files(&source));
+            continue;
+        }
+        if source.exists() {
+            files.push(PathBuf::from(entry));
+            continue;
+        }
+
+        if entry.contains(['*', '?', '[']) {
+            // Without a slash a gitignore glob matches at any depth, walking
+            // dependency and build directories; match from the root instead
+            let pattern = if entry.trim_end_matches('/').contains('/') {
+                entry.to_string()
+            } else {
+                format!("/{entry}")
+            };
+            let glob = OverrideBuilder::new(source_dir)
+                .add(&pattern)
+                .and_then(|builder| builder.build())
+                .map_err(|e| {
+                    ContainerError::Other(anyhow::anyhow!("Invalid copy_files glob '{entry}': {e}"))
+                })?;
+            // Only walk below the glob's literal leading directories
+            let base: PathBuf = Path::new(entry.trim_start_matches('/'))
+                .components()
+                .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+                .collect();
+            let matched: Vec<_> = walk_files(&source_dir.join(&base))
+                .into_iter()
+                .filter(|path| glob.matched(path, false).is_whitelist())
+                .collect();
+            if matched.is_empty() {
+                tracing::warn!("copy_files glob '{}' matched no files, skipping", entry);
+            }
+            files.extend(matched);
+            continue;
+        }
+
+        return Err(ContainerError::Other(anyhow::anyhow!(
+            "File {source:?} does not exist in the project directory"
+        )));
+    }
+
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Copy a project file into a worktree, rewriting line endings of text files
+/// unless `line_endings` is [`LineEndings::Preserve`].
+pub fn copy_project_file(
+    source: &Path,
+    target: &Path,
+    line_endings: LineEndings,
+) -> std::io::Result<()> {
+    let crlf = match line_endings {
+        LineEndings::Preserve => return std::fs::copy(source, target).map(|_| ()),
+        LineEndings::Lf => false,
+        LineEndings::Crlf => true,
+    };
+    let content = std::fs::read(source)?;
+    match normalize_line_endings(&content, crlf) {
+        Some(normalized) => std::fs::write(target, normalized),
+        // Binary files are copied verbatim
+        None => std::fs::write(target, content),
+    }
+}
+
+/// Run `process`'s log normalizer over its persisted raw logs on a fresh store.
+/// `None` when the process has no logs or isn't a coding agent run.
+pub async fn normalize_persisted_logs(
+    pool: &SqlitePool,
+    process: &ExecutionProcess,
+    current_dir: &Path,
+) -> Option<Arc<MsgStore>> {
+    let log_records = match ExecutionProcessLogs::find_by_execution_id(pool, process.id).await {
+        Ok(records) if !records.is_empty() => records,
+        Ok(_) => return None, // No logs exist
+        Err(e) => {
+            tracing::error!("Failed to fetch logs for execution {}: {}", process.id, e);
+            return None;
+        }
+    };
+
+    let raw_messages = match ExecutionProcessLogs::parse_logs(&log_records) {
+        Ok(msgs) => msgs,
+        Err(e) => {
+            tracing::error!("Failed to parse logs for execution {}: {}", process.id, e);
+            return None;
+        }
+    };
+
+    let executor_profile_id = match process.executor_action() {
+        Ok(executor_action) => match executor_action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                request.executor_profile_id.clone()
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                request.executor_profile_id.clone()
+            }
+            _ => {
+                tracing::debug!(
+                    "Executor action doesn't support log normalization: {:?}",
+                    process.executor_action()
+                );
+                return None;
+            }
+        },
+        Err(e) => {
+            tracing::error!("Failed to parse executor action: {}", e);
+            return None;
+        }
+    };
+
+    // Include JsonPatch messages (already normalized) and Stdout/Stderr (need normalization).
+    // Finished is pushed after populating so the normalizer knows when to stop.
+    let store = Arc::new(MsgStore::new());
+    for msg in raw_messages {
+        if matches!(
+            msg,
+            LogMsg::Stdout(_) | LogMsg::Stderr(_) | LogMsg::JsonPatch(_)
+        ) {
+            store.push(msg);
+        }
+    }
+    store.push_finished();
+
+    // Log normalization only uses the path for make_path_relative(), so the
+    // worktree doesn't need to exist
+    ExecutorConfigs::get_cached()
+        .get_coding_agent_or_default(&executor_profile_id)
+        .normalize_logs(store.clone(), current_dir);
+
+    Some(store)
+}
+
+/// JsonPatch messages the normalizer spawned by [`normalize_persisted_logs`] writes
+/// to `store`. The normalizer runs as a spawned task, so this polls the history
+/// until patches appear and gives it a moment to finish, or gives up after ~5s.
+pub async fn collect_normalized_patches(store: &MsgStore) -> Vec<LogMsg> {
+    let is_patch = |msg: &LogMsg| matches!(msg, LogMsg::JsonPatch(_));
+    for _ in 0..=100 {
+        if store.get_history().iter().any(is_patch) {
+            // Wait for normalizer to finish (it stops at Finished)
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            return store.get_history().into_iter().filter(is_patch).collect();
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    Vec::new()
+}
+
 #[async_trait]
 pub trait ContainerService {
     fn msg_stores(&self) -> &Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>;
@@ -77,6 +576,8 @@ pub trait ContainerService {
 
     fn git(&self) -> &GitService;
 
+    fn branch_status_cache(&self) -> &BranchStatusCache;
+
     fn share_publisher(&self) -> Option<&SharePublisher>;
 
     fn task_attempt_to_current_dir(&self, task_attempt: &TaskAttempt) -> PathBuf;
@@ -91,10 +592,87 @@ pub trait ContainerService {
         custom_branch: Option<String>,
         use_existing_branch: bool,
         conversation_history: Option<String>,
+        overrides: AttemptOverrides,
+    ) -> Result<TaskAttempt, ContainerError>;
+
+    /// Create an attempt on an existing branch without creating a new branch or starting
+    /// an agent. The attempt's changes are the branch's commits since it forked from
+    /// `base_branch`.
+    async fn adopt_branch(
+        &self,
+        task: &Task,
+        executor: BaseCodingAgent,
+        branch: &str,
+        base_branch: &str,
     ) -> Result<TaskAttempt, ContainerError>;
 
     async fn kill_all_running_processes(&self) -> Result<(), ContainerError>;
 
+    /// Number of executions whose child process is currently tracked.
+    /// Default implementation reports none (for deployments without local processes).
+    async fn running_child_count(&self) -> usize {
+        0
+    }
+
+    /// Start the next queued follow-up of every attempt that has nothing running,
+    /// for queues restored at startup that no finishing execution will pick up.
+    /// Default implementation does nothing (for deployments without queued follow-ups).
+    async fn resume_queued_follow_ups(&self) -> Result<(), ContainerError> {
+        Ok(())
+    }
+
+    /// Ask running executions to stop without waiting for them to exit.
+    /// Default implementation does nothing (for deployments without local processes).
+    async fn interrupt_running_processes(&self) {}
+
+    /// Stop finished executions from starting next actions or queued follow-ups.
+    /// Default implementation does nothing (for deployments without local processes).
+    fn begin_draining(&self) {}
+
+    /// Whether `begin_draining` has been called.
+    fn is_draining(&self) -> bool {
+        false
+    }
+
+    /// Stop starting new work, interrupt running executions and give them up to
+    /// `grace` to exit cleanly, then kill the rest via `kill_all_running_processes`.
+    /// Executions that exit meanwhile still get their changes auto-committed (see
+    /// `commits_on_exit`). Dev servers never finish by themselves, so they are
+    /// stopped straight away.
+    async fn drain_running_processes(&self, grace: Duration) -> Result<(), ContainerError> {
+        self.begin_draining();
+        let deadline = Instant::now() + grace;
+        for process in ExecutionProcess::find_running(&self.db().pool).await? {
+            if process.run_reason == ExecutionProcessRunReason::DevServer
+                && let Err(e) = self
+                    .stop_execution(&process, ExecutionProcessStatus::Killed)
+                    .await
+            {
+                tracing::error!("Failed to stop dev server {}: {}", process.id, e);
+            }
+        }
+        let mut remaining = self.running_child_count().await;
+        if remaining > 0 {
+            self.interrupt_running_processes().await;
+            tracing::info!(
+                "Waiting up to {:?} for {} interrupted execution(s) to exit",
+                grace,
+                remaining
+            );
+        }
+        while remaining > 0 && Instant::now() < deadline {
+            tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+            remaining = self.running_child_count().await;
+        }
+        if remaining > 0 {
+            tracing::warn!(
+                "{} execution(s) still running after the shutdown grace period",
+                remaining
+            );
+        }
+        self.kill_all_running_processes().await
+    }
+
     /// Signal the worktree cleanup background task to stop.
     /// Default implementation does nothing (for deployments without worktree cleanup).
     fn request_worktree_cleanup_shutdown(&self) {}
@@ -123,6 +701,30 @@ pub trait ContainerService {
         Ok(false)
     }
 
+    /// Kill every running execution process in a project, dev servers included.
+    /// Returns how many were stopped; failures are logged and left out of the count.
+    async fn stop_project_processes(&self, project_id: Uuid) -> Result<usize, ContainerError> {
+        let processes =
+            ExecutionProcess::find_running_by_project(&self.db().pool, project_id).await?;
+
+        let mut stopped = 0;
+        for process in processes {
+            match self
+                .stop_execution(&process, ExecutionProcessStatus::Killed)
+                .await
+            {
+                Ok(()) => stopped += 1,
+                Err(e) => tracing::error!(
+                    "Failed to stop execution process {} in project {}: {}",
+                    process.id,
+                    project_id,
+                    e
+                ),
+            }
+        }
+        Ok(stopped)
+    }
+
     /// Stop execution processes for task attempts without cleanup
     async fn stop_task_processes(
         &self,
@@ -135,7 +737,7 @@ pub trait ContainerService {
     }
 
     /// A context is finalized when
-    /// - Always when the execution process has failed or been killed
+    /// - Always when the execution process has failed, been killed or timed out
     /// - Never when the run reason is DevServer
     /// - The next action is None (no follow-up actions)
     fn should_finalize(&self, ctx: &ExecutionContext) -> bool {
@@ -145,10 +747,12 @@ pub trait ContainerService {
         ) {
             return false;
         }
-        // Always finalize failed or killed executions, regardless of next action
+        // Always finalize failed, killed or timed out executions, regardless of next action
         if matches!(
             ctx.execution_process.status,
-            ExecutionProcessStatus::Failed | ExecutionProcessStatus::Killed
+            ExecutionProcessStatus::Failed
+                | ExecutionProcessStatus::Killed
+                | ExecutionProcessStatus::TimedOut
         ) {
             return true;
         }
@@ -167,21 +771,15 @@ pub trait ContainerService {
         share_publisher: Option<&SharePublisher>,
         ctx: &ExecutionContext,
     ) {
-        match Task::update_status(&self.db().pool, ctx.task.id, TaskStatus::InReview).await {
-            Ok(_) => {
-                if let Some(publisher) = share_publisher
-                    && let Err(err) = publisher.update_shared_task_by_id(ctx.task.id).await
-                {
-                    tracing::warn!(
-                        ?err,
-                        "Failed to propagate shared task update for {}",
-                        ctx.task.id
-                    );
-                }
-            }
-            Err(e) => {
-                tracing::error!("Failed to update task status to InReview: {e}");
-            }
+        if let Err(e) = update_task_status_and_share(
+            &self.db().pool,
+            share_publisher.cloned(),
+            ctx.task.id,
+            TaskStatus::InReview,
+        )
+        .await
+        {
+            tracing::error!("Failed to update task status to InReview: {e}");
         }
         let notify_cfg = config.read().await.notifications.clone();
         NotificationService::notify_execution_halted(notify_cfg, ctx).await;
@@ -239,24 +837,18 @@ pub trait ContainerService {
                 TaskAttempt::find_by_id(&self.db().pool, process.task_attempt_id).await
                 && let Ok(Some(task)) = task_attempt.parent_task(&self.db().pool).await
             {
-                match Task::update_status(&self.db().pool, task.id, TaskStatus::InReview).await {
-                    Ok(_) => {
-                        if let Some(publisher) = self.share_publisher()
-                            && let Err(err) = publisher.update_shared_task_by_id(task.id).await
-                        {
-                            tracing::warn!(
-                                ?err,
-                                "Failed to propagate shared task update for {}",
-                                task.id
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!(
-                            "Failed to update task status to InReview for orphaned attempt: {}",
-                            e
-                        );
-                    }
+                if let Err(e) = update_task_status_and_share(
+                    &self.db().pool,
+                    self.share_publisher().cloned(),
+                    task.id,
+                    TaskStatus::InReview,
+                )
+                .await
+                {
+                    tracing::error!(
+                        "Failed to update task status to InReview for orphaned attempt: {}",
+                        e
+                    );
                 }
             }
         }
@@ -311,17 +903,12 @@ pub trait ContainerService {
         Ok(())
     }
 
-    fn cleanup_action(&self, cleanup_script: Option<String>) -> Option<Box<ExecutorAction>> {
-        cleanup_script.map(|script| {
-            Box::new(ExecutorAction::new(
-                ExecutorActionType::ScriptRequest(ScriptRequest {
-                    script,
-                    language: ScriptRequestLanguage::Bash,
-                    context: ScriptContext::CleanupScript,
-                }),
-                None,
-            ))
-        })
+    fn cleanup_action(
+        &self,
+        cleanup_script: Option<String>,
+        language: ScriptRequestLanguage,
+    ) -> Option<Box<ExecutorAction>> {
+        cleanup_script_action(cleanup_script, language)
     }
 
     async fn try_stop(&self, task_attempt: &TaskAttempt) {
@@ -370,6 +957,38 @@ pub trait ContainerService {
 
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError>;
 
+    /// The message `try_commit_changes` would use for this execution right now.
+    async fn auto_commit_message(&self, ctx: &ExecutionContext) -> Option<String> {
+        let mode = self.auto_commit_message_mode().await;
+        resolve_auto_commit_message(&self.db().pool, mode, ctx).await
+    }
+
+    /// Push the attempt branch after a successful auto-commit if the project opted in.
+    async fn try_auto_push(&self, ctx: &ExecutionContext) {
+        let project = match Project::find_by_id(&self.db().pool, ctx.task.project_id).await {
+            Ok(Some(project)) => project,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!("Failed to load project for auto-push: {}", e);
+                return;
+            }
+        };
+        if !project.auto_push {
+            return;
+        }
+
+        let git = self.git().clone();
+        let worktree_path = self.task_attempt_to_current_dir(&ctx.task_attempt);
+        let branch = ctx.task_attempt.branch.clone();
+        if let Err(e) = tokio::task::spawn_blocking(move || {
+            push_after_auto_commit(&git, &worktree_path, &branch)
+        })
+        .await
+        {
+            tracing::warn!("Auto-push task failed: {}", e);
+        }
+    }
+
     async fn copy_project_files(
         &self,
         source_dir: &Path,
@@ -384,6 +1003,14 @@ pub trait ContainerService {
         stats_only: bool,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, ContainerError>;
 
+    /// The attempt's current diff, collected in one go. Contents are omitted
+    /// under the same rules as [`Self::stream_diff`].
+    async fn collect_diff(
+        &self,
+        task_attempt: &TaskAttempt,
+        stats_only: bool,
+    ) -> Result<Vec<Diff>, ContainerError>;
+
     /// Fetch the MsgStore for a given execution ID, panicking if missing.
     async fn get_msg_store_by_id(&self, uuid: &Uuid) -> Option<Arc<MsgStore>> {
         let map = self.msg_stores().read().await;
@@ -392,65 +1019,201 @@ pub trait ContainerService {
 
     async fn git_branch_prefix(&self) -> String;
 
-    async fn git_branch_from_task_attempt(&self, attempt_id: &Uuid, task_title: &str) -> String {
-        let task_title_id = git_branch_id(task_title);
-        let prefix = self.git_branch_prefix().await;
+    /// File sink settings for raw logs; `None` keeps logs in the database only.
+    async fn log_file_sink_config(&self) -> Option<LogFileSinkConfig>;
 
-        if prefix.is_empty() {
-            format!("{}-{}", short_uuid(attempt_id), task_title_id)
-        } else {
-            format!("{}/{}-{}", prefix, short_uuid(attempt_id), task_title_id)
+    async fn auto_commit_message_mode(&self) -> AutoCommitMessageMode;
+
+    async fn execution_limits(&self) -> ExecutionLimitsConfig;
+
+    /// Running coding agent executions, globally and within `project` if given.
+    async fn execution_slots(
+        &self,
+        project: Option<&Project>,
+    ) -> Result<ExecutionSlots, ContainerError> {
+        let pool = &self.db().pool;
+        let limits = self.execution_limits().await;
+        let running = ExecutionProcess::count_running_coding_agents(pool, None).await?;
+        let project_running = match project {
+            Some(project) => {
+                Some(ExecutionProcess::count_running_coding_agents(pool, Some(project.id)).await?)
+            }
+            None => None,
+        };
+        Ok(ExecutionSlots {
+            running,
+            max: limits.max_concurrent.map(i64::from),
+            project_running,
+            project_max: project.and_then(|p| p.max_concurrent_executions),
+        })
+    }
+
+    /// Claim a free coding agent slot, failing right away when a cap is exhausted.
+    /// The returned guard must be held until the execution's process row exists.
+    async fn claim_execution_slot(
+        &self,
+        project: &Project,
+    ) -> Result<MutexGuard<'static, ()>, ContainerError> {
+        let guard = EXECUTION_SLOT_LOCK.lock().await;
+        match self.execution_slots(Some(project)).await?.exhausted() {
+            Some((running, limit)) => {
+                Err(ContainerError::ConcurrencyLimitReached { running, limit })
+            }
+            None => Ok(guard),
         }
     }
 
+    async fn open_log_file_sink(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Option<LogFileSink> {
+        let config = self.log_file_sink_config().await?;
+        let path = LogFileSink::resolve_path(
+            &config,
+            &utils::assets::asset_dir(),
+            execution_process.id,
+            execution_process.task_attempt_id,
+        );
+        match LogFileSink::open(path.clone(), config.max_file_bytes, config.max_files).await {
+            Ok(sink) => Some(sink),
+            Err(e) => {
+                tracing::error!(
+                    "Failed to open log file {} for execution {}: {}",
+                    path.display(),
+                    execution_process.id,
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Branch name for a new attempt, from the project's branch template when set.
+    async fn git_branch_from_task_attempt(&self, attempt_id: &Uuid, task: &Task) -> String {
+        let prefix = self.git_branch_prefix().await;
+        let template = match Project::find_by_id(&self.db().pool, task.project_id).await {
+            Ok(project) => project.and_then(|p| p.branch_template),
+            Err(e) => {
+                tracing::warn!("Failed to load project for branch template: {}", e);
+                None
+            }
+        };
+
+        branch_name::branch_name(
+            template.as_deref(),
+            &branch_name::BranchNameContext {
+                prefix: &prefix,
+                attempt_id: *attempt_id,
+                task_id: task.id,
+                title: &task.title,
+            },
+        )
+    }
+
     async fn stream_raw_logs(
         &self,
         id: &Uuid,
     ) -> Option<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>> {
+        self.stream_raw_logs_sequenced(id)
+            .await
+            .map(|stream| stream.map_ok(|(_, msg)| msg).boxed())
+    }
+
+    /// Raw Stdout/Stderr logs (then `Finished`) with the sequence number each
+    /// message was stored under, identical whether served from memory or the DB.
+    async fn stream_raw_logs_sequenced(
+        &self,
+        id: &Uuid,
+    ) -> Option<futures::stream::BoxStream<'static, Result<(u64, LogMsg), std::io::Error>>> {
         if let Some(store) = self.get_msg_store_by_id(id).await {
             // First try in-memory store
             return Some(
                 store
-                    .history_plus_stream()
+                    .sequenced_history_plus_stream()
                     .filter(|msg| {
                         future::ready(matches!(
                             msg,
-                            Ok(LogMsg::Stdout(..) | LogMsg::Stderr(..) | LogMsg::Finished)
+                            Ok((
+                                _,
+                                LogMsg::Stdout(..) | LogMsg::Stderr(..) | LogMsg::Finished
+                            ))
                         ))
                     })
                     .boxed(),
             );
-        } else {
-            // Fallback: load from DB and create direct stream
-            let log_records =
-                match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
-                    Ok(records) if !records.is_empty() => records,
-                    Ok(_) => return None, // No logs exist
-                    Err(e) => {
-                        tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
-                        return None;
-                    }
-                };
+        }
+
+        // Fallback: load from DB and create direct stream
+        let messages = self.raw_logs_from_db(id).await?;
+        let finished_seq = messages.last().map(|(seq, _)| seq + 1).unwrap_or(0);
+        let stream = futures::stream::iter(
+            messages
+                .into_iter()
+                .chain(std::iter::once((finished_seq, LogMsg::Finished)))
+                .map(Ok::<_, std::io::Error>),
+        )
+        .boxed();
+
+        Some(stream)
+    }
+
+    /// Stored raw Stdout/Stderr logs with sequence numbers greater than `after_seq`.
+    /// Reads from memory while the process is live, falling back to the DB once the
+    /// store is gone or has already evicted messages past the cursor.
+    async fn raw_log_history(
+        &self,
+        id: &Uuid,
+        after_seq: Option<u64>,
+    ) -> Option<Vec<(u64, LogMsg)>> {
+        let is_after = |seq: u64| after_seq.is_none_or(|after| seq > after);
+
+        if let Some(store) = self.get_msg_store_by_id(id).await {
+            let history = store.get_sequenced_history();
+            let first_retained = history.first().map(|(seq, _)| *seq).unwrap_or(0);
+            let covers_cursor = after_seq.map(|after| after + 1).unwrap_or(0) >= first_retained;
+            if covers_cursor {
+                return Some(
+                    history
+                        .into_iter()
+                        .filter(|(seq, msg)| {
+                            is_after(*seq) && matches!(msg, LogMsg::Stdout(_) | LogMsg::Stderr(_))
+                        })
+                        .collect(),
+                );
+            }
+        }
 
-            let messages = match ExecutionProcessLogs::parse_logs(&log_records) {
-                Ok(msgs) => msgs,
+        let messages = self.raw_logs_from_db(id).await?;
+        Some(
+            messages
+                .into_iter()
+                .filter(|(seq, _)| is_after(*seq))
+                .collect(),
+        )
+    }
+
+    /// Persisted raw Stdout/Stderr logs for an execution, with sequence numbers.
+    async fn raw_logs_from_db(&self, id: &Uuid) -> Option<Vec<(u64, LogMsg)>> {
+        let log_records =
+            match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
+                Ok(records) if !records.is_empty() => records,
+                Ok(_) => return None, // No logs exist
                 Err(e) => {
-                    tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                    tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
                     return None;
                 }
             };
 
-            // Direct stream from parsed messages
-            let stream = futures::stream::iter(
-                messages
-                    .into_iter()
-                    .filter(|m| matches!(m, LogMsg::Stdout(_) | LogMsg::Stderr(_)))
-                    .chain(std::iter::once(LogMsg::Finished))
-                    .map(Ok::<_, std::io::Error>),
-            )
-            .boxed();
-
-            Some(stream)
+        match ExecutionProcessLogs::parse_sequenced_logs(&log_records) {
+            Ok(msgs) => Some(
+                msgs.into_iter()
+                    .filter(|(_, m)| matches!(m, LogMsg::Stdout(_) | LogMsg::Stderr(_)))
+                    .collect(),
+            ),
+            Err(e) => {
+                tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                None
+            }
         }
     }
 
@@ -472,40 +1235,6 @@ pub trait ContainerService {
             )
         } else {
             // Fallback: load from DB and normalize
-            let log_records =
-                match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
-                    Ok(records) if !records.is_empty() => records,
-                    Ok(_) => return None, // No logs exist
-                    Err(e) => {
-                        tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
-                        return None;
-                    }
-                };
-
-            let raw_messages = match ExecutionProcessLogs::parse_logs(&log_records) {
-                Ok(msgs) => msgs,
-                Err(e) => {
-                    tracing::error!("Failed to parse logs for execution {}: {}", id, e);
-                    return None;
-                }
-            };
-
-            // Create temporary store and populate
-            // Include JsonPatch messages (already normalized) and Stdout/Stderr (need normalization)
-            // NOTE: We push Finished AFTER populating so the normalizer knows when to stop,
-            // but the return stream will wait for JsonPatch entries from the live broadcast.
-            let temp_store = Arc::new(MsgStore::new());
-            for msg in raw_messages {
-                if matches!(
-                    msg,
-                    LogMsg::Stdout(_) | LogMsg::Stderr(_) | LogMsg::JsonPatch(_)
-                ) {
-                    temp_store.push(msg);
-                }
-            }
-            // Push Finished to signal end of raw logs to the normalizer
-            temp_store.push_finished();
-
             let process = match ExecutionProcess::find_by_id(&self.db().pool, *id).await {
                 Ok(Some(process)) => process,
                 Ok(None) => {
@@ -534,87 +1263,18 @@ pub trait ContainerService {
                     return None;
                 }
             };
-
-            // Note: ensure_container_exists is NOT needed here - log normalization only uses
-            // the path string for make_path_relative(), which doesn't access the filesystem.
             let current_dir = self.task_attempt_to_current_dir(&task_attempt);
 
-            let executor_action = if let Ok(executor_action) = process.executor_action() {
-                executor_action
-            } else {
-                tracing::error!(
-                    "Failed to parse executor action: {:?}",
-                    process.executor_action()
-                );
-                return None;
-            };
+            let temp_store =
+                normalize_persisted_logs(&self.db().pool, &process, &current_dir).await?;
 
-            // Spawn normalizer on populated store
-            match executor_action.typ() {
-                ExecutorActionType::CodingAgentInitialRequest(request) => {
-                    let executor = ExecutorConfigs::get_cached()
-                        .get_coding_agent_or_default(&request.executor_profile_id);
-                    executor.normalize_logs(temp_store.clone(), &current_dir);
-                }
-                ExecutorActionType::CodingAgentFollowUpRequest(request) => {
-                    let executor = ExecutorConfigs::get_cached()
-                        .get_coding_agent_or_default(&request.executor_profile_id);
-                    executor.normalize_logs(temp_store.clone(), &current_dir);
-                }
-                _ => {
-                    tracing::debug!(
-                        "Executor action doesn't support log normalization: {:?}",
-                        process.executor_action()
-                    );
-                    return None;
-                }
-            }
-
-            // For historic logs loaded from DB, we need to wait for the normalizer to
-            // complete before returning entries. The normalizer runs as a spawned task
-            // that reads stdout entries and emits JsonPatch entries.
-            //
-            // We create a stream that polls the store's history until we see that the
-            // normalizer has finished (by detecting JsonPatch entries or timeout).
-            // Then we return all JsonPatch entries from history followed by Finished.
+            // For historic logs loaded from DB, wait for the normalizer to complete
+            // before returning its JsonPatch entries, followed by Finished.
             Some(
                 async_stream::stream! {
-                    let mut poll_count = 0usize;
-
-                    // Poll for JsonPatch entries in history
-                    // The normalizer adds them as it processes stdout
-                    loop {
-                        let history = temp_store.get_history();
-                        let json_patch_count = history
-                            .iter()
-                            .filter(|msg| matches!(msg, LogMsg::JsonPatch(_)))
-                            .count();
-
-                        // If we have JsonPatch entries, wait a bit more to ensure normalizer is fully done
-                        if json_patch_count > 0 {
-                            // Wait for normalizer to finish (it stops at Finished)
-                            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
-
-                            // Get final history and yield all JsonPatch entries
-                            let final_history = temp_store.get_history();
-                            for msg in final_history {
-                                if matches!(msg, LogMsg::JsonPatch(_)) {
-                                    yield Ok::<_, std::io::Error>(msg);
-                                }
-                            }
-                            break;
-                        }
-
-                        poll_count += 1;
-                        // Timeout after ~5 seconds (100 * 50ms) if no JsonPatch entries appear
-                        if poll_count > 100 {
-                            break;
-                        }
-
-                        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+                    for msg in collect_normalized_patches(&temp_store).await {
+                        yield Ok::<_, std::io::Error>(msg);
                     }
-
-                    // Always yield Finished at the end
                     yield Ok(LogMsg::Finished);
                 }
                 .boxed(),
@@ -622,7 +1282,45 @@ pub trait ContainerService {
         }
     }
 
-    fn spawn_stream_raw_logs_to_db(&self, execution_id: &Uuid) -> JoinHandle<()> {
+    /// Conversation patches a process has produced so far: the live history while
+    /// its store is in memory, otherwise its persisted raw logs normalized again.
+    async fn normalized_log_patches(&self, process: &ExecutionProcess) -> Vec<Patch> {
+        let messages = if let Some(store) = self.get_msg_store_by_id(&process.id).await {
+            store.get_history()
+        } else {
+            let task_attempt = match process.parent_task_attempt(&self.db().pool).await {
+                Ok(Some(task_attempt)) => task_attempt,
+                Ok(None) => return Vec::new(),
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to fetch task attempt {}: {}",
+                        process.task_attempt_id,
+                        e
+                    );
+                    return Vec::new();
+                }
+            };
+            let current_dir = self.task_attempt_to_current_dir(&task_attempt);
+            match normalize_persisted_logs(&self.db().pool, process, &current_dir).await {
+                Some(store) => collect_normalized_patches(&store).await,
+                None => Vec::new(),
+            }
+        };
+
+        messages
+            .into_iter()
+            .filter_map(|msg| match msg {
+                LogMsg::JsonPatch(patch) => Some(patch),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn spawn_stream_raw_logs_to_db(
+        &self,
+        execution_id: &Uuid,
+        mut log_file_sink: Option<LogFileSink>,
+    ) -> JoinHandle<()> {
         let execution_id = *execution_id;
         let msg_stores = self.msg_stores().clone();
         let db = self.db().clone();
@@ -635,9 +1333,9 @@ pub trait ContainerService {
             };
 
             if let Some(store) = store {
-                let mut stream = store.history_plus_stream();
+                let mut stream = store.sequenced_history_plus_stream();
 
-                while let Some(Ok(msg)) = stream.next().await {
+                while let Some(Ok((seq, msg))) = stream.next().await {
                     match &msg {
                         LogMsg::Stdout(_) | LogMsg::Stderr(_) => {
                             // Serialize this individual message as a JSONL line
@@ -645,11 +1343,22 @@ pub trait ContainerService {
                                 Ok(jsonl_line) => {
                                     let jsonl_line_with_newline = format!("{jsonl_line}\n");
 
+                                    if let Some(sink) = log_file_sink.as_mut()
+                                        && let Err(e) = sink.append(&jsonl_line_with_newline).await
+                                    {
+                                        tracing::error!(
+                                            "Failed to write log file for execution {}: {}",
+                                            execution_id,
+                                            e
+                                        );
+                                    }
+
                                     // Append this line to the database
                                     if let Err(e) = ExecutionProcessLogs::append_log_line(
                                         &db.pool,
                                         execution_id,
                                         &jsonl_line_with_newline,
+                                        Some(seq as i64),
                                     )
                                     .await
                                     {
@@ -741,14 +1450,18 @@ pub trait ContainerService {
             None => base_prompt,
         };
 
-        let cleanup_action = self.cleanup_action(project.cleanup_script);
+        let script_language = project.effective_script_language();
+        let cleanup_action = self.cleanup_action(
+            task_attempt.effective_cleanup_script(project.cleanup_script),
+            script_language,
+        );
 
         // Choose whether to execute the setup_script or coding agent first
         let execution_process = if let Some(setup_script) = project.setup_script {
             let executor_action = ExecutorAction::new(
                 ExecutorActionType::ScriptRequest(ScriptRequest {
                     script: setup_script,
-                    language: ScriptRequestLanguage::Bash,
+                    language: script_language,
                     context: ScriptContext::SetupScript,
                 }),
                 // once the setup script is done, run the initial coding agent request
@@ -799,20 +1512,26 @@ pub trait ContainerService {
             .parent_task(&self.db().pool)
             .await?
             .ok_or(SqlxError::RowNotFound)?;
+        // Coding agents are the heavy processes; hold a slot until the process row
+        // created below counts towards the running total
+        let slot_guard = if run_reason == &ExecutionProcessRunReason::CodingAgent {
+            let project = Project::find_by_id(&self.db().pool, task.project_id)
+                .await?
+                .ok_or(SqlxError::RowNotFound)?;
+            Some(self.claim_execution_slot(&project).await?)
+        } else {
+            None
+        };
         if task.status != TaskStatus::InProgress
             && run_reason != &ExecutionProcessRunReason::DevServer
         {
-            Task::update_status(&self.db().pool, task.id, TaskStatus::InProgress).await?;
-
-            if let Some(publisher) = self.share_publisher()
-                && let Err(err) = publisher.update_shared_task_by_id(task.id).await
-            {
-                tracing::warn!(
-                    ?err,
-                    "Failed to propagate shared task update for {}",
-                    task.id
-                );
-            }
+            update_task_status_and_share(
+                &self.db().pool,
+                self.share_publisher().cloned(),
+                task.id,
+                TaskStatus::InProgress,
+            )
+            .await?;
         }
         // Create new execution process record
         // Capture current HEAD as the "before" commit for this execution
@@ -837,6 +1556,7 @@ pub trait ContainerService {
             before_head_commit.as_deref(),
         )
         .await?;
+        drop(slot_guard);
 
         if let Some(prompt) = match executor_action.typ() {
             ExecutorActionType::CodingAgentInitialRequest(coding_agent_request) => {
@@ -891,29 +1611,20 @@ pub trait ContainerService {
                     &self.db().pool,
                     execution_process.id,
                     &format!("{json_line}\n"),
+                    None,
                 )
                 .await;
             }
 
             // Emit NextAction with failure context for coding agent requests
-            if let ContainerError::ExecutorError(ExecutorError::ExecutableNotFound { program }) =
-                &start_error
-            {
-                let help_text = format!("The required executable `{program}` is not installed.");
-                let error_message = NormalizedEntry {
-                    timestamp: None,
-                    entry_type: NormalizedEntryType::ErrorMessage {
-                        error_type: NormalizedEntryError::SetupRequired,
-                    },
-                    content: help_text,
-                    metadata: None,
-                };
+            if let Some(error_message) = start_error_entry(&start_error) {
                 let patch = ConversationPatch::add_normalized_entry(2, error_message);
                 if let Ok(json_line) = serde_json::to_string::<LogMsg>(&LogMsg::JsonPatch(patch)) {
                     let _ = ExecutionProcessLogs::append_log_line(
                         &self.db().pool,
                         execution_process.id,
                         &format!("{json_line}\n"),
+                        None,
                     )
                     .await;
                 }
@@ -945,7 +1656,8 @@ pub trait ContainerService {
             }
         }
 
-        self.spawn_stream_raw_logs_to_db(&execution_process.id);
+        let log_file_sink = self.open_log_file_sink(&execution_process).await;
+        self.spawn_stream_raw_logs_to_db(&execution_process.id, log_file_sink);
         Ok(execution_process)
     }
 
@@ -975,8 +1687,33 @@ pub trait ContainerService {
             ) => ExecutionProcessRunReason::CodingAgent,
         };
 
-        self.start_execution(&ctx.task_attempt, next_action, &next_run_reason)
-            .await?;
+        // A chained agent start (after its setup script) waits for a slot to free
+        // up instead of failing the attempt, without holding up other starts
+        let deadline = Instant::now()
+            + Duration::from_secs(u64::from(self.execution_limits().await.slot_wait_secs));
+        loop {
+            let freed = EXECUTION_SLOT_FREED.notified();
+            tokio::pin!(freed);
+            freed.as_mut().enable();
+            match self
+                .start_execution(&ctx.task_attempt, next_action, &next_run_reason)
+                .await
+            {
+                Err(ContainerError::ConcurrencyLimitReached { running, limit })
+                    if Instant::now() < deadline =>
+                {
+                    tracing::debug!(
+                        "Waiting for an execution slot for task attempt {} ({running} of {limit} in use)",
+                        ctx.task_attempt.id
+                    );
+                    let _ = tokio::time::timeout_at(deadline.into(), freed).await;
+                }
+                result => {
+                    result?;
+                    break;
+                }
+            }
+        }
 
         tracing::debug!("Started next action: {:?}", next_action);
         Ok(())
@@ -993,3 +1730,550 @@ pub trait ContainerService {
         Ok(false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_required_start_error_emits_reauth_entry() {
+        let err = ContainerError::ExecutorError(ExecutorError::AuthRequired(
+            "Codex authentication required".to_string(),
+        ));
+        let entry = start_error_entry(&err).expect("auth errors produce an entry");
+
+        assert!(entry.is_auth_required());
+        assert!(matches!(
+            entry.entry_type,
+            NormalizedEntryType::ErrorMessage {
+                error_type: NormalizedEntryError::AuthRequired
+            }
+        ));
+        assert!(entry.content.starts_with("Codex authentication required"));
+
+        let other = ContainerError::Other(anyhow::anyhow!("boom"));
+        assert!(start_error_entry(&other).is_none());
+    }
+
+    #[test]
+    fn test_executions_stopped_while_draining_are_committed() {
+        use ExecutionProcessRunReason::{CleanupScript, CodingAgent};
+        use ExecutionProcessStatus::{Completed, Failed, Killed, Running};
+
+        assert!(commits_on_exit(&CodingAgent, &Completed, Some(0), false));
+        assert!(!commits_on_exit(&CodingAgent, &Completed, Some(1), false));
+        assert!(!commits_on_exit(&CodingAgent, &Failed, Some(130), false));
+        assert!(commits_on_exit(&CleanupScript, &Failed, Some(1), false));
+        assert!(!commits_on_exit(&CleanupScript, &Running, None, false));
+
+        // An agent interrupted by shutdown exits with a failure or is killed,
+        // and its changes are committed anyway
+        assert!(commits_on_exit(&CodingAgent, &Failed, Some(130), true));
+        assert!(commits_on_exit(&CodingAgent, &Killed, None, true));
+    }
+
+    #[tokio::test]
+    async fn test_slot_freed_wakes_a_waiter_registered_before_the_check() {
+        let freed = EXECUTION_SLOT_FREED.notified();
+        tokio::pin!(freed);
+        freed.as_mut().enable();
+
+        // The slot frees up between the failed check and the wait
+        notify_execution_slot_freed();
+
+        tokio::time::timeout(Duration::from_secs(1), freed)
+            .await
+            .expect("a freed slot wakes the waiting start");
+    }
+
+    #[test]
+    fn test_deleted_worktree_flag_is_cleared_once() {
+        let store = MsgStore::new();
+        let flags = DeletedWorktreeFlags::default();
+        let flagged = Uuid::new_v4();
+
+        // Attempts cleaned up without being flagged never get a remove
+        flags.clear(&store, Uuid::new_v4());
+        assert!(store.get_history().is_empty());
+
+        let worktree = DeletedWorktree {
+            attempt_id: flagged,
+            path: "/tmp/gone".to_string(),
+        };
+        flags.set(&store, worktree.clone());
+        flags.set(&store, worktree);
+        assert_eq!(store.get_history().len(), 1);
+
+        // Recreated, then reused by follow-ups, diffs and dev servers
+        flags.clear(&store, flagged);
+        flags.clear(&store, flagged);
+        flags.clear(&store, flagged);
+        let history = store.get_history();
+        assert_eq!(history.len(), 2);
+        assert!(matches!(
+            &history[1],
+            LogMsg::JsonPatch(patch) if *patch == deleted_worktree_patch::clear(flagged)
+        ));
+    }
+
+    #[test]
+    fn test_execution_slots_report_the_exhausted_cap() {
+        let mut slots = ExecutionSlots {
+            running: 3,
+            max: None,
+            project_running: Some(1),
+            project_max: Some(2),
+        };
+        assert_eq!(slots.exhausted(), None);
+
+        slots.project_running = Some(2);
+        assert_eq!(slots.exhausted(), Some((2, 2)));
+
+        slots.max = Some(3);
+        assert_eq!(slots.exhausted(), Some((3, 3)));
+
+        slots.max = Some(4);
+        slots.project_max = None;
+        assert_eq!(slots.exhausted(), None);
+    }
+
+    /// An in-memory database holding one finished Claude Code run whose stdout was
+    /// persisted line by line, the way the raw log streamer stores it.
+    async fn persisted_claude_run(stdout_lines: &[&str]) -> (SqlitePool, ExecutionProcess) {
+        use db::{
+            DBService,
+            models::{project::CreateProject, task::CreateTask, task_attempt::CreateTaskAttempt},
+        };
+
+        let pool = DBService::new_in_memory().await.unwrap().pool;
+
+        let project = Project::create(
+            &pool,
+            &CreateProject {
+                name: "Persisted".to_string(),
+                git_repo_path: "/tmp/persisted-repo".to_string(),
+                use_existing_repo: true,
+                ..Default::default()
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let task = Task::create(
+            &pool,
+            &CreateTask::from_title_description(project.id, "Look it up".to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let attempt = TaskAttempt::create(
+            &pool,
+            &CreateTaskAttempt {
+                executor: BaseCodingAgent::ClaudeCode,
+                base_branch: "main".to_string(),
+                branch: "vk/persisted".to_string(),
+                is_orchestrator: false,
+            },
+            Uuid::new_v4(),
+            task.id,
+        )
+        .await
+        .unwrap();
+        let process = ExecutionProcess::create(
+            &pool,
+            &CreateExecutionProcess {
+                task_attempt_id: attempt.id,
+                executor_action: ExecutorAction::new(
+                    ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                        prompt: "Look it up".to_string(),
+                        executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+                        is_orchestrator: false,
+                    }),
+                    None,
+                ),
+                run_reason: ExecutionProcessRunReason::CodingAgent,
+            },
+            Uuid::new_v4(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        for (seq, line) in stdout_lines.iter().enumerate() {
+            let msg = LogMsg::Stdout(format!("{line}\n"));
+            ExecutionProcessLogs::append_log_line(
+                &pool,
+                process.id,
+                &format!("{}\n", serde_json::to_string(&msg).unwrap()),
+                Some(seq as i64),
+            )
+            .await
+            .unwrap();
+        }
+
+        (pool, process)
+    }
+
+    async fn renormalized_patches(pool: &SqlitePool, process: &ExecutionProcess) -> Vec<Patch> {
+        let store = normalize_persisted_logs(pool, process, Path::new("/tmp/persisted-repo"))
+            .await
+            .expect("coding agent runs are normalized");
+        collect_normalized_patches(&store)
+            .await
+            .into_iter()
+            .filter_map(|msg| match msg {
+                LogMsg::JsonPatch(patch) => Some(patch),
+                _ => None,
+            })
+            .collect()
+    }
+
+    const MCP_TOOL_USE: &str = r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","id":"toolu_1","name":"mcp__github__search_issues","input":{"query":"open issues"}}]}}"#;
+    const MCP_TOOL_RESULT: &str = r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":"3 open issues","is_error":false}]}}"#;
+
+    #[tokio::test]
+    async fn test_mcp_calls_are_read_from_persisted_raw_logs() {
+        let (pool, process) = persisted_claude_run(&[MCP_TOOL_USE, MCP_TOOL_RESULT]).await;
+
+        // Only raw output is persisted, so the calls come from normalizing it again
+        let persisted = ExecutionProcessLogs::find_by_execution_id(&pool, process.id)
+            .await
+            .unwrap();
+        assert!(
+            ExecutionProcessLogs::parse_logs(&persisted)
+                .unwrap()
+                .iter()
+                .all(|msg| matches!(msg, LogMsg::Stdout(_)))
+        );
+
+        let patches = renormalized_patches(&pool, &process).await;
+        let calls = executors::mcp_calls::extract_mcp_calls_from_patches(&patches);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].server, "github");
+        assert_eq!(calls[0].tool, "search_issues");
+        assert_eq!(
+            calls[0].arguments,
+            Some(serde_json::json!({ "query": "open issues" }))
+        );
+        assert!(matches!(
+            calls[0].status,
+            executors::logs::ToolStatus::Success
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_usage_is_read_from_persisted_raw_logs() {
+        let (pool, process) = persisted_claude_run(&[
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Done."}]}}"#,
+            r#"{"type":"result","subtype":"success","is_error":false,"result":"Done.","usage":{"input_tokens":120,"output_tokens":45,"cache_creation_input_tokens":300,"cache_read_input_tokens":700}}"#,
+        ])
+        .await;
+
+        let patches = renormalized_patches(&pool, &process).await;
+        assert_eq!(
+            executors::usage::usage_from_patches(&patches),
+            executors::logs::TokenUsage {
+                input_tokens: 120,
+                output_tokens: 45,
+                cached_tokens: 1000,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_conversation_export_is_built_from_persisted_raw_logs() {
+        let (pool, process) = persisted_claude_run(&[
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Renamed the helper and updated its callers."}]}}"#,
+        ])
+        .await;
+
+        let patches = renormalized_patches(&pool, &process).await;
+        let entries = executors::logs::utils::patch::latest_normalized_entries(&patches);
+        let export =
+            executors::conversation_export::export_to_markdown(&entries, "CLAUDE_CODE", None);
+        assert!(export.message_count > 0);
+        assert!(
+            export
+                .markdown
+                .contains("Renamed the helper and updated its callers.")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_auto_commit_uses_the_previewed_message() {
+        let td = tempfile::TempDir::new().unwrap();
+        let repo_path = td.path().join("repo");
+        let git = GitService::new();
+        git.initialize_repo_with_main_branch(&repo_path).unwrap();
+        let repo = git2::Repository::open(&repo_path).unwrap();
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test User").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+
+        let (pool, process) = persisted_claude_run(&[]).await;
+        TaskAttempt::update_container_ref(
+            &pool,
+            process.task_attempt_id,
+            repo_path.to_str().unwrap(),
+        )
+        .await
+        .unwrap();
+        ExecutorSession::create(
+            &pool,
+            &CreateExecutorSession {
+                task_attempt_id: process.task_attempt_id,
+                execution_process_id: process.id,
+                prompt: None,
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        ExecutorSession::update_summary(&pool, process.id, "Add greeting module")
+            .await
+            .unwrap();
+        let ctx = ExecutionProcess::load_context(&pool, process.id)
+            .await
+            .unwrap();
+
+        // What the preview endpoint reports, then what auto-commit actually stores
+        let mode = AutoCommitMessageMode::SessionSummary;
+        let preview = resolve_auto_commit_message(&pool, mode, &ctx)
+            .await
+            .unwrap();
+        std::fs::write(repo_path.join("greeting.txt"), "hello\n").unwrap();
+        assert!(
+            auto_commit_execution(&pool, &git, &ctx, mode, false, LineEndings::default())
+                .await
+                .unwrap()
+        );
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message().unwrap().trim_end(), preview);
+        assert_eq!(preview, "Add greeting module");
+    }
+
+    fn commit_file(git: &GitService, repo_path: &Path, name: &str, content: &str) {
+        std::fs::write(repo_path.join(name), content).unwrap();
+        assert!(git.commit(repo_path, &format!("update {name}")).unwrap());
+    }
+
+    #[test]
+    fn test_push_after_auto_commit_updates_mock_remote() {
+        let td = tempfile::TempDir::new().unwrap();
+        let remote_path = td.path().join("remote.git");
+        let remote = git2::Repository::init_bare(&remote_path).unwrap();
+
+        let repo_path = td.path().join("repo");
+        let git = GitService::new();
+        git.initialize_repo_with_main_branch(&repo_path).unwrap();
+        let repo = git2::Repository::open(&repo_path).unwrap();
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test User").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+        repo.remote("origin", remote_path.to_str().unwrap())
+            .unwrap();
+
+        commit_file(&git, &repo_path, "a.txt", "one\n");
+        assert!(push_after_auto_commit(&git, &repo_path, "main"));
+        let head = git.get_head_info(&repo_path).unwrap().oid;
+        let pushed = remote.refname_to_id("refs/heads/main").unwrap();
+        assert_eq!(pushed.to_string(), head);
+
+        // Rewrite local history so the remote has diverged: logged, not fatal
+        let first = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.reset(
+            first.parent(0).unwrap().as_object(),
+            git2::ResetType::Hard,
+            None,
+        )
+        .unwrap();
+        commit_file(&git, &repo_path, "b.txt", "two\n");
+        assert!(!push_after_auto_commit(&git, &repo_path, "main"));
+        assert_eq!(remote.refname_to_id("refs/heads/main").unwrap(), pushed);
+    }
+
+    #[test]
+    fn test_no_change_cleanup_records_empty_commit_when_allowed() {
+        let td = tempfile::TempDir::new().unwrap();
+        let repo_path = td.path().join("repo");
+        let git = GitService::new();
+        git.initialize_repo_with_main_branch(&repo_path).unwrap();
+        let repo = git2::Repository::open(&repo_path).unwrap();
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test User").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+
+        let before = repo.head().unwrap().peel_to_commit().unwrap();
+        let message = "Cleanup script";
+        assert!(
+            !commit_execution_changes(&git, &repo_path, message, false, LineEndings::Preserve)
+                .unwrap()
+        );
+        assert_eq!(
+            repo.head().unwrap().peel_to_commit().unwrap().id(),
+            before.id()
+        );
+
+        assert!(
+            commit_execution_changes(&git, &repo_path, message, true, LineEndings::Preserve)
+                .unwrap()
+        );
+        let after = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_ne!(after.id(), before.id());
+        assert_eq!(after.parent_id(0).unwrap(), before.id());
+        assert_eq!(after.tree_id(), before.tree_id());
+        assert_eq!(after.summary(), Some(message));
+    }
+
+    #[test]
+    fn test_expand_copy_files() {
+        let td = tempfile::TempDir::new().unwrap();
+        let source = td.path();
+        for file in [
+            ".env",
+            ".env.d/a.env",
+            ".env.d/nested/b.env",
+            "config/dev.env",
+            "config/prod.env",
+            "config/readme.md",
+            "node_modules/pkg/dep.env",
+            "pages/[id].tsx",
+            "routes/[slug].tsx",
+            "routes/s.tsx",
+        ] {
+            let path = source.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "x").unwrap();
+        }
+        // Ignored files are still copied
+        std::fs::write(source.join(".gitignore"), ".env.d/\n").unwrap();
+
+        let files = expand_copy_files(source, ".env, .env.d, config/*.env, missing/*.env").unwrap();
+        assert_eq!(
+            files,
+            [
+                ".env",
+                ".env.d/a.env",
+                ".env.d/nested/b.env",
+                "config/dev.env",
+                "config/prod.env",
+            ]
+            .map(PathBuf::from)
+        );
+
+        assert!(expand_copy_files(source, ".env, missing.env").is_err());
+
+        // Bare globs are anchored at the root instead of searching every directory
+        assert_eq!(
+            expand_copy_files(source, "*.env").unwrap(),
+            [PathBuf::from(".env")]
+        );
+        assert_eq!(
+            expand_copy_files(source, "**/dep.env").unwrap(),
+            [PathBuf::from("node_modules/pkg/dep.env")]
+        );
+
+        // Existing paths are literal; escaped brackets match literally in globs
+        assert_eq!(
+            expand_copy_files(source, "pages/[id].tsx, routes/\\[slug\\].*").unwrap(),
+            ["pages/[id].tsx", "routes/[slug].tsx"].map(PathBuf::from)
+        );
+    }
+
+    #[test]
+    fn test_copied_crlf_file_is_committed_with_lf() {
+        let td = tempfile::TempDir::new().unwrap();
+        let repo_path = td.path().join("repo");
+        let git = GitService::new();
+        git.initialize_repo_with_main_branch(&repo_path).unwrap();
+        let repo = git2::Repository::open(&repo_path).unwrap();
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test User").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+        cfg.set_bool("core.autocrlf", false).unwrap();
+
+        let source_dir = td.path().join("project");
+        std::fs::create_dir_all(&source_dir).unwrap();
+        std::fs::write(source_dir.join(".env"), "A=1\r\nB=2\r\n").unwrap();
+        copy_project_file(
+            &source_dir.join(".env"),
+            &repo_path.join(".env"),
+            LineEndings::Lf,
+        )
+        .unwrap();
+        // Files written by the agent with CRLF are normalized on commit
+        std::fs::write(repo_path.join("notes.txt"), "one\r\ntwo\r\n").unwrap();
+
+        assert!(
+            commit_execution_changes(&git, &repo_path, "Copy env", false, LineEndings::Lf).unwrap()
+        );
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        let committed = |name: &str| {
+            let blob = repo.find_blob(tree.get_name(name).unwrap().id()).unwrap();
+            blob.content().to_vec()
+        };
+        assert_eq!(committed(".env"), b"A=1\nB=2\n");
+        assert_eq!(committed("notes.txt"), b"one\ntwo\n");
+    }
+
+    #[test]
+    fn test_setup_script_action_runs_setup_only() {
+        let action = setup_script_action("npm install".to_string(), ScriptRequestLanguage::Sh);
+        assert!(matches!(
+            action.typ(),
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script,
+                language: ScriptRequestLanguage::Sh,
+                context: ScriptContext::SetupScript,
+            }) if script == "npm install"
+        ));
+        assert!(action.next_action().is_none());
+    }
+
+    #[test]
+    fn test_attempt_cleanup_override_replaces_project_script() {
+        let mut attempt = TaskAttempt {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            container_ref: None,
+            branch: "vk/override".to_string(),
+            target_branch: "main".to_string(),
+            executor: "CLAUDE_CODE".to_string(),
+            worktree_deleted: false,
+            setup_completed_at: None,
+            is_orchestrator: false,
+            cleanup_script_override: Some("cargo test -p db".to_string()),
+            env_overrides: None,
+            pin_worktree: false,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let project_script = Some("cargo test --workspace".to_string());
+        let language = ScriptRequestLanguage::default();
+
+        let action = cleanup_script_action(
+            attempt.effective_cleanup_script(project_script.clone()),
+            language,
+        )
+        .unwrap();
+        assert!(matches!(
+            action.typ(),
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script,
+                context: ScriptContext::CleanupScript,
+                ..
+            }) if script == "cargo test -p db"
+        ));
+
+        attempt.cleanup_script_override = None;
+        let action =
+            cleanup_script_action(attempt.effective_cleanup_script(project_script), language)
+                .unwrap();
+        assert!(matches!(
+            action.typ(),
+            ExecutorActionType::ScriptRequest(ScriptRequest { script, .. })
+                if script == "cargo test --workspace"
+        ));
+    }
+}
@@ -0,0 +1,187 @@
+//! Detect the URL a dev server is serving on from its output.
+
+use std::sync::{Arc, LazyLock};
+
+use futures::StreamExt;
+use regex::Regex;
+use url::Url;
+use utils::{log_msg::LogMsg, msg_store::MsgStore};
+
+/// Full URLs on a local or IP host, e.g. `http://localhost:5173/`
+static URL_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)https?://(?:\[[0-9a-f:]+\]|localhost|127\.0\.0\.1|0\.0\.0\.0|\d{1,3}(?:\.\d{1,3}){3})(?::\d{2,5})?(?:/\S*)?",
+    )
+    .expect("valid url pattern")
+});
+
+/// Bare `host:port` mentions, e.g. `listening on 0.0.0.0:8080`
+static HOST_PORT_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(?:localhost|127\.0\.0\.1|0\.0\.0\.0|\[[0-9a-f:]+\]|(?:\d{1,3}\.){3}\d{1,3}):(\d{2,5})",
+    )
+    .expect("valid host:port pattern")
+});
+
+static ANSI_ESCAPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]").expect("valid ansi pattern"));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedUrl {
+    pub url: String,
+    pub port: Option<u16>,
+}
+
+pub fn parse_url_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    Regex::new(pattern)
+}
+
+/// Find a dev server URL in one line of output. A project `pattern` replaces the
+/// built-in ones: its `url` or `port` named group (else the first group, else the
+/// whole match) is used, and a bare port becomes `http://localhost:<port>`.
+pub fn detect_dev_server_url(line: &str, pattern: Option<&Regex>) -> Option<DetectedUrl> {
+    let line = ANSI_ESCAPE.replace_all(line, "");
+
+    if let Some(pattern) = pattern {
+        let captures = pattern.captures(&line)?;
+        let found = captures
+            .name("url")
+            .or_else(|| captures.name("port"))
+            .or_else(|| captures.get(1))
+            .or_else(|| captures.get(0))?
+            .as_str();
+        return match found.parse::<u16>() {
+            Ok(port) => Some(localhost_url("http", port)),
+            Err(_) => normalize_url(found),
+        };
+    }
+
+    if let Some(found) = URL_PATTERN.find(&line)
+        && let Some(detected) = normalize_url(found.as_str())
+    {
+        return Some(detected);
+    }
+
+    let captures = HOST_PORT_PATTERN.captures(&line)?;
+    let port = captures[1].parse::<u16>().ok()?;
+    let scheme = if line.to_ascii_lowercase().contains("https") {
+        "https"
+    } else {
+        "http"
+    };
+    Some(localhost_url(scheme, port))
+}
+
+fn localhost_url(scheme: &str, port: u16) -> DetectedUrl {
+    DetectedUrl {
+        url: format!("{scheme}://localhost:{port}"),
+        port: Some(port),
+    }
+}
+
+/// Parse `raw`, pointing wildcard binds like `0.0.0.0` at localhost
+fn normalize_url(raw: &str) -> Option<DetectedUrl> {
+    let mut url = Url::parse(raw).ok()?;
+    if matches!(url.host_str(), Some("0.0.0.0" | "[::]")) {
+        url.set_host(Some("localhost")).ok()?;
+    }
+    Some(DetectedUrl {
+        port: url.port_or_known_default(),
+        url: url.to_string(),
+    })
+}
+
+/// Read the dev server's stdout and stderr in `store` until a line yields a URL.
+/// `None` means the process finished without printing one.
+pub async fn watch_for_dev_server_url(
+    store: Arc<MsgStore>,
+    pattern: Option<Regex>,
+) -> Option<DetectedUrl> {
+    let mut stream = store.history_plus_stream();
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+
+    while let Some(Ok(msg)) = stream.next().await {
+        let (buffer, chunk) = match msg {
+            LogMsg::Stdout(chunk) => (&mut stdout, chunk),
+            LogMsg::Stderr(chunk) => (&mut stderr, chunk),
+            LogMsg::Finished => break,
+            _ => continue,
+        };
+        buffer.push_str(&chunk);
+        // Only complete lines are checked so a URL split across chunks isn't cut short
+        while let Some(newline) = buffer.find('\n') {
+            let line: String = buffer.drain(..=newline).collect();
+            if let Some(detected) = detect_dev_server_url(&line, pattern.as_ref()) {
+                return Some(detected);
+            }
+        }
+    }
+
+    [stdout, stderr]
+        .iter()
+        .find_map(|rest| detect_dev_server_url(rest, pattern.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_common_dev_server_output() {
+        let vite = detect_dev_server_url(
+            "  \x1b[32m➜\x1b[39m  Local:   \x1b[36mhttp://localhost:\x1b[1m5173\x1b[22m/\x1b[39m",
+            None,
+        )
+        .unwrap();
+        assert_eq!(vite.url, "http://localhost:5173/");
+        assert_eq!(vite.port, Some(5173));
+
+        let wildcard = detect_dev_server_url("Listening on http://0.0.0.0:8080", None).unwrap();
+        assert_eq!(wildcard.url, "http://localhost:8080/");
+
+        let bare = detect_dev_server_url("server bound to 127.0.0.1:4000", None).unwrap();
+        assert_eq!(bare.url, "http://localhost:4000");
+        assert_eq!(bare.port, Some(4000));
+
+        assert_eq!(detect_dev_server_url("Compiling 42 modules", None), None);
+    }
+
+    #[test]
+    fn test_project_pattern_overrides_defaults() {
+        let pattern = parse_url_pattern(r"ready on port (?P<port>\d+)").unwrap();
+        let detected = detect_dev_server_url("ready on port 3001", Some(&pattern)).unwrap();
+        assert_eq!(detected.url, "http://localhost:3001");
+
+        // The built-in patterns are not consulted once a project pattern is set
+        assert_eq!(
+            detect_dev_server_url("http://localhost:5173/", Some(&pattern)),
+            None
+        );
+
+        let pattern = parse_url_pattern(r"App: (?P<url>https://\S+)").unwrap();
+        let detected =
+            detect_dev_server_url("App: https://myapp.test:8443/home", Some(&pattern)).unwrap();
+        assert_eq!(detected.url, "https://myapp.test:8443/home");
+        assert_eq!(detected.port, Some(8443));
+    }
+
+    #[tokio::test]
+    async fn test_watch_joins_chunks_into_lines() {
+        let store = Arc::new(MsgStore::new());
+        store.push_stdout("starting...\nLocal: http://local");
+        store.push_stdout("host:5173/\n");
+
+        let detected = watch_for_dev_server_url(store, None).await.unwrap();
+        assert_eq!(detected.url, "http://localhost:5173/");
+    }
+
+    #[tokio::test]
+    async fn test_watch_returns_none_when_nothing_detected() {
+        let store = Arc::new(MsgStore::new());
+        store.push_stdout("build finished\n");
+        store.push_finished();
+
+        assert_eq!(watch_for_dev_server_url(store, None).await, None);
+    }
+}
@@ -3,16 +3,21 @@ use std::{str::FromStr, sync::Arc};
 use db::{
     DBService,
     models::{
-        execution_process::ExecutionProcess, scratch::Scratch,
-        shared_task::SharedTask as SharedDbTask, task::Task, task_attempt::TaskAttempt,
+        execution_process::ExecutionProcess,
+        scratch::Scratch,
+        shared_task::SharedTask as SharedDbTask,
+        task::{Task, TaskStatus},
+        task_attempt::TaskAttempt,
     },
 };
 use serde_json::json;
 use sqlx::{Error as SqlxError, Sqlite, SqlitePool, decode::Decode, sqlite::SqliteOperation};
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, mpsc};
 use utils::msg_store::MsgStore;
 use uuid::Uuid;
 
+use crate::services::webhook::TaskStatusChange;
+
 #[path = "events/patches.rs"]
 pub mod patches;
 #[path = "events/streams.rs"]
@@ -21,10 +26,40 @@ mod streams;
 pub mod types;
 
 pub use patches::{
-    execution_process_patch, scratch_patch, shared_task_patch, task_attempt_patch, task_patch,
+    agent_login_patch, deleted_worktree_patch, execution_process_patch, pr_sync_patch,
+    scratch_patch, shared_task_patch, task_attempt_patch, task_patch,
 };
 pub use types::{EventError, EventPatch, EventPatchInner, HookTables, RecordTypes};
 
+/// Column positions in the `tasks` table, which migrations only ever append to
+const TASK_PROJECT_ID_COLUMN: i32 = 1;
+const TASK_STATUS_COLUMN: i32 = 4;
+
+/// Read a task's status change out of an update, if the update changes it
+fn task_status_change(
+    preupdate: &sqlx::sqlite::PreupdateHookResult<'_>,
+) -> Option<TaskStatusChange> {
+    let decode_status = |value| <TaskStatus as Decode<Sqlite>>::decode(value).ok();
+    let previous_status = decode_status(preupdate.get_old_column_value(TASK_STATUS_COLUMN).ok()?)?;
+    let status = decode_status(preupdate.get_new_column_value(TASK_STATUS_COLUMN).ok()?)?;
+    if previous_status == status {
+        return None;
+    }
+    let task_id = <Uuid as Decode<Sqlite>>::decode(preupdate.get_old_column_value(0).ok()?).ok()?;
+    let project_id = <Uuid as Decode<Sqlite>>::decode(
+        preupdate
+            .get_old_column_value(TASK_PROJECT_ID_COLUMN)
+            .ok()?,
+    )
+    .ok()?;
+    Some(TaskStatusChange {
+        task_id,
+        project_id,
+        previous_status,
+        status,
+    })
+}
+
 #[derive(Clone)]
 pub struct EventService {
     msg_store: Arc<MsgStore>,
@@ -74,11 +109,13 @@ impl EventService {
         Ok(())
     }
 
-    /// Creates the hook function that should be used with DBService::new_with_after_connect
+    /// Creates the hook function that should be used with DBService::new_with_after_connect.
+    /// Every task status change is also sent to `task_status_tx`.
     pub fn create_hook(
         msg_store: Arc<MsgStore>,
         entry_count: Arc<RwLock<usize>>,
         db_service: DBService,
+        task_status_tx: mpsc::UnboundedSender<TaskStatusChange>,
     ) -> impl for<'a> Fn(
         &'a mut sqlx::sqlite::SqliteConnection,
     ) -> std::pin::Pin<
@@ -90,12 +127,19 @@ impl EventService {
             let msg_store_for_hook = msg_store.clone();
             let entry_count_for_hook = entry_count.clone();
             let db_for_hook = db_service.clone();
+            let task_status_tx = task_status_tx.clone();
             Box::pin(async move {
                 let mut handle = conn.lock_handle().await?;
                 let runtime_handle = tokio::runtime::Handle::current();
                 handle.set_preupdate_hook({
                     let msg_store_for_preupdate = msg_store_for_hook.clone();
                     move |preupdate: sqlx::sqlite::PreupdateHookResult<'_>| {
+                        if preupdate.operation == SqliteOperation::Update
+                            && preupdate.table == "tasks"
+                            && let Some(change) = task_status_change(&preupdate)
+                        {
+                            let _ = task_status_tx.send(change);
+                        }
                         if preupdate.operation != SqliteOperation::Delete {
                             return;
                         }
@@ -453,3 +497,67 @@ impl EventService {
         &self.msg_store
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Connection, sqlite::SqliteConnection};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_task_status_changes_are_read_from_updates() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("../db/migrations")
+            .run(&mut conn)
+            .await
+            .unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        conn.lock_handle()
+            .await
+            .unwrap()
+            .set_preupdate_hook(move |preupdate| {
+                if preupdate.operation == SqliteOperation::Update
+                    && let Some(change) = task_status_change(&preupdate)
+                {
+                    let _ = tx.send(change);
+                }
+            });
+
+        let project_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO projects (id, name, git_repo_path) VALUES ($1, 'p', '/tmp/p')")
+            .bind(project_id)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO tasks (id, project_id, title) VALUES ($1, $2, 't')")
+            .bind(task_id)
+            .bind(project_id)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        // Updates that leave the status alone report nothing
+        sqlx::query("UPDATE tasks SET title = 'renamed' WHERE id = $1")
+            .bind(task_id)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE tasks SET status = 'inprogress' WHERE id = $1")
+            .bind(task_id)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            TaskStatusChange {
+                task_id,
+                project_id,
+                previous_status: TaskStatus::Todo,
+                status: TaskStatus::InProgress,
+            }
+        );
+        assert!(rx.try_recv().is_err());
+    }
+}
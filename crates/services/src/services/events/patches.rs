@@ -214,3 +214,91 @@ pub mod scratch_patch {
         })])
     }
 }
+
+/// Helper functions for creating PR sync status patches.
+/// There is a single status, kept at path "/pr_sync".
+pub mod pr_sync_patch {
+    use super::*;
+    use crate::services::pr_monitor::PrSyncStatus;
+
+    const PR_SYNC_PATH: &str = "/pr_sync";
+
+    /// Create patch setting the current PR sync status
+    pub fn set(status: &PrSyncStatus) -> Patch {
+        Patch(vec![PatchOperation::Add(AddOperation {
+            path: PR_SYNC_PATH
+                .try_into()
+                .expect("PR sync path should be valid"),
+            value: serde_json::to_value(status)
+                .expect("PR sync status serialization should not fail"),
+        })])
+    }
+}
+
+/// Helper functions for creating agent login patches
+pub mod agent_login_patch {
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+    use crate::services::agent_login::AgentLoginStatus;
+
+    fn agent_login_path(agent: BaseCodingAgent) -> String {
+        format!(
+            "/agent_logins/{}",
+            escape_pointer_segment(&agent.to_string())
+        )
+    }
+
+    /// Create patch flagging an agent whose login is due for renewal
+    pub fn set(status: &AgentLoginStatus) -> Patch {
+        Patch(vec![PatchOperation::Add(AddOperation {
+            path: agent_login_path(status.agent)
+                .try_into()
+                .expect("Agent login path should be valid"),
+            value: serde_json::to_value(status)
+                .expect("Agent login status serialization should not fail"),
+        })])
+    }
+
+    /// Create patch clearing an agent's renewal flag
+    pub fn clear(agent: BaseCodingAgent) -> Patch {
+        Patch(vec![PatchOperation::Remove(RemoveOperation {
+            path: agent_login_path(agent)
+                .try_into()
+                .expect("Agent login path should be valid"),
+        })])
+    }
+}
+
+/// Helper functions for creating externally deleted worktree patches
+pub mod deleted_worktree_patch {
+    use super::*;
+    use crate::services::container::DeletedWorktree;
+
+    fn deleted_worktree_path(attempt_id: Uuid) -> String {
+        format!(
+            "/deleted_worktrees/{}",
+            escape_pointer_segment(&attempt_id.to_string())
+        )
+    }
+
+    /// Create patch flagging an attempt whose worktree has gone missing
+    pub fn set(worktree: &DeletedWorktree) -> Patch {
+        Patch(vec![PatchOperation::Add(AddOperation {
+            path: deleted_worktree_path(worktree.attempt_id)
+                .try_into()
+                .expect("Deleted worktree path should be valid"),
+            value: serde_json::to_value(worktree)
+                .expect("Deleted worktree serialization should not fail"),
+        })])
+    }
+
+    /// Create patch clearing the flag once the worktree has been recreated
+    pub fn clear(attempt_id: Uuid) -> Patch {
+        Patch(vec![PatchOperation::Remove(RemoveOperation {
+            path: deleted_worktree_path(attempt_id)
+                .try_into()
+                .expect("Deleted worktree path should be valid"),
+        })])
+    }
+}
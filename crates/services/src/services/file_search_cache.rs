@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     path::{Path, PathBuf},
     sync::Arc,
     time::{Duration, Instant},
@@ -14,7 +15,7 @@ use notify_debouncer_full::{DebounceEventResult, new_debouncer};
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 use thiserror::Error;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use tracing::{error, info, warn};
 use ts_rs::TS;
 
@@ -56,6 +57,66 @@ pub struct SearchQuery {
     pub q: String,
     #[serde(default)]
     pub mode: SearchMode,
+    /// Comma-separated list of file extensions to include (e.g. `rs,ts`)
+    #[serde(default)]
+    pub ext: Option<String>,
+    /// Only return paths under this directory (relative to the repo root)
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+impl SearchQuery {
+    pub fn filter(&self) -> SearchFilter {
+        SearchFilter::new(self.ext.as_deref(), self.path.as_deref())
+    }
+}
+
+/// Optional scoping applied on top of the text query
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilter {
+    /// Lowercased extensions without the leading dot; empty means any
+    pub extensions: Vec<String>,
+    /// Lowercased, `/`-separated path prefix without leading or trailing slashes
+    pub path_prefix: Option<String>,
+}
+
+impl SearchFilter {
+    pub fn new(extensions: Option<&str>, path_prefix: Option<&str>) -> Self {
+        let extensions = extensions
+            .map(|raw| {
+                raw.split(',')
+                    .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+                    .filter(|ext| !ext.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let path_prefix = path_prefix
+            .map(|p| p.replace('\\', "/").trim_matches('/').to_lowercase())
+            .filter(|p| !p.is_empty());
+        Self {
+            extensions,
+            path_prefix,
+        }
+    }
+
+    /// `path_lowercase` must be relative to the repo root. Extension filters only
+    /// ever match files, so directories are dropped when one is set.
+    pub fn matches(&self, path_lowercase: &str, extension: Option<&str>, is_file: bool) -> bool {
+        if let Some(prefix) = &self.path_prefix {
+            let normalized = path_lowercase.replace('\\', "/");
+            let under_prefix = normalized
+                .strip_prefix(prefix.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+            if !under_prefix {
+                return false;
+            }
+        }
+        if !self.extensions.is_empty() {
+            return is_file
+                && extension.is_some_and(|ext| self.extensions.iter().any(|e| e == ext));
+        }
+        true
+    }
 }
 
 /// FST-indexed file search result
@@ -65,7 +126,8 @@ pub struct IndexedFile {
     pub is_file: bool,
     pub match_type: SearchMatchType,
     pub path_lowercase: Arc<str>,
-    pub is_ignored: bool, // Track if file is gitignored
+    pub is_ignored: bool,            // Track if file is gitignored
+    pub extension: Option<Arc<str>>, // Lowercased, used for extension filtering
 }
 
 /// File index build result containing indexed files and FST map
@@ -105,6 +167,55 @@ pub enum CacheError {
     BuildError(String),
 }
 
+/// Indexing state of one repository in the cache
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum IndexStatus {
+    Pending,
+    Indexing,
+    Done,
+    Error { message: String },
+}
+
+impl IndexStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Done | Self::Error { .. })
+    }
+}
+
+/// A repository's indexing status changed
+#[derive(Debug, Clone)]
+pub struct IndexStatusUpdate {
+    pub repo_path: PathBuf,
+    pub status: IndexStatus,
+}
+
+/// Tracks each repository's indexing status and broadcasts every change
+#[derive(Clone)]
+struct IndexStatusTracker {
+    statuses: Arc<DashMap<PathBuf, IndexStatus>>,
+    updates: broadcast::Sender<IndexStatusUpdate>,
+}
+
+impl IndexStatusTracker {
+    fn new() -> Self {
+        Self {
+            statuses: Arc::new(DashMap::new()),
+            updates: broadcast::channel(256).0,
+        }
+    }
+
+    fn set(&self, repo_path: &Path, status: IndexStatus) {
+        self.statuses
+            .insert(repo_path.to_path_buf(), status.clone());
+        // No subscribers is fine; nobody is watching progress
+        let _ = self.updates.send(IndexStatusUpdate {
+            repo_path: repo_path.to_path_buf(),
+            status,
+        });
+    }
+}
+
 /// File search cache with FST indexing
 pub struct FileSearchCache {
     cache: Cache<PathBuf, CachedRepo>,
@@ -112,6 +223,7 @@ pub struct FileSearchCache {
     file_ranker: FileRanker,
     build_queue: mpsc::UnboundedSender<PathBuf>,
     watchers: DashMap<PathBuf, RecommendedWatcher>,
+    index_status: IndexStatusTracker,
 }
 
 impl FileSearchCache {
@@ -127,16 +239,19 @@ impl FileSearchCache {
         let cache_for_worker = cache.clone();
         let git_service = GitService::new();
         let file_ranker = FileRanker::new();
+        let index_status = IndexStatusTracker::new();
 
         // Spawn background worker
         let worker_git_service = git_service.clone();
         let worker_file_ranker = file_ranker.clone();
+        let worker_index_status = index_status.clone();
         tokio::spawn(async move {
             Self::background_worker(
                 build_receiver,
                 cache_for_worker,
                 worker_git_service,
                 worker_file_ranker,
+                worker_index_status,
             )
             .await;
         });
@@ -147,15 +262,52 @@ impl FileSearchCache {
             file_ranker,
             build_queue: build_sender,
             watchers: DashMap::new(),
+            index_status,
         }
     }
 
+    /// Indexing status of every repository the cache has been asked to build
+    pub fn index_statuses(&self) -> HashMap<PathBuf, IndexStatus> {
+        self.index_status
+            .statuses
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+
+    /// Indexing status of one repository, if it was ever queued
+    pub fn index_status(&self, repo_path: &Path) -> Option<IndexStatus> {
+        self.index_status
+            .statuses
+            .get(repo_path)
+            .map(|status| status.clone())
+    }
+
+    /// Receive every indexing status change from now on
+    pub fn subscribe_index_status(&self) -> broadcast::Receiver<IndexStatusUpdate> {
+        self.index_status.updates.subscribe()
+    }
+
+    fn enqueue_build(&self, repo_path: PathBuf) -> Result<(), String> {
+        self.index_status.set(&repo_path, IndexStatus::Pending);
+        self.build_queue.send(repo_path.clone()).map_err(|e| {
+            self.index_status.set(
+                &repo_path,
+                IndexStatus::Error {
+                    message: e.to_string(),
+                },
+            );
+            e.to_string()
+        })
+    }
+
     /// Search files in repository using cache
     pub async fn search(
         &self,
         repo_path: &Path,
         query: &str,
         mode: SearchMode,
+        filter: &SearchFilter,
     ) -> Result<Vec<SearchResult>, CacheError> {
         let repo_path_buf = repo_path.to_path_buf();
 
@@ -165,11 +317,11 @@ impl FileSearchCache {
             && head_info.oid == cached.head_sha
         {
             // Cache hit - perform fast search with mode-based filtering
-            return Ok(self.search_in_cache(&cached, query, mode).await);
+            return Ok(self.search_in_cache(&cached, query, mode, filter).await);
         }
 
         // Cache miss - trigger background refresh and return error
-        if let Err(e) = self.build_queue.send(repo_path_buf) {
+        if let Err(e) = self.enqueue_build(repo_path_buf) {
             warn!("Failed to enqueue cache build: {}", e);
         }
 
@@ -179,7 +331,7 @@ impl FileSearchCache {
     /// Pre-warm cache for given repositories
     pub async fn warm_repos(&self, repo_paths: Vec<PathBuf>) -> Result<(), String> {
         for repo_path in repo_paths {
-            if let Err(e) = self.build_queue.send(repo_path.clone()) {
+            if let Err(e) = self.enqueue_build(repo_path.clone()) {
                 error!(
                     "Failed to enqueue repo for warming: {:?} - {}",
                     repo_path, e
@@ -216,19 +368,23 @@ impl FileSearchCache {
             repo_paths
         );
 
-        // Warm the cache
-        self.warm_repos(repo_paths.clone())
+        self.warm_and_watch(&repo_paths).await?;
+
+        info!("File search cache warming completed");
+        Ok(())
+    }
+
+    /// Queue repositories for indexing and rebuild them whenever their HEAD moves
+    pub async fn warm_and_watch(&self, repo_paths: &[PathBuf]) -> Result<(), String> {
+        self.warm_repos(repo_paths.to_vec())
             .await
             .map_err(|e| format!("Failed to warm cache: {e}"))?;
 
-        // Setup watchers for active projects
-        for repo_path in &repo_paths {
+        for repo_path in repo_paths {
             if let Err(e) = self.setup_watcher(repo_path).await {
                 warn!("Failed to setup watcher for {:?}: {}", repo_path, e);
             }
         }
-
-        info!("File search cache warming completed");
         Ok(())
     }
 
@@ -238,13 +394,20 @@ impl FileSearchCache {
         cached: &CachedRepo,
         query: &str,
         mode: SearchMode,
+        filter: &SearchFilter,
     ) -> Vec<SearchResult> {
         let query_lower = query.to_lowercase();
         let mut results = Vec::new();
 
         // Search through indexed files with mode-based filtering
         for indexed_file in &cached.indexed_files {
-            if indexed_file.path_lowercase.contains(&query_lower) {
+            if indexed_file.path_lowercase.contains(&query_lower)
+                && filter.matches(
+                    &indexed_file.path_lowercase,
+                    indexed_file.extension.as_deref(),
+                    indexed_file.is_file,
+                )
+            {
                 // Apply mode-based filtering
                 match mode {
                     SearchMode::TaskForm => {
@@ -397,12 +560,17 @@ impl FileSearchCache {
                 SearchMatchType::FullPath
             };
 
+            let extension = path
+                .extension()
+                .map(|ext| Arc::from(ext.to_string_lossy().to_lowercase().as_str()));
+
             let indexed_file = IndexedFile {
                 path: relative_path_str,
                 is_file: path.is_file(),
                 match_type,
                 path_lowercase: Arc::from(relative_path_lower.as_str()),
                 is_ignored,
+                extension,
             };
 
             // Store the key for FST along with file index
@@ -436,6 +604,7 @@ impl FileSearchCache {
         cache: Cache<PathBuf, CachedRepo>,
         git_service: GitService,
         file_ranker: FileRanker,
+        index_status: IndexStatusTracker,
     ) {
         while let Some(repo_path) = build_receiver.recv().await {
             let cache_builder = FileSearchCache {
@@ -444,15 +613,19 @@ impl FileSearchCache {
                 file_ranker: file_ranker.clone(),
                 build_queue: mpsc::unbounded_channel().0, // Dummy sender
                 watchers: DashMap::new(),
+                index_status: index_status.clone(),
             };
 
+            index_status.set(&repo_path, IndexStatus::Indexing);
             match cache_builder.build_repo_cache(&repo_path).await {
                 Ok(cached_repo) => {
                     cache.insert(repo_path.clone(), cached_repo).await;
+                    index_status.set(&repo_path, IndexStatus::Done);
                     info!("Successfully cached repo: {:?}", repo_path);
                 }
                 Err(e) => {
                     error!("Failed to cache repo {:?}: {}", repo_path, e);
+                    index_status.set(&repo_path, IndexStatus::Error { message: e });
                 }
             }
         }
@@ -519,3 +692,78 @@ impl Default for FileSearchCache {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, rel: &str) {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "x").unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_search_with_extension_and_path_filter() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "src/main.rs");
+        write(root, "src/main.ts");
+        write(root, "docs/main.md");
+        write(root, "tools/src/main.rs");
+
+        let index = FileSearchCache::build_file_index(root).unwrap();
+        let cached = CachedRepo {
+            head_sha: String::new(),
+            fst_index: index.map,
+            indexed_files: index.files,
+            stats: Arc::new(FileStats::default()),
+            build_ts: Instant::now(),
+        };
+        let cache = FileSearchCache::new();
+
+        let filter = SearchFilter::new(Some("rs, .TS"), None);
+        let mut paths: Vec<String> = cache
+            .search_in_cache(&cached, "main", SearchMode::Settings, &filter)
+            .await
+            .into_iter()
+            .map(|r| r.path.replace('\\', "/"))
+            .collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec!["src/main.rs", "src/main.ts", "tools/src/main.rs"]
+        );
+
+        let filter = SearchFilter::new(Some("rs"), Some("src/"));
+        let paths: Vec<String> = cache
+            .search_in_cache(&cached, "main", SearchMode::Settings, &filter)
+            .await
+            .into_iter()
+            .map(|r| r.path.replace('\\', "/"))
+            .collect();
+        assert_eq!(paths, vec!["src/main.rs"]);
+    }
+
+    #[tokio::test]
+    async fn test_warm_reports_status_progress() {
+        let dir = tempfile::tempdir().unwrap(); // Not a git repo, so indexing fails
+        let cache = FileSearchCache::new();
+        let mut updates = cache.subscribe_index_status();
+
+        cache
+            .warm_repos(vec![dir.path().to_path_buf()])
+            .await
+            .unwrap();
+
+        let mut seen = Vec::new();
+        while seen.last().is_none_or(|s: &IndexStatus| !s.is_finished()) {
+            let update = updates.recv().await.unwrap();
+            assert_eq!(update.repo_path, dir.path());
+            seen.push(update.status);
+        }
+        assert_eq!(seen[..2], [IndexStatus::Pending, IndexStatus::Indexing]);
+        assert!(matches!(seen[2], IndexStatus::Error { .. }));
+        assert_eq!(cache.index_status(dir.path()), seen.last().cloned());
+    }
+}
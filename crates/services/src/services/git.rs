@@ -1,11 +1,13 @@
 use std::{
     collections::HashMap,
+    io::Read,
     path::Path,
     sync::{LazyLock, Mutex},
     time::{Duration, Instant},
 };
 
 use chrono::{DateTime, Utc};
+use db::models::project::MergeStrategy;
 use git2::{
     BranchType, Delta, DiffFindOptions, DiffOptions, Error as GitError, Reference, Remote,
     Repository, Sort,
@@ -14,6 +16,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use ts_rs::TS;
 use utils::diff::{Diff, DiffChangeKind, FileDiffDetails};
+use uuid::Uuid;
 
 mod cli;
 
@@ -21,7 +24,7 @@ use cli::{ChangeType, StatusDiffEntry, StatusDiffOptions};
 pub use cli::{GitCli, GitCliError};
 
 use super::file_ranker::FileStat;
-use crate::services::github::GitHubRepoInfo;
+use crate::services::git_provider::RepoInfo;
 
 #[derive(Debug, Error)]
 pub enum GitServiceError {
@@ -43,6 +46,14 @@ pub enum GitServiceError {
     WorktreeDirty(String, String),
     #[error("Rebase in progress; resolve or abort it before retrying")]
     RebaseInProgress,
+    #[error("No rebase in progress")]
+    NoRebaseInProgress,
+    #[error("Commit {0} is not on the current branch")]
+    CommitNotOnBranch(String),
+    #[error("Remote not found: {0}")]
+    RemoteNotFound(String),
+    #[error("{0} has no unresolved conflict")]
+    FileNotConflicted(String),
 }
 /// Service for managing Git operations in task execution workflows
 #[derive(Clone)]
@@ -69,6 +80,39 @@ pub enum ConflictOp {
     Revert,
 }
 
+/// Which version of a conflicted file to keep
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum ConflictSide {
+    /// The attempt branch's version
+    Ours,
+    /// The incoming version, e.g. the target branch during a rebase
+    Theirs,
+}
+
+impl ConflictSide {
+    /// The `git checkout` flag selecting this side while `op` is in progress.
+    /// A rebase replays the attempt's commits onto the target, so git's own
+    /// "ours" and "theirs" are swapped relative to the attempt.
+    fn checkout_flag(self, op: Option<&ConflictOp>) -> &'static str {
+        match (self, op) {
+            (Self::Ours, Some(ConflictOp::Rebase)) | (Self::Theirs, _) => "--theirs",
+            (Self::Ours, _) => "--ours",
+        }
+    }
+}
+
+/// How far `git reset` rewinds: branch only, branch and index, or everything
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum ResetMode {
+    Soft,
+    Mixed,
+    Hard,
+}
+
 #[derive(Debug, Serialize, TS)]
 pub struct GitBranch {
     pub name: String,
@@ -78,6 +122,39 @@ pub struct GitBranch {
     pub last_commit_date: DateTime<Utc>,
 }
 
+/// A changed file that is binary or larger than the requested threshold
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct LargeFileEntry {
+    pub path: String,
+    #[ts(type = "number")]
+    pub size_bytes: u64,
+    pub is_binary: bool,
+}
+
+/// Notes ref holding the metadata attached to commits made for task attempts
+pub const COMMIT_NOTES_REF: &str = "refs/notes/vibe-kanban";
+
+/// Task metadata stored as a JSON git note on commits made for an attempt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct CommitNote {
+    pub task_id: Uuid,
+    pub attempt_id: Uuid,
+    pub executor: String,
+}
+
+/// A commit on an attempt branch
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct CommitInfo {
+    pub oid: String,
+    pub subject: String,
+    pub author_name: String,
+    pub author_email: String,
+    #[ts(type = "Date")]
+    pub timestamp: DateTime<Utc>,
+    /// Task metadata from the commit's note, if it was made by vibe-kanban
+    pub note: Option<CommitNote>,
+}
+
 #[derive(Debug, Clone)]
 pub struct HeadInfo {
     pub branch: String,
@@ -150,6 +227,12 @@ pub enum DiffTarget<'p> {
         repo_path: &'p Path,
         commit_sha: &'p str,
     },
+    /// Changes between two commits (`from..to`)
+    Range {
+        repo_path: &'p Path,
+        from_sha: &'p str,
+        to_sha: &'p str,
+    },
 }
 
 impl Default for GitService {
@@ -279,6 +362,16 @@ impl GitService {
     }
 
     pub fn commit(&self, path: &Path, message: &str) -> Result<bool, GitServiceError> {
+        self.commit_with_autocrlf(path, message, None)
+    }
+
+    /// Like [`GitService::commit`], staging with the given `core.autocrlf` value if set.
+    pub fn commit_with_autocrlf(
+        &self,
+        path: &Path,
+        message: &str,
+        autocrlf: Option<&str>,
+    ) -> Result<bool, GitServiceError> {
         // Use Git CLI to respect sparse-checkout semantics for staging and commit
         let git = GitCli::new();
         let has_changes = git
@@ -289,8 +382,11 @@ impl GitService {
             return Ok(false);
         }
 
-        git.add_all(path)
-            .map_err(|e| GitServiceError::InvalidRepository(format!("git add failed: {e}")))?;
+        match autocrlf {
+            Some(autocrlf) => git.add_all_with_autocrlf(path, autocrlf),
+            None => git.add_all(path),
+        }
+        .map_err(|e| GitServiceError::InvalidRepository(format!("git add failed: {e}")))?;
         // Only ensure identity once we know we're about to commit
         self.ensure_cli_commit_identity(path)?;
         git.commit(path, message)
@@ -298,6 +394,94 @@ impl GitService {
         Ok(true)
     }
 
+    /// Attach `note` to commit `oid` under [`COMMIT_NOTES_REF`], replacing any
+    /// existing note. Notes refs are shared by all worktrees of a repository, so
+    /// the note outlives the worktree it was written from.
+    pub fn write_note(
+        &self,
+        repo_path: &Path,
+        oid: &str,
+        note: &CommitNote,
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let oid = git2::Oid::from_str(oid)?;
+        let signature = self.signature_with_fallback(&repo)?;
+        let content = serde_json::to_string(note).map_err(|e| {
+            GitServiceError::InvalidRepository(format!("Failed to serialize commit note: {e}"))
+        })?;
+        repo.note(
+            &signature,
+            &signature,
+            Some(COMMIT_NOTES_REF),
+            oid,
+            &content,
+            true,
+        )?;
+        Ok(())
+    }
+
+    /// Notes that aren't valid metadata JSON are treated as absent.
+    fn find_commit_note(repo: &Repository, oid: git2::Oid) -> Option<CommitNote> {
+        let note = repo.find_note(Some(COMMIT_NOTES_REF), oid).ok()?;
+        serde_json::from_str(note.message()?).ok()
+    }
+
+    /// Record a commit with no changes, e.g. to mark that a script ran.
+    pub fn commit_empty(&self, path: &Path, message: &str) -> Result<(), GitServiceError> {
+        self.ensure_cli_commit_identity(path)?;
+        GitCli::new()
+            .commit_allow_empty(path, message)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git commit failed: {e}")))
+    }
+
+    /// Files changed in the worktree since `base_commit` that are binary or
+    /// larger than `threshold_bytes`. Deleted files are not reported.
+    pub fn find_large_files(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+        threshold_bytes: u64,
+    ) -> Result<Vec<LargeFileEntry>, GitServiceError> {
+        // Binary detection only inspects the start of a file, like git itself
+        const BINARY_SNIFF_BYTES: u64 = 8000;
+
+        let entries = GitCli::new()
+            .diff_status(worktree_path, base_commit, StatusDiffOptions::default())
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git diff failed: {e}")))?;
+
+        let mut large_files = Vec::new();
+        for entry in entries {
+            if matches!(entry.change, ChangeType::Deleted) {
+                continue;
+            }
+            let abs_path = worktree_path.join(&entry.path);
+            let Ok(metadata) = std::fs::metadata(&abs_path) else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            let mut head = Vec::new();
+            std::fs::File::open(&abs_path)
+                .and_then(|f| f.take(BINARY_SNIFF_BYTES).read_to_end(&mut head))
+                .map_err(|e| {
+                    GitServiceError::InvalidRepository(format!(
+                        "Failed to read {}: {e}",
+                        entry.path
+                    ))
+                })?;
+            let is_binary = Self::looks_binary(&head);
+            if is_binary || metadata.len() > threshold_bytes {
+                large_files.push(LargeFileEntry {
+                    path: entry.path,
+                    size_bytes: metadata.len(),
+                    is_binary,
+                });
+            }
+        }
+        Ok(large_files)
+    }
+
     /// Get diffs between branches or worktree changes
     pub fn get_diffs(
         &self,
@@ -414,11 +598,62 @@ impl GitService {
                 let mut find_opts = git2::DiffFindOptions::new();
                 diff.find_similar(Some(&mut find_opts))?;
 
+                self.convert_diff_to_file_diffs(diff, &repo)
+            }
+            DiffTarget::Range {
+                repo_path,
+                from_sha,
+                to_sha,
+            } => {
+                let repo = self.open_repo(repo_path)?;
+                let resolve = |sha: &str| {
+                    git2::Oid::from_str(sha)
+                        .map_err(|_| {
+                            GitServiceError::InvalidRepository(format!("Invalid commit SHA: {sha}"))
+                        })
+                        .and_then(|oid| Ok(repo.find_commit(oid)?.tree()?))
+                };
+                let from_tree = resolve(from_sha)?;
+                let to_tree = resolve(to_sha)?;
+
+                let mut diff_opts = git2::DiffOptions::new();
+                diff_opts.include_typechange(true);
+                if let Some(paths) = path_filter {
+                    for path in paths {
+                        diff_opts.pathspec(*path);
+                    }
+                }
+
+                let mut diff =
+                    repo.diff_tree_to_tree(Some(&from_tree), Some(&to_tree), Some(&mut diff_opts))?;
+
+                let mut find_opts = git2::DiffFindOptions::new();
+                diff.find_similar(Some(&mut find_opts))?;
+
                 self.convert_diff_to_file_diffs(diff, &repo)
             }
         }
     }
 
+    /// Diff the tip of `to_branch` against the tip of `from_branch`, so the
+    /// result shows what `to_branch` has that `from_branch` does not.
+    pub fn diff_between_branches(
+        &self,
+        repo_path: &Path,
+        from_branch: &str,
+        to_branch: &str,
+        path_filter: Option<&[&str]>,
+    ) -> Result<Vec<Diff>, GitServiceError> {
+        self.get_diffs(
+            DiffTarget::Branch {
+                repo_path,
+                branch_name: to_branch,
+                base_branch: from_branch,
+            },
+            path_filter,
+        )
+    }
+
     /// Convert git2::Diff to our Diff structs
     fn convert_diff_to_file_diffs(
         &self,
@@ -577,6 +812,11 @@ impl GitService {
         }
     }
 
+    /// Heuristic used for filesystem content: binary files contain null bytes
+    fn looks_binary(bytes: &[u8]) -> bool {
+        bytes.contains(&0)
+    }
+
     /// Helper function to read file content from filesystem with safety guards
     fn read_file_to_string(repo: &Repository, rel_path: &Path) -> Option<String> {
         let workdir = repo.workdir()?;
@@ -602,7 +842,7 @@ impl GitService {
         }
 
         // Binary guard - skip files containing null bytes
-        if bytes.contains(&0) {
+        if Self::looks_binary(&bytes) {
             tracing::debug!("Skipping binary file: {:?}", abs_path);
             return None;
         }
@@ -773,7 +1013,7 @@ impl GitService {
         Ok(None)
     }
 
-    /// Merge changes from a task branch into the base branch.
+    /// Merge changes from a task branch into the base branch as a single squash commit.
     pub fn merge_changes(
         &self,
         base_worktree_path: &Path,
@@ -781,6 +1021,48 @@ impl GitService {
         task_branch_name: &str,
         base_branch_name: &str,
         commit_message: &str,
+    ) -> Result<String, GitServiceError> {
+        self.squash_merge_changes(
+            base_worktree_path,
+            task_worktree_path,
+            task_branch_name,
+            base_branch_name,
+            commit_message,
+        )
+    }
+
+    /// Collapse every commit on the task branch into one commit on the base
+    /// branch with `commit_message`, returning the new commit's id.
+    pub fn squash_merge_changes(
+        &self,
+        base_worktree_path: &Path,
+        task_worktree_path: &Path,
+        task_branch_name: &str,
+        base_branch_name: &str,
+        commit_message: &str,
+    ) -> Result<String, GitServiceError> {
+        self.merge_changes_with_strategy(
+            base_worktree_path,
+            task_worktree_path,
+            task_branch_name,
+            base_branch_name,
+            commit_message,
+            MergeStrategy::Squash,
+        )
+    }
+
+    /// Merge changes from a task branch into the base branch using `strategy`.
+    ///
+    /// `Rebase` replays the task commits onto the base branch when it has moved
+    /// ahead and then fast-forwards it; `commit_message` is unused in that case.
+    pub fn merge_changes_with_strategy(
+        &self,
+        base_worktree_path: &Path,
+        task_worktree_path: &Path,
+        task_branch_name: &str,
+        base_branch_name: &str,
+        commit_message: &str,
+        strategy: MergeStrategy,
     ) -> Result<String, GitServiceError> {
         // Open the repositories
         let task_repo = self.open_repo(task_worktree_path)?;
@@ -792,9 +1074,19 @@ impl GitService {
             self.get_branch_status(base_worktree_path, task_branch_name, base_branch_name)?;
 
         if task_behind > 0 {
-            return Err(GitServiceError::BranchesDiverged(format!(
-                "Cannot merge: base branch '{base_branch_name}' is {task_behind} commits ahead of task branch '{task_branch_name}'. The base branch has moved forward since the task was created.",
-            )));
+            if strategy != MergeStrategy::Rebase {
+                return Err(GitServiceError::BranchesDiverged(format!(
+                    "Cannot merge: base branch '{base_branch_name}' is {task_behind} commits ahead of task branch '{task_branch_name}'. The base branch has moved forward since the task was created.",
+                )));
+            }
+            // Bring the task branch up to date so the base can be fast-forwarded
+            self.rebase_branch(
+                base_worktree_path,
+                task_worktree_path,
+                base_branch_name,
+                base_branch_name,
+                task_branch_name,
+            )?;
         }
 
         // Check where base branch is checked out (if anywhere)
@@ -818,16 +1110,28 @@ impl GitService {
 
                 // Use CLI merge in base context
                 self.ensure_cli_commit_identity(&base_checkout_path)?;
-                let sha = git_cli
-                    .merge_squash_commit(
+                let sha = match strategy {
+                    MergeStrategy::Squash => git_cli.merge_squash_commit(
                         &base_checkout_path,
                         base_branch_name,
                         task_branch_name,
                         commit_message,
-                    )
-                    .map_err(|e| {
-                        GitServiceError::InvalidRepository(format!("CLI merge failed: {e}"))
-                    })?;
+                    ),
+                    MergeStrategy::Merge => git_cli.merge_no_ff_commit(
+                        &base_checkout_path,
+                        base_branch_name,
+                        task_branch_name,
+                        commit_message,
+                    ),
+                    MergeStrategy::Rebase => git_cli.merge_ff_only(
+                        &base_checkout_path,
+                        base_branch_name,
+                        task_branch_name,
+                    ),
+                }
+                .map_err(|e| {
+                    GitServiceError::InvalidRepository(format!("CLI merge failed: {e}"))
+                })?;
 
                 // Update task branch ref for continuity
                 let task_refname = format!("refs/heads/{task_branch_name}");
@@ -848,31 +1152,119 @@ impl GitService {
                 let base_commit = base_branch.get().peel_to_commit()?;
                 let task_commit = task_branch.get().peel_to_commit()?;
 
-                // Create the squash commit in-memory (no checkout) and update the base branch ref
-                let signature = self.signature_with_fallback(&task_repo)?;
-                let squash_commit_id = self.perform_squash_merge(
-                    &task_repo,
-                    &base_commit,
-                    &task_commit,
-                    &signature,
-                    commit_message,
-                    base_branch_name,
-                )?;
+                // Create the commit in-memory (no checkout) and update the base branch ref
+                let merged_commit_id = match strategy {
+                    MergeStrategy::Squash => {
+                        let signature = self.signature_with_fallback(&task_repo)?;
+                        self.perform_squash_merge(
+                            &task_repo,
+                            &base_commit,
+                            &task_commit,
+                            &signature,
+                            commit_message,
+                            base_branch_name,
+                        )?
+                    }
+                    MergeStrategy::Merge => {
+                        let signature = self.signature_with_fallback(&task_repo)?;
+                        self.perform_merge_commit(
+                            &task_repo,
+                            &base_commit,
+                            &task_commit,
+                            &signature,
+                            commit_message,
+                            base_branch_name,
+                        )?
+                    }
+                    MergeStrategy::Rebase => {
+                        // The task branch contains the base tip at this point
+                        let refname = format!("refs/heads/{base_branch_name}");
+                        task_repo.reference(
+                            &refname,
+                            task_commit.id(),
+                            true,
+                            "Fast-forward merge",
+                        )?;
+                        task_commit.id()
+                    }
+                };
 
-                // Update the task branch to the new squash commit so follow-up
+                // Update the task branch to the merged commit so follow-up
                 // work can continue from the merged state without conflicts.
                 let task_refname = format!("refs/heads/{task_branch_name}");
                 base_repo.reference(
                     &task_refname,
-                    squash_commit_id,
+                    merged_commit_id,
                     true,
-                    "Reset task branch after squash merge",
+                    "Reset task branch after merge",
                 )?;
 
-                Ok(squash_commit_id.to_string())
+                Ok(merged_commit_id.to_string())
             }
         }
     }
+
+    /// Check whether merging the task branch into the base branch would
+    /// conflict, without touching refs, the index or any working tree.
+    ///
+    /// Returns the conflicting paths; empty means the merge would apply cleanly.
+    pub fn merge_changes_dry_run(
+        &self,
+        repo_path: &Path,
+        task_branch_name: &str,
+        base_branch_name: &str,
+        strategy: MergeStrategy,
+    ) -> Result<Vec<String>, GitServiceError> {
+        // Same precondition the real merge enforces
+        let (_, task_behind) =
+            self.get_branch_status(repo_path, task_branch_name, base_branch_name)?;
+        if task_behind > 0 && strategy != MergeStrategy::Rebase {
+            return Err(GitServiceError::BranchesDiverged(format!(
+                "Cannot merge: base branch '{base_branch_name}' is {task_behind} commits ahead of task branch '{task_branch_name}'. The base branch has moved forward since the task was created.",
+            )));
+        }
+
+        self.conflicting_paths(repo_path, task_branch_name, base_branch_name)
+    }
+
+    /// Paths that would conflict when combining the task branch with the base
+    /// branch, computed in memory. Empty means the branches combine cleanly.
+    pub fn conflicting_paths(
+        &self,
+        repo_path: &Path,
+        task_branch_name: &str,
+        base_branch_name: &str,
+    ) -> Result<Vec<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let base_commit = Self::find_branch(&repo, base_branch_name)?
+            .get()
+            .peel_to_commit()?;
+        let task_commit = Self::find_branch(&repo, task_branch_name)?
+            .get()
+            .peel_to_commit()?;
+
+        let mut merge_opts = git2::MergeOptions::new();
+        merge_opts.find_renames(true);
+        // The resulting index is only inspected, never written
+        let index = repo.merge_commits(&base_commit, &task_commit, Some(&merge_opts))?;
+        if !index.has_conflicts() {
+            return Ok(Vec::new());
+        }
+
+        let mut paths = Vec::new();
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+            if let Some(entry) = entry {
+                let path = String::from_utf8_lossy(&entry.path).to_string();
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        Ok(paths)
+    }
+
     fn get_branch_status_inner(
         &self,
         repo: &Repository,
@@ -927,6 +1319,62 @@ impl GitService {
         Ok(Commit::new(oid))
     }
 
+    /// Commits reachable from `branch_name` but not from `base_commit`, newest first.
+    pub fn list_commits_between(
+        &self,
+        repo_path: &Path,
+        base_commit: &Commit,
+        branch_name: &str,
+    ) -> Result<Vec<CommitInfo>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let tip = Self::find_branch(&repo, branch_name)?
+            .get()
+            .peel_to_commit()?
+            .id();
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(tip)?;
+        revwalk.hide(base_commit.as_oid())?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+        revwalk
+            .map(|oid| -> Result<CommitInfo, GitServiceError> {
+                let commit = repo.find_commit(oid?)?;
+                let author = commit.author();
+                Ok(CommitInfo {
+                    oid: commit.id().to_string(),
+                    subject: commit.summary().unwrap_or("(no subject)").to_string(),
+                    author_name: author.name().unwrap_or_default().to_string(),
+                    author_email: author.email().unwrap_or_default().to_string(),
+                    timestamp: DateTime::from_timestamp(commit.time().seconds(), 0)
+                        .unwrap_or_else(Utc::now),
+                    note: Self::find_commit_note(&repo, commit.id()),
+                })
+            })
+            .collect()
+    }
+
+    /// Commit range an existing local branch contributes on top of `base_branch_name`,
+    /// as `(merge_base, branch_tip)`. Used when adopting a manually created branch.
+    pub fn branch_commit_range(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        base_branch_name: &str,
+    ) -> Result<(Commit, Commit), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let branch = repo
+            .find_branch(branch_name, BranchType::Local)
+            .map_err(|_| GitServiceError::BranchNotFound(branch_name.to_string()))?;
+        let tip = branch.get().peel_to_commit()?.id();
+        let base_tip = Self::find_branch(&repo, base_branch_name)?
+            .get()
+            .peel_to_commit()?
+            .id();
+        let merge_base = repo.merge_base(tip, base_tip)?;
+        Ok((Commit::new(merge_base), Commit::new(tip)))
+    }
+
     pub fn get_remote_branch_status(
         &self,
         repo_path: &Path,
@@ -1099,6 +1547,16 @@ impl GitService {
             .map_err(|e| GitServiceError::InvalidRepository(format!("git status failed: {e}")))
     }
 
+    /// Return the worktree status as raw `git status --porcelain=v2` text
+    pub fn get_worktree_status_porcelain(
+        &self,
+        worktree_path: &Path,
+    ) -> Result<String, GitServiceError> {
+        let cli = GitCli::new();
+        cli.status_porcelain_v2(worktree_path)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git status failed: {e}")))
+    }
+
     /// Stage all changes in the working tree
     pub fn add_all(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
         let cli = GitCli::new();
@@ -1167,6 +1625,49 @@ impl GitService {
         outcome
     }
 
+    /// Rewind the branch checked out in `worktree_path` to `commit_sha`, which must be
+    /// the current HEAD or one of its ancestors. A `Hard` reset refuses to discard
+    /// uncommitted changes unless `force` is set. Returns the new HEAD sha.
+    pub fn reset_branch_to_commit(
+        &self,
+        worktree_path: &Path,
+        commit_sha: &str,
+        mode: ResetMode,
+        force: bool,
+    ) -> Result<String, GitServiceError> {
+        let repo = self.open_repo(worktree_path)?;
+        let head_oid = repo.head()?.peel_to_commit()?.id();
+        let target_oid = repo
+            .revparse_single(commit_sha)
+            .and_then(|obj| obj.peel_to_commit())
+            .map_err(|_| GitServiceError::CommitNotOnBranch(commit_sha.to_string()))?
+            .id();
+
+        if target_oid != head_oid && !repo.graph_descendant_of(head_oid, target_oid)? {
+            return Err(GitServiceError::CommitNotOnBranch(commit_sha.to_string()));
+        }
+        if mode == ResetMode::Hard && !force {
+            self.check_worktree_clean(&repo)?;
+        }
+
+        let flag = match mode {
+            ResetMode::Soft => "--soft",
+            ResetMode::Mixed => "--mixed",
+            ResetMode::Hard => "--hard",
+        };
+        let target = target_oid.to_string();
+        let cli = GitCli::new();
+        cli.git(worktree_path, ["reset", flag, target.as_str()])
+            .map_err(|e| {
+                GitServiceError::InvalidRepository(format!("git reset {flag} failed: {e}"))
+            })?;
+        if mode == ResetMode::Hard {
+            // Reapply sparse-checkout if configured (non-fatal)
+            let _ = cli.git(worktree_path, ["sparse-checkout", "reapply"]);
+        }
+        Ok(target)
+    }
+
     /// Reset the given worktree to the specified commit SHA.
     /// If `force` is false and the worktree is dirty, returns WorktreeDirty error.
     pub fn reset_worktree_to_commit(
@@ -1204,6 +1705,20 @@ impl GitService {
         Ok(())
     }
 
+    /// Add a worktree for an existing branch with sparse-checkout limited to `patterns`
+    pub fn add_sparse_worktree(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        branch: &str,
+        patterns: &[String],
+    ) -> Result<(), GitServiceError> {
+        let git = GitCli::new();
+        git.worktree_add_sparse(repo_path, worktree_path, branch, patterns)
+            .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
+        Ok(())
+    }
+
     /// Remove a worktree
     pub fn remove_worktree(
         &self,
@@ -1299,16 +1814,13 @@ impl GitService {
         Ok(path.map(|p| p.to_string_lossy().to_string()))
     }
 
-    /// Perform a squash merge of task branch into base branch, but fail on conflicts
-    fn perform_squash_merge(
+    /// Merge the task commit into the base commit in memory, failing on conflicts
+    fn merged_tree<'a>(
         &self,
-        repo: &Repository,
+        repo: &'a Repository,
         base_commit: &git2::Commit,
         task_commit: &git2::Commit,
-        signature: &git2::Signature,
-        commit_message: &str,
-        base_branch_name: &str,
-    ) -> Result<git2::Oid, GitServiceError> {
+    ) -> Result<git2::Tree<'a>, GitServiceError> {
         // In-memory merge to detect conflicts without touching the working tree
         let mut merge_opts = git2::MergeOptions::new();
         // Safety and correctness options
@@ -1325,7 +1837,20 @@ impl GitService {
 
         // Write the merged tree back to the repository
         let tree_id = index.write_tree_to(repo)?;
-        let tree = repo.find_tree(tree_id)?;
+        Ok(repo.find_tree(tree_id)?)
+    }
+
+    /// Perform a squash merge of task branch into base branch, but fail on conflicts
+    fn perform_squash_merge(
+        &self,
+        repo: &Repository,
+        base_commit: &git2::Commit,
+        task_commit: &git2::Commit,
+        signature: &git2::Signature,
+        commit_message: &str,
+        base_branch_name: &str,
+    ) -> Result<git2::Oid, GitServiceError> {
+        let tree = self.merged_tree(repo, base_commit, task_commit)?;
 
         // Create a squash commit: use merged tree with base_commit as sole parent
         let squash_commit_id = repo.commit(
@@ -1344,6 +1869,33 @@ impl GitService {
         Ok(squash_commit_id)
     }
 
+    /// Create a merge commit of task branch into base branch, but fail on conflicts
+    fn perform_merge_commit(
+        &self,
+        repo: &Repository,
+        base_commit: &git2::Commit,
+        task_commit: &git2::Commit,
+        signature: &git2::Signature,
+        commit_message: &str,
+        base_branch_name: &str,
+    ) -> Result<git2::Oid, GitServiceError> {
+        let tree = self.merged_tree(repo, base_commit, task_commit)?;
+
+        let merge_commit_id = repo.commit(
+            None,
+            signature,
+            signature,
+            commit_message,
+            &tree,
+            &[base_commit, task_commit],
+        )?;
+
+        let refname = format!("refs/heads/{base_branch_name}");
+        repo.reference(&refname, merge_commit_id, true, "Merge")?;
+
+        Ok(merge_commit_id)
+    }
+
     /// Rebase a worktree branch onto a new base
     pub fn rebase_branch(
         &self,
@@ -1571,49 +2123,71 @@ impl GitService {
         })
     }
 
-    /// Abort an in-progress rebase in this worktree (no-op if none).
+    /// Resolve `file` by keeping one side of its conflict and staging the result.
+    /// Returns the files that are still conflicted.
+    pub fn resolve_conflict(
+        &self,
+        worktree_path: &Path,
+        file: &str,
+        side: ConflictSide,
+    ) -> Result<Vec<String>, GitServiceError> {
+        if !self
+            .get_conflicted_files(worktree_path)?
+            .iter()
+            .any(|f| f == file)
+        {
+            return Err(GitServiceError::FileNotConflicted(file.to_string()));
+        }
+        let op = self.detect_conflict_op(worktree_path)?;
+        let git = GitCli::new();
+        git.checkout_conflict_side(worktree_path, file, side.checkout_flag(op.as_ref()))
+            .map_err(|e| {
+                GitServiceError::InvalidRepository(format!("git checkout for {file} failed: {e}"))
+            })?;
+        self.get_conflicted_files(worktree_path)
+    }
+
+    /// Abort the in-progress rebase in this worktree, restoring the branch to its
+    /// pre-rebase state whether or not conflicts are present. Errors if no rebase
+    /// is in progress.
     pub fn abort_rebase(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
+        if !self.is_rebase_in_progress(worktree_path)? {
+            return Err(GitServiceError::NoRebaseInProgress);
+        }
         let git = GitCli::new();
         git.abort_rebase(worktree_path).map_err(|e| {
             GitServiceError::InvalidRepository(format!("git rebase --abort failed: {e}"))
         })
     }
 
+    /// Abort whichever operation left this worktree mid-conflict, using the
+    /// matching `--abort` for a merge, cherry-pick or revert. No-op if none is in
+    /// progress.
     pub fn abort_conflicts(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
         let git = GitCli::new();
-        if git.is_rebase_in_progress(worktree_path).unwrap_or(false) {
-            // If there are no conflicted files, prefer `git rebase --quit` to clean up metadata
-            let has_conflicts = !self
-                .get_conflicted_files(worktree_path)
-                .unwrap_or_default()
-                .is_empty();
-            if has_conflicts {
-                return self.abort_rebase(worktree_path);
-            } else {
-                return git.quit_rebase(worktree_path).map_err(|e| {
-                    GitServiceError::InvalidRepository(format!("git rebase --quit failed: {e}"))
-                });
+        let Some(op) = self.detect_conflict_op(worktree_path)? else {
+            return Ok(());
+        };
+        let (result, command) = match op {
+            ConflictOp::Rebase => {
+                // If there are no conflicted files, prefer `git rebase --quit` to clean up metadata
+                let has_conflicts = !self
+                    .get_conflicted_files(worktree_path)
+                    .unwrap_or_default()
+                    .is_empty();
+                if has_conflicts {
+                    return self.abort_rebase(worktree_path);
+                }
+                (git.quit_rebase(worktree_path), "git rebase --quit")
             }
-        }
-        if git.is_merge_in_progress(worktree_path).unwrap_or(false) {
-            return git.abort_merge(worktree_path).map_err(|e| {
-                GitServiceError::InvalidRepository(format!("git merge --abort failed: {e}"))
-            });
-        }
-        if git
-            .is_cherry_pick_in_progress(worktree_path)
-            .unwrap_or(false)
-        {
-            return git.abort_cherry_pick(worktree_path).map_err(|e| {
-                GitServiceError::InvalidRepository(format!("git cherry-pick --abort failed: {e}"))
-            });
-        }
-        if git.is_revert_in_progress(worktree_path).unwrap_or(false) {
-            return git.abort_revert(worktree_path).map_err(|e| {
-                GitServiceError::InvalidRepository(format!("git revert --abort failed: {e}"))
-            });
-        }
-        Ok(())
+            ConflictOp::Merge => (git.abort_merge(worktree_path), "git merge --abort"),
+            ConflictOp::CherryPick => (
+                git.abort_cherry_pick(worktree_path),
+                "git cherry-pick --abort",
+            ),
+            ConflictOp::Revert => (git.abort_revert(worktree_path), "git revert --abort"),
+        };
+        result.map_err(|e| GitServiceError::InvalidRepository(format!("{command} failed: {e}")))
     }
 
     pub fn find_branch<'a>(
@@ -1633,21 +2207,28 @@ impl GitService {
         }
     }
 
-    /// Extract GitHub owner and repo name from git repo path
-    pub fn get_github_repo_info(
+    /// Identify the hosting provider and repository from the default remote
+    pub fn get_repo_info(&self, repo_path: &Path) -> Result<RepoInfo, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let remote_name = self.default_remote_name(&repo);
+        self.get_remote_repo_info(repo_path, &remote_name)
+    }
+
+    /// Identify the hosting provider and repository from the named remote
+    pub fn get_remote_repo_info(
         &self,
         repo_path: &Path,
-    ) -> Result<GitHubRepoInfo, GitServiceError> {
+        remote_name: &str,
+    ) -> Result<RepoInfo, GitServiceError> {
         let repo = self.open_repo(repo_path)?;
-        let remote_name = self.default_remote_name(&repo);
-        let remote = repo.find_remote(&remote_name).map_err(|_| {
+        let remote = repo.find_remote(remote_name).map_err(|_| {
             GitServiceError::InvalidRepository(format!("No '{remote_name}' remote found"))
         })?;
 
         let url = remote
             .url()
             .ok_or_else(|| GitServiceError::InvalidRepository("Remote has no URL".to_string()))?;
-        GitHubRepoInfo::from_remote_url(url).map_err(|e| {
+        RepoInfo::from_remote_url(url).map_err(|e| {
             GitServiceError::InvalidRepository(format!("Failed to parse remote URL: {e}"))
         })
     }
@@ -1689,18 +2270,37 @@ impl GitService {
         })
     }
 
+    /// Remote a push of `branch_name` goes to: the requested one, else the one the
+    /// branch already tracks, else the default remote
+    pub fn push_remote_name(
+        &self,
+        worktree_path: &Path,
+        branch_name: &str,
+        remote: Option<&str>,
+    ) -> Result<String, GitServiceError> {
+        if let Some(name) = remote {
+            return Ok(name.to_string());
+        }
+        let repo = Repository::open(worktree_path)?;
+        Ok(self
+            .get_remote_name_from_branch_name(worktree_path, branch_name)
+            .unwrap_or_else(|_| self.default_remote_name(&repo)))
+    }
+
     pub fn push_to_github(
         &self,
         worktree_path: &Path,
         branch_name: &str,
+        remote: Option<&str>,
         force: bool,
     ) -> Result<(), GitServiceError> {
         let repo = Repository::open(worktree_path)?;
         self.check_worktree_clean(&repo)?;
 
-        // Get the remote
-        let remote_name = self.default_remote_name(&repo);
-        let remote = repo.find_remote(&remote_name)?;
+        let remote_name = self.push_remote_name(worktree_path, branch_name, remote)?;
+        let remote = repo
+            .find_remote(&remote_name)
+            .map_err(|_| GitServiceError::RemoteNotFound(remote_name.clone()))?;
 
         let remote_url = remote
             .url()
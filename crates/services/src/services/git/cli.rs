@@ -106,6 +106,32 @@ impl GitCli {
         Ok(())
     }
 
+    /// Add a worktree for an existing branch that only materializes paths
+    /// matching `patterns` (non-cone sparse-checkout, gitignore syntax).
+    pub fn worktree_add_sparse(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        branch: &str,
+        patterns: &[String],
+    ) -> Result<(), GitCliError> {
+        self.ensure_available()?;
+
+        let mut args: Vec<OsString> = vec!["worktree".into(), "add".into(), "--no-checkout".into()];
+        args.push(worktree_path.as_os_str().into());
+        args.push(OsString::from(branch));
+        self.git(repo_path, args)?;
+
+        // Sparse settings are per worktree, so the main checkout is unaffected
+        let mut args: Vec<OsString> =
+            vec!["sparse-checkout".into(), "set".into(), "--no-cone".into()];
+        args.extend(patterns.iter().map(OsString::from));
+        self.git(worktree_path, args)?;
+        self.git(worktree_path, ["checkout", branch])?;
+
+        Ok(())
+    }
+
     /// Run `git -C <repo> worktree remove <path>`
     pub fn worktree_remove(
         &self,
@@ -185,6 +211,14 @@ impl GitCli {
         Ok(Self::parse_name_status(&out))
     }
 
+    /// Raw `git status --porcelain=v2` output, including branch and stash headers
+    pub fn status_porcelain_v2(&self, worktree_path: &Path) -> Result<String, GitCliError> {
+        self.git(
+            worktree_path,
+            ["status", "--porcelain=v2", "--branch", "--show-stash"],
+        )
+    }
+
     /// Return `git status --porcelain` parsed into a structured summary
     pub fn get_worktree_status(&self, worktree_path: &Path) -> Result<WorktreeStatus, GitCliError> {
         let out = self.git(worktree_path, ["status", "--porcelain"])?;
@@ -247,6 +281,18 @@ impl GitCli {
         Ok(())
     }
 
+    /// Stage all changes with `core.autocrlf` overridden, so text files are
+    /// normalized to LF in the index (subject to `.gitattributes`).
+    pub fn add_all_with_autocrlf(
+        &self,
+        worktree_path: &Path,
+        autocrlf: &str,
+    ) -> Result<(), GitCliError> {
+        let setting = format!("core.autocrlf={autocrlf}");
+        self.git(worktree_path, ["-c", setting.as_str(), "add", "-A"])?;
+        Ok(())
+    }
+
     /// Stage specific files in the working tree.
     pub fn add_files(&self, worktree_path: &Path, files: &[String]) -> Result<(), GitCliError> {
         if files.is_empty() {
@@ -305,6 +351,16 @@ impl GitCli {
         self.git(worktree_path, ["commit", "-m", message])?;
         Ok(())
     }
+
+    /// Commit with the given message even if nothing is staged.
+    pub fn commit_allow_empty(
+        &self,
+        worktree_path: &Path,
+        message: &str,
+    ) -> Result<(), GitCliError> {
+        self.git(worktree_path, ["commit", "--allow-empty", "-m", message])?;
+        Ok(())
+    }
     /// Fetch a branch to the given remote using native git authentication.
     pub fn fetch_with_refspec(
         &self,
@@ -557,6 +613,43 @@ impl GitCli {
         Ok(sha)
     }
 
+    /// Merge `from_branch` into `base_branch` with a merge commit, even when a
+    /// fast-forward would be possible. Returns the new HEAD sha.
+    pub fn merge_no_ff_commit(
+        &self,
+        repo_path: &Path,
+        base_branch: &str,
+        from_branch: &str,
+        message: &str,
+    ) -> Result<String, GitCliError> {
+        self.git(repo_path, ["checkout", base_branch]).map(|_| ())?;
+        self.git(repo_path, ["merge", "--no-ff", "-m", message, from_branch])
+            .map(|_| ())?;
+        let sha = self
+            .git(repo_path, ["rev-parse", "HEAD"])?
+            .trim()
+            .to_string();
+        Ok(sha)
+    }
+
+    /// Fast-forward `base_branch` to `from_branch`, failing if it has diverged.
+    /// Returns the new HEAD sha.
+    pub fn merge_ff_only(
+        &self,
+        repo_path: &Path,
+        base_branch: &str,
+        from_branch: &str,
+    ) -> Result<String, GitCliError> {
+        self.git(repo_path, ["checkout", base_branch]).map(|_| ())?;
+        self.git(repo_path, ["merge", "--ff-only", from_branch])
+            .map(|_| ())?;
+        let sha = self
+            .git(repo_path, ["rev-parse", "HEAD"])?
+            .trim()
+            .to_string();
+        Ok(sha)
+    }
+
     /// Update a ref to a specific sha in the repo.
     pub fn update_ref(
         &self,
@@ -608,6 +701,24 @@ impl GitCli {
         self.git(worktree_path, ["revert", "--abort"]).map(|_| ())
     }
 
+    /// Resolve a conflicted file by checking out one side (`side_flag` is `--ours`
+    /// or `--theirs`) and staging it. When that side deleted the file, the
+    /// deletion is staged instead.
+    pub fn checkout_conflict_side(
+        &self,
+        worktree_path: &Path,
+        path: &str,
+        side_flag: &str,
+    ) -> Result<(), GitCliError> {
+        match self.git(worktree_path, ["checkout", side_flag, "--", path]) {
+            Ok(_) => self.git(worktree_path, ["add", "--", path]).map(|_| ()),
+            Err(GitCliError::CommandFailed(msg)) if msg.contains("does not have") => self
+                .git(worktree_path, ["rm", "--quiet", "--", path])
+                .map(|_| ()),
+            Err(e) => Err(e),
+        }
+    }
+
     /// List files currently in a conflicted (unmerged) state in the worktree.
     pub fn get_conflicted_files(&self, worktree_path: &Path) -> Result<Vec<String>, GitCliError> {
         // `--diff-filter=U` lists paths with unresolved conflicts
@@ -0,0 +1,51 @@
+//! Picks the hosting provider (GitHub or GitLab) for a repository from its
+//! remote URL, so PR/MR operations go through the matching CLI.
+
+pub use db::models::merge::GitProvider;
+
+use crate::services::{github::GitHubRepoInfo, gitlab::GitLabRepoInfo};
+
+/// Hosts GitHub serves git over, including SSH over the HTTPS port
+const GITHUB_HOSTS: &[&str] = &["github.com", "www.github.com", "ssh.github.com"];
+
+/// Hosts of providers without PR/MR support here
+const OTHER_PROVIDER_HOSTS: &[&str] = &[
+    "bitbucket.org",
+    "codeberg.org",
+    "dev.azure.com",
+    "ssh.dev.azure.com",
+    "git.sr.ht",
+];
+
+#[derive(Debug, Clone)]
+pub enum RepoInfo {
+    GitHub(GitHubRepoInfo),
+    GitLab(GitLabRepoInfo),
+}
+
+impl RepoInfo {
+    /// GitHub when the remote's host is github.com. Any other host is taken to be
+    /// a GitLab instance, since self-managed GitLab can live anywhere, except the
+    /// well-known hosts of other providers, which are unsupported.
+    pub fn from_remote_url(remote_url: &str) -> Result<Self, String> {
+        let unsupported =
+            || format!("Remote is neither a GitHub nor a GitLab repository: {remote_url}");
+        let remote = GitLabRepoInfo::from_remote_url(remote_url).map_err(|_| unsupported())?;
+        if GITHUB_HOSTS.contains(&remote.host.as_str()) {
+            return GitHubRepoInfo::from_remote_url(remote_url)
+                .map(Self::GitHub)
+                .map_err(|e| e.to_string());
+        }
+        if OTHER_PROVIDER_HOSTS.contains(&remote.host.as_str()) {
+            return Err(unsupported());
+        }
+        Ok(Self::GitLab(remote))
+    }
+
+    pub fn provider(&self) -> GitProvider {
+        match self {
+            Self::GitHub(_) => GitProvider::GitHub,
+            Self::GitLab(_) => GitProvider::GitLab,
+        }
+    }
+}
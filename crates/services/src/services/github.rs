@@ -1,11 +1,14 @@
 use std::time::Duration;
 
 use backon::{ExponentialBuilder, Retryable};
+use chrono::{DateTime, Utc};
 use db::models::merge::PullRequestInfo;
 use regex::Regex;
+use serde::Serialize;
 use thiserror::Error;
 use tokio::task;
 use tracing::info;
+use ts_rs::TS;
 
 mod cli;
 
@@ -102,6 +105,18 @@ impl GitHubRepoInfo {
     }
 }
 
+/// GitHub API quota left for the authenticated `gh` user
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct GitHubRateLimit {
+    #[ts(type = "number")]
+    pub limit: u64,
+    #[ts(type = "number")]
+    pub remaining: u64,
+    /// When `remaining` is restored to `limit`
+    #[ts(type = "Date")]
+    pub reset_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CreatePrRequest {
     pub title: String,
@@ -144,6 +159,19 @@ impl GitHubService {
             })
     }
 
+    /// Current rate limit for the API the PR commands use
+    pub async fn rate_limit(&self) -> Result<GitHubRateLimit, GitHubServiceError> {
+        let cli = self.gh_cli.clone();
+        task::spawn_blocking(move || cli.rate_limit())
+            .await
+            .map_err(|err| {
+                GitHubServiceError::Repository(format!(
+                    "Failed to execute GitHub CLI for rate limit: {err}"
+                ))
+            })?
+            .map_err(GitHubServiceError::from)
+    }
+
     /// Create a pull request on GitHub
     pub async fn create_pr(
         &self,
@@ -15,7 +15,7 @@ use serde_json::Value;
 use thiserror::Error;
 use utils::shell::resolve_executable_path_blocking;
 
-use crate::services::github::{CreatePrRequest, GitHubRepoInfo};
+use crate::services::github::{CreatePrRequest, GitHubRateLimit, GitHubRepoInfo};
 
 /// High-level errors originating from the GitHub CLI.
 #[derive(Debug, Error)]
@@ -167,6 +167,13 @@ impl GhCli {
         ])?;
         Self::parse_pr_list(&raw)
     }
+
+    /// Read the GraphQL API rate limit, which the `gh pr` commands draw from.
+    /// Querying it does not count against the limit.
+    pub fn rate_limit(&self) -> Result<GitHubRateLimit, GhCliError> {
+        let raw = self.run(["api", "rate_limit"])?;
+        Self::parse_rate_limit(&raw)
+    }
 }
 
 impl GhCli {
@@ -243,6 +250,31 @@ impl GhCli {
             .collect()
     }
 
+    fn parse_rate_limit(raw: &str) -> Result<GitHubRateLimit, GhCliError> {
+        let value: Value = serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse gh api rate_limit response: {err}; raw: {raw}"
+            ))
+        })?;
+        let graphql = &value["resources"]["graphql"];
+        let limit = graphql.get("limit").and_then(Value::as_u64);
+        let remaining = graphql.get("remaining").and_then(Value::as_u64);
+        let reset_at = graphql
+            .get("reset")
+            .and_then(Value::as_i64)
+            .and_then(|secs| DateTime::from_timestamp(secs, 0));
+        match (limit, remaining, reset_at) {
+            (Some(limit), Some(remaining), Some(reset_at)) => Ok(GitHubRateLimit {
+                limit,
+                remaining,
+                reset_at,
+            }),
+            _ => Err(GhCliError::UnexpectedOutput(format!(
+                "gh api rate_limit response missing GraphQL limits: {value:#?}"
+            ))),
+        }
+    }
+
     fn extract_pr_info(value: &Value) -> Option<PullRequestInfo> {
         let number = value.get("number")?.as_i64()?;
         let url = value.get("url")?.as_str()?.to_string();
@@ -275,3 +307,21 @@ impl GhCli {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rate_limit() {
+        let raw = r#"{"resources":{"core":{"limit":5000,"remaining":4990,"reset":1700000000},
+            "graphql":{"limit":5000,"used":4950,"remaining":50,"reset":1700003600}}}"#;
+
+        let rate_limit = GhCli::parse_rate_limit(raw).unwrap();
+        assert_eq!(rate_limit.limit, 5000);
+        assert_eq!(rate_limit.remaining, 50);
+        assert_eq!(rate_limit.reset_at.timestamp(), 1700003600);
+
+        assert!(GhCli::parse_rate_limit(r#"{"resources":{}}"#).is_err());
+    }
+}
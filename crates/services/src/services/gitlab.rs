@@ -0,0 +1,232 @@
+use std::time::Duration;
+
+use backon::{ExponentialBuilder, Retryable};
+use db::models::merge::PullRequestInfo;
+use regex::Regex;
+use thiserror::Error;
+use tokio::task;
+use tracing::info;
+
+mod cli;
+
+use cli::{GlabCli, GlabCliError};
+
+use crate::services::github::CreatePrRequest;
+
+#[derive(Debug, Error)]
+pub enum GitLabServiceError {
+    #[error("Repository error: {0}")]
+    Repository(String),
+    #[error("Merge request error: {0}")]
+    MergeRequest(String),
+    #[error("GitLab authentication failed: {0}")]
+    AuthFailed(GlabCliError),
+    #[error("Insufficient permissions: {0}")]
+    InsufficientPermissions(GlabCliError),
+    #[error("GitLab project not found or no access: {0}")]
+    ProjectNotFoundOrNoAccess(GlabCliError),
+    #[error(
+        "GitLab CLI is not installed or not available in PATH. Please install it from https://gitlab.com/gitlab-org/cli and authenticate with 'glab auth login'"
+    )]
+    GlabCliNotInstalled(GlabCliError),
+}
+
+impl From<GlabCliError> for GitLabServiceError {
+    fn from(error: GlabCliError) -> Self {
+        match &error {
+            GlabCliError::AuthFailed(_) => Self::AuthFailed(error),
+            GlabCliError::NotAvailable => Self::GlabCliNotInstalled(error),
+            GlabCliError::CommandFailed(msg) => {
+                let lower = msg.to_ascii_lowercase();
+                if lower.contains("403") || lower.contains("forbidden") {
+                    Self::InsufficientPermissions(error)
+                } else if lower.contains("404") || lower.contains("not found") {
+                    Self::ProjectNotFoundOrNoAccess(error)
+                } else {
+                    Self::MergeRequest(msg.to_string())
+                }
+            }
+            GlabCliError::UnexpectedOutput(msg) => Self::MergeRequest(msg.to_string()),
+        }
+    }
+}
+
+impl GitLabServiceError {
+    pub fn should_retry(&self) -> bool {
+        !matches!(
+            self,
+            GitLabServiceError::AuthFailed(_)
+                | GitLabServiceError::InsufficientPermissions(_)
+                | GitLabServiceError::ProjectNotFoundOrNoAccess(_)
+                | GitLabServiceError::GlabCliNotInstalled(_)
+        )
+    }
+}
+
+/// A GitLab project, identified by its host and full namespace path
+/// (`group/subgroup/project`), so self-managed instances on any host work too.
+#[derive(Debug, Clone)]
+pub struct GitLabRepoInfo {
+    pub host: String,
+    pub project_path: String,
+}
+
+impl GitLabRepoInfo {
+    pub fn from_remote_url(remote_url: &str) -> Result<Self, GitLabServiceError> {
+        // Supports SSH, HTTPS and merge request URLs. See tests for examples.
+        let re = Regex::new(
+            r"^(?:[a-z][a-z0-9+.-]*://)?(?:[^@/]+@)?(?P<host>[^/:]+)(?::\d*)?[:/](?P<path>.+?)(?:\.git)?/?$",
+        )
+        .map_err(|e| GitLabServiceError::Repository(format!("Failed to compile regex: {e}")))?;
+
+        // Merge request and other web URLs put everything after the project under `/-/`
+        let project_url = remote_url.trim().split("/-/").next().unwrap_or_default();
+        let caps = re
+            .captures(project_url)
+            .filter(|caps| caps["path"].contains('/'))
+            .ok_or_else(|| {
+                GitLabServiceError::Repository(format!("Invalid GitLab URL format: {remote_url}"))
+            })?;
+
+        Ok(Self {
+            host: caps["host"].to_ascii_lowercase(),
+            project_path: caps["path"].to_string(),
+        })
+    }
+
+    /// Web URL of the project, which `glab --repo` accepts for any host
+    pub fn web_url(&self) -> String {
+        format!("https://{}/{}", self.host, self.project_path)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GitLabService {
+    glab_cli: GlabCli,
+}
+
+impl GitLabService {
+    pub fn new() -> Result<Self, GitLabServiceError> {
+        Ok(Self {
+            glab_cli: GlabCli::new(),
+        })
+    }
+
+    pub async fn check_token(&self) -> Result<(), GitLabServiceError> {
+        let cli = self.glab_cli.clone();
+        task::spawn_blocking(move || cli.check_auth())
+            .await
+            .map_err(|err| {
+                GitLabServiceError::Repository(format!(
+                    "Failed to execute GitLab CLI for auth check: {err}"
+                ))
+            })?
+            .map_err(GitLabServiceError::from)
+    }
+
+    /// Create a merge request on GitLab
+    pub async fn create_pr(
+        &self,
+        repo_info: &GitLabRepoInfo,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, GitLabServiceError> {
+        (|| async {
+            let cli = self.glab_cli.clone();
+            let request_clone = request.clone();
+            let repo_clone = repo_info.clone();
+            let mr = task::spawn_blocking(move || cli.create_mr(&request_clone, &repo_clone))
+                .await
+                .map_err(|err| {
+                    GitLabServiceError::MergeRequest(format!(
+                        "Failed to execute GitLab CLI for MR creation: {err}"
+                    ))
+                })?
+                .map_err(GitLabServiceError::from)?;
+
+            info!(
+                "Created GitLab MR !{} for branch {} in {}",
+                mr.number, request.head_branch, repo_info.project_path
+            );
+            Ok(mr)
+        })
+        .retry(retry_policy())
+        .when(|e: &GitLabServiceError| e.should_retry())
+        .notify(|err: &GitLabServiceError, dur: Duration| {
+            tracing::warn!(
+                "GitLab API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    /// Update and get the status of a merge request
+    pub async fn update_pr_status(
+        &self,
+        repo_info: &GitLabRepoInfo,
+        mr_number: i64,
+    ) -> Result<PullRequestInfo, GitLabServiceError> {
+        (|| async {
+            let cli = self.glab_cli.clone();
+            let repo = repo_info.clone();
+            task::spawn_blocking(move || cli.view_mr(&repo, mr_number))
+                .await
+                .map_err(|err| {
+                    GitLabServiceError::MergeRequest(format!(
+                        "Failed to execute GitLab CLI for viewing MR !{mr_number}: {err}"
+                    ))
+                })?
+                .map_err(GitLabServiceError::from)
+        })
+        .retry(retry_policy())
+        .when(|err: &GitLabServiceError| err.should_retry())
+        .notify(|err: &GitLabServiceError, dur: Duration| {
+            tracing::warn!(
+                "GitLab API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    /// List all merge requests for a branch (including closed/merged)
+    pub async fn list_all_prs_for_branch(
+        &self,
+        repo_info: &GitLabRepoInfo,
+        branch_name: &str,
+    ) -> Result<Vec<PullRequestInfo>, GitLabServiceError> {
+        (|| async {
+            let cli = self.glab_cli.clone();
+            let repo = repo_info.clone();
+            let branch = branch_name.to_string();
+            task::spawn_blocking(move || cli.list_mrs_for_branch(&repo, &branch))
+                .await
+                .map_err(|err| {
+                    GitLabServiceError::MergeRequest(format!(
+                        "Failed to execute GitLab CLI for listing MRs on branch '{branch_name}': {err}"
+                    ))
+                })?
+                .map_err(GitLabServiceError::from)
+        })
+        .retry(retry_policy())
+        .when(|e: &GitLabServiceError| e.should_retry())
+        .notify(|err: &GitLabServiceError, dur: Duration| {
+            tracing::warn!(
+                "GitLab API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+}
+
+fn retry_policy() -> ExponentialBuilder {
+    ExponentialBuilder::default()
+        .with_min_delay(Duration::from_secs(1))
+        .with_max_delay(Duration::from_secs(30))
+        .with_max_times(3)
+        .with_jitter()
+}
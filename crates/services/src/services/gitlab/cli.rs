@@ -0,0 +1,314 @@
+//! Minimal helpers around the GitLab CLI (`glab`).
+//!
+//! Mirrors `github/cli.rs`: merge requests are created, viewed and listed
+//! through `glab` so authentication stays with the user's CLI setup.
+
+use std::{
+    ffi::{OsStr, OsString},
+    process::Command,
+};
+
+use chrono::{DateTime, Utc};
+use db::models::merge::{MergeStatus, PullRequestInfo};
+use serde_json::Value;
+use thiserror::Error;
+use utils::shell::resolve_executable_path_blocking;
+
+use crate::services::{github::CreatePrRequest, gitlab::GitLabRepoInfo};
+
+/// High-level errors originating from the GitLab CLI.
+#[derive(Debug, Error)]
+pub enum GlabCliError {
+    #[error("GitLab CLI (`glab`) executable not found or not runnable")]
+    NotAvailable,
+    #[error("GitLab CLI command failed: {0}")]
+    CommandFailed(String),
+    #[error("GitLab CLI authentication failed: {0}")]
+    AuthFailed(String),
+    #[error("GitLab CLI returned unexpected output: {0}")]
+    UnexpectedOutput(String),
+}
+
+/// Newtype wrapper for invoking the `glab` command.
+#[derive(Debug, Clone, Default)]
+pub struct GlabCli;
+
+impl GlabCli {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Generic helper to execute `glab <args>` and return stdout on success.
+    fn run<I, S>(&self, args: I) -> Result<String, GlabCliError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let glab = resolve_executable_path_blocking("glab").ok_or(GlabCliError::NotAvailable)?;
+        let mut cmd = Command::new(&glab);
+        for arg in args {
+            cmd.arg(arg);
+        }
+        let output = cmd
+            .output()
+            .map_err(|err| GlabCliError::CommandFailed(err.to_string()))?;
+
+        if output.status.success() {
+            return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let lower = stderr.to_ascii_lowercase();
+        if lower.contains("401")
+            || lower.contains("unauthorized")
+            || lower.contains("not authenticated")
+            || lower.contains("glab auth login")
+        {
+            return Err(GlabCliError::AuthFailed(stderr));
+        }
+
+        Err(GlabCliError::CommandFailed(stderr))
+    }
+
+    /// Run `glab mr create` and parse the merge request URL it prints.
+    pub fn create_mr(
+        &self,
+        request: &CreatePrRequest,
+        repo_info: &GitLabRepoInfo,
+    ) -> Result<PullRequestInfo, GlabCliError> {
+        let args: Vec<OsString> = vec![
+            "mr".into(),
+            "create".into(),
+            "--repo".into(),
+            repo_info.web_url().into(),
+            "--source-branch".into(),
+            (&request.head_branch).into(),
+            "--target-branch".into(),
+            (&request.base_branch).into(),
+            "--title".into(),
+            (&request.title).into(),
+            "--description".into(),
+            request.body.as_deref().unwrap_or("").into(),
+            "--yes".into(),
+        ];
+
+        let raw = self.run(args)?;
+        Self::parse_mr_create_text(&raw)
+    }
+
+    /// Ensure the GitLab CLI has valid auth.
+    pub fn check_auth(&self) -> Result<(), GlabCliError> {
+        match self.run(["auth", "status"]) {
+            Ok(_) => Ok(()),
+            Err(GlabCliError::CommandFailed(msg)) => Err(GlabCliError::AuthFailed(msg)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Retrieve details for a single merge request.
+    pub fn view_mr(
+        &self,
+        repo_info: &GitLabRepoInfo,
+        mr_number: i64,
+    ) -> Result<PullRequestInfo, GlabCliError> {
+        let raw = self.run([
+            "mr",
+            "view",
+            &mr_number.to_string(),
+            "--repo",
+            &repo_info.web_url(),
+            "--output",
+            "json",
+        ])?;
+        Self::parse_mr_view(&raw)
+    }
+
+    /// List merge requests whose source is `branch` (includes closed/merged).
+    pub fn list_mrs_for_branch(
+        &self,
+        repo_info: &GitLabRepoInfo,
+        branch: &str,
+    ) -> Result<Vec<PullRequestInfo>, GlabCliError> {
+        let raw = self.run([
+            "mr",
+            "list",
+            "--repo",
+            &repo_info.web_url(),
+            "--source-branch",
+            branch,
+            "--all",
+            "--output",
+            "json",
+        ])?;
+        Self::parse_mr_list(&raw)
+    }
+}
+
+impl GlabCli {
+    fn parse_mr_create_text(raw: &str) -> Result<PullRequestInfo, GlabCliError> {
+        let mr_url = raw
+            .lines()
+            .rev()
+            .flat_map(|line| line.split_whitespace())
+            .find(|token| token.starts_with("http") && token.contains("/merge_requests/"))
+            .ok_or_else(|| {
+                GlabCliError::UnexpectedOutput(format!(
+                    "glab mr create did not return a merge request URL; raw output: {raw}"
+                ))
+            })?
+            .trim_end_matches(['.', ',', ';'])
+            .to_string();
+
+        let number = mr_url
+            .rsplit('/')
+            .next()
+            .and_then(|n| n.parse::<i64>().ok())
+            .ok_or_else(|| {
+                GlabCliError::UnexpectedOutput(format!(
+                    "Failed to parse MR number from URL '{mr_url}'"
+                ))
+            })?;
+
+        Ok(PullRequestInfo {
+            number,
+            url: mr_url,
+            status: MergeStatus::Open,
+            merged_at: None,
+            merge_commit_sha: None,
+        })
+    }
+
+    fn parse_mr_view(raw: &str) -> Result<PullRequestInfo, GlabCliError> {
+        let value: Value = serde_json::from_str(raw.trim()).map_err(|err| {
+            GlabCliError::UnexpectedOutput(format!(
+                "Failed to parse glab mr view response: {err}; raw: {raw}"
+            ))
+        })?;
+        Self::extract_mr_info(&value).ok_or_else(|| {
+            GlabCliError::UnexpectedOutput(format!(
+                "glab mr view response missing required fields: {value:#?}"
+            ))
+        })
+    }
+
+    fn parse_mr_list(raw: &str) -> Result<Vec<PullRequestInfo>, GlabCliError> {
+        let value: Value = serde_json::from_str(raw.trim()).map_err(|err| {
+            GlabCliError::UnexpectedOutput(format!(
+                "Failed to parse glab mr list response: {err}; raw: {raw}"
+            ))
+        })?;
+        let arr = value.as_array().ok_or_else(|| {
+            GlabCliError::UnexpectedOutput(format!(
+                "glab mr list response is not an array: {value:#?}"
+            ))
+        })?;
+        arr.iter()
+            .map(|item| {
+                Self::extract_mr_info(item).ok_or_else(|| {
+                    GlabCliError::UnexpectedOutput(format!(
+                        "glab mr list item missing required fields: {item:#?}"
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    fn extract_mr_info(value: &Value) -> Option<PullRequestInfo> {
+        let number = value.get("iid")?.as_i64()?;
+        let url = value.get("web_url")?.as_str()?.to_string();
+        let state = value
+            .get("state")
+            .and_then(Value::as_str)
+            .unwrap_or("opened");
+        let merged_at = value
+            .get("merged_at")
+            .and_then(Value::as_str)
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+        // Squash merges record the squashed commit separately
+        let merge_commit_sha = ["merge_commit_sha", "squash_commit_sha"]
+            .iter()
+            .find_map(|key| value.get(*key).and_then(Value::as_str))
+            .map(|s| s.to_string());
+        Some(PullRequestInfo {
+            number,
+            url,
+            status: match state {
+                "opened" => MergeStatus::Open,
+                "merged" => MergeStatus::Merged,
+                "closed" | "locked" => MergeStatus::Closed,
+                _ => MergeStatus::Unknown,
+            },
+            merged_at,
+            merge_commit_sha,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_mr_create_text() {
+        let raw = "Creating merge request for feature into main in group/sub/repo\n\n\
+                   !42 Add the thing (feature)\n \
+                   https://gitlab.example.com/group/sub/repo/-/merge_requests/42.\n";
+        let info = GlabCli::parse_mr_create_text(raw).unwrap();
+        assert_eq!(info.number, 42);
+        assert_eq!(
+            info.url,
+            "https://gitlab.example.com/group/sub/repo/-/merge_requests/42"
+        );
+        assert_eq!(info.status, MergeStatus::Open);
+
+        assert!(GlabCli::parse_mr_create_text("https://gitlab.com/group/repo").is_err());
+        assert!(
+            GlabCli::parse_mr_create_text("https://gitlab.com/group/repo/-/merge_requests/new")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_extract_mr_info() {
+        let merged = json!({
+            "iid": 7,
+            "web_url": "https://gitlab.com/group/repo/-/merge_requests/7",
+            "state": "merged",
+            "merged_at": "2024-05-01T12:00:00Z",
+            "merge_commit_sha": null,
+            "squash_commit_sha": "abc123",
+        });
+        let info = GlabCli::extract_mr_info(&merged).unwrap();
+        assert_eq!(info.number, 7);
+        assert_eq!(info.status, MergeStatus::Merged);
+        assert_eq!(
+            info.merged_at.unwrap().to_rfc3339(),
+            "2024-05-01T12:00:00+00:00"
+        );
+        // Squash merges report the squashed commit
+        assert_eq!(info.merge_commit_sha.as_deref(), Some("abc123"));
+
+        for (state, status) in [
+            ("opened", MergeStatus::Open),
+            ("closed", MergeStatus::Closed),
+            ("locked", MergeStatus::Closed),
+            ("draft", MergeStatus::Unknown),
+        ] {
+            let value = json!({
+                "iid": 1,
+                "web_url": "https://gitlab.com/group/repo/-/merge_requests/1",
+                "state": state,
+            });
+            let info = GlabCli::extract_mr_info(&value).unwrap();
+            assert_eq!(info.status, status);
+            assert_eq!(info.merged_at, None);
+            assert_eq!(info.merge_commit_sha, None);
+        }
+
+        assert!(GlabCli::extract_mr_info(&json!({"web_url": "https://gitlab.com"})).is_none());
+        assert!(GlabCli::extract_mr_info(&json!({"iid": 1})).is_none());
+    }
+}
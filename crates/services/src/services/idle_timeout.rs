@@ -0,0 +1,193 @@
+//! Stop agents that have gone quiet for longer than the configured idle timeout.
+
+use std::{collections::HashSet, time::Duration};
+
+use executors::logs::{
+    NormalizedEntry, NormalizedEntryError, NormalizedEntryType, ToolStatus,
+    utils::{ConversationPatch, EntryIndexProvider, patch::extract_normalized_entry_from_patch},
+};
+use tokio::{
+    sync::broadcast::{Receiver, error::RecvError},
+    time::Instant,
+};
+use utils::{log_msg::LogMsg, msg_store::MsgStore};
+
+/// Wait until `timeout` passes without a new message on `rx`, which should be
+/// subscribed to the execution's `MsgStore`. The timer is paused while a tool
+/// call waits on a user's approval, since the agent is blocked rather than
+/// stuck. Returns `false` when the execution finished first.
+pub async fn wait_for_idle(mut rx: Receiver<LogMsg>, timeout: Duration) -> bool {
+    let mut last_msg_at = Instant::now();
+    let mut pending_approvals = HashSet::new();
+    loop {
+        let msg = if pending_approvals.is_empty() {
+            match tokio::time::timeout_at(last_msg_at + timeout, rx.recv()).await {
+                Err(_) => return true,
+                Ok(msg) => msg,
+            }
+        } else {
+            rx.recv().await
+        };
+        match msg {
+            Ok(LogMsg::Finished) | Err(RecvError::Closed) => return false,
+            Ok(LogMsg::JsonPatch(patch)) => {
+                if let Some((index, entry)) = extract_normalized_entry_from_patch(&patch) {
+                    if awaits_approval(&entry) {
+                        pending_approvals.insert(index);
+                    } else {
+                        pending_approvals.remove(&index);
+                    }
+                }
+                last_msg_at = Instant::now();
+            }
+            // A lagged receiver still means output is flowing
+            Ok(_) | Err(RecvError::Lagged(_)) => last_msg_at = Instant::now(),
+        }
+    }
+}
+
+fn awaits_approval(entry: &NormalizedEntry) -> bool {
+    matches!(
+        entry.entry_type,
+        NormalizedEntryType::ToolUse {
+            status: ToolStatus::PendingApproval { .. },
+            ..
+        }
+    )
+}
+
+/// Append an error entry to the conversation explaining why the execution was stopped
+pub fn push_idle_timeout_entry(store: &MsgStore, timeout: Duration) {
+    let index = EntryIndexProvider::start_from(store).next();
+    store.push_patch(ConversationPatch::add_normalized_entry(
+        index,
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::ErrorMessage {
+                error_type: NormalizedEntryError::Other,
+            },
+            content: format!(
+                "Execution stopped: no output for {} seconds (execution_idle_timeout_secs)",
+                timeout.as_secs()
+            ),
+            metadata: None,
+        },
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use executors::logs::ActionType;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_output_resets_the_idle_timer() {
+        let store = MsgStore::new();
+        let timeout = Duration::from_millis(200);
+        let watcher = tokio::spawn(wait_for_idle(store.get_receiver(), timeout));
+
+        for _ in 0..3 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            store.push_stdout("still working\n");
+        }
+        assert!(!watcher.is_finished());
+
+        let idle = tokio::time::timeout(Duration::from_secs(5), watcher)
+            .await
+            .expect("watcher should fire once output stops")
+            .unwrap();
+        assert!(idle);
+    }
+
+    #[tokio::test]
+    async fn test_finished_execution_is_not_idle() {
+        let store = MsgStore::new();
+        let watcher = tokio::spawn(wait_for_idle(store.get_receiver(), Duration::from_secs(60)));
+
+        store.push_finished();
+        let idle = tokio::time::timeout(Duration::from_secs(5), watcher)
+            .await
+            .expect("watcher should stop when the execution finishes")
+            .unwrap();
+        assert!(!idle);
+    }
+
+    fn tool_use(status: ToolStatus) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::ToolUse {
+                tool_name: "Bash".to_string(),
+                action_type: ActionType::Other {
+                    description: "rm -rf build".to_string(),
+                },
+                status,
+            },
+            content: "rm -rf build".to_string(),
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pending_approval_pauses_the_idle_timer() {
+        let store = MsgStore::new();
+        let timeout = Duration::from_millis(100);
+        let watcher = tokio::spawn(wait_for_idle(store.get_receiver(), timeout));
+
+        store.push_patch(ConversationPatch::add_normalized_entry(
+            0,
+            tool_use(ToolStatus::PendingApproval {
+                approval_id: "approval-1".to_string(),
+                requested_at: Utc::now(),
+                timeout_at: Utc::now() + chrono::Duration::hours(1),
+            }),
+        ));
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert!(!watcher.is_finished());
+
+        // Once the user answers, a quiet agent is stopped as usual
+        store.push_patch(ConversationPatch::replace(0, tool_use(ToolStatus::Created)));
+        let idle = tokio::time::timeout(Duration::from_secs(5), watcher)
+            .await
+            .expect("watcher should fire once the approval is answered")
+            .unwrap();
+        assert!(idle);
+    }
+
+    #[test]
+    fn test_timeout_entry_does_not_reuse_the_normalizers_index() {
+        let store = MsgStore::new();
+        // The normalizer has claimed index 0 but not pushed its entry yet
+        let normalizer = EntryIndexProvider::start_from(&store);
+        assert_eq!(normalizer.next(), 0);
+
+        push_idle_timeout_entry(&store, Duration::from_secs(300));
+
+        let history = store.get_history();
+        let Some(LogMsg::JsonPatch(patch)) = history.last() else {
+            panic!("expected the timeout entry");
+        };
+        assert_eq!(extract_normalized_entry_from_patch(patch).unwrap().0, 1);
+        assert_eq!(normalizer.next(), 2);
+    }
+
+    #[test]
+    fn test_timeout_entry_follows_existing_entries() {
+        let store = MsgStore::new();
+        store.push_patch(ConversationPatch::add_normalized_entry(
+            0,
+            NormalizedEntry {
+                timestamp: None,
+                entry_type: NormalizedEntryType::AssistantMessage,
+                content: "Thinking...".to_string(),
+                metadata: None,
+            },
+        ));
+
+        push_idle_timeout_entry(&store, Duration::from_secs(300));
+
+        let index = EntryIndexProvider::start_from(&store).current();
+        assert_eq!(index, 2);
+    }
+}
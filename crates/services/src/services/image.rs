@@ -3,7 +3,8 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use db::models::image::{CreateImage, Image};
+use db::models::image::{CreateImage, Image, TaskImage};
+use regex::{Captures, Regex};
 use sha2::{Digest, Sha256};
 use sqlx::SqlitePool;
 use uuid::Uuid;
@@ -185,6 +186,46 @@ impl ImageService {
         self.copy_images(worktree_path, images)
     }
 
+    /// Associate `image_ids` with a task and copy the task's images into the worktree.
+    /// Ids with no stored image are skipped with a warning.
+    pub async fn attach_images_to_task(
+        &self,
+        worktree_path: &Path,
+        task_id: Uuid,
+        image_ids: &[Uuid],
+    ) -> Result<(), ImageError> {
+        let mut found = Vec::with_capacity(image_ids.len());
+        for &id in image_ids {
+            if Image::find_by_id(&self.pool, id).await?.is_some() {
+                found.push(id);
+            } else {
+                tracing::warn!("Skipping missing image {} for task {}", id, task_id);
+            }
+        }
+        if found.is_empty() {
+            return Ok(());
+        }
+
+        TaskImage::associate_many_dedup(&self.pool, task_id, &found).await?;
+        self.copy_images_by_task_to_worktree(worktree_path, task_id)
+            .await
+    }
+
+    /// Rewrite worktree-relative `.vibe-images/...` references in `prompt` to
+    /// absolute paths, so agents can open them whatever their working directory.
+    pub fn canonicalise_image_paths(prompt: &str, worktree_path: &Path) -> String {
+        let images_dir = worktree_path.join(utils::path::VIBE_IMAGES_DIR);
+        let pattern = format!(
+            r#"(^|[\s(\["']){}/"#,
+            regex::escape(utils::path::VIBE_IMAGES_DIR)
+        );
+        let re = Regex::new(&pattern).expect("image path pattern is valid");
+        re.replace_all(prompt, |caps: &Captures| {
+            format!("{}{}/", &caps[1], images_dir.display())
+        })
+        .into_owned()
+    }
+
     fn copy_images(&self, worktree_path: &Path, images: Vec<Image>) -> Result<(), ImageError> {
         if images.is_empty() {
             return Ok(());
@@ -216,3 +257,21 @@ impl ImageService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonicalise_image_paths() {
+        let worktree = Path::new("/tmp/worktree");
+        let prompt = "See ![shot](.vibe-images/a.png) and .vibe-images/b.png\n\
+                      but not docs/.vibe-images/c.png";
+
+        assert_eq!(
+            ImageService::canonicalise_image_paths(prompt, worktree),
+            "See ![shot](/tmp/worktree/.vibe-images/a.png) and /tmp/worktree/.vibe-images/b.png\n\
+             but not docs/.vibe-images/c.png"
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use std::path::{Path, PathBuf};
+
+use tokio::{fs, io::AsyncWriteExt};
+use uuid::Uuid;
+
+use crate::services::config::LogFileSinkConfig;
+
+/// Appends raw JSONL execution logs to disk, rotating by size.
+///
+/// The active file lives at the resolved path; rotated segments are renamed to
+/// `<path>.1`, `<path>.2`, ... in the order they were filled, so concatenating
+/// the numbered segments followed by the active file reproduces the full log.
+pub struct LogFileSink {
+    path: PathBuf,
+    max_file_bytes: u64,
+    max_files: Option<u32>,
+    current_bytes: u64,
+    next_segment: u32,
+    file: Option<fs::File>,
+}
+
+impl LogFileSink {
+    /// Resolve the configured path template for an execution process.
+    pub fn resolve_path(
+        config: &LogFileSinkConfig,
+        base_dir: &Path,
+        execution_id: Uuid,
+        task_attempt_id: Uuid,
+    ) -> PathBuf {
+        let rendered = config
+            .path_template
+            .replace("{execution_id}", &execution_id.to_string())
+            .replace("{task_attempt_id}", &task_attempt_id.to_string());
+        let path = PathBuf::from(rendered);
+        if path.is_absolute() {
+            path
+        } else {
+            base_dir.join(path)
+        }
+    }
+
+    pub async fn open(
+        path: PathBuf,
+        max_file_bytes: u64,
+        max_files: Option<u32>,
+    ) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let segments = Self::rotated_segments(&path).await?;
+        let next_segment = segments.last().map(|(n, _)| n + 1).unwrap_or(1);
+        let current_bytes = match fs::metadata(&path).await {
+            Ok(meta) => meta.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self {
+            path,
+            max_file_bytes: max_file_bytes.max(1),
+            max_files,
+            current_bytes,
+            next_segment,
+            file: None,
+        })
+    }
+
+    /// Append a single JSONL line (including its trailing newline).
+    pub async fn append(&mut self, line: &str) -> std::io::Result<()> {
+        let len = line.len() as u64;
+        if self.current_bytes > 0 && self.current_bytes + len > self.max_file_bytes {
+            self.rotate().await?;
+        }
+
+        if self.file.is_none() {
+            self.file = Some(
+                fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)
+                    .await?,
+            );
+        }
+        if let Some(file) = self.file.as_mut() {
+            file.write_all(line.as_bytes()).await?;
+            file.flush().await?;
+        }
+        self.current_bytes += len;
+        Ok(())
+    }
+
+    async fn rotate(&mut self) -> std::io::Result<()> {
+        // Drop the handle before renaming so this also works on Windows
+        self.file = None;
+        fs::rename(
+            &self.path,
+            Self::segment_path(&self.path, self.next_segment),
+        )
+        .await?;
+        self.next_segment += 1;
+        self.current_bytes = 0;
+
+        if let Some(max_files) = self.max_files {
+            let segments = Self::rotated_segments(&self.path).await?;
+            // The active file counts towards the limit
+            let keep = max_files.saturating_sub(1) as usize;
+            if segments.len() > keep {
+                for (_, path) in &segments[..segments.len() - keep] {
+                    fs::remove_file(path).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn segment_path(path: &Path, index: u32) -> PathBuf {
+        let mut name = path.as_os_str().to_owned();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+
+    /// Rotated segments for `path`, ordered oldest first.
+    async fn rotated_segments(path: &Path) -> std::io::Result<Vec<(u32, PathBuf)>> {
+        let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+            return Ok(Vec::new());
+        };
+        let prefix = format!("{}.", file_name.to_string_lossy());
+
+        let mut segments = Vec::new();
+        let mut entries = match fs::read_dir(dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(segments),
+            Err(e) => return Err(e),
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if let Some(index) = name
+                .strip_prefix(&prefix)
+                .and_then(|suffix| suffix.parse::<u32>().ok())
+            {
+                segments.push((index, entry.path()));
+            }
+        }
+        segments.sort_by_key(|(index, _)| *index);
+        Ok(segments)
+    }
+
+    /// Read back every retained segment plus the active file as one JSONL string.
+    pub async fn read_all(path: &Path) -> std::io::Result<String> {
+        let mut out = String::new();
+        for (_, segment) in Self::rotated_segments(path).await? {
+            out.push_str(&fs::read_to_string(segment).await?);
+        }
+        match fs::read_to_string(path).await {
+            Ok(active) => out.push_str(&active),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rotation_splits_files_and_preserves_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("process.jsonl");
+        let mut sink = LogFileSink::open(path.clone(), 64, None).await.unwrap();
+
+        let mut expected = String::new();
+        for i in 0..20 {
+            let line = format!("{{\"Stdout\":\"line {i}\"}}\n");
+            expected.push_str(&line);
+            sink.append(&line).await.unwrap();
+        }
+
+        let segments = LogFileSink::rotated_segments(&path).await.unwrap();
+        assert!(segments.len() > 1, "expected multiple rotated files");
+        assert_eq!(LogFileSink::read_all(&path).await.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_rotation_respects_max_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("process.jsonl");
+        let mut sink = LogFileSink::open(path.clone(), 16, Some(3)).await.unwrap();
+
+        for i in 0..10 {
+            sink.append(&format!("{{\"n\":{i:04}}}\n")).await.unwrap();
+        }
+
+        let segments = LogFileSink::rotated_segments(&path).await.unwrap();
+        assert_eq!(segments.len(), 2);
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_resolve_path_template() {
+        let config = LogFileSinkConfig {
+            path_template: "logs/{task_attempt_id}/{execution_id}.jsonl".to_string(),
+            max_file_bytes: 1024,
+            max_files: None,
+        };
+        let exec = Uuid::new_v4();
+        let attempt = Uuid::new_v4();
+        let path = LogFileSink::resolve_path(&config, Path::new("/base"), exec, attempt);
+        assert_eq!(
+            path,
+            PathBuf::from(format!("/base/logs/{attempt}/{exec}.jsonl"))
+        );
+    }
+}
@@ -1,9 +1,13 @@
+pub mod agent_login;
 pub mod analytics;
 pub mod approvals;
 pub mod auth;
+pub mod branch_name;
+pub mod branch_status;
 pub mod commit_message;
 pub mod config;
 pub mod container;
+pub mod dev_server_url;
 pub mod diff_stream;
 pub mod events;
 pub mod file_ranker;
@@ -11,12 +15,20 @@ pub mod file_search_cache;
 pub mod filesystem;
 pub mod filesystem_watcher;
 pub mod git;
+pub mod git_provider;
 pub mod github;
+pub mod gitlab;
+pub mod idle_timeout;
 pub mod image;
+pub mod log_file_sink;
 pub mod notification;
 pub mod oauth_credentials;
+pub mod output_abort;
 pub mod pr_monitor;
+pub mod project_bundle;
 pub mod queued_message;
 pub mod remote_client;
 pub mod share;
+pub mod task_registry;
+pub mod webhook;
 pub mod worktree_manager;
@@ -1,9 +1,13 @@
 use std::sync::OnceLock;
 
-use db::models::execution_process::{ExecutionContext, ExecutionProcessStatus};
+use db::models::{
+    execution_process::{ExecutionContext, ExecutionProcessStatus},
+    task::Task,
+    task_attempt::TaskAttempt,
+};
 use utils::{self, port_file::read_port_file};
 
-use crate::services::config::SoundFile;
+use crate::services::{config::SoundFile, git::ConflictOp};
 
 /// Service for handling cross-platform notifications including sound alerts and push notifications
 #[derive(Debug, Clone)]
@@ -34,6 +38,10 @@ impl NotificationService {
                 "🛑 '{}' execution cancelled by user\nBranch: {:?}\nExecutor: {}",
                 ctx.task.title, ctx.task_attempt.branch, ctx.task_attempt.executor
             ),
+            ExecutionProcessStatus::TimedOut => format!(
+                "⏱️ '{}' execution stopped after producing no output\nBranch: {:?}\nExecutor: {}",
+                ctx.task.title, ctx.task_attempt.branch, ctx.task_attempt.executor
+            ),
             _ => {
                 tracing::warn!(
                     "Tried to notify attempt completion for {} but process is still running!",
@@ -44,13 +52,37 @@ impl NotificationService {
         };
 
         // Construct URL to open when notification is clicked
-        let url = Self::build_attempt_url(ctx).await;
+        let url = Self::build_attempt_url(&ctx.task, &ctx.task_attempt).await;
+
+        Self::notify(config, &title, &message, url.as_deref()).await;
+    }
+
+    /// Tell the user a git operation on an attempt stopped on conflicts
+    pub async fn notify_merge_conflict(
+        config: NotificationConfig,
+        task: &Task,
+        task_attempt: &TaskAttempt,
+        op: &ConflictOp,
+    ) {
+        let op_name = match op {
+            ConflictOp::Rebase => "rebase",
+            ConflictOp::Merge => "merge",
+            ConflictOp::CherryPick => "cherry-pick",
+            ConflictOp::Revert => "revert",
+        };
+        let title = format!("Conflicts: {}", task.title);
+        let message = format!(
+            "⚠️ '{}' hit conflicts during {}\nBranch: {:?}\nResolve them to continue.",
+            task.title, op_name, task_attempt.branch
+        );
+
+        let url = Self::build_attempt_url(task, task_attempt).await;
 
         Self::notify(config, &title, &message, url.as_deref()).await;
     }
 
     /// Build the URL for the task attempt page
-    async fn build_attempt_url(ctx: &ExecutionContext) -> Option<String> {
+    async fn build_attempt_url(task: &Task, task_attempt: &TaskAttempt) -> Option<String> {
         let port = match read_port_file("vibe-kanban").await {
             Ok(p) => p,
             Err(e) => {
@@ -61,7 +93,7 @@ impl NotificationService {
 
         Some(format!(
             "http://127.0.0.1:{}/projects/{}/tasks/{}/attempts/{}",
-            port, ctx.task.project_id, ctx.task.id, ctx.task_attempt.id
+            port, task.project_id, task.id, task_attempt.id
         ))
     }
 
@@ -0,0 +1,140 @@
+//! Abort agents whose output matches a project's configured pattern.
+
+use std::{future::Future, sync::Arc};
+
+use executors::logs::{
+    NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
+    utils::{ConversationPatch, EntryIndexProvider, patch::extract_normalized_entry_from_patch},
+};
+use futures::StreamExt;
+use regex::Regex;
+use utils::{log_msg::LogMsg, msg_store::MsgStore};
+
+pub fn parse_abort_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    Regex::new(pattern)
+}
+
+/// Watch the normalized conversation in `store` until an assistant message
+/// matches `pattern`. On a match an error entry explaining the abort is appended
+/// and `stop` is run with the matched text. Returns `false` when the execution
+/// finished first.
+pub async fn abort_on_pattern<F, Fut>(store: Arc<MsgStore>, pattern: Regex, stop: F) -> bool
+where
+    F: FnOnce(String) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut stream = store.history_plus_stream();
+
+    while let Some(Ok(msg)) = stream.next().await {
+        let patch = match msg {
+            LogMsg::JsonPatch(patch) => patch,
+            LogMsg::Finished => return false,
+            _ => continue,
+        };
+        let Some((_, entry)) = extract_normalized_entry_from_patch(&patch) else {
+            continue;
+        };
+        if !matches!(entry.entry_type, NormalizedEntryType::AssistantMessage) {
+            continue;
+        }
+        if let Some(found) = pattern.find(&entry.content) {
+            let matched = found.as_str().to_string();
+            store.push_patch(ConversationPatch::add_normalized_entry(
+                EntryIndexProvider::start_from(&store).next(),
+                NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::ErrorMessage {
+                        error_type: NormalizedEntryError::Other,
+                    },
+                    content: format!(
+                        "Execution stopped: output matched the abort pattern `{}` (\"{matched}\")",
+                        pattern.as_str()
+                    ),
+                    metadata: None,
+                },
+            ));
+            stop(matched).await;
+            return true;
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn assistant(content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::AssistantMessage,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_matching_output_stops_execution() {
+        let store = Arc::new(MsgStore::new());
+        let pattern = parse_abort_pattern(r"(?i)i cannot continue").unwrap();
+        let (stopped_tx, stopped_rx) = tokio::sync::oneshot::channel();
+        let watcher = tokio::spawn(abort_on_pattern(
+            store.clone(),
+            pattern,
+            |matched| async move {
+                let _ = stopped_tx.send(matched);
+            },
+        ));
+
+        // The normalizer claims its indexes from the store's shared provider
+        let normalizer = EntryIndexProvider::start_from(&store);
+        store.push_patch(ConversationPatch::add_normalized_entry(
+            normalizer.next(),
+            assistant("Working on the migration."),
+        ));
+        store.push_patch(ConversationPatch::add_normalized_entry(
+            normalizer.next(),
+            assistant("I cannot continue without database access."),
+        ));
+
+        let matched = tokio::time::timeout(Duration::from_secs(5), stopped_rx)
+            .await
+            .expect("the execution should be stopped on the matching message")
+            .unwrap();
+        assert_eq!(matched, "I cannot continue");
+        assert!(watcher.await.unwrap());
+
+        let note = store
+            .get_history()
+            .iter()
+            .filter_map(|msg| match msg {
+                LogMsg::JsonPatch(patch) => extract_normalized_entry_from_patch(patch),
+                _ => None,
+            })
+            .last()
+            .unwrap();
+        assert_eq!(note.0, 2);
+        assert!(matches!(
+            note.1.entry_type,
+            NormalizedEntryType::ErrorMessage { .. }
+        ));
+        // Entries the normalizer adds after the abort don't overwrite the note
+        assert_eq!(normalizer.next(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_finished_execution_without_match() {
+        let store = Arc::new(MsgStore::new());
+        store.push_patch(ConversationPatch::add_normalized_entry(
+            0,
+            assistant("All done."),
+        ));
+        store.push_finished();
+
+        let pattern = parse_abort_pattern("I cannot continue").unwrap();
+        assert!(!abort_on_pattern(store, pattern, |_| async {}).await);
+    }
+}
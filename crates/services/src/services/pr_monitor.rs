@@ -1,23 +1,32 @@
-use std::time::Duration;
+use std::{
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
 
+use chrono::{DateTime, Utc};
 use db::{
     DBService,
     models::{
-        merge::{Merge, MergeStatus, PrMerge},
+        merge::{GitProvider, Merge, MergeStatus, PrMerge},
         task::{Task, TaskStatus},
         task_attempt::{TaskAttempt, TaskAttemptError},
     },
 };
+use serde::Serialize;
 use serde_json::json;
 use sqlx::error::Error as SqlxError;
 use thiserror::Error;
-use tokio::{sync::watch, time::interval};
-use tracing::{debug, error, info};
+use tokio::{sync::watch, time::sleep};
+use tracing::{debug, error, info, warn};
+use ts_rs::TS;
 
 use crate::services::{
     analytics::AnalyticsContext,
-    github::{GitHubRepoInfo, GitHubService, GitHubServiceError},
-    share::SharePublisher,
+    events::{EventService, pr_sync_patch},
+    github::{GitHubRateLimit, GitHubRepoInfo, GitHubService, GitHubServiceError},
+    gitlab::{GitLabRepoInfo, GitLabService, GitLabServiceError},
+    share::{SharePublisher, with_share_publisher},
+    task_registry::TaskRegistry,
 };
 
 #[derive(Debug, Error)]
@@ -25,14 +34,55 @@ enum PrMonitorError {
     #[error(transparent)]
     GitHubServiceError(#[from] GitHubServiceError),
     #[error(transparent)]
+    GitLabServiceError(#[from] GitLabServiceError),
+    #[error(transparent)]
     TaskAttemptError(#[from] TaskAttemptError),
     #[error(transparent)]
     Sqlx(#[from] SqlxError),
 }
 
-/// Service to monitor GitHub PRs and update task status when they are merged
+/// Below this many remaining GitHub API points, polling waits for the rate limit to reset
+const GITHUB_RATE_LIMIT_LOW_WATERMARK: u64 = 100;
+
+/// GitHub rate-limit state of PR syncing, published on the event stream at `/pr_sync`
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct PrSyncStatus {
+    pub rate_limit: GitHubRateLimit,
+    /// Set while PR polling is paused until the rate limit resets
+    #[ts(type = "Date | null")]
+    pub paused_until: Option<DateTime<Utc>>,
+}
+
+/// Timings of the PR monitor's polls since startup
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrPollStats {
+    /// Polls run, whether or not they succeeded
+    pub polls: u64,
+    /// Time spent across all polls
+    pub total: Duration,
+    /// Duration of the most recent poll
+    pub last: Option<Duration>,
+}
+
+static POLL_STATS: LazyLock<Mutex<PrPollStats>> =
+    LazyLock::new(|| Mutex::new(PrPollStats::default()));
+
+/// Current PR poll timings, for metrics
+pub fn pr_poll_stats() -> PrPollStats {
+    *POLL_STATS.lock().unwrap()
+}
+
+fn record_poll(elapsed: Duration) {
+    let mut stats = POLL_STATS.lock().unwrap();
+    stats.polls += 1;
+    stats.total += elapsed;
+    stats.last = Some(elapsed);
+}
+
+/// Service to monitor GitHub PRs and GitLab MRs and update task status when they are merged
 pub struct PrMonitorService {
     db: DBService,
+    events: EventService,
     poll_interval: Duration,
     analytics: Option<AnalyticsContext>,
     publisher: Option<SharePublisher>,
@@ -62,17 +112,21 @@ impl PrMonitorHandle {
 impl PrMonitorService {
     pub async fn spawn(
         db: DBService,
+        events: EventService,
         analytics: Option<AnalyticsContext>,
         publisher: Option<SharePublisher>,
+        task_registry: &TaskRegistry,
     ) -> PrMonitorHandle {
         let service = Self {
             db,
+            events,
             poll_interval: Duration::from_secs(60), // Check every minute
             analytics,
             publisher,
         };
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
-        let join_handle = tokio::spawn(async move {
+        // Stopped through the shutdown channel, so the registry must not abort it
+        let join_handle = task_registry.spawn("pr_monitor", false, async move {
             service.start(shutdown_rx).await;
         });
         PrMonitorHandle {
@@ -87,7 +141,7 @@ impl PrMonitorService {
             self.poll_interval
         );
 
-        let mut interval = interval(self.poll_interval);
+        let mut next_poll = Duration::ZERO;
 
         loop {
             tokio::select! {
@@ -97,24 +151,34 @@ impl PrMonitorService {
                         break;
                     }
                 }
-                _ = interval.tick() => {
-                    if let Err(e) = self.check_all_open_prs().await {
-                        error!("Error checking open PRs: {}", e);
-                    }
+                _ = sleep(next_poll) => {
+                    let started = Instant::now();
+                    let result = self.check_all_open_prs().await;
+                    record_poll(started.elapsed());
+                    next_poll = match result {
+                        Ok(delay) => delay,
+                        Err(e) => {
+                            error!("Error checking open PRs: {}", e);
+                            self.poll_interval
+                        }
+                    };
                 }
             }
         }
         info!("PR monitoring service stopped");
     }
 
-    /// Check all open PRs for updates with the provided GitHub token
-    async fn check_all_open_prs(&self) -> Result<(), PrMonitorError> {
+    /// Check all open PRs for updates, returning how long to wait before the next check
+    async fn check_all_open_prs(&self) -> Result<Duration, PrMonitorError> {
         let open_prs = Merge::get_open_prs(&self.db.pool).await?;
 
         if open_prs.is_empty() {
             debug!("No open PRs to check");
-            return Ok(());
+            return Ok(self.poll_interval);
         }
+        let checks_github = open_prs
+            .iter()
+            .any(|pr| matches!(pr.provider, GitProvider::GitHub));
 
         info!("Checking {} open PRs", open_prs.len());
 
@@ -126,18 +190,66 @@ impl PrMonitorService {
                 );
             }
         }
-        Ok(())
+
+        if checks_github {
+            return Ok(self.github_poll_delay().await);
+        }
+        Ok(self.poll_interval)
+    }
+
+    /// Publish GitHub's remaining rate limit and pick the next poll delay: the
+    /// usual interval, or the time until the limit resets when it is nearly used up.
+    async fn github_poll_delay(&self) -> Duration {
+        let rate_limit = match GitHubService::new() {
+            Ok(github) => github.rate_limit().await,
+            Err(e) => Err(e),
+        };
+        let rate_limit = match rate_limit {
+            Ok(rate_limit) => rate_limit,
+            Err(e) => {
+                debug!("Could not read GitHub rate limit: {}", e);
+                return self.poll_interval;
+            }
+        };
+
+        let paused_until =
+            (rate_limit.remaining < GITHUB_RATE_LIMIT_LOW_WATERMARK).then_some(rate_limit.reset_at);
+        let delay = paused_until
+            .and_then(|until| (until - Utc::now()).to_std().ok())
+            .map_or(self.poll_interval, |wait| wait.max(self.poll_interval));
+        if let Some(until) = paused_until {
+            warn!(
+                "GitHub rate limit nearly exhausted ({} of {} left), pausing PR sync until {}",
+                rate_limit.remaining, rate_limit.limit, until
+            );
+        }
+
+        self.events
+            .msg_store()
+            .push_patch(pr_sync_patch::set(&PrSyncStatus {
+                rate_limit,
+                paused_until,
+            }));
+        delay
     }
 
     /// Check the status of a specific PR
     async fn check_pr_status(&self, pr_merge: &PrMerge) -> Result<(), PrMonitorError> {
-        // GitHubService now uses gh CLI, no token needed
-        let github_service = GitHubService::new()?;
-        let repo_info = GitHubRepoInfo::from_remote_url(&pr_merge.pr_info.url)?;
-
-        let pr_status = github_service
-            .update_pr_status(&repo_info, pr_merge.pr_info.number)
-            .await?;
+        // Both services use their CLI's auth, no token needed
+        let pr_status = match pr_merge.provider {
+            GitProvider::GitHub => {
+                let repo_info = GitHubRepoInfo::from_remote_url(&pr_merge.pr_info.url)?;
+                GitHubService::new()?
+                    .update_pr_status(&repo_info, pr_merge.pr_info.number)
+                    .await?
+            }
+            GitProvider::GitLab => {
+                let repo_info = GitLabRepoInfo::from_remote_url(&pr_merge.pr_info.url)?;
+                GitLabService::new()?
+                    .update_pr_status(&repo_info, pr_merge.pr_info.number)
+                    .await?
+            }
+        };
 
         debug!(
             "PR #{} status: {:?} (was open)",
@@ -146,7 +258,7 @@ impl PrMonitorService {
 
         // Update the PR status in the database
         if !matches!(&pr_status.status, MergeStatus::Open) {
-            // Update merge status with the latest information from GitHub
+            // Update merge status with the latest information from the provider
             Merge::update_status(
                 &self.db.pool,
                 pr_merge.id,
@@ -182,17 +294,11 @@ impl PrMonitorService {
                     );
                 }
 
-                if let Some(publisher) = &self.publisher
-                    && let Err(err) = publisher
-                        .update_shared_task_by_id(task_attempt.task_id)
-                        .await
-                {
-                    tracing::warn!(
-                        ?err,
-                        "Failed to propagate shared task update for {}",
-                        task_attempt.task_id
-                    );
-                }
+                let task_id = task_attempt.task_id;
+                with_share_publisher(self.publisher.clone(), task_id, |publisher| async move {
+                    publisher.update_shared_task_by_id(task_id).await
+                })
+                .await;
             }
         }
 
@@ -0,0 +1,542 @@
+//! Portable export/import of a project's settings and tasks.
+//!
+//! Bundles never include worktrees or execution history; attempts are carried as
+//! metadata only and come back with their worktrees marked as deleted.
+
+use std::{collections::HashMap, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    project::{CreateProject, MergeStrategy, Project},
+    task::{CreateTask, Task, TaskStatus},
+    task_attempt::{CreateTaskAttempt, TaskAttempt, TaskAttemptError},
+};
+use executors::{
+    actions::script::ScriptRequestLanguage,
+    executors::BaseCodingAgent,
+    mcp_config::{McpServerDefinition, validate_mcp_server_definitions},
+    profile::ExecutorProfileId,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{
+    branch_name::validate_branch_template, commit_message::validate_commit_message_template,
+    dev_server_url::parse_url_pattern, output_abort::parse_abort_pattern,
+    worktree_manager::validate_sparse_patterns,
+};
+
+pub const PROJECT_BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum ProjectBundleError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    TaskAttempt(#[from] TaskAttemptError),
+    #[error("Project not found")]
+    ProjectNotFound,
+    #[error("Unsupported bundle version {0}")]
+    UnsupportedVersion(u32),
+    #[error("Invalid bundle: {0}")]
+    Invalid(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct ProjectBundle {
+    pub version: u32,
+    #[ts(type = "Date")]
+    pub exported_at: DateTime<Utc>,
+    pub project: BundledProjectSettings,
+    pub tasks: Vec<BundledTask>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct BundledProjectSettings {
+    pub name: String,
+    pub setup_script: Option<String>,
+    pub dev_script: Option<String>,
+    pub cleanup_script: Option<String>,
+    pub copy_files: Option<String>,
+    pub env_template: HashMap<String, String>,
+    pub merge_strategy: MergeStrategy,
+    pub auto_push: bool,
+    pub abort_on_output_pattern: Option<String>,
+    #[serde(default)]
+    pub default_executor_profile_id: Option<ExecutorProfileId>,
+    #[serde(default)]
+    pub commit_message_template: Option<String>,
+    #[serde(default)]
+    pub branch_template: Option<String>,
+    #[serde(default)]
+    pub mcp_servers: Vec<McpServerDefinition>,
+    #[serde(default)]
+    pub worktree_sparse_patterns: Option<String>,
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub max_concurrent_executions: Option<i64>,
+    #[serde(default)]
+    pub dev_server_url_pattern: Option<String>,
+    #[serde(default)]
+    pub script_language: Option<ScriptRequestLanguage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct BundledTask {
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    /// Empty unless the bundle was exported with attempts
+    #[serde(default)]
+    pub attempts: Vec<BundledAttempt>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct BundledAttempt {
+    pub branch: String,
+    pub target_branch: String,
+    pub executor: String,
+    pub is_orchestrator: bool,
+}
+
+impl ProjectBundle {
+    pub async fn export(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        include_attempts: bool,
+    ) -> Result<Self, ProjectBundleError> {
+        let project = Project::find_by_id(pool, project_id)
+            .await?
+            .ok_or(ProjectBundleError::ProjectNotFound)?;
+
+        let mut tasks = Vec::new();
+        // Listed newest first; bundle oldest first so import preserves creation order
+        let rows = Task::find_by_project_id_with_attempt_status(pool, project_id).await?;
+        for row in rows.into_iter().rev() {
+            let attempts = if include_attempts {
+                TaskAttempt::fetch_all(pool, Some(row.task.id))
+                    .await?
+                    .into_iter()
+                    .rev()
+                    .map(|attempt| BundledAttempt {
+                        branch: attempt.branch,
+                        target_branch: attempt.target_branch,
+                        executor: attempt.executor,
+                        is_orchestrator: attempt.is_orchestrator,
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            tasks.push(BundledTask {
+                title: row.task.title,
+                description: row.task.description,
+                status: row.task.status,
+                attempts,
+            });
+        }
+
+        Ok(Self {
+            version: PROJECT_BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            project: BundledProjectSettings {
+                name: project.name,
+                setup_script: project.setup_script,
+                dev_script: project.dev_script,
+                cleanup_script: project.cleanup_script,
+                copy_files: project.copy_files,
+                env_template: project.env_template.0,
+                merge_strategy: project.merge_strategy,
+                auto_push: project.auto_push,
+                abort_on_output_pattern: project.abort_on_output_pattern,
+                default_executor_profile_id: project.default_executor_profile_id.map(|p| p.0),
+                commit_message_template: project.commit_message_template,
+                branch_template: project.branch_template,
+                mcp_servers: project.mcp_servers.0,
+                worktree_sparse_patterns: project.worktree_sparse_patterns,
+                max_concurrent_executions: project.max_concurrent_executions,
+                dev_server_url_pattern: project.dev_server_url_pattern,
+                script_language: project.script_language,
+            },
+            tasks,
+        })
+    }
+
+    /// Check the bundle can be imported without partially applying it.
+    pub fn validate(&self) -> Result<(), ProjectBundleError> {
+        if self.version != PROJECT_BUNDLE_VERSION {
+            return Err(ProjectBundleError::UnsupportedVersion(self.version));
+        }
+        if self.project.name.trim().is_empty() {
+            return Err(ProjectBundleError::Invalid(
+                "project name is empty".to_string(),
+            ));
+        }
+
+        let settings = &self.project;
+        for (label, script) in [
+            ("setup_script", &settings.setup_script),
+            ("dev_script", &settings.dev_script),
+            ("cleanup_script", &settings.cleanup_script),
+        ] {
+            if script.as_deref().is_some_and(|s| s.contains('\0')) {
+                return Err(ProjectBundleError::Invalid(format!(
+                    "{label} contains a NUL byte"
+                )));
+            }
+        }
+        if let Some(pattern) = &settings.abort_on_output_pattern
+            && let Err(e) = parse_abort_pattern(pattern)
+        {
+            return Err(ProjectBundleError::Invalid(format!(
+                "abort_on_output_pattern: {e}"
+            )));
+        }
+        if let Some(pattern) = &settings.dev_server_url_pattern
+            && let Err(e) = parse_url_pattern(pattern)
+        {
+            return Err(ProjectBundleError::Invalid(format!(
+                "dev_server_url_pattern: {e}"
+            )));
+        }
+        if let Some(template) = &settings.commit_message_template
+            && let Err(e) = validate_commit_message_template(template)
+        {
+            return Err(ProjectBundleError::Invalid(format!(
+                "commit_message_template: {e}"
+            )));
+        }
+        if let Some(template) = &settings.branch_template
+            && let Err(e) = validate_branch_template(template)
+        {
+            return Err(ProjectBundleError::Invalid(format!("branch_template: {e}")));
+        }
+        if let Err(e) = validate_mcp_server_definitions(&settings.mcp_servers) {
+            return Err(ProjectBundleError::Invalid(format!("mcp_servers: {e}")));
+        }
+        if let Some(patterns) = &settings.worktree_sparse_patterns
+            && let Err(e) = validate_sparse_patterns(patterns)
+        {
+            return Err(ProjectBundleError::Invalid(format!(
+                "worktree_sparse_patterns: {e}"
+            )));
+        }
+
+        for task in &self.tasks {
+            if task.title.trim().is_empty() {
+                return Err(ProjectBundleError::Invalid(
+                    "task title is empty".to_string(),
+                ));
+            }
+            for attempt in &task.attempts {
+                for branch in [&attempt.branch, &attempt.target_branch] {
+                    if !git2::Branch::name_is_valid(branch).unwrap_or(false) {
+                        return Err(ProjectBundleError::Invalid(format!(
+                            "invalid branch name '{branch}'"
+                        )));
+                    }
+                }
+                if BaseCodingAgent::from_str(&attempt.executor).is_err() {
+                    return Err(ProjectBundleError::Invalid(format!(
+                        "unknown executor '{}'",
+                        attempt.executor
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Recreate the bundled project at `git_repo_path`, which must already be a
+    /// git repository.
+    pub async fn import(
+        &self,
+        pool: &SqlitePool,
+        git_repo_path: String,
+    ) -> Result<Project, ProjectBundleError> {
+        self.validate()?;
+
+        // Import all or nothing so a failure never leaves a half-imported project
+        let mut tx = pool.begin().await?;
+        let settings = &self.project;
+        let project = Project::create(
+            &mut *tx,
+            &CreateProject {
+                name: settings.name.clone(),
+                git_repo_path,
+                use_existing_repo: true,
+                setup_script: settings.setup_script.clone(),
+                dev_script: settings.dev_script.clone(),
+                cleanup_script: settings.cleanup_script.clone(),
+                copy_files: settings.copy_files.clone(),
+                env_template: settings.env_template.clone(),
+                merge_strategy: settings.merge_strategy,
+                auto_push: settings.auto_push,
+                abort_on_output_pattern: settings.abort_on_output_pattern.clone(),
+                default_executor_profile_id: settings.default_executor_profile_id.clone(),
+                commit_message_template: settings.commit_message_template.clone(),
+                branch_template: settings.branch_template.clone(),
+                mcp_servers: settings.mcp_servers.clone(),
+                worktree_sparse_patterns: settings.worktree_sparse_patterns.clone(),
+                max_concurrent_executions: settings.max_concurrent_executions,
+                dev_server_url_pattern: settings.dev_server_url_pattern.clone(),
+                script_language: settings.script_language,
+            },
+            Uuid::new_v4(),
+        )
+        .await?;
+
+        for bundled in &self.tasks {
+            let mut create_task = CreateTask::from_title_description(
+                project.id,
+                bundled.title.clone(),
+                bundled.description.clone(),
+            );
+            create_task.status = Some(bundled.status.clone());
+            let task = Task::create(&mut *tx, &create_task, Uuid::new_v4()).await?;
+
+            for attempt in &bundled.attempts {
+                let executor = BaseCodingAgent::from_str(&attempt.executor)
+                    .map_err(|_| ProjectBundleError::Invalid(attempt.executor.clone()))?;
+                let created = TaskAttempt::create(
+                    &mut *tx,
+                    &CreateTaskAttempt {
+                        executor,
+                        base_branch: attempt.target_branch.clone(),
+                        branch: attempt.branch.clone(),
+                        is_orchestrator: attempt.is_orchestrator,
+                    },
+                    Uuid::new_v4(),
+                    task.id,
+                )
+                .await?;
+                TaskAttempt::mark_worktree_deleted(&mut *tx, created.id).await?;
+            }
+        }
+        tx.commit().await?;
+
+        Ok(project)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use db::DBService;
+
+    use super::*;
+
+    async fn fresh_pool() -> SqlitePool {
+        DBService::new_in_memory().await.unwrap().pool
+    }
+
+    #[tokio::test]
+    async fn test_export_import_round_trip() {
+        let source = fresh_pool().await;
+        let project = Project::create(
+            &source,
+            &CreateProject {
+                name: "Bundle me".to_string(),
+                git_repo_path: "/tmp/source-repo".to_string(),
+                use_existing_repo: true,
+                setup_script: Some("npm ci".to_string()),
+                dev_script: None,
+                cleanup_script: Some("npm run lint -- --fix".to_string()),
+                copy_files: Some(".env".to_string()),
+                env_template: HashMap::from([("NODE_ENV".to_string(), "test".to_string())]),
+                merge_strategy: MergeStrategy::Rebase,
+                auto_push: true,
+                abort_on_output_pattern: Some("(?i)i cannot continue".to_string()),
+                default_executor_profile_id: Some(ExecutorProfileId::new(
+                    BaseCodingAgent::ClaudeCode,
+                )),
+                commit_message_template: Some("{title}\n\n{description}".to_string()),
+                branch_template: Some("feature/{short_id}-{title}".to_string()),
+                mcp_servers: vec![McpServerDefinition {
+                    name: "db".to_string(),
+                    command: "dbmcp".to_string(),
+                    args: vec!["--readonly".to_string()],
+                    env: HashMap::new(),
+                }],
+                worktree_sparse_patterns: Some("/*\n!/assets/".to_string()),
+                max_concurrent_executions: Some(2),
+                dev_server_url_pattern: Some(r"ready on port (\d+)".to_string()),
+                script_language: Some(ScriptRequestLanguage::PowerShell),
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+
+        let first = Task::create(
+            &source,
+            &CreateTask::from_title_description(
+                project.id,
+                "Add login".to_string(),
+                Some("OAuth flow".to_string()),
+            ),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let mut done = CreateTask::from_title_description(project.id, "Fix CI".to_string(), None);
+        done.status = Some(TaskStatus::Done);
+        Task::create(&source, &done, Uuid::new_v4()).await.unwrap();
+        TaskAttempt::create(
+            &source,
+            &CreateTaskAttempt {
+                executor: BaseCodingAgent::ClaudeCode,
+                base_branch: "main".to_string(),
+                branch: "vk/1a2b-add-login".to_string(),
+                is_orchestrator: false,
+            },
+            Uuid::new_v4(),
+            first.id,
+        )
+        .await
+        .unwrap();
+
+        let bundle = ProjectBundle::export(&source, project.id, true)
+            .await
+            .unwrap();
+        let json = serde_json::to_string(&bundle).unwrap();
+        let bundle: ProjectBundle = serde_json::from_str(&json).unwrap();
+
+        let target = fresh_pool().await;
+        let imported = bundle
+            .import(&target, "/tmp/target-repo".to_string())
+            .await
+            .unwrap();
+        assert_eq!(imported.git_repo_path.to_string_lossy(), "/tmp/target-repo");
+
+        let mut reexported = ProjectBundle::export(&target, imported.id, true)
+            .await
+            .unwrap();
+        assert_eq!(reexported.project, bundle.project);
+        // Tasks created within the same instant have no defined order
+        let mut expected = bundle.tasks.clone();
+        expected.sort_by(|a, b| a.title.cmp(&b.title));
+        reexported.tasks.sort_by(|a, b| a.title.cmp(&b.title));
+        assert_eq!(reexported.tasks, expected);
+        assert_eq!(expected.len(), 2);
+        assert_eq!(expected[0].title, "Add login");
+        assert_eq!(expected[0].attempts.len(), 1);
+        assert_eq!(expected[1].status, TaskStatus::Done);
+
+        let attempts = TaskAttempt::fetch_all(&target, None).await.unwrap();
+        assert!(attempts.iter().all(|a| a.worktree_deleted));
+    }
+
+    #[tokio::test]
+    async fn test_failed_import_leaves_nothing_behind() {
+        let pool = fresh_pool().await;
+        // Fail the last write of the import, after the project and task rows went in
+        sqlx::query(
+            "CREATE TRIGGER fail_attempt_insert BEFORE INSERT ON task_attempts
+             BEGIN SELECT RAISE(ABORT, 'boom'); END",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let bundle = ProjectBundle {
+            version: PROJECT_BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            project: BundledProjectSettings {
+                name: "p".to_string(),
+                setup_script: None,
+                dev_script: None,
+                cleanup_script: None,
+                copy_files: None,
+                env_template: HashMap::new(),
+                merge_strategy: MergeStrategy::default(),
+                auto_push: false,
+                abort_on_output_pattern: None,
+                default_executor_profile_id: None,
+                commit_message_template: None,
+                branch_template: None,
+                mcp_servers: Vec::new(),
+                worktree_sparse_patterns: None,
+                max_concurrent_executions: None,
+                dev_server_url_pattern: None,
+                script_language: None,
+            },
+            tasks: vec![BundledTask {
+                title: "t".to_string(),
+                description: None,
+                status: TaskStatus::Todo,
+                attempts: vec![BundledAttempt {
+                    branch: "vk/ok".to_string(),
+                    target_branch: "main".to_string(),
+                    executor: "CLAUDE_CODE".to_string(),
+                    is_orchestrator: false,
+                }],
+            }],
+        };
+        assert!(
+            bundle
+                .import(&pool, "/tmp/target-repo".to_string())
+                .await
+                .is_err()
+        );
+
+        assert!(Project::find_all(&pool).await.unwrap().is_empty());
+        let tasks: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(tasks, 0);
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_branch_names() {
+        let mut bundle = ProjectBundle {
+            version: PROJECT_BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            project: BundledProjectSettings {
+                name: "p".to_string(),
+                setup_script: None,
+                dev_script: None,
+                cleanup_script: None,
+                copy_files: None,
+                env_template: HashMap::new(),
+                merge_strategy: MergeStrategy::default(),
+                auto_push: false,
+                abort_on_output_pattern: None,
+                default_executor_profile_id: None,
+                commit_message_template: None,
+                branch_template: None,
+                mcp_servers: Vec::new(),
+                worktree_sparse_patterns: None,
+                max_concurrent_executions: None,
+                dev_server_url_pattern: None,
+                script_language: None,
+            },
+            tasks: vec![BundledTask {
+                title: "t".to_string(),
+                description: None,
+                status: TaskStatus::Todo,
+                attempts: vec![BundledAttempt {
+                    branch: "bad..name".to_string(),
+                    target_branch: "main".to_string(),
+                    executor: "CLAUDE_CODE".to_string(),
+                    is_orchestrator: false,
+                }],
+            }],
+        };
+        assert!(matches!(
+            bundle.validate(),
+            Err(ProjectBundleError::Invalid(_))
+        ));
+
+        bundle.tasks[0].attempts[0].branch = "vk/ok".to_string();
+        assert!(bundle.validate().is_ok());
+        bundle.version = 99;
+        assert!(matches!(
+            bundle.validate(),
+            Err(ProjectBundleError::UnsupportedVersion(99))
+        ));
+    }
+}
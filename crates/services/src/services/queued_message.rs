@@ -1,9 +1,17 @@
-use std::sync::Arc;
+use std::{collections::VecDeque, sync::Arc};
 
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
-use db::models::scratch::DraftFollowUpData;
+use db::models::{
+    execution_process::ExecutionProcessStatus,
+    scratch::{
+        DraftFollowUpData, QueuedFollowUpData, Scratch, ScratchError, ScratchPayload, ScratchType,
+        UpdateScratch,
+    },
+};
 use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tokio::sync::Mutex;
 use ts_rs::TS;
 use uuid::Uuid;
 
@@ -26,49 +34,140 @@ pub struct QueuedMessage {
 pub enum QueueStatus {
     /// No message queued
     Empty,
-    /// Message is queued and waiting for execution to complete
-    Queued { message: QueuedMessage },
+    /// Messages are queued and waiting for execution to complete
+    Queued {
+        /// The most recently queued message
+        message: QueuedMessage,
+        /// Every queued message, in the order they will run
+        pending: Vec<QueuedMessage>,
+    },
 }
 
-/// In-memory service for managing queued follow-up messages.
-/// One queued message per task attempt.
+/// Whether an attempt's queued messages should run after an execution that ended with
+/// `status`. A failed, killed or timed-out execution discards the queue instead.
+pub fn runs_queued_after(status: &ExecutionProcessStatus) -> bool {
+    !matches!(
+        status,
+        ExecutionProcessStatus::Failed
+            | ExecutionProcessStatus::Killed
+            | ExecutionProcessStatus::TimedOut
+    )
+}
+
+/// FIFO of queued follow-up messages per task attempt.
+///
+/// Queues are kept in memory and mirrored to a `FOLLOW_UP_QUEUE` scratch row per
+/// attempt so they survive restarts.
 #[derive(Clone)]
 pub struct QueuedMessageService {
-    queue: Arc<DashMap<Uuid, QueuedMessage>>,
+    pool: SqlitePool,
+    queue: Arc<DashMap<Uuid, VecDeque<QueuedMessage>>>,
+    // Serializes change-then-persist so rows are written in the order changes happen
+    persist_lock: Arc<Mutex<()>>,
 }
 
 impl QueuedMessageService {
-    pub fn new() -> Self {
+    pub fn new(pool: SqlitePool) -> Self {
         Self {
+            pool,
             queue: Arc::new(DashMap::new()),
+            persist_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Load queues persisted by a previous run. Returns the number of attempts with queued messages.
+    pub async fn restore(&self) -> Result<usize, ScratchError> {
+        let mut restored = 0;
+        for scratch in Scratch::find_all(&self.pool).await? {
+            let ScratchPayload::FollowUpQueue(entries) = scratch.payload else {
+                continue;
+            };
+            let messages: VecDeque<_> = entries
+                .into_iter()
+                .map(|entry| QueuedMessage {
+                    task_attempt_id: scratch.id,
+                    data: entry.data,
+                    queued_at: entry.queued_at,
+                })
+                .collect();
+            if !messages.is_empty() {
+                self.queue.insert(scratch.id, messages);
+                restored += 1;
+            }
         }
+        Ok(restored)
     }
 
-    /// Queue a message for a task attempt. Replaces any existing queued message.
-    pub fn queue_message(&self, task_attempt_id: Uuid, data: DraftFollowUpData) -> QueuedMessage {
+    /// Append a message to the end of a task attempt's queue.
+    pub async fn queue_message(
+        &self,
+        task_attempt_id: Uuid,
+        data: DraftFollowUpData,
+    ) -> Result<QueuedMessage, ScratchError> {
+        let _guard = self.persist_lock.lock().await;
         let queued = QueuedMessage {
             task_attempt_id,
             data,
             queued_at: Utc::now(),
         };
-        self.queue.insert(task_attempt_id, queued.clone());
-        queued
+        self.queue
+            .entry(task_attempt_id)
+            .or_default()
+            .push_back(queued.clone());
+        self.persist(task_attempt_id).await?;
+        Ok(queued)
     }
 
-    /// Cancel/remove a queued message for a task attempt
-    pub fn cancel_queued(&self, task_attempt_id: Uuid) -> Option<QueuedMessage> {
-        self.queue.remove(&task_attempt_id).map(|(_, v)| v)
+    /// Remove every queued message for a task attempt, returning them in queue order
+    pub async fn cancel_queued(
+        &self,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<QueuedMessage>, ScratchError> {
+        let _guard = self.persist_lock.lock().await;
+        let removed = self
+            .queue
+            .remove(&task_attempt_id)
+            .map(|(_, messages)| messages.into())
+            .unwrap_or_default();
+        self.persist(task_attempt_id).await?;
+        Ok(removed)
     }
 
-    /// Get the queued message for a task attempt (if any)
-    pub fn get_queued(&self, task_attempt_id: Uuid) -> Option<QueuedMessage> {
-        self.queue.get(&task_attempt_id).map(|r| r.clone())
+    /// All queued messages for a task attempt, in the order they will run
+    pub fn get_queued(&self, task_attempt_id: Uuid) -> Vec<QueuedMessage> {
+        self.queue
+            .get(&task_attempt_id)
+            .map(|messages| messages.iter().cloned().collect())
+            .unwrap_or_default()
     }
 
-    /// Take (remove and return) the queued message for a task attempt.
-    /// Used by finalization flow to consume the queued message.
-    pub fn take_queued(&self, task_attempt_id: Uuid) -> Option<QueuedMessage> {
-        self.queue.remove(&task_attempt_id).map(|(_, v)| v)
+    /// Remove and return the oldest queued message for a task attempt.
+    /// Used by the finalization flow to run queued messages one at a time.
+    pub async fn pop_queued(
+        &self,
+        task_attempt_id: Uuid,
+    ) -> Result<Option<QueuedMessage>, ScratchError> {
+        let _guard = self.persist_lock.lock().await;
+        let popped = self
+            .queue
+            .get_mut(&task_attempt_id)
+            .and_then(|mut messages| messages.pop_front());
+        self.queue
+            .remove_if(&task_attempt_id, |_, messages| messages.is_empty());
+        if popped.is_some() {
+            self.persist(task_attempt_id).await?;
+        }
+        Ok(popped)
+    }
+
+    /// Number of queued messages across all task attempts
+    pub fn total_queued(&self) -> usize {
+        self.queue.iter().map(|messages| messages.len()).sum()
+    }
+
+    /// Task attempts that have queued messages
+    pub fn queued_attempt_ids(&self) -> Vec<Uuid> {
+        self.queue.iter().map(|entry| *entry.key()).collect()
     }
 
     /// Check if a task attempt has a queued message
@@ -78,15 +177,180 @@ impl QueuedMessageService {
 
     /// Get queue status for frontend display
     pub fn get_status(&self, task_attempt_id: Uuid) -> QueueStatus {
-        match self.get_queued(task_attempt_id) {
-            Some(msg) => QueueStatus::Queued { message: msg },
+        let pending = self.get_queued(task_attempt_id);
+        match pending.last().cloned() {
+            Some(message) => QueueStatus::Queued { message, pending },
             None => QueueStatus::Empty,
         }
     }
+
+    /// Write the attempt's queue to its scratch row, or delete the row once the queue is empty
+    async fn persist(&self, task_attempt_id: Uuid) -> Result<(), ScratchError> {
+        let entries: Vec<QueuedFollowUpData> = self
+            .get_queued(task_attempt_id)
+            .into_iter()
+            .map(|message| QueuedFollowUpData {
+                data: message.data,
+                queued_at: message.queued_at,
+            })
+            .collect();
+
+        if entries.is_empty() {
+            Scratch::delete(&self.pool, task_attempt_id, &ScratchType::FollowUpQueue).await?;
+        } else {
+            Scratch::update(
+                &self.pool,
+                task_attempt_id,
+                &ScratchType::FollowUpQueue,
+                &UpdateScratch {
+                    payload: ScratchPayload::FollowUpQueue(entries),
+                },
+            )
+            .await?;
+        }
+        Ok(())
+    }
 }
 
-impl Default for QueuedMessageService {
-    fn default() -> Self {
-        Self::new()
+#[cfg(test)]
+mod tests {
+    use db::DBService;
+
+    use super::*;
+
+    fn follow_up(message: &str) -> DraftFollowUpData {
+        DraftFollowUpData {
+            message: message.to_string(),
+            variant: None,
+            image_ids: vec![],
+        }
+    }
+
+    fn messages(queued: &[QueuedMessage]) -> Vec<&str> {
+        queued.iter().map(|q| q.data.message.as_str()).collect()
+    }
+
+    async fn persisted(pool: &SqlitePool, task_attempt_id: Uuid) -> Option<Vec<String>> {
+        let scratch = Scratch::find_by_id(pool, task_attempt_id, &ScratchType::FollowUpQueue)
+            .await
+            .unwrap()?;
+        let ScratchPayload::FollowUpQueue(entries) = scratch.payload else {
+            panic!("unexpected payload {:?}", scratch.payload);
+        };
+        Some(entries.into_iter().map(|e| e.data.message).collect())
+    }
+
+    #[test]
+    fn test_runs_queued_after() {
+        assert!(runs_queued_after(&ExecutionProcessStatus::Completed));
+        assert!(!runs_queued_after(&ExecutionProcessStatus::Failed));
+        assert!(!runs_queued_after(&ExecutionProcessStatus::Killed));
+        assert!(!runs_queued_after(&ExecutionProcessStatus::TimedOut));
+    }
+
+    #[tokio::test]
+    async fn test_messages_pop_in_fifo_order() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let service = QueuedMessageService::new(db.pool.clone());
+        let attempt_id = Uuid::new_v4();
+
+        for message in ["first", "second", "third"] {
+            service
+                .queue_message(attempt_id, follow_up(message))
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            messages(&service.get_queued(attempt_id)),
+            ["first", "second", "third"]
+        );
+        match service.get_status(attempt_id) {
+            QueueStatus::Queued { message, pending } => {
+                assert_eq!(message.data.message, "third");
+                assert_eq!(pending.len(), 3);
+            }
+            QueueStatus::Empty => panic!("expected queued messages"),
+        }
+
+        let popped = service.pop_queued(attempt_id).await.unwrap().unwrap();
+        assert_eq!(popped.data.message, "first");
+        assert_eq!(
+            messages(&service.get_queued(attempt_id)),
+            ["second", "third"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_queue_is_persisted_and_restored() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let service = QueuedMessageService::new(db.pool.clone());
+        let attempt_id = Uuid::new_v4();
+
+        service
+            .queue_message(attempt_id, follow_up("first"))
+            .await
+            .unwrap();
+        service
+            .queue_message(attempt_id, follow_up("second"))
+            .await
+            .unwrap();
+        assert_eq!(
+            persisted(&db.pool, attempt_id).await.unwrap(),
+            ["first", "second"]
+        );
+
+        // A new service on the same database picks the queue up in order
+        let restored = QueuedMessageService::new(db.pool.clone());
+        assert_eq!(restored.restore().await.unwrap(), 1);
+        assert_eq!(restored.queued_attempt_ids(), [attempt_id]);
+        assert_eq!(
+            messages(&restored.get_queued(attempt_id)),
+            ["first", "second"]
+        );
+        let popped = restored.pop_queued(attempt_id).await.unwrap().unwrap();
+        assert_eq!(popped.data.message, "first");
+        assert_eq!(persisted(&db.pool, attempt_id).await.unwrap(), ["second"]);
+    }
+
+    #[tokio::test]
+    async fn test_draining_the_queue_deletes_its_scratch() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let service = QueuedMessageService::new(db.pool.clone());
+        let attempt_id = Uuid::new_v4();
+
+        service
+            .queue_message(attempt_id, follow_up("only"))
+            .await
+            .unwrap();
+        assert!(service.pop_queued(attempt_id).await.unwrap().is_some());
+
+        assert!(!service.has_queued(attempt_id));
+        assert!(matches!(service.get_status(attempt_id), QueueStatus::Empty));
+        assert_eq!(persisted(&db.pool, attempt_id).await, None);
+        assert!(service.pop_queued(attempt_id).await.unwrap().is_none());
+
+        let restored = QueuedMessageService::new(db.pool.clone());
+        assert_eq!(restored.restore().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_removes_queue_and_scratch() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let service = QueuedMessageService::new(db.pool.clone());
+        let attempt_id = Uuid::new_v4();
+
+        service
+            .queue_message(attempt_id, follow_up("first"))
+            .await
+            .unwrap();
+        service
+            .queue_message(attempt_id, follow_up("second"))
+            .await
+            .unwrap();
+
+        let dropped = service.cancel_queued(attempt_id).await.unwrap();
+        assert_eq!(messages(&dropped), ["first", "second"]);
+        assert_eq!(service.total_queued(), 0);
+        assert_eq!(persisted(&db.pool, attempt_id).await, None);
     }
 }
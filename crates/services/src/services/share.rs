@@ -17,7 +17,7 @@ use db::{
     DBService,
     models::{
         shared_task::{SharedActivityCursor, SharedTask, SharedTaskInput},
-        task::{SyncTask, Task},
+        task::{SyncTask, Task, TaskStatus},
     },
 };
 use processor::ActivityProcessor;
@@ -26,14 +26,16 @@ use remote::{
     ClientMessage, ServerMessage,
     db::{tasks::SharedTask as RemoteSharedTask, users::UserData as RemoteUserData},
 };
+use serde::Serialize;
 use sqlx::{Executor, Sqlite, SqlitePool};
 use thiserror::Error;
 use tokio::{
-    sync::{mpsc, oneshot},
+    sync::{mpsc, oneshot, watch},
     task::JoinHandle,
     time::{MissedTickBehavior, interval, sleep},
 };
 use tokio_tungstenite::tungstenite::Message as WsMessage;
+use ts_rs::TS;
 use url::Url;
 use utils::ws::{
     WS_AUTH_REFRESH_INTERVAL, WsClient, WsConfig, WsError, WsHandler, WsResult, run_ws_client,
@@ -90,20 +92,141 @@ pub enum ShareError {
 
 const WS_BACKOFF_BASE_DELAY: Duration = Duration::from_secs(1);
 const WS_BACKOFF_MAX_DELAY: Duration = Duration::from_secs(30);
+/// Consecutive failed restarts of the sync loop before giving up
+const MAX_SYNC_RESTARTS: u32 = 10;
+
+/// Connection state of the shared task synchronizer, for display in the UI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum RemoteSyncState {
+    /// Starting up; no connection established yet
+    Connecting,
+    /// Connected to the remote and receiving activity
+    Connected,
+    /// The connection failed or dropped and is being retried
+    Reconnecting {
+        /// Consecutive failed attempts so far
+        attempt: u32,
+    },
+    /// Retries were exhausted; sync stays stopped until it is started again
+    Failed { error: String },
+}
+
+impl RemoteSyncState {
+    /// How much attention the state needs; the worst one is reported for the whole sync
+    fn severity(&self) -> (u8, u32) {
+        match self {
+            Self::Connected => (0, 0),
+            Self::Connecting => (1, 0),
+            Self::Reconnecting { attempt } => (2, *attempt),
+            Self::Failed { .. } => (3, 0),
+        }
+    }
+}
+
+/// Connection state of the sync loop itself and of each project watcher. Every
+/// change republishes the aggregate, so one project can't mask another's state.
+#[derive(Clone)]
+struct SyncStates {
+    inner: Arc<StdMutex<SyncStatesInner>>,
+    tx: Arc<watch::Sender<RemoteSyncState>>,
+}
+
+struct SyncStatesInner {
+    supervisor: RemoteSyncState,
+    projects: HashMap<Uuid, RemoteSyncState>,
+}
+
+impl SyncStates {
+    fn new() -> (Self, watch::Receiver<RemoteSyncState>) {
+        let (tx, rx) = watch::channel(RemoteSyncState::Connecting);
+        let states = Self {
+            inner: Arc::new(StdMutex::new(SyncStatesInner {
+                supervisor: RemoteSyncState::Connecting,
+                projects: HashMap::new(),
+            })),
+            tx: Arc::new(tx),
+        };
+        (states, rx)
+    }
+
+    /// State of the sync loop, independent of the project watchers
+    fn supervisor(&self) -> RemoteSyncState {
+        self.inner.lock().unwrap().supervisor.clone()
+    }
+
+    fn set_supervisor(&self, state: RemoteSyncState) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.supervisor = state;
+        self.publish(&inner);
+    }
+
+    fn add_project(&self, project_id: Uuid) {
+        let mut inner = self.inner.lock().unwrap();
+        inner
+            .projects
+            .insert(project_id, RemoteSyncState::Connecting);
+        self.publish(&inner);
+    }
+
+    /// Update a tracked project; a watcher that was already stopped is ignored
+    fn set_project(&self, project_id: Uuid, state: RemoteSyncState) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(current) = inner.projects.get_mut(&project_id) {
+            *current = state;
+            self.publish(&inner);
+        }
+    }
+
+    fn remove_project(&self, project_id: Uuid) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.projects.remove(&project_id).is_some() {
+            self.publish(&inner);
+        }
+    }
+
+    fn publish(&self, inner: &SyncStatesInner) {
+        let aggregate = if inner.supervisor == RemoteSyncState::Connected {
+            inner
+                .projects
+                .values()
+                .max_by_key(|state| state.severity())
+                .cloned()
+                .unwrap_or(RemoteSyncState::Connected)
+        } else {
+            inner.supervisor.clone()
+        };
+        self.tx.send_if_modified(|current| {
+            let changed = *current != aggregate;
+            *current = aggregate;
+            changed
+        });
+    }
+}
 
 struct Backoff {
     current: Duration,
+    attempt: u32,
 }
 
 impl Backoff {
     fn new() -> Self {
         Self {
             current: WS_BACKOFF_BASE_DELAY,
+            attempt: 0,
         }
     }
 
     fn reset(&mut self) {
         self.current = WS_BACKOFF_BASE_DELAY;
+        self.attempt = 0;
+    }
+
+    /// Count a failed attempt, returning the attempt number and the delay
+    /// before the next one.
+    fn next_attempt(&mut self) -> (u32, Duration) {
+        self.attempt += 1;
+        (self.attempt, self.current)
     }
 
     async fn wait(&mut self) {
@@ -129,6 +252,7 @@ pub struct RemoteSync {
     processor: ActivityProcessor,
     config: ShareConfig,
     auth_ctx: AuthContext,
+    states: SyncStates,
 }
 
 impl RemoteSync {
@@ -138,43 +262,85 @@ impl RemoteSync {
             .expect("failed to create remote client");
         let processor =
             ActivityProcessor::new(db.clone(), config.clone(), remote_client, auth_ctx.clone());
+        let (states, state_rx) = SyncStates::new();
         let sync = Self {
             db,
             processor,
             config,
             auth_ctx,
+            states,
         };
         let (shutdown_tx, shutdown_rx) = oneshot::channel();
-        let join = tokio::spawn(async move {
-            if let Err(e) = sync.run(shutdown_rx).await {
-                tracing::error!(?e, "remote sync terminated unexpectedly");
+        let join = tokio::spawn(sync.supervise(shutdown_rx));
+
+        RemoteSyncHandle::new(shutdown_tx, join, state_rx)
+    }
+
+    /// Run the sync loop, restarting it with exponential backoff whenever it
+    /// fails until shutdown is requested or `MAX_SYNC_RESTARTS` consecutive
+    /// restarts fail without ever connecting.
+    async fn supervise(self, mut shutdown_rx: oneshot::Receiver<()>) {
+        let mut backoff = Backoff::new();
+
+        loop {
+            let err = match self.run(&mut shutdown_rx).await {
+                Ok(()) => return,
+                Err(err) => err,
+            };
+
+            // A loop that got running before failing starts a fresh retry cycle
+            if self.states.supervisor() == RemoteSyncState::Connected {
+                backoff.reset();
+            }
+
+            let (attempt, delay) = backoff.next_attempt();
+            if attempt > MAX_SYNC_RESTARTS {
+                tracing::error!(
+                    ?err,
+                    attempts = MAX_SYNC_RESTARTS,
+                    "remote sync failed; giving up"
+                );
+                self.states.set_supervisor(RemoteSyncState::Failed {
+                    error: err.to_string(),
+                });
+                return;
             }
-        });
 
-        RemoteSyncHandle::new(shutdown_tx, join)
+            tracing::warn!(
+                ?err,
+                attempt,
+                ?delay,
+                "remote sync failed; restarting after backoff"
+            );
+            self.states
+                .set_supervisor(RemoteSyncState::Reconnecting { attempt });
+            tokio::select! {
+                _ = &mut shutdown_rx => {
+                    tracing::info!("remote sync shutdown requested during restart backoff");
+                    return;
+                }
+                _ = backoff.wait() => {}
+            }
+        }
     }
 
-    pub async fn run(self, mut shutdown_rx: oneshot::Receiver<()>) -> Result<(), ShareError> {
+    pub async fn run(&self, shutdown_rx: &mut oneshot::Receiver<()>) -> Result<(), ShareError> {
         let mut watchers: HashMap<Uuid, ProjectWatcher> = HashMap::new();
         let (event_tx, mut event_rx) = mpsc::unbounded_channel();
         let mut refresh_interval = interval(Duration::from_secs(5));
         refresh_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
-        self.reconcile_watchers(&mut watchers, &event_tx).await?;
+        if let Err(err) = self.reconcile_watchers(&mut watchers, &event_tx).await {
+            self.stop_watchers(&mut watchers);
+            return Err(err);
+        }
+        self.states.set_supervisor(RemoteSyncState::Connected);
 
         loop {
             tokio::select! {
-                _ = &mut shutdown_rx => {
+                _ = &mut *shutdown_rx => {
                     tracing::info!("remote sync shutdown requested");
-                    for (project_id, watcher) in watchers.drain() {
-                        tracing::info!(%project_id, "stopping watcher due to shutdown");
-                        let _ = watcher.shutdown.send(());
-                        tokio::spawn(async move {
-                            if let Err(err) = watcher.join.await {
-                                tracing::debug!(?err, %project_id, "project watcher join failed during shutdown");
-                            }
-                        });
-                    }
+                    self.stop_watchers(&mut watchers);
                     return Ok(());
                 }
                 Some(event) = event_rx.recv() => {
@@ -187,9 +353,13 @@ impl RemoteSync {
                         }
                     }
                     watchers.remove(&event.project_id);
+                    self.states.remove_project(event.project_id);
                 }
                 _ = refresh_interval.tick() => {
-                    self.reconcile_watchers(&mut watchers, &event_tx).await?;
+                    if let Err(err) = self.reconcile_watchers(&mut watchers, &event_tx).await {
+                        self.stop_watchers(&mut watchers);
+                        return Err(err);
+                    }
                 }
             }
         }
@@ -222,6 +392,7 @@ impl RemoteSync {
         for project_id in to_remove {
             if let Some(watcher) = watchers.remove(&project_id) {
                 tracing::info!(%project_id, "remote project unlinked; shutting down watcher");
+                self.states.remove_project(project_id);
                 let _ = watcher.shutdown.send(());
                 tokio::spawn(async move {
                     if let Err(err) = watcher.join.await {
@@ -258,6 +429,8 @@ impl RemoteSync {
         let auth_ctx = self.auth_ctx.clone();
         let remote_client = processor.remote_client();
         let db = self.db.clone();
+        let states = self.states.clone();
+        states.add_project(project_id);
         let (shutdown_tx, shutdown_rx) = oneshot::channel();
 
         let join = tokio::spawn(async move {
@@ -268,6 +441,7 @@ impl RemoteSync {
                 auth_ctx,
                 remote_client,
                 project_id,
+                states,
                 shutdown_rx,
             )
             .await;
@@ -280,6 +454,19 @@ impl RemoteSync {
             join,
         })
     }
+
+    fn stop_watchers(&self, watchers: &mut HashMap<Uuid, ProjectWatcher>) {
+        for (project_id, watcher) in watchers.drain() {
+            tracing::info!(%project_id, "stopping project watcher");
+            self.states.remove_project(project_id);
+            let _ = watcher.shutdown.send(());
+            tokio::spawn(async move {
+                if let Err(err) = watcher.join.await {
+                    tracing::debug!(?err, %project_id, "project watcher join failed during shutdown");
+                }
+            });
+        }
+    }
 }
 
 struct SharedWsHandler {
@@ -383,6 +570,7 @@ async fn project_watcher_task(
     auth_ctx: AuthContext,
     remote_client: RemoteClient,
     remote_project_id: Uuid,
+    states: SyncStates,
     mut shutdown_rx: oneshot::Receiver<()>,
 ) -> Result<(), ShareError> {
     let mut backoff = Backoff::new();
@@ -435,7 +623,11 @@ async fn project_watcher_task(
         .await
         {
             Ok(conn) => {
+                if backoff.attempt > 0 {
+                    tracing::info!(%remote_project_id, attempts = backoff.attempt, "project websocket reconnected");
+                }
                 backoff.reset();
+                states.set_project(remote_project_id, RemoteSyncState::Connected);
                 conn
             }
             Err(ShareError::MissingAuth) => {
@@ -447,7 +639,9 @@ async fn project_watcher_task(
                 continue;
             }
             Err(err) => {
-                tracing::error!(%remote_project_id, ?err, "failed to establish websocket; retrying");
+                let (attempt, delay) = backoff.next_attempt();
+                tracing::error!(%remote_project_id, ?err, attempt, ?delay, "failed to establish websocket; retrying");
+                states.set_project(remote_project_id, RemoteSyncState::Reconnecting { attempt });
                 tokio::select! {
                     _ = &mut shutdown_rx => return Ok(()),
                     _ = backoff.wait() => {}
@@ -476,6 +670,9 @@ async fn project_watcher_task(
                 if let Err(err) = ws_connection.close() {
                     tracing::debug!(?err, %remote_project_id, "project websocket already closed when reconnecting");
                 }
+                let (attempt, delay) = backoff.next_attempt();
+                tracing::info!(%remote_project_id, attempt, ?delay, "reconnecting project websocket after backoff");
+                states.set_project(remote_project_id, RemoteSyncState::Reconnecting { attempt });
                 tokio::select! {
                     _ = &mut shutdown_rx => {
                         tracing::info!(%remote_project_id, "shutdown received during reconnect wait");
@@ -542,18 +739,34 @@ pub struct RemoteSyncHandle {
 struct RemoteSyncHandleInner {
     shutdown: StdMutex<Option<oneshot::Sender<()>>>,
     join: StdMutex<Option<JoinHandle<()>>>,
+    state: watch::Receiver<RemoteSyncState>,
 }
 
 impl RemoteSyncHandle {
-    fn new(shutdown: oneshot::Sender<()>, join: JoinHandle<()>) -> Self {
+    fn new(
+        shutdown: oneshot::Sender<()>,
+        join: JoinHandle<()>,
+        state: watch::Receiver<RemoteSyncState>,
+    ) -> Self {
         Self {
             inner: Arc::new(RemoteSyncHandleInner {
                 shutdown: StdMutex::new(Some(shutdown)),
                 join: StdMutex::new(Some(join)),
+                state,
             }),
         }
     }
 
+    /// Current connection state of the synchronizer
+    pub fn state(&self) -> RemoteSyncState {
+        self.inner.state.borrow().clone()
+    }
+
+    /// Watch connection state changes
+    pub fn subscribe_state(&self) -> watch::Receiver<RemoteSyncState> {
+        self.inner.state.clone()
+    }
+
     pub fn request_shutdown(&self) {
         if let Some(tx) = self.inner.shutdown.lock().unwrap().take() {
             let _ = tx.send(());
@@ -649,6 +862,46 @@ where
     Ok(())
 }
 
+/// Run `f` with the share publisher when sharing is configured. Sharing is
+/// best-effort: without a publisher nothing is attempted, and failures are logged
+/// rather than returned so the calling operation still completes.
+pub async fn with_share_publisher<F, Fut>(publisher: Option<SharePublisher>, task_id: Uuid, f: F)
+where
+    F: FnOnce(SharePublisher) -> Fut,
+    Fut: Future<Output = Result<(), ShareError>>,
+{
+    let Some(publisher) = publisher else {
+        tracing::debug!(
+            "Share publisher unavailable; skipping remote update for {}",
+            task_id
+        );
+        return;
+    };
+    if let Err(err) = f(publisher).await {
+        tracing::warn!(
+            ?err,
+            "Failed to propagate shared task update for {}",
+            task_id
+        );
+    }
+}
+
+/// Move a task to `status` and broadcast the change to its shared task. Only
+/// the local update can fail; sharing stays best-effort.
+pub async fn update_task_status_and_share(
+    pool: &SqlitePool,
+    publisher: Option<SharePublisher>,
+    task_id: Uuid,
+    status: TaskStatus,
+) -> Result<(), sqlx::Error> {
+    Task::update_status(pool, task_id, status).await?;
+    with_share_publisher(publisher, task_id, |publisher| async move {
+        publisher.update_shared_task_by_id(task_id).await
+    })
+    .await;
+    Ok(())
+}
+
 pub async fn link_shared_tasks_to_project(
     pool: &SqlitePool,
     current_user_id: Option<uuid::Uuid>,
@@ -704,3 +957,106 @@ impl Drop for SharedTaskLinkingGuard {
         SHARED_TASK_LINKING_LOCK.lock().unwrap().count -= 1;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_share_operations_are_skipped_when_sharing_is_off() {
+        let attempted = AtomicBool::new(false);
+        with_share_publisher(None, Uuid::new_v4(), |publisher| {
+            attempted.store(true, Ordering::SeqCst);
+            async move { publisher.update_shared_task_by_id(Uuid::new_v4()).await }
+        })
+        .await;
+        assert!(!attempted.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_sync_state_aggregates_project_watchers() {
+        let (states, rx) = SyncStates::new();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(*rx.borrow(), RemoteSyncState::Connecting);
+
+        states.set_supervisor(RemoteSyncState::Connected);
+        assert_eq!(*rx.borrow(), RemoteSyncState::Connected);
+
+        states.add_project(first);
+        states.add_project(second);
+        states.set_project(first, RemoteSyncState::Connected);
+        assert_eq!(*rx.borrow(), RemoteSyncState::Connecting);
+
+        // One project reconnecting isn't hidden by the other connecting
+        states.set_project(second, RemoteSyncState::Reconnecting { attempt: 2 });
+        states.set_project(first, RemoteSyncState::Connected);
+        assert_eq!(*rx.borrow(), RemoteSyncState::Reconnecting { attempt: 2 });
+
+        states.remove_project(second);
+        assert_eq!(*rx.borrow(), RemoteSyncState::Connected);
+        // Late updates from a stopped watcher are dropped
+        states.set_project(second, RemoteSyncState::Reconnecting { attempt: 3 });
+        assert_eq!(*rx.borrow(), RemoteSyncState::Connected);
+    }
+
+    #[test]
+    fn test_project_connections_do_not_reset_the_supervisor() {
+        let (states, rx) = SyncStates::new();
+        let project = Uuid::new_v4();
+        states.add_project(project);
+
+        states.set_supervisor(RemoteSyncState::Reconnecting { attempt: 1 });
+        states.set_project(project, RemoteSyncState::Connected);
+        assert_eq!(
+            states.supervisor(),
+            RemoteSyncState::Reconnecting { attempt: 1 }
+        );
+        assert_eq!(*rx.borrow(), RemoteSyncState::Reconnecting { attempt: 1 });
+
+        states.set_supervisor(RemoteSyncState::Failed {
+            error: "db".to_string(),
+        });
+        assert!(matches!(*rx.borrow(), RemoteSyncState::Failed { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_merge_and_finalize_status_updates_complete_without_sharing() {
+        use db::{
+            DBService,
+            models::{project::CreateProject, task::CreateTask},
+        };
+
+        let pool = DBService::new_in_memory().await.unwrap().pool;
+        let project = db::models::project::Project::create(
+            &pool,
+            &CreateProject {
+                name: "Unshared".to_string(),
+                git_repo_path: "/tmp/unshared-repo".to_string(),
+                use_existing_repo: true,
+                ..Default::default()
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let task = Task::create(
+            &pool,
+            &CreateTask::from_title_description(project.id, "Ship it".to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+
+        // Finalizing moves the task to review, merging marks it done
+        for status in [TaskStatus::InReview, TaskStatus::Done] {
+            update_task_status_and_share(&pool, None, task.id, status.clone())
+                .await
+                .unwrap();
+            let stored = Task::find_by_id(&pool, task.id).await.unwrap().unwrap();
+            assert_eq!(stored.status, status);
+            assert!(stored.shared_task_id.is_none());
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use std::{future::Future, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::Serialize;
+use thiserror::Error;
+use tokio::task::{AbortHandle, JoinHandle};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Finished jobs are kept around for inspection until this many have piled up
+const MAX_FINISHED_ENTRIES: usize = 50;
+
+#[derive(Debug, Error)]
+pub enum TaskRegistryError {
+    #[error("Background task {0} not found")]
+    NotFound(Uuid),
+    #[error("Background task '{0}' cannot be cancelled")]
+    NotCancellable(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum BackgroundTaskStatus {
+    Running,
+    Finished,
+    Cancelled,
+}
+
+/// Snapshot of a registered background job
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct BackgroundTaskInfo {
+    pub id: Uuid,
+    pub label: String,
+    pub status: BackgroundTaskStatus,
+    /// Whether the job can be aborted without leaving state inconsistent
+    pub cancellable: bool,
+    #[ts(type = "Date")]
+    pub started_at: DateTime<Utc>,
+}
+
+struct RegisteredTask {
+    label: String,
+    cancellable: bool,
+    cancelled: bool,
+    started_at: DateTime<Utc>,
+    abort: AbortHandle,
+}
+
+impl RegisteredTask {
+    fn status(&self) -> BackgroundTaskStatus {
+        if self.cancelled {
+            BackgroundTaskStatus::Cancelled
+        } else if self.abort.is_finished() {
+            BackgroundTaskStatus::Finished
+        } else {
+            BackgroundTaskStatus::Running
+        }
+    }
+}
+
+/// In-memory registry of long-lived `tokio::spawn` jobs, so they can be listed
+/// and, where safe, cancelled.
+#[derive(Clone, Default)]
+pub struct TaskRegistry {
+    tasks: Arc<DashMap<Uuid, RegisteredTask>>,
+}
+
+impl TaskRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn `future` on the runtime and record it under `label`.
+    pub fn spawn<F>(
+        &self,
+        label: impl Into<String>,
+        cancellable: bool,
+        future: F,
+    ) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.prune_finished();
+        let handle = tokio::spawn(future);
+        self.tasks.insert(
+            Uuid::new_v4(),
+            RegisteredTask {
+                label: label.into(),
+                cancellable,
+                cancelled: false,
+                started_at: Utc::now(),
+                abort: handle.abort_handle(),
+            },
+        );
+        handle
+    }
+
+    /// All registered jobs, newest first
+    pub fn list(&self) -> Vec<BackgroundTaskInfo> {
+        let mut tasks: Vec<_> = self
+            .tasks
+            .iter()
+            .map(|entry| BackgroundTaskInfo {
+                id: *entry.key(),
+                label: entry.label.clone(),
+                status: entry.status(),
+                cancellable: entry.cancellable,
+                started_at: entry.started_at,
+            })
+            .collect();
+        tasks.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        tasks
+    }
+
+    /// Abort a running job. Jobs registered as not cancellable are refused.
+    pub fn cancel(&self, id: Uuid) -> Result<BackgroundTaskInfo, TaskRegistryError> {
+        let mut entry = self
+            .tasks
+            .get_mut(&id)
+            .ok_or(TaskRegistryError::NotFound(id))?;
+        if !entry.cancellable {
+            return Err(TaskRegistryError::NotCancellable(entry.label.clone()));
+        }
+        if entry.status() == BackgroundTaskStatus::Running {
+            entry.abort.abort();
+            entry.cancelled = true;
+        }
+        Ok(BackgroundTaskInfo {
+            id,
+            label: entry.label.clone(),
+            status: entry.status(),
+            cancellable: entry.cancellable,
+            started_at: entry.started_at,
+        })
+    }
+
+    fn prune_finished(&self) {
+        let finished = self
+            .tasks
+            .iter()
+            .filter(|entry| entry.status() != BackgroundTaskStatus::Running)
+            .count();
+        if finished >= MAX_FINISHED_ENTRIES {
+            self.tasks
+                .retain(|_, task| task.status() == BackgroundTaskStatus::Running);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_register_list_and_cancel_dummy_job() {
+        let registry = TaskRegistry::new();
+        let handle = registry.spawn("dummy", true, std::future::pending::<()>());
+        registry.spawn("pinned", false, std::future::pending::<()>());
+
+        let listed = registry.list();
+        assert_eq!(listed.len(), 2);
+        let dummy = listed.iter().find(|t| t.label == "dummy").unwrap();
+        assert_eq!(dummy.status, BackgroundTaskStatus::Running);
+        assert!(dummy.cancellable);
+
+        let cancelled = registry.cancel(dummy.id).unwrap();
+        assert_eq!(cancelled.status, BackgroundTaskStatus::Cancelled);
+        assert!(handle.await.unwrap_err().is_cancelled());
+
+        let pinned = listed.iter().find(|t| t.label == "pinned").unwrap();
+        assert!(matches!(
+            registry.cancel(pinned.id),
+            Err(TaskRegistryError::NotCancellable(_))
+        ));
+        assert!(matches!(
+            registry.cancel(Uuid::new_v4()),
+            Err(TaskRegistryError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,308 @@
+//! Outgoing webhooks for task status changes, fed by the database hook that
+//! sees every write to a task's status.
+
+use std::{sync::Arc, time::Duration};
+
+use backon::{ExponentialBuilder, Retryable};
+use chrono::{DateTime, Utc};
+use db::{
+    DBService,
+    models::{task::TaskStatus, task_attempt::TaskAttempt},
+};
+use serde::Serialize;
+use tokio::sync::{RwLock, mpsc};
+use uuid::Uuid;
+
+use crate::services::{
+    config::{Config, WebhooksConfig},
+    task_registry::TaskRegistry,
+};
+
+/// A task's status as it is being overwritten in the database
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskStatusChange {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub previous_status: TaskStatus,
+    pub status: TaskStatus,
+}
+
+/// Body POSTed to every configured webhook URL
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskStatusWebhookPayload {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub status: TaskStatus,
+    pub previous_status: TaskStatus,
+    /// The task's most recent attempt, if it has one
+    pub attempt_id: Option<Uuid>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct WebhookService {
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+    client: reqwest::Client,
+    task_registry: TaskRegistry,
+    retry_policy: fn() -> ExponentialBuilder,
+}
+
+impl WebhookService {
+    pub fn new(db: DBService, config: Arc<RwLock<Config>>, task_registry: TaskRegistry) -> Self {
+        Self {
+            db,
+            config,
+            client: reqwest::Client::new(),
+            task_registry,
+            retry_policy,
+        }
+    }
+
+    /// Deliver status changes to the configured webhooks until every sender is
+    /// dropped.
+    pub async fn run(self, mut changes: mpsc::UnboundedReceiver<TaskStatusChange>) {
+        while let Some(change) = changes.recv().await {
+            self.on_status_change(change).await;
+        }
+    }
+
+    async fn on_status_change(&self, change: TaskStatusChange) {
+        if change.previous_status == change.status {
+            return;
+        }
+        let webhooks = self.config.read().await.webhooks.clone();
+        if !should_notify(&webhooks, &change.status) {
+            return;
+        }
+
+        let attempt_id = match TaskAttempt::fetch_all(&self.db.pool, Some(change.task_id)).await {
+            Ok(attempts) => attempts.first().map(|attempt| attempt.id),
+            Err(e) => {
+                tracing::warn!("Failed to load attempts for task {}: {}", change.task_id, e);
+                None
+            }
+        };
+        let payload = TaskStatusWebhookPayload {
+            task_id: change.task_id,
+            project_id: change.project_id,
+            status: change.status,
+            previous_status: change.previous_status,
+            attempt_id,
+            changed_at: Utc::now(),
+        };
+        for url in webhooks.urls {
+            let client = self.client.clone();
+            let payload = payload.clone();
+            let retry_policy = self.retry_policy;
+            self.task_registry
+                .spawn("webhook_delivery", true, async move {
+                    if let Err(e) = deliver(&client, &url, &payload, retry_policy()).await {
+                        tracing::warn!(
+                            "Giving up on webhook {} for task {}: {}",
+                            url,
+                            payload.task_id,
+                            e
+                        );
+                    }
+                });
+        }
+    }
+}
+
+fn should_notify(webhooks: &WebhooksConfig, status: &TaskStatus) -> bool {
+    !webhooks.urls.is_empty()
+        && (webhooks.statuses.is_empty() || webhooks.statuses.contains(status))
+}
+
+/// The first configured webhook URL that isn't an absolute http(s) URL
+pub fn invalid_webhook_url(webhooks: &WebhooksConfig) -> Option<&str> {
+    webhooks
+        .urls
+        .iter()
+        .find(|url| {
+            !url::Url::parse(url).is_ok_and(|parsed| matches!(parsed.scheme(), "http" | "https"))
+        })
+        .map(String::as_str)
+}
+
+async fn deliver(
+    client: &reqwest::Client,
+    url: &str,
+    payload: &TaskStatusWebhookPayload,
+    retry_policy: ExponentialBuilder,
+) -> Result<(), reqwest::Error> {
+    (|| async {
+        client
+            .post(url)
+            .json(payload)
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await?
+            .error_for_status()
+            .map(|_| ())
+    })
+    .retry(retry_policy)
+    .notify(|e, dur| {
+        tracing::debug!("Webhook {} failed ({}), retrying in {:?}", url, e, dur);
+    })
+    .await
+}
+
+fn retry_policy() -> ExponentialBuilder {
+    ExponentialBuilder::default()
+        .with_min_delay(Duration::from_secs(1))
+        .with_max_delay(Duration::from_secs(30))
+        .with_max_times(3)
+        .with_jitter()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::{Json, Router, extract::State, http::StatusCode, routing::post};
+    use serde_json::Value;
+
+    use super::*;
+
+    fn fast_retry_policy() -> ExponentialBuilder {
+        ExponentialBuilder::default()
+            .with_min_delay(Duration::from_millis(1))
+            .with_max_delay(Duration::from_millis(5))
+            .with_max_times(3)
+    }
+
+    fn payload() -> TaskStatusWebhookPayload {
+        TaskStatusWebhookPayload {
+            task_id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            status: TaskStatus::Done,
+            previous_status: TaskStatus::InReview,
+            attempt_id: None,
+            changed_at: Utc::now(),
+        }
+    }
+
+    /// Serve a webhook endpoint that fails the first `failures` requests and
+    /// forwards every accepted body to the returned channel.
+    async fn spawn_endpoint(
+        failures: usize,
+    ) -> (String, Arc<AtomicUsize>, mpsc::UnboundedReceiver<Value>) {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let (tx, rx) = mpsc::unbounded_channel();
+        let app = Router::new()
+            .route(
+                "/hook",
+                post(
+                    |State((hits, tx)): State<(Arc<AtomicUsize>, mpsc::UnboundedSender<Value>)>,
+                     Json(body): Json<Value>| async move {
+                        if hits.fetch_add(1, Ordering::SeqCst) < failures {
+                            return StatusCode::INTERNAL_SERVER_ERROR;
+                        }
+                        let _ = tx.send(body);
+                        StatusCode::OK
+                    },
+                ),
+            )
+            .with_state((hits.clone(), tx));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (url, hits, rx)
+    }
+
+    #[test]
+    fn test_should_notify() {
+        let mut webhooks = WebhooksConfig::default();
+        assert!(!should_notify(&webhooks, &TaskStatus::Done));
+
+        webhooks.urls.push("http://localhost:9000/hook".to_string());
+        assert!(should_notify(&webhooks, &TaskStatus::InProgress));
+
+        webhooks.statuses = vec![TaskStatus::Done];
+        assert!(should_notify(&webhooks, &TaskStatus::Done));
+        assert!(!should_notify(&webhooks, &TaskStatus::InReview));
+    }
+
+    #[test]
+    fn test_invalid_webhook_url() {
+        let mut webhooks = WebhooksConfig::default();
+        webhooks.urls = vec![
+            "https://example.com/hook".to_string(),
+            "http://localhost:9000/hook".to_string(),
+        ];
+        assert_eq!(invalid_webhook_url(&webhooks), None);
+
+        webhooks.urls.push("example.com/hook".to_string());
+        assert_eq!(invalid_webhook_url(&webhooks), Some("example.com/hook"));
+
+        webhooks.urls = vec!["ftp://example.com/hook".to_string()];
+        assert_eq!(
+            invalid_webhook_url(&webhooks),
+            Some("ftp://example.com/hook")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_status_changes_are_filtered_before_delivery() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let (url, hits, mut delivered) = spawn_endpoint(0).await;
+
+        let mut config = Config::default();
+        config.webhooks.urls = vec![url];
+        config.webhooks.statuses = vec![TaskStatus::Done];
+        let service = WebhookService::new(db, Arc::new(RwLock::new(config)), TaskRegistry::new());
+
+        let change = |previous_status, status| TaskStatusChange {
+            task_id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            previous_status,
+            status,
+        };
+        // Neither a status outside the filter nor a rewrite of the same status is sent
+        service
+            .on_status_change(change(TaskStatus::Todo, TaskStatus::InProgress))
+            .await;
+        service
+            .on_status_change(change(TaskStatus::Done, TaskStatus::Done))
+            .await;
+        let done = change(TaskStatus::InReview, TaskStatus::Done);
+        service.on_status_change(done.clone()).await;
+
+        let body = tokio::time::timeout(Duration::from_secs(5), delivered.recv())
+            .await
+            .expect("the Done transition is delivered")
+            .unwrap();
+        assert_eq!(body["task_id"], done.task_id.to_string());
+        assert_eq!(body["previous_status"], "inreview");
+        assert_eq!(body["status"], "done");
+        assert!(body["attempt_id"].is_null());
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_delivery_retries_failed_requests() {
+        let (url, hits, mut delivered) = spawn_endpoint(2).await;
+        let client = reqwest::Client::new();
+
+        deliver(&client, &url, &payload(), fast_retry_policy())
+            .await
+            .expect("delivered on the third try");
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+        assert!(delivered.recv().await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_delivery_gives_up_after_max_retries() {
+        let (url, hits, _delivered) = spawn_endpoint(usize::MAX).await;
+        let client = reqwest::Client::new();
+
+        let result = deliver(&client, &url, &payload(), fast_retry_policy()).await;
+        assert!(result.is_err());
+        // The first try plus three retries
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+    }
+}
@@ -1,7 +1,9 @@
 use std::{
     collections::HashMap,
+    io,
     path::{Path, PathBuf},
     sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 use git2::{Error as GitError, Repository};
@@ -11,10 +13,39 @@ use utils::shell::resolve_executable_path;
 
 use super::git::{GitService, GitServiceError};
 
+/// Default number of worktrees that may be created at once; override with
+/// `VK_WORKTREE_CREATION_CONCURRENCY`.
+const DEFAULT_WORKTREE_CREATION_CONCURRENCY: usize = 4;
+
+/// How long a measured worktree size is reused before the tree is walked again
+const DISK_USAGE_CACHE_TTL: Duration = Duration::from_secs(30);
+
 // Global synchronization for worktree creation to prevent race conditions
 lazy_static::lazy_static! {
     static ref WORKTREE_CREATION_LOCKS: Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> =
         Arc::new(Mutex::new(HashMap::new()));
+    // Caps creations across distinct paths so bulk attempt creation doesn't thrash disk and git
+    static ref WORKTREE_CREATION_PERMITS: tokio::sync::Semaphore =
+        tokio::sync::Semaphore::new(worktree_creation_concurrency());
+    static ref DISK_USAGE_CACHE: Mutex<HashMap<PathBuf, (Instant, u64)>> =
+        Mutex::new(HashMap::new());
+}
+
+fn worktree_creation_concurrency() -> usize {
+    std::env::var("VK_WORKTREE_CREATION_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|permits| *permits > 0)
+        .unwrap_or(DEFAULT_WORKTREE_CREATION_CONCURRENCY)
+}
+
+/// Run `f` once a global worktree creation permit is available.
+async fn with_creation_permit<F: std::future::Future>(f: F) -> F::Output {
+    let _permit = WORKTREE_CREATION_PERMITS
+        .acquire()
+        .await
+        .expect("worktree creation semaphore is never closed");
+    f.await
 }
 
 #[derive(Debug, Clone)]
@@ -56,16 +87,54 @@ pub enum WorktreeError {
     UnsafePath(String),
 }
 
+/// Parse a project's sparse-checkout patterns: one per line, blank lines and
+/// `#` comments ignored. An empty result means a full checkout.
+pub fn parse_sparse_patterns(patterns: Option<&str>) -> Vec<String> {
+    patterns
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SparsePatternError {
+    #[error("Sparse-checkout pattern '{0}' must not start with '-'")]
+    LeadingDash(String),
+    #[error(
+        "Sparse-checkout patterns only exclude paths; add one that includes paths, such as '/*'"
+    )]
+    NothingIncluded,
+}
+
+/// Check a project's sparse-checkout patterns before they are saved, since they
+/// are passed straight to `git sparse-checkout set`.
+pub fn validate_sparse_patterns(patterns: &str) -> Result<(), SparsePatternError> {
+    let parsed = parse_sparse_patterns(Some(patterns));
+    // git would read these as options
+    if let Some(pattern) = parsed.iter().find(|p| p.starts_with('-')) {
+        return Err(SparsePatternError::LeadingDash(pattern.clone()));
+    }
+    if !parsed.is_empty() && parsed.iter().all(|p| p.starts_with('!')) {
+        return Err(SparsePatternError::NothingIncluded);
+    }
+    Ok(())
+}
+
 pub struct WorktreeManager;
 
 impl WorktreeManager {
-    /// Create a worktree with a new branch
+    /// Create a worktree with a new branch. Non-empty `sparse_patterns` limit
+    /// the checkout to matching paths.
     pub async fn create_worktree(
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
         base_branch: &str,
         create_branch: bool,
+        sparse_patterns: &[String],
     ) -> Result<(), WorktreeError> {
         if create_branch {
             let repo_path_owned = repo_path.to_path_buf();
@@ -87,7 +156,7 @@ impl WorktreeManager {
             .map_err(|e| WorktreeError::TaskJoin(format!("Task join error: {e}")))??;
         }
 
-        Self::ensure_worktree_exists(repo_path, branch_name, worktree_path).await
+        Self::ensure_worktree_exists(repo_path, branch_name, worktree_path, sparse_patterns).await
     }
 
     /// Ensure worktree exists, recreating if necessary with proper synchronization
@@ -96,6 +165,7 @@ impl WorktreeManager {
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
+        sparse_patterns: &[String],
     ) -> Result<(), WorktreeError> {
         let path_str = worktree_path.to_string_lossy().to_string();
 
@@ -119,7 +189,13 @@ impl WorktreeManager {
 
         // If worktree doesn't exist or isn't properly set up, recreate it
         info!("Worktree needs recreation at path: {}", path_str);
-        Self::recreate_worktree_internal(repo_path, branch_name, worktree_path).await
+        with_creation_permit(Self::recreate_worktree_internal(
+            repo_path,
+            branch_name,
+            worktree_path,
+            sparse_patterns,
+        ))
+        .await
     }
 
     /// Internal worktree recreation function (always recreates)
@@ -127,6 +203,7 @@ impl WorktreeManager {
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
+        sparse_patterns: &[String],
     ) -> Result<(), WorktreeError> {
         let path_str = worktree_path.to_string_lossy().to_string();
         let branch_name_owned = branch_name.to_string();
@@ -182,6 +259,7 @@ impl WorktreeManager {
             &worktree_path_owned,
             &worktree_name,
             &path_str,
+            sparse_patterns,
         )
         .await
     }
@@ -317,17 +395,31 @@ impl WorktreeManager {
         worktree_path: &Path,
         worktree_name: &str,
         path_str: &str,
+        sparse_patterns: &[String],
     ) -> Result<(), WorktreeError> {
         let git_repo_path = git_repo_path.to_path_buf();
         let branch_name = branch_name.to_string();
         let worktree_path = worktree_path.to_path_buf();
         let worktree_name = worktree_name.to_string();
         let path_str = path_str.to_string();
+        let sparse_patterns = sparse_patterns.to_vec();
 
         tokio::task::spawn_blocking(move || -> Result<(), WorktreeError> {
             // Prefer git CLI for worktree add to inherit sparse-checkout semantics
             let git_service = GitService::new();
-            match git_service.add_worktree(&git_repo_path, &worktree_path, &branch_name, false) {
+            let add_worktree = || {
+                if sparse_patterns.is_empty() {
+                    git_service.add_worktree(&git_repo_path, &worktree_path, &branch_name, false)
+                } else {
+                    git_service.add_sparse_worktree(
+                        &git_repo_path,
+                        &worktree_path,
+                        &branch_name,
+                        &sparse_patterns,
+                    )
+                }
+            };
+            match add_worktree() {
                 Ok(()) => {
                     if !worktree_path.exists() {
                         return Err(WorktreeError::Repository(format!(
@@ -363,12 +455,7 @@ impl WorktreeManager {
                     if worktree_path.exists() {
                         std::fs::remove_dir_all(&worktree_path).map_err(WorktreeError::Io)?;
                     }
-                    if let Err(e2) = git_service.add_worktree(
-                        &git_repo_path,
-                        &worktree_path,
-                        &branch_name,
-                        false,
-                    ) {
+                    if let Err(e2) = add_worktree() {
                         // Check again after retry
                         let error_str = e2.to_string();
                         if error_str.contains("is already used by worktree")
@@ -553,6 +640,73 @@ impl WorktreeManager {
         .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))?
     }
 
+    /// Total size in bytes of the files under a managed worktree. Symlinks are not
+    /// followed. Results are cached briefly so repeated polls don't re-walk large trees.
+    pub async fn worktree_disk_usage(worktree_path: &Path) -> Result<u64, WorktreeError> {
+        Self::verify_path_safe_for_deletion(worktree_path)?;
+        if !worktree_path.exists() {
+            return Err(WorktreeError::InvalidPath(format!(
+                "Worktree does not exist: {}",
+                worktree_path.display()
+            )));
+        }
+
+        if let Some((measured_at, bytes)) =
+            DISK_USAGE_CACHE.lock().unwrap().get(worktree_path).copied()
+            && measured_at.elapsed() < DISK_USAGE_CACHE_TTL
+        {
+            return Ok(bytes);
+        }
+
+        let path = worktree_path.to_path_buf();
+        let bytes = tokio::task::spawn_blocking(move || Self::directory_size(&path))
+            .await
+            .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))??;
+
+        let mut cache = DISK_USAGE_CACHE.lock().unwrap();
+        // Drop expired sizes so deleted worktrees don't linger in the cache
+        cache.retain(|_, (measured_at, _)| measured_at.elapsed() < DISK_USAGE_CACHE_TTL);
+        cache.insert(worktree_path.to_path_buf(), (Instant::now(), bytes));
+        Ok(bytes)
+    }
+
+    /// Files removed while the tree is being walked (e.g. by a running agent) are
+    /// skipped rather than failing the whole measurement.
+    fn directory_size(root: &Path) -> Result<u64, WorktreeError> {
+        fn skip_not_found<T>(result: io::Result<T>) -> io::Result<Option<T>> {
+            match result {
+                Ok(value) => Ok(Some(value)),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e),
+            }
+        }
+
+        let mut total = 0;
+        let mut pending = vec![root.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let Some(entries) = skip_not_found(std::fs::read_dir(&dir))? else {
+                continue;
+            };
+            for entry in entries {
+                let Some(entry) = skip_not_found(entry)? else {
+                    continue;
+                };
+                // file_type() does not follow symlinks
+                let Some(file_type) = skip_not_found(entry.file_type())? else {
+                    continue;
+                };
+                if file_type.is_dir() {
+                    pending.push(entry.path());
+                } else if file_type.is_file()
+                    && let Some(metadata) = skip_not_found(entry.metadata())?
+                {
+                    total += metadata.len();
+                }
+            }
+        }
+        Ok(total)
+    }
+
     /// Get the base directory for vibe-kanban worktrees
     pub fn get_worktree_base_dir() -> std::path::PathBuf {
         utils::path::get_vibe_kanban_temp_dir().join("worktrees")
@@ -767,4 +921,73 @@ mod tests {
             traversal
         );
     }
+
+    #[test]
+    fn test_validate_sparse_patterns() {
+        assert_eq!(validate_sparse_patterns(""), Ok(()));
+        assert_eq!(validate_sparse_patterns("/*\n!/assets/\n# docs\n"), Ok(()));
+        assert_eq!(validate_sparse_patterns("\\-literal-dash"), Ok(()));
+        assert_eq!(
+            validate_sparse_patterns("/src/\n--no-cone"),
+            Err(SparsePatternError::LeadingDash("--no-cone".to_string()))
+        );
+        assert_eq!(
+            validate_sparse_patterns("!/assets/\n!/vendor/"),
+            Err(SparsePatternError::NothingIncluded)
+        );
+    }
+
+    #[test]
+    fn test_directory_size_skips_symlinks() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.txt"), b"hello").unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        std::fs::write(dir.path().join("nested").join("b.bin"), [0u8; 100]).unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(dir.path().join("nested"), dir.path().join("link-to-nested"))
+            .unwrap();
+
+        assert_eq!(WorktreeManager::directory_size(dir.path()).unwrap(), 105);
+    }
+
+    #[test]
+    fn test_directory_size_skips_vanished_paths() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(
+            WorktreeManager::directory_size(&dir.path().join("removed")).unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_creations_are_capped() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let limit = worktree_creation_concurrency();
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_seen = Arc::new(AtomicUsize::new(0));
+
+        let creations: Vec<_> = (0..limit * 3)
+            .map(|_| {
+                let in_flight = in_flight.clone();
+                let max_seen = max_seen.clone();
+                tokio::spawn(with_creation_permit(async move {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_seen.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                }))
+            })
+            .collect();
+        for creation in creations {
+            creation.await.unwrap();
+        }
+
+        let max_seen = max_seen.load(Ordering::SeqCst);
+        assert!(max_seen >= 1);
+        assert!(
+            max_seen <= limit,
+            "{max_seen} creations ran at once, limit is {limit}"
+        );
+    }
 }
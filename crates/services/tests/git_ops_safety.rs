@@ -5,7 +5,9 @@ use std::{
 };
 
 use git2::{PushOptions, Repository, build::CheckoutBuilder};
-use services::services::git::{GitCli, GitCliError, GitService};
+use services::services::git::{
+    ConflictOp, ConflictSide, GitCli, GitCliError, GitService, GitServiceError,
+};
 use tempfile::TempDir;
 // Avoid direct git CLI usage in tests; exercise GitService instead.
 
@@ -719,6 +721,169 @@ fn rebase_refuses_to_abort_existing_rebase() {
     // Note: We do not auto-abort; user should resolve or abort explicitly
 }
 
+#[test]
+fn abort_rebase_restores_pre_rebase_branch() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+    let service = GitService::new();
+
+    let wt_repo = Repository::open(&worktree_path).unwrap();
+    let before = wt_repo.head().unwrap().peel_to_commit().unwrap().id();
+
+    // Nothing to abort yet
+    assert!(matches!(
+        service.abort_rebase(&worktree_path),
+        Err(GitServiceError::NoRebaseInProgress)
+    ));
+
+    let _ = service
+        .rebase_branch(
+            &repo_path,
+            &worktree_path,
+            "new-base",
+            "old-base",
+            "feature",
+        )
+        .expect_err("rebase should stop on conflicts");
+    assert!(service.is_rebase_in_progress(&worktree_path).unwrap());
+
+    service.abort_rebase(&worktree_path).expect("abort rebase");
+
+    assert!(!service.is_rebase_in_progress(&worktree_path).unwrap());
+    let after = wt_repo.head().unwrap().peel_to_commit().unwrap().id();
+    assert_eq!(before, after, "branch should be back at its pre-rebase tip");
+}
+
+fn start_conflicting_rebase(root: &TempDir) -> PathBuf {
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(root);
+    let _ = GitService::new()
+        .rebase_branch(
+            &repo_path,
+            &worktree_path,
+            "new-base",
+            "old-base",
+            "feature",
+        )
+        .expect_err("rebase should stop on conflicts");
+    worktree_path
+}
+
+#[test]
+fn resolve_conflict_keeps_the_attempt_side_during_rebase() {
+    let td = TempDir::new().unwrap();
+    let worktree_path = start_conflicting_rebase(&td);
+    let service = GitService::new();
+
+    assert!(matches!(
+        service.resolve_conflict(&worktree_path, "missing.txt", ConflictSide::Ours),
+        Err(GitServiceError::FileNotConflicted(_))
+    ));
+
+    let remaining = service
+        .resolve_conflict(&worktree_path, "conflict.txt", ConflictSide::Ours)
+        .expect("resolve with ours");
+    assert!(remaining.is_empty());
+    // During a rebase "ours" still means the attempt branch's change
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("conflict.txt")).unwrap(),
+        "feature version\n"
+    );
+    assert!(service.is_rebase_in_progress(&worktree_path).unwrap());
+}
+
+#[test]
+fn resolve_conflict_takes_the_incoming_side_during_rebase() {
+    let td = TempDir::new().unwrap();
+    let worktree_path = start_conflicting_rebase(&td);
+    let service = GitService::new();
+
+    let remaining = service
+        .resolve_conflict(&worktree_path, "conflict.txt", ConflictSide::Theirs)
+        .expect("resolve with theirs");
+    assert!(remaining.is_empty());
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("conflict.txt")).unwrap(),
+        "new-base version\n"
+    );
+}
+
+// Start `args` in the feature worktree, expecting it to stop on conflict.txt,
+// then abort it and check the worktree is back at its starting commit and clean
+fn assert_abort_conflicts_restores_worktree(args: &[&str], expected_op: ConflictOp) {
+    let td = TempDir::new().unwrap();
+    let (_repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+    let service = GitService::new();
+    let git = GitCli::new();
+
+    let wt_repo = Repository::open(&worktree_path).unwrap();
+    let before = wt_repo.head().unwrap().peel_to_commit().unwrap().id();
+
+    let _ = git
+        .git(&worktree_path, args.iter().copied())
+        .expect_err("operation should stop on conflicts");
+    assert_eq!(
+        service.detect_conflict_op(&worktree_path).unwrap(),
+        Some(expected_op)
+    );
+    assert_eq!(
+        service.get_conflicted_files(&worktree_path).unwrap(),
+        vec!["conflict.txt".to_string()]
+    );
+
+    service
+        .abort_conflicts(&worktree_path)
+        .expect("abort conflicts");
+
+    assert_eq!(service.detect_conflict_op(&worktree_path).unwrap(), None);
+    let after = wt_repo.head().unwrap().peel_to_commit().unwrap().id();
+    assert_eq!(before, after, "branch should be back at its original tip");
+    let status = git.git(&worktree_path, ["status", "--porcelain"]).unwrap();
+    assert!(status.trim().is_empty(), "worktree not clean: {status}");
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("conflict.txt")).unwrap(),
+        "feature version\n"
+    );
+}
+
+#[test]
+fn abort_conflicts_aborts_merge() {
+    assert_abort_conflicts_restores_worktree(&["merge", "new-base"], ConflictOp::Merge);
+}
+
+#[test]
+fn abort_conflicts_aborts_cherry_pick() {
+    assert_abort_conflicts_restores_worktree(&["cherry-pick", "new-base"], ConflictOp::CherryPick);
+}
+
+#[test]
+fn abort_conflicts_aborts_revert() {
+    assert_abort_conflicts_restores_worktree(
+        &["revert", "--no-edit", "old-base"],
+        ConflictOp::Revert,
+    );
+}
+
+#[test]
+fn abort_conflicts_aborts_rebase() {
+    let td = TempDir::new().unwrap();
+    let worktree_path = start_conflicting_rebase(&td);
+    let service = GitService::new();
+    assert_eq!(
+        service.detect_conflict_op(&worktree_path).unwrap(),
+        Some(ConflictOp::Rebase)
+    );
+
+    service
+        .abort_conflicts(&worktree_path)
+        .expect("abort conflicts");
+
+    assert_eq!(service.detect_conflict_op(&worktree_path).unwrap(), None);
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("conflict.txt")).unwrap(),
+        "feature version\n"
+    );
+}
+
 #[test]
 fn rebase_fast_forwards_when_no_unique_commits() {
     let td = TempDir::new().unwrap();
@@ -951,6 +1116,31 @@ fn merge_refreshes_main_worktree_when_on_base() {
     assert_eq!(oid, merge_sha);
 }
 
+#[test]
+fn sparse_worktree_limits_checkout_without_touching_main_repo() {
+    let td = TempDir::new().unwrap();
+    let repo_path = td.path().join("repo_sparse_wt");
+    let s = GitService::new();
+    s.initialize_repo_with_main_branch(&repo_path).unwrap();
+    let repo = Repository::open(&repo_path).unwrap();
+    configure_user(&repo);
+    checkout_branch(&repo, "main");
+    write_file(&repo_path, "src/lib.rs", "fn main() {}\n");
+    write_file(&repo_path, "assets/big.bin", "BIN\n");
+    let _ = s.commit(&repo_path, "baseline").unwrap();
+
+    create_branch_from_head(&repo, "feature");
+    let wt = td.path().join("wt_sparse_patterns");
+    let patterns = vec!["/*".to_string(), "!/assets/".to_string()];
+    s.add_sparse_worktree(&repo_path, &wt, "feature", &patterns)
+        .unwrap();
+
+    assert!(wt.join("src/lib.rs").exists());
+    assert!(!wt.join("assets/big.bin").exists());
+    // The main checkout keeps every file
+    assert!(repo_path.join("assets/big.bin").exists());
+}
+
 #[test]
 fn sparse_checkout_respected_in_worktree_diffs_and_commit() {
     let td = TempDir::new().unwrap();
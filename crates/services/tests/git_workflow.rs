@@ -4,13 +4,17 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use db::models::project::MergeStrategy;
 use git2::{Repository, build::CheckoutBuilder};
 use services::services::{
-    git::{DiffTarget, GitCli, GitService},
+    git::{CommitNote, DiffTarget, GitCli, GitService, GitServiceError, ResetMode},
+    git_provider::{GitProvider, RepoInfo},
     github::{GitHubRepoInfo, GitHubServiceError},
+    gitlab::GitLabRepoInfo,
 };
 use tempfile::TempDir;
 use utils::diff::DiffChangeKind;
+use uuid::Uuid;
 
 fn add_path(repo_path: &Path, path: &str) {
     let git = GitCli::new();
@@ -183,6 +187,36 @@ fn worktree_clean_detects_staged_deleted_and_renamed() {
     assert!(!s.is_worktree_clean(&repo_path).unwrap());
 }
 
+#[test]
+fn worktree_status_porcelain_reports_rename_and_untracked() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    write_file(&repo_path, "notes.txt", "keep me\n");
+    let s = GitService::new();
+    let _ = s.commit(&repo_path, "seed").unwrap();
+
+    GitCli::new()
+        .git(&repo_path, ["mv", "notes.txt", "notes-renamed.txt"])
+        .unwrap();
+    write_file(&repo_path, "scratch.txt", "draft\n");
+
+    let repo = Repository::open(&repo_path).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let blob = head.tree().unwrap().get_name("notes.txt").unwrap().id();
+    let expected = format!(
+        "# branch.oid {head}\n\
+         # branch.head main\n\
+         2 R. N... 100644 100644 100644 {blob} {blob} R100 notes-renamed.txt\tnotes.txt\n\
+         ? scratch.txt\n",
+        head = head.id(),
+    );
+
+    assert_eq!(
+        s.get_worktree_status_porcelain(&repo_path).unwrap(),
+        expected
+    );
+}
+
 #[test]
 fn diff_added_binary_file_has_no_content() {
     // ensure binary file content is not loaded (null byte guard)
@@ -259,6 +293,34 @@ fn commit_and_is_worktree_clean() {
     );
 }
 
+#[test]
+fn range_diff_since_marker_only_shows_new_changes() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    write_file(&repo_path, "seen.txt", "reviewed\n");
+    assert!(s.commit(&repo_path, "reviewed change").unwrap());
+    let marker = s.get_head_info(&repo_path).unwrap().oid;
+
+    write_file(&repo_path, "fresh.txt", "new\n");
+    assert!(s.commit(&repo_path, "new change").unwrap());
+    let head = s.get_head_info(&repo_path).unwrap().oid;
+
+    let diffs = s
+        .get_diffs(
+            DiffTarget::Range {
+                repo_path: Path::new(&repo_path),
+                from_sha: &marker,
+                to_sha: &head,
+            },
+            None,
+        )
+        .unwrap();
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].new_path.as_deref(), Some("fresh.txt"));
+}
+
 #[test]
 fn commit_in_detached_head_succeeds_via_service() {
     let td = TempDir::new().unwrap();
@@ -311,6 +373,73 @@ fn branch_status_ahead_and_behind() {
     assert_eq!((ahead2, behind2), (2, 1));
 }
 
+#[test]
+fn list_commits_between_returns_branch_commits_newest_first() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    write_file(&repo_path, "base.txt", "base\n");
+    let _ = s.commit(&repo_path, "base").unwrap();
+    create_branch(&repo_path, "feature");
+
+    // main moves on; its commits must not be listed
+    write_file(&repo_path, "main.txt", "m1\n");
+    let _ = s.commit(&repo_path, "m1").unwrap();
+
+    checkout_branch(&repo_path, "feature");
+    write_file(&repo_path, "a.txt", "a\n");
+    let _ = s.commit(&repo_path, "first").unwrap();
+    write_file(&repo_path, "b.txt", "b\n");
+    let _ = s.commit(&repo_path, "second").unwrap();
+
+    let base = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let commits = s
+        .list_commits_between(&repo_path, &base, "feature")
+        .unwrap();
+    let subjects: Vec<_> = commits.iter().map(|c| c.subject.as_str()).collect();
+    assert_eq!(subjects, vec!["second", "first"]);
+    assert_eq!(commits[0].oid, s.get_head_info(&repo_path).unwrap().oid);
+    assert_eq!(commits[0].author_name, "Test User");
+    assert_eq!(commits[0].author_email, "test@example.com");
+}
+
+#[test]
+fn commit_notes_round_trip_and_show_in_commit_list() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    write_file(&repo_path, "base.txt", "base\n");
+    let _ = s.commit(&repo_path, "base").unwrap();
+    create_branch(&repo_path, "feature");
+    checkout_branch(&repo_path, "feature");
+    write_file(&repo_path, "a.txt", "a\n");
+    let _ = s.commit(&repo_path, "noted").unwrap();
+    write_file(&repo_path, "b.txt", "b\n");
+    let _ = s.commit(&repo_path, "plain").unwrap();
+
+    let base = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let commits = s
+        .list_commits_between(&repo_path, &base, "feature")
+        .unwrap();
+    let noted_oid = commits[1].oid.clone();
+    assert_eq!(commits[1].note, None);
+
+    let note = CommitNote {
+        task_id: Uuid::new_v4(),
+        attempt_id: Uuid::new_v4(),
+        executor: "CLAUDE_CODE".to_string(),
+    };
+    s.write_note(&repo_path, &noted_oid, &note).unwrap();
+
+    let commits = s
+        .list_commits_between(&repo_path, &base, "feature")
+        .unwrap();
+    assert_eq!(commits[0].note, None);
+    assert_eq!(commits[1].note, Some(note));
+}
+
 #[test]
 fn get_all_branches_lists_current_and_others() {
     let td = TempDir::new().unwrap();
@@ -398,6 +527,39 @@ fn worktree_diff_respects_path_filter() {
     );
 }
 
+#[test]
+fn find_large_files_reports_committed_binary_only() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    write_file(&repo_path, "README.md", "readme\n");
+    let s = GitService::new();
+    let _ = s.commit(&repo_path, "baseline").unwrap();
+    create_branch(&repo_path, "feature");
+    checkout_branch(&repo_path, "feature");
+
+    let mut blob = vec![0u8; 64 * 1024];
+    blob.iter_mut()
+        .enumerate()
+        .for_each(|(i, b)| *b = (i % 251) as u8);
+    fs::create_dir_all(repo_path.join("assets")).unwrap();
+    fs::write(repo_path.join("assets/model.bin"), &blob).unwrap();
+    write_file(
+        &repo_path,
+        "src/lib.rs",
+        "pub fn answer() -> u32 {\n    42\n}\n",
+    );
+    let _ = s.commit(&repo_path, "add model and code").unwrap();
+
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let large = s
+        .find_large_files(&repo_path, &base_commit, 16 * 1024)
+        .unwrap();
+    assert_eq!(large.len(), 1, "{large:?}");
+    assert_eq!(large[0].path, "assets/model.bin");
+    assert_eq!(large[0].size_bytes, blob.len() as u64);
+    assert!(large[0].is_binary);
+}
+
 #[test]
 fn get_branch_oid_nonexistent_errors() {
     let td = TempDir::new().unwrap();
@@ -487,6 +649,84 @@ fn github_repo_info_parses_https_and_ssh_urls() {
     }
 }
 
+#[test]
+fn gitlab_repo_info_parses_nested_groups_and_mr_urls() {
+    let info = GitLabRepoInfo::from_remote_url("git@gitlab.com:group/sub/repo.git").unwrap();
+    assert_eq!(info.host, "gitlab.com");
+    assert_eq!(info.project_path, "group/sub/repo");
+    assert_eq!(info.web_url(), "https://gitlab.com/group/sub/repo");
+
+    let info =
+        GitLabRepoInfo::from_remote_url("ssh://git@gitlab.example.com:2222/team/repo.git").unwrap();
+    assert_eq!(info.host, "gitlab.example.com");
+    assert_eq!(info.project_path, "team/repo");
+
+    let info = GitLabRepoInfo::from_remote_url("https://gitlab.com/group/repo/-/merge_requests/12")
+        .unwrap();
+    assert_eq!(info.project_path, "group/repo");
+
+    // Self-managed instances can use any host
+    let info = GitLabRepoInfo::from_remote_url("git@git.Example.com:team/repo.git").unwrap();
+    assert_eq!(info.host, "git.example.com");
+    assert_eq!(info.project_path, "team/repo");
+
+    assert!(GitLabRepoInfo::from_remote_url("https://example.com/repo").is_err());
+    assert!(GitLabRepoInfo::from_remote_url("not a url").is_err());
+}
+
+#[test]
+fn repo_info_selects_provider_from_remote() {
+    let github = RepoInfo::from_remote_url("https://github.com/owner/repo.git").unwrap();
+    assert_eq!(github.provider(), GitProvider::GitHub);
+
+    let gitlab = RepoInfo::from_remote_url("https://gitlab.com/group/repo.git").unwrap();
+    assert_eq!(gitlab.provider(), GitProvider::GitLab);
+
+    let github = RepoInfo::from_remote_url("git@github.com:owner/repo.git").unwrap();
+    assert_eq!(github.provider(), GitProvider::GitHub);
+
+    // The host is matched exactly, not as a substring of the URL
+    let gitlab = RepoInfo::from_remote_url("https://git.example.com/github.com/repo.git").unwrap();
+    assert_eq!(gitlab.provider(), GitProvider::GitLab);
+    let gitlab = RepoInfo::from_remote_url("https://notgithub.com/owner/repo.git").unwrap();
+    assert_eq!(gitlab.provider(), GitProvider::GitLab);
+
+    assert!(RepoInfo::from_remote_url("https://bitbucket.org/owner/repo.git").is_err());
+    assert!(RepoInfo::from_remote_url("not a url").is_err());
+}
+
+#[test]
+fn push_remote_provider_follows_the_selected_remote() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let repo = Repository::open(&repo_path).unwrap();
+    repo.remote("origin", "https://github.com/owner/repo.git")
+        .unwrap();
+    repo.remote("upstream", "https://gitlab.com/group/repo.git")
+        .unwrap();
+    let s = GitService::new();
+
+    let default = s.push_remote_name(&repo_path, "main", None).unwrap();
+    assert_eq!(default, "origin");
+    assert_eq!(
+        s.get_remote_repo_info(&repo_path, &default)
+            .unwrap()
+            .provider(),
+        GitProvider::GitHub
+    );
+
+    let selected = s
+        .push_remote_name(&repo_path, "main", Some("upstream"))
+        .unwrap();
+    assert_eq!(selected, "upstream");
+    assert_eq!(
+        s.get_remote_repo_info(&repo_path, &selected)
+            .unwrap()
+            .provider(),
+        GitProvider::GitLab
+    );
+}
+
 #[test]
 fn squash_merge_libgit2_sets_author_without_user() {
     // Verify merge_changes (libgit2 path) uses fallback author when no config exists
@@ -544,3 +784,343 @@ fn squash_merge_libgit2_sets_author_without_user() {
         assert_eq!(email.as_deref(), Some("noreply@vibekanban.com"));
     }
 }
+
+/// Repo on `main` with a `feature` worktree two commits ahead. The main repo is
+/// left on `dev` so merges take the libgit2 path.
+fn setup_feature_worktree(td: &TempDir, advance_main: bool) -> (GitService, PathBuf, PathBuf) {
+    let repo_path = init_repo_main(td);
+    let worktree_path = td.path().join("wt_feature");
+    let s = GitService::new();
+
+    create_branch(&repo_path, "feature");
+    s.add_worktree(&repo_path, &worktree_path, "feature", false)
+        .unwrap();
+    for file in ["a.txt", "b.txt"] {
+        write_file(&worktree_path, file, &format!("{file}\n"));
+        assert!(s.commit(&worktree_path, &format!("add {file}")).unwrap());
+    }
+
+    if advance_main {
+        write_file(&repo_path, "main.txt", "main\n");
+        assert!(s.commit(&repo_path, "main moves ahead").unwrap());
+    }
+
+    create_branch(&repo_path, "dev");
+    checkout_branch(&repo_path, "dev");
+    (s, repo_path, worktree_path)
+}
+
+fn branch_tip<'a>(repo: &'a Repository, name: &str) -> git2::Commit<'a> {
+    repo.find_branch(name, git2::BranchType::Local)
+        .unwrap()
+        .get()
+        .peel_to_commit()
+        .unwrap()
+}
+
+#[test]
+fn squash_merge_changes_adds_single_commit() {
+    let td = TempDir::new().unwrap();
+    let (s, repo_path, worktree_path) = setup_feature_worktree(&td, false);
+    let repo = Repository::open(&repo_path).unwrap();
+    let old_main = branch_tip(&repo, "main").id();
+
+    let sha = s
+        .squash_merge_changes(&repo_path, &worktree_path, "feature", "main", "squashed")
+        .unwrap();
+
+    let tip = branch_tip(&repo, "main");
+    assert_eq!(tip.id().to_string(), sha);
+    assert_eq!(tip.parent_ids().collect::<Vec<_>>(), vec![old_main]);
+    assert_eq!(tip.message(), Some("squashed"));
+    let tree = tip.tree().unwrap();
+    assert!(tree.get_name("a.txt").is_some() && tree.get_name("b.txt").is_some());
+}
+
+#[test]
+fn squash_commit_note_is_visible_on_the_target_branch() {
+    let td = TempDir::new().unwrap();
+    let (s, repo_path, worktree_path) = setup_feature_worktree(&td, false);
+    let repo = Repository::open(&repo_path).unwrap();
+    let old_main = branch_tip(&repo, "main").id().to_string();
+
+    let sha = s
+        .merge_changes_with_strategy(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squashed",
+            MergeStrategy::Squash,
+        )
+        .unwrap();
+    let note = CommitNote {
+        task_id: Uuid::new_v4(),
+        attempt_id: Uuid::new_v4(),
+        executor: "CLAUDE_CODE".to_string(),
+    };
+    s.write_note(&repo_path, &sha, &note).unwrap();
+
+    // The squash commit only exists on main, and its note is read back from there
+    let commits = s
+        .list_commits_between(&repo_path, &old_main, "main")
+        .unwrap();
+    assert_eq!(commits.len(), 1);
+    assert_eq!(commits[0].oid, sha);
+    assert_eq!(commits[0].note, Some(note));
+}
+
+#[test]
+fn merge_strategy_merge_creates_merge_commit() {
+    let td = TempDir::new().unwrap();
+    let (s, repo_path, worktree_path) = setup_feature_worktree(&td, false);
+    let repo = Repository::open(&repo_path).unwrap();
+    let old_main = branch_tip(&repo, "main").id();
+    let feature_tip = branch_tip(&repo, "feature").id();
+
+    let sha = s
+        .merge_changes_with_strategy(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "merged",
+            MergeStrategy::Merge,
+        )
+        .unwrap();
+
+    let tip = branch_tip(&repo, "main");
+    assert_eq!(tip.id().to_string(), sha);
+    assert_eq!(
+        tip.parent_ids().collect::<Vec<_>>(),
+        vec![old_main, feature_tip]
+    );
+    assert_eq!(tip.message(), Some("merged"));
+}
+
+#[test]
+fn merge_strategy_rebase_produces_linear_history() {
+    let td = TempDir::new().unwrap();
+    let (s, repo_path, worktree_path) = setup_feature_worktree(&td, true);
+    let repo = Repository::open(&repo_path).unwrap();
+    let old_main = branch_tip(&repo, "main").id();
+
+    let sha = s
+        .merge_changes_with_strategy(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "unused",
+            MergeStrategy::Rebase,
+        )
+        .unwrap();
+
+    // main was fast-forwarded to the rebased feature branch
+    let tip = branch_tip(&repo, "main");
+    assert_eq!(tip.id().to_string(), sha);
+    assert_eq!(branch_tip(&repo, "feature").id(), tip.id());
+
+    // No merge commits, and the feature commits sit on top of the old main tip
+    let mut walk = repo.revwalk().unwrap();
+    walk.push(tip.id()).unwrap();
+    let history: Vec<_> = walk
+        .map(|oid| repo.find_commit(oid.unwrap()).unwrap())
+        .collect();
+    assert!(history.iter().all(|c| c.parent_count() <= 1));
+    let summaries: Vec<_> = history
+        .iter()
+        .take(3)
+        .map(|c| c.summary().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        summaries,
+        vec!["add b.txt", "add a.txt", "main moves ahead"]
+    );
+    assert_eq!(history[2].id(), old_main);
+}
+
+#[test]
+fn merge_dry_run_reports_conflicts_without_writing() {
+    let td = TempDir::new().unwrap();
+    let (s, repo_path, _worktree_path) = setup_feature_worktree(&td, false);
+    assert!(
+        s.merge_changes_dry_run(&repo_path, "feature", "main", MergeStrategy::Squash)
+            .unwrap()
+            .is_empty()
+    );
+
+    // main now edits a file the feature branch also adds
+    checkout_branch(&repo_path, "main");
+    write_file(&repo_path, "a.txt", "main version\n");
+    assert!(s.commit(&repo_path, "main adds a.txt").unwrap());
+    checkout_branch(&repo_path, "dev");
+
+    let repo = Repository::open(&repo_path).unwrap();
+    let main_before = branch_tip(&repo, "main").id();
+    let feature_before = branch_tip(&repo, "feature").id();
+
+    assert!(matches!(
+        s.merge_changes_dry_run(&repo_path, "feature", "main", MergeStrategy::Squash),
+        Err(GitServiceError::BranchesDiverged(_))
+    ));
+    let conflicts = s
+        .merge_changes_dry_run(&repo_path, "feature", "main", MergeStrategy::Rebase)
+        .unwrap();
+    assert_eq!(conflicts, vec!["a.txt".to_string()]);
+
+    assert_eq!(branch_tip(&repo, "main").id(), main_before);
+    assert_eq!(branch_tip(&repo, "feature").id(), feature_before);
+    assert!(!repo_path.join(".git").join("MERGE_HEAD").exists());
+}
+
+#[test]
+fn conflicting_paths_previews_a_new_target_branch() {
+    let td = TempDir::new().unwrap();
+    let (s, repo_path, _worktree_path) = setup_feature_worktree(&td, false);
+
+    // develop branches off main and edits a file the feature branch also adds
+    create_branch(&repo_path, "develop");
+    checkout_branch(&repo_path, "develop");
+    write_file(&repo_path, "a.txt", "develop version\n");
+    assert!(s.commit(&repo_path, "develop adds a.txt").unwrap());
+    checkout_branch(&repo_path, "dev");
+
+    let repo = Repository::open(&repo_path).unwrap();
+    let feature_before = branch_tip(&repo, "feature").id();
+
+    assert!(
+        s.conflicting_paths(&repo_path, "feature", "main")
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        s.conflicting_paths(&repo_path, "feature", "develop")
+            .unwrap(),
+        vec!["a.txt".to_string()]
+    );
+    assert_eq!(branch_tip(&repo, "feature").id(), feature_before);
+}
+
+#[test]
+fn reset_branch_to_commit_mixed_keeps_changes_unstaged() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    write_file(&repo_path, "a.txt", "one\n");
+    assert!(s.commit(&repo_path, "first").unwrap());
+    let first = s.get_head_info(&repo_path).unwrap().oid;
+    write_file(&repo_path, "a.txt", "two\n");
+    write_file(&repo_path, "b.txt", "new\n");
+    assert!(s.commit(&repo_path, "second").unwrap());
+
+    let head = s
+        .reset_branch_to_commit(&repo_path, &first, ResetMode::Mixed, false)
+        .unwrap();
+    assert_eq!(head, first);
+    assert_eq!(s.get_head_info(&repo_path).unwrap().oid, first);
+
+    // Working tree keeps the later content, but nothing is staged
+    assert_eq!(
+        fs::read_to_string(repo_path.join("a.txt")).unwrap(),
+        "two\n"
+    );
+    let repo = Repository::open(&repo_path).unwrap();
+    let a = repo.status_file(Path::new("a.txt")).unwrap();
+    assert!(a.contains(git2::Status::WT_MODIFIED));
+    assert!(!a.intersects(git2::Status::INDEX_MODIFIED));
+    let b = repo.status_file(Path::new("b.txt")).unwrap();
+    assert!(b.contains(git2::Status::WT_NEW));
+}
+
+#[test]
+fn reset_branch_to_commit_rejects_non_ancestor_and_dirty_hard_reset() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    write_file(&repo_path, "a.txt", "one\n");
+    assert!(s.commit(&repo_path, "first").unwrap());
+    let first = s.get_head_info(&repo_path).unwrap().oid;
+
+    // A commit only reachable from another branch is not an ancestor of HEAD
+    create_branch(&repo_path, "other");
+    checkout_branch(&repo_path, "other");
+    write_file(&repo_path, "other.txt", "x\n");
+    assert!(s.commit(&repo_path, "other").unwrap());
+    let other = s.get_head_info(&repo_path).unwrap().oid;
+    checkout_branch(&repo_path, "main");
+
+    let err = s
+        .reset_branch_to_commit(&repo_path, &other, ResetMode::Soft, false)
+        .unwrap_err();
+    assert!(matches!(err, GitServiceError::CommitNotOnBranch(_)));
+
+    write_file(&repo_path, "a.txt", "dirty\n");
+    let err = s
+        .reset_branch_to_commit(&repo_path, &first, ResetMode::Hard, false)
+        .unwrap_err();
+    assert!(matches!(err, GitServiceError::WorktreeDirty(_, _)));
+    s.reset_branch_to_commit(&repo_path, &first, ResetMode::Hard, true)
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(repo_path.join("a.txt")).unwrap(),
+        "one\n"
+    );
+}
+
+#[test]
+fn adopted_branch_history_drives_diff_and_branch_status() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    let fork_point = s.get_head_info(&repo_path).unwrap().oid;
+
+    // Work started by hand on a branch outside vibe-kanban
+    create_branch(&repo_path, "manual-work");
+    checkout_branch(&repo_path, "manual-work");
+    write_file(&repo_path, "one.txt", "one\n");
+    assert!(s.commit(&repo_path, "first manual commit").unwrap());
+    write_file(&repo_path, "two.txt", "two\n");
+    assert!(s.commit(&repo_path, "second manual commit").unwrap());
+    let manual_tip = s.get_head_info(&repo_path).unwrap().oid;
+    checkout_branch(&repo_path, "main");
+    write_file(&repo_path, "main.txt", "main\n");
+    assert!(s.commit(&repo_path, "main moves ahead").unwrap());
+
+    let (base_commit, head_commit) = s
+        .branch_commit_range(&repo_path, "manual-work", "main")
+        .unwrap();
+    assert_eq!(base_commit.to_string(), fork_point);
+    assert_eq!(head_commit.to_string(), manual_tip);
+
+    // Adoption checks the existing branch out without creating a new one
+    let worktree_path = td.path().join("wt_adopted");
+    s.add_worktree(&repo_path, &worktree_path, "manual-work", false)
+        .unwrap();
+    assert_eq!(s.get_head_info(&worktree_path).unwrap().oid, manual_tip);
+
+    assert_eq!(
+        s.get_branch_status(&repo_path, "manual-work", "main")
+            .unwrap(),
+        (2, 1)
+    );
+    let diffs = s
+        .get_diffs(
+            DiffTarget::Worktree {
+                worktree_path: &worktree_path,
+                base_commit: &base_commit,
+            },
+            None,
+        )
+        .unwrap();
+    let mut paths: Vec<_> = diffs.iter().filter_map(|d| d.new_path.clone()).collect();
+    paths.sort();
+    assert_eq!(paths, vec!["one.txt".to_string(), "two.txt".to_string()]);
+
+    assert!(matches!(
+        s.branch_commit_range(&repo_path, "no-such-branch", "main"),
+        Err(GitServiceError::BranchNotFound(_))
+    ));
+}
@@ -47,7 +47,7 @@ pub enum DiffChangeKind {
 
 /// Converts a replace diff to a list of unified diff hunks.
 /// Uses a context limit of 3 lines.
-fn create_unified_diff_hunks(old: &str, new: &str) -> Vec<String> {
+pub fn create_unified_diff_hunks(old: &str, new: &str) -> Vec<String> {
     let old = ensure_newline(old);
     let new = ensure_newline(new);
 
@@ -1,4 +1,5 @@
 use axum::{extract::ws::Message, response::sse::Event};
+use futures::{Stream, StreamExt, future};
 use json_patch::Patch;
 use serde::{Deserialize, Serialize};
 
@@ -77,3 +78,88 @@ impl LogMsg {
         }
     }
 }
+
+/// Map a log stream to SSE events, ending the stream once `Finished` has been sent.
+pub fn sse_until_finished<S>(stream: S) -> impl Stream<Item = Result<Event, std::io::Error>>
+where
+    S: Stream<Item = Result<LogMsg, std::io::Error>>,
+{
+    stream.scan(false, |finished, item| {
+        if *finished {
+            return future::ready(None);
+        }
+        if matches!(item, Ok(LogMsg::Finished)) {
+            *finished = true;
+        }
+        future::ready(Some(item.map(|msg| msg.to_sse_event())))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::response::{IntoResponse, Sse};
+    use json_patch::Patch;
+    use serde_json::json;
+
+    use super::*;
+
+    fn sample_messages() -> Vec<LogMsg> {
+        let patch: Patch = serde_json::from_value(json!([
+            { "op": "add", "path": "/entries/0", "value": { "type": "STDOUT", "content": "hi" } }
+        ]))
+        .unwrap();
+        vec![
+            LogMsg::JsonPatch(patch.clone()),
+            LogMsg::JsonPatch(patch),
+            LogMsg::Finished,
+            // Anything after Finished must not be delivered
+            LogMsg::Stdout("late".to_string()),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_sse_stream_matches_ws_and_closes_on_finished() {
+        let messages = sample_messages();
+
+        let ws_patches: Vec<serde_json::Value> = messages
+            .iter()
+            .take_while(|m| !matches!(m, LogMsg::Finished))
+            .map(|m| match m.to_ws_message_unchecked() {
+                Message::Text(text) => {
+                    let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+                    value["JsonPatch"].clone()
+                }
+                other => panic!("unexpected ws message {other:?}"),
+            })
+            .collect();
+
+        let stream = futures::stream::iter(messages.into_iter().map(Ok));
+        let response = Sse::new(sse_until_finished(stream)).into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+
+        let mut sse_patches = Vec::new();
+        let mut events = Vec::new();
+        for block in body.split("\n\n").filter(|b| !b.trim().is_empty()) {
+            let mut event = "";
+            let mut data = "";
+            for line in block.lines() {
+                if let Some(v) = line.strip_prefix("event: ") {
+                    event = v;
+                } else if let Some(v) = line.strip_prefix("data: ") {
+                    data = v;
+                }
+            }
+            events.push(event.to_string());
+            if event == EV_JSON_PATCH {
+                sse_patches.push(serde_json::from_str::<serde_json::Value>(data).unwrap());
+            }
+        }
+
+        assert_eq!(sse_patches, ws_patches);
+        assert_eq!(events.last().map(String::as_str), Some(EV_FINISHED));
+        assert!(!events.iter().any(|e| e == EV_STDOUT));
+    }
+}
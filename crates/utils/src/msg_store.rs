@@ -1,12 +1,12 @@
 use std::{
     collections::VecDeque,
-    sync::{Arc, RwLock},
+    sync::{Arc, RwLock, atomic::AtomicUsize},
 };
 
 use axum::response::sse::Event;
 use futures::{StreamExt, TryStreamExt, future};
 use tokio::{sync::broadcast, task::JoinHandle};
-use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::{BroadcastStream, errors::BroadcastStreamRecvError};
 
 use crate::{log_msg::LogMsg, stream_lines::LinesStreamExt};
 
@@ -15,6 +15,7 @@ const HISTORY_BYTES: usize = 100000 * 1024;
 
 #[derive(Clone)]
 struct StoredMsg {
+    seq: u64,
     msg: LogMsg,
     bytes: usize,
 }
@@ -22,11 +23,17 @@ struct StoredMsg {
 struct Inner {
     history: VecDeque<StoredMsg>,
     total_bytes: usize,
+    /// Sequence number the next pushed message gets; never reused, even after
+    /// older messages are evicted from `history`
+    next_seq: u64,
 }
 
 pub struct MsgStore {
     inner: RwLock<Inner>,
     sender: broadcast::Sender<LogMsg>,
+    /// Next normalized-entry index, shared by everything that appends entries to
+    /// this store's conversation
+    entry_index: Arc<AtomicUsize>,
 }
 
 impl Default for MsgStore {
@@ -42,16 +49,22 @@ impl MsgStore {
             inner: RwLock::new(Inner {
                 history: VecDeque::with_capacity(32),
                 total_bytes: 0,
+                next_seq: 0,
             }),
             sender,
+            entry_index: Arc::new(AtomicUsize::new(0)),
         }
     }
 
     pub fn push(&self, msg: LogMsg) {
-        let _ = self.sender.send(msg.clone()); // live listeners
         let bytes = msg.approx_bytes();
 
+        // Broadcast under the lock so subscribers can line live messages up with
+        // the history snapshot they took (see `sequenced_history_plus_stream`)
         let mut inner = self.inner.write().unwrap();
+        let _ = self.sender.send(msg.clone()); // live listeners
+        let seq = inner.next_seq;
+        inner.next_seq += 1;
         while inner.total_bytes.saturating_add(bytes) > HISTORY_BYTES {
             if let Some(front) = inner.history.pop_front() {
                 inner.total_bytes = inner.total_bytes.saturating_sub(front.bytes);
@@ -59,7 +72,7 @@ impl MsgStore {
                 break;
             }
         }
-        inner.history.push_back(StoredMsg { msg, bytes });
+        inner.history.push_back(StoredMsg { seq, msg, bytes });
         inner.total_bytes = inner.total_bytes.saturating_add(bytes);
     }
 
@@ -83,6 +96,11 @@ impl MsgStore {
         self.push(LogMsg::Finished);
     }
 
+    /// Counter behind every `EntryIndexProvider` for this store
+    pub fn entry_index(&self) -> Arc<AtomicUsize> {
+        self.entry_index.clone()
+    }
+
     pub fn get_receiver(&self) -> broadcast::Receiver<LogMsg> {
         self.sender.subscribe()
     }
@@ -97,17 +115,58 @@ impl MsgStore {
             .collect()
     }
 
+    /// Retained history paired with each message's sequence number.
+    pub fn get_sequenced_history(&self) -> Vec<(u64, LogMsg)> {
+        self.inner
+            .read()
+            .unwrap()
+            .history
+            .iter()
+            .map(|s| (s.seq, s.msg.clone()))
+            .collect()
+    }
+
     /// History then live, as `LogMsg`.
     pub fn history_plus_stream(
         &self,
     ) -> futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>> {
-        let (history, rx) = (self.get_history(), self.get_receiver());
+        self.sequenced_history_plus_stream()
+            .map_ok(|(_, msg)| msg)
+            .boxed()
+    }
+
+    /// History then live, with each message's sequence number. Messages dropped by
+    /// a lagging receiver still advance the sequence so numbers stay accurate.
+    pub fn sequenced_history_plus_stream(
+        &self,
+    ) -> futures::stream::BoxStream<'static, Result<(u64, LogMsg), std::io::Error>> {
+        let (history, rx, next_seq) = {
+            let inner = self.inner.read().unwrap();
+            let history: Vec<_> = inner
+                .history
+                .iter()
+                .map(|s| (s.seq, s.msg.clone()))
+                .collect();
+            (history, self.sender.subscribe(), inner.next_seq)
+        };
 
         let hist = futures::stream::iter(history.into_iter().map(Ok::<_, std::io::Error>));
-        let live = BroadcastStream::new(rx)
-            .filter_map(|res| async move { res.ok().map(Ok::<_, std::io::Error>) });
+        let live = BroadcastStream::new(rx).scan(next_seq, |next, res| {
+            let item = match res {
+                Ok(msg) => {
+                    let seq = *next;
+                    *next += 1;
+                    Some(Ok::<_, std::io::Error>((seq, msg)))
+                }
+                Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                    *next += skipped;
+                    None
+                }
+            };
+            future::ready(Some(item))
+        });
 
-        Box::pin(hist.chain(live))
+        Box::pin(hist.chain(live.filter_map(future::ready)))
     }
 
     pub fn stdout_chunked_stream(
@@ -175,3 +234,32 @@ impl MsgStore {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sequence_continues_from_history_into_live() {
+        let store = MsgStore::new();
+        store.push_stdout("a");
+        store.push_stderr("b");
+
+        let mut stream = store.sequenced_history_plus_stream();
+        store.push_stdout("c");
+        store.push_finished();
+
+        let mut seqs = Vec::new();
+        while let Some(Ok((seq, msg))) = stream.next().await {
+            seqs.push(seq);
+            if matches!(msg, LogMsg::Finished) {
+                break;
+            }
+        }
+        assert_eq!(seqs, vec![0, 1, 2, 3]);
+        assert_eq!(
+            store.get_sequenced_history().last().map(|(seq, _)| *seq),
+            Some(3)
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use regex::Regex;
 use uuid::Uuid;
 
@@ -40,6 +42,121 @@ pub fn truncate_to_char_boundary(content: &str, max_len: usize) -> &str {
     &content[..cutoff]
 }
 
+/// Substitute `{name}` and `{{name}}` placeholders in `template` with entries
+/// from `values`. On failure returns the names of placeholders with no value, deduplicated in
+/// order of first appearance.
+pub fn render_placeholders(
+    template: &str,
+    values: &HashMap<String, String>,
+) -> Result<String, Vec<String>> {
+    let re =
+        Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}|\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap();
+    let mut missing: Vec<String> = Vec::new();
+    let rendered = re.replace_all(template, |caps: &regex::Captures| {
+        let name = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+        match values.get(name) {
+            Some(value) => value.clone(),
+            None => {
+                if !missing.iter().any(|m| m == name) {
+                    missing.push(name.to_string());
+                }
+                caps[0].to_string()
+            }
+        }
+    });
+    if missing.is_empty() {
+        Ok(rendered.into_owned())
+    } else {
+        Err(missing)
+    }
+}
+
+/// Rewrite every line ending in `content` as LF, or CRLF when `crlf` is set.
+/// Returns `None` for content that looks binary (contains a NUL byte).
+pub fn normalize_line_endings(content: &[u8], crlf: bool) -> Option<Vec<u8>> {
+    if content.contains(&0) {
+        return None;
+    }
+    let mut out = Vec::with_capacity(content.len());
+    let mut bytes = content.iter().copied().peekable();
+    while let Some(b) = bytes.next() {
+        match b {
+            b'\r' if bytes.peek() == Some(&b'\n') => {}
+            b'\n' if crlf => out.extend_from_slice(b"\r\n"),
+            _ => out.push(b),
+        }
+    }
+    Some(out)
+}
+
+/// Replace every occurrence of the non-empty `secrets` in `content` with `[REDACTED]`.
+pub fn redact_secrets(content: &str, secrets: &[String]) -> String {
+    let mut secrets: Vec<&str> = secrets
+        .iter()
+        .map(String::as_str)
+        .filter(|s| !s.is_empty())
+        .collect();
+    // Longer secrets first so one containing another is fully masked
+    secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    secrets
+        .into_iter()
+        .fold(content.to_string(), |acc, secret| {
+            acc.replace(secret, "[REDACTED]")
+        })
+}
+
+/// Masks secrets in output that arrives in chunks, holding back just enough of
+/// the tail that a secret split across two chunks is still caught.
+pub struct SecretRedactor {
+    secrets: Vec<String>,
+    pending: String,
+}
+
+impl SecretRedactor {
+    pub fn new(secrets: &[String]) -> Self {
+        let mut secrets: Vec<String> = secrets.iter().filter(|s| !s.is_empty()).cloned().collect();
+        secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        Self {
+            secrets,
+            pending: String::new(),
+        }
+    }
+
+    /// Redacted output that is safe to emit after `chunk`; may be empty.
+    pub fn push(&mut self, chunk: &str) -> String {
+        self.pending.push_str(chunk);
+        // A secret can only be cut off inside the last `longest - 1` bytes
+        let hold = self.secrets.first().map_or(0, |s| s.len() - 1);
+        let mut cut = self.pending.len().saturating_sub(hold);
+        while !self.pending.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        // Never split a secret that is already complete
+        let mut matches: Vec<(usize, usize)> = self
+            .secrets
+            .iter()
+            .flat_map(|secret| {
+                self.pending
+                    .match_indices(secret.as_str())
+                    .map(|(start, m)| (start, start + m.len()))
+            })
+            .collect();
+        matches.sort_unstable();
+        for (start, end) in matches {
+            if start < cut {
+                cut = cut.max(end);
+            }
+        }
+        let ready: String = self.pending.drain(..cut).collect();
+        redact_secrets(&ready, &self.secrets)
+    }
+
+    /// Redacted remainder once the output has ended.
+    pub fn finish(&mut self) -> String {
+        redact_secrets(&std::mem::take(&mut self.pending), &self.secrets)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -57,4 +174,79 @@ mod tests {
         assert_eq!(truncate_to_char_boundary(input, 5), "🔥");
         assert_eq!(truncate_to_char_boundary(input, 3), "");
     }
+
+    #[test]
+    fn test_render_placeholders() {
+        use std::collections::HashMap;
+
+        use super::render_placeholders;
+
+        let values = HashMap::from([("area".to_string(), "auth".to_string())]);
+        assert_eq!(
+            render_placeholders("Fix {area} bug in {area}", &values).unwrap(),
+            "Fix auth bug in auth"
+        );
+        assert_eq!(
+            render_placeholders("{area}: {ticket} / {ticket} {owner}", &values).unwrap_err(),
+            vec!["ticket".to_string(), "owner".to_string()]
+        );
+        assert_eq!(
+            render_placeholders("no placeholders, {} or { x }", &values).unwrap(),
+            "no placeholders, {} or { x }"
+        );
+        assert_eq!(
+            render_placeholders("{{area}}: {{ area }} and {area}", &values).unwrap(),
+            "auth: auth and auth"
+        );
+        assert_eq!(
+            render_placeholders("{{summary}}", &values).unwrap_err(),
+            vec!["summary".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_normalize_line_endings() {
+        use super::normalize_line_endings;
+
+        let mixed = b"a\r\nb\nc\rd\r\n";
+        assert_eq!(
+            normalize_line_endings(mixed, false).unwrap(),
+            b"a\nb\nc\rd\n"
+        );
+        assert_eq!(
+            normalize_line_endings(mixed, true).unwrap(),
+            b"a\r\nb\r\nc\rd\r\n"
+        );
+        assert_eq!(normalize_line_endings(b"\0\r\n", false), None);
+    }
+
+    #[test]
+    fn test_redact_secrets() {
+        use super::redact_secrets;
+
+        let secrets = vec!["sk-1".to_string(), "sk-123".to_string(), String::new()];
+        assert_eq!(
+            redact_secrets("key=sk-123 other=sk-1", &secrets),
+            "key=[REDACTED] other=[REDACTED]"
+        );
+        assert_eq!(redact_secrets("nothing here", &secrets), "nothing here");
+    }
+
+    #[test]
+    fn test_secret_redactor_masks_secrets_split_across_chunks() {
+        use super::SecretRedactor;
+
+        let secrets = vec!["sk-1".to_string(), "sk-123".to_string()];
+        let mut redactor = SecretRedactor::new(&secrets);
+        let mut out = String::new();
+        for chunk in ["token=s", "k-1", "23 and sk-", "1 done", " sk-12"] {
+            out.push_str(&redactor.push(chunk));
+        }
+        out.push_str(&redactor.finish());
+        assert_eq!(out, "token=[REDACTED] and [REDACTED] done sk-12");
+
+        let mut passthrough = SecretRedactor::new(&[]);
+        assert_eq!(passthrough.push("no secrets"), "no secrets");
+        assert_eq!(passthrough.finish(), "");
+    }
 }
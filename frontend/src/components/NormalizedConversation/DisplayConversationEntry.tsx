@@ -10,7 +10,10 @@ import {
   type TaskWithAttemptStatus,
   type JsonValue,
 } from 'shared/types.ts';
-import type { ProcessStartPayload } from '@/types/logs';
+import type {
+  NextActionEntryType,
+  ProcessStartPayload,
+} from '@/types/logs';
 import FileChangeRenderer from './FileChangeRenderer';
 import { useExpandable } from '@/stores/useExpandableStore';
 import {
@@ -811,6 +814,7 @@ const DisplayConversationEntry = memo(function DisplayConversationEntry({
           execution_processes={entry.entry_type.execution_processes}
           task={task}
           needsSetup={entry.entry_type.needs_setup}
+          needsReauth={(entry.entry_type as NextActionEntryType).needs_reauth}
         />
       </div>
     );
@@ -46,6 +46,7 @@ type NextActionCardProps = {
   execution_processes: number;
   task?: TaskWithAttemptStatus;
   needsSetup?: boolean;
+  needsReauth?: boolean;
 };
 
 export function NextActionCard({
@@ -56,6 +57,7 @@ export function NextActionCard({
   execution_processes,
   task,
   needsSetup,
+  needsReauth,
 }: NextActionCardProps) {
   const { t } = useTranslation('tasks');
   const { config } = useUserSystem();
@@ -156,7 +158,9 @@ export function NextActionCard({
   );
 
   const setupHelpText = canAutoSetup
-    ? t('attempt.setupHelpText', { agent: attempt?.executor })
+    ? needsReauth
+      ? t('attempt.reauthHelpText', { agent: attempt?.executor })
+      : t('attempt.setupHelpText', { agent: attempt?.executor })
     : null;
 
   const editorName = getIdeName(config?.editor?.editor_type);
@@ -61,6 +61,18 @@ const ProjectFormDialogImpl = NiceModal.create<ProjectFormDialogProps>(() => {
       dev_script: null,
       cleanup_script: null,
       copy_files: null,
+      env_template: {},
+      merge_strategy: 'squash',
+      auto_push: false,
+      abort_on_output_pattern: null,
+      default_executor_profile_id: null,
+      commit_message_template: null,
+      branch_template: null,
+      mcp_servers: [],
+      worktree_sparse_patterns: null,
+      max_concurrent_executions: null,
+      dev_server_url_pattern: null,
+      script_language: null,
     };
 
     createProject.mutate(createData);
@@ -91,6 +103,18 @@ const ProjectFormDialogImpl = NiceModal.create<ProjectFormDialogProps>(() => {
       dev_script: null,
       cleanup_script: null,
       copy_files: null,
+      env_template: {},
+      merge_strategy: 'squash',
+      auto_push: false,
+      abort_on_output_pattern: null,
+      default_executor_profile_id: null,
+      commit_message_template: null,
+      branch_template: null,
+      mcp_servers: [],
+      worktree_sparse_patterns: null,
+      max_concurrent_executions: null,
+      dev_server_url_pattern: null,
+      script_language: null,
     };
 
     createProject.mutate(createData);
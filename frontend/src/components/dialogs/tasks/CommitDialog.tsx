@@ -166,10 +166,19 @@ const CommitDialogImpl = NiceModal.create<CommitDialogProps>(({ attemptId }) =>
     setCommitting(true);
 
     try {
-      await attemptsApi.commit(attemptId, {
+      const result = await attemptsApi.commit(attemptId, {
         files: Array.from(selectedFiles),
         message: commitMessage.trim(),
+        force_when_dirty: false,
       });
+      if (!result.success) {
+        setError(
+          result.error?.type === 'commit_blocked_by_conflict'
+            ? t('commit.dialog.errors.blockedByConflict')
+            : result.message || t('commit.dialog.errors.commitFailed')
+        );
+        return;
+      }
 
       // Invalidate branch status to reflect the new commit
       queryClient.invalidateQueries({ queryKey: ['branchStatus', attemptId] });
@@ -136,6 +136,7 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
         title: prTitle,
         body: prBody || null,
         target_branch: prBaseBranch || null,
+        body_from_conversation: false,
       });
 
       if (result.success) {
@@ -189,6 +190,18 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
           setError(result.message || t(gitCliErrorKey));
           setGhCliHelp(null);
           return;
+        } else if (
+          result.error.type === 'glab_cli_not_installed' ||
+          result.error.type === 'glab_cli_not_logged_in'
+        ) {
+          const glabCliErrorKey =
+            result.error.type === 'glab_cli_not_logged_in'
+              ? 'createPrDialog.errors.glabCliNotLoggedIn'
+              : 'createPrDialog.errors.glabCliNotInstalled';
+
+          setError(result.message || t(glabCliErrorKey));
+          setGhCliHelp(null);
+          return;
         } else if (result.error.type === 'target_branch_not_found') {
           setError(
             t('createPrDialog.errors.targetBranchNotFound', {
@@ -56,6 +56,7 @@ export function OrchestratorPanel({ projectId }: OrchestratorPanelProps) {
         last_attempt_failed: displayOrchestrator.latest_process?.status === 'failed',
         executor: 'CLAUDE_CODE',
         latest_task_attempt_id: displayOrchestrator.attempt.id,
+        labels: [],
       }
     : null;
 
@@ -12,6 +12,8 @@ import {
 } from '@/components/ui/dropdown-menu.tsx';
 import { Button } from '@/components/ui/button.tsx';
 import {
+  Archive,
+  ArchiveRestore,
   Calendar,
   Edit,
   ExternalLink,
@@ -85,6 +87,20 @@ function ProjectCard({
     }
   };
 
+  const handleToggleArchived = async () => {
+    try {
+      if (project.archived) {
+        await projectsApi.unarchive(project.id);
+      } else {
+        await projectsApi.archive(project.id);
+      }
+      fetchProjects();
+    } catch (error) {
+      console.error('Failed to archive project:', error);
+      setError(t('archiveFailed'));
+    }
+  };
+
   const handleEdit = (project: ProjectWithTaskCounts) => {
     onEdit(project);
   };
@@ -182,6 +198,19 @@ function ProjectCard({
                   <Edit className="mr-2 h-4 w-4" />
                   {t('common:buttons.edit')}
                 </DropdownMenuItem>
+                <DropdownMenuItem
+                  onClick={(e) => {
+                    e.stopPropagation();
+                    handleToggleArchived();
+                  }}
+                >
+                  {project.archived ? (
+                    <ArchiveRestore className="mr-2 h-4 w-4" />
+                  ) : (
+                    <Archive className="mr-2 h-4 w-4" />
+                  )}
+                  {project.archived ? t('unarchive') : t('archive')}
+                </DropdownMenuItem>
                 <DropdownMenuItem
                   onClick={(e) => {
                     e.stopPropagation();
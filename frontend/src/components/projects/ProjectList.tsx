@@ -5,6 +5,8 @@ import { useTranslation } from 'react-i18next';
 import { Button } from '@/components/ui/button';
 import { Card, CardContent } from '@/components/ui/card';
 import { Alert, AlertDescription } from '@/components/ui/alert';
+import { Label } from '@/components/ui/label';
+import { Switch } from '@/components/ui/switch';
 import { ProjectWithTaskCounts } from 'shared/types';
 import { ProjectFormDialog } from '@/components/dialogs/projects/ProjectFormDialog';
 import { projectsApi } from '@/lib/api';
@@ -25,13 +27,14 @@ export function ProjectList() {
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState('');
   const [focusedProjectId, setFocusedProjectId] = useState<string | null>(null);
+  const [showArchived, setShowArchived] = useState(false);
 
   const fetchProjects = useCallback(async () => {
     setLoading(true);
     setError('');
 
     try {
-      const result = await projectsApi.getAll();
+      const result = await projectsApi.getAll(showArchived);
       setProjects(result);
     } catch (error) {
       console.error('Failed to fetch projects:', error);
@@ -39,7 +42,7 @@ export function ProjectList() {
     } finally {
       setLoading(false);
     }
-  }, [t]);
+  }, [t, showArchived]);
 
   const handleCreateProject = async () => {
     try {
@@ -79,10 +82,26 @@ export function ProjectList() {
           <h1 className="text-3xl font-bold tracking-tight">{t('title')}</h1>
           <p className="text-muted-foreground">{t('subtitle')}</p>
         </div>
-        <Button onClick={handleCreateProject}>
-          <Plus className="mr-2 h-4 w-4" />
-          {t('createProject')}
-        </Button>
+        <div className="flex items-center gap-4">
+          <div className="flex items-center gap-2">
+            <Switch
+              id="show-archived-switch"
+              checked={showArchived}
+              onCheckedChange={setShowArchived}
+              aria-label={t('showArchived')}
+            />
+            <Label
+              htmlFor="show-archived-switch"
+              className="text-sm cursor-pointer"
+            >
+              {t('showArchived')}
+            </Label>
+          </div>
+          <Button onClick={handleCreateProject}>
+            <Plus className="mr-2 h-4 w-4" />
+            {t('createProject')}
+          </Button>
+        </div>
       </div>
 
       {(error || hasTimedOut) && !isServerStarting && (
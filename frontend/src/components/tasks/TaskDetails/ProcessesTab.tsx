@@ -52,6 +52,8 @@ function ProcessesTab({ attemptId }: ProcessesTabProps) {
         return <AlertCircle className="h-4 w-4 text-destructive" />;
       case 'killed':
         return <Square className="h-4 w-4 text-gray-500" />;
+      case 'timedout':
+        return <Clock className="h-4 w-4 text-orange-500" />;
       default:
         return <Clock className="h-4 w-4 text-gray-400" />;
     }
@@ -67,6 +69,8 @@ function ProcessesTab({ attemptId }: ProcessesTabProps) {
         return 'bg-red-50 border-red-200 text-red-800';
       case 'killed':
         return 'bg-gray-50 border-gray-200 text-gray-800';
+      case 'timedout':
+        return 'bg-orange-50 border-orange-200 text-orange-800';
       default:
         return 'bg-gray-50 border-gray-200 text-gray-800';
     }
@@ -87,14 +87,8 @@ export function NoServerContent({
     updateProject.mutate(
       {
         projectId: project.id,
-        data: {
-          name: project.name,
-          git_repo_path: project.git_repo_path,
-          setup_script: project.setup_script ?? null,
-          dev_script: script,
-          cleanup_script: project.cleanup_script ?? null,
-          copy_files: project.copy_files ?? null,
-        },
+        // Send the project's current settings so only the dev script changes
+        data: { ...project, dev_script: script },
       },
       {
         onSuccess: () => {
@@ -190,11 +190,15 @@ export function TaskFollowUpSection({
       // Don't create empty scratch entries - only save if there's actual content,
       // a variant is selected, or scratch already exists (to allow clearing a draft)
       if (!message.trim() && !variant && !scratchRef.current) return;
+      // Keep any images already attached to the draft
+      const current = scratchRef.current?.payload;
+      const image_ids =
+        current?.type === 'DRAFT_FOLLOW_UP' ? current.data.image_ids : [];
       try {
         await updateScratch({
           payload: {
             type: 'DRAFT_FOLLOW_UP',
-            data: { message, variant },
+            data: { message, variant, image_ids },
           },
         });
       } catch (e) {
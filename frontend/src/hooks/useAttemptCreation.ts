@@ -38,6 +38,8 @@ export function useAttemptCreation({
         use_existing_branch: useExistingBranch ?? false,
         custom_branch: customBranch?.trim() || null,
         conversation_history: conversationHistory ?? null,
+        cleanup_script_override: null,
+        env_overrides: null,
       }),
     onSuccess: (newAttempt: TaskAttempt) => {
       queryClient.setQueryData(
@@ -6,10 +6,11 @@ import { useExecutionProcessesContext } from '@/contexts/ExecutionProcessesConte
 import type { AttemptData } from '@/lib/types';
 import { BaseCodingAgent, type ExecutionProcess } from 'shared/types';
 
-// Executors that support the compact command
-const COMPACT_SUPPORTED_EXECUTORS = new Set<BaseCodingAgent>([
-  BaseCodingAgent.CLAUDE_CODE,
-]);
+// Context compaction command for each executor that supports it
+const COMPACT_COMMANDS: Partial<Record<BaseCodingAgent, string>> = {
+  [BaseCodingAgent.CLAUDE_CODE]: '/compact',
+  [BaseCodingAgent.GEMINI]: '/compress',
+};
 
 // Helper to extract base executor from an execution process
 function getBaseExecutor(process: ExecutionProcess): BaseCodingAgent | null {
@@ -25,10 +26,15 @@ function getBaseExecutor(process: ExecutionProcess): BaseCodingAgent | null {
   return null;
 }
 
+// Get the compaction command for a process's executor, if it supports one
+function compactCommandFor(process: ExecutionProcess): string | null {
+  const executor = getBaseExecutor(process);
+  return (executor && COMPACT_COMMANDS[executor]) ?? null;
+}
+
 // Check if a process's executor supports compaction
 function supportsCompact(process: ExecutionProcess): boolean {
-  const executor = getBaseExecutor(process);
-  return executor !== null && COMPACT_SUPPORTED_EXECUTORS.has(executor);
+  return compactCommandFor(process) !== null;
 }
 
 export function useAttemptExecution(attemptId?: string, taskId?: string) {
@@ -104,17 +110,19 @@ export function useAttemptExecution(attemptId?: string, taskId?: string) {
     );
   }, [executionProcesses]);
 
-  // Check if the most recent coding agent process supports compact (for when not running)
-  const latestCodingAgentSupportsCompact = useMemo(() => {
+  // Compact command of the most recent coding agent process (for when not running)
+  const latestCodingAgentCompactCommand = useMemo(() => {
     // Find the most recent coding agent process
     const codingAgentProcesses = executionProcesses.filter(
       (p) => p.run_reason === 'codingagent'
     );
-    if (codingAgentProcesses.length === 0) return false;
+    if (codingAgentProcesses.length === 0) return null;
     // Last one is the most recent (processes are typically in chronological order)
     const latest = codingAgentProcesses[codingAgentProcesses.length - 1];
-    return supportsCompact(latest);
+    return compactCommandFor(latest);
   }, [executionProcesses]);
+  const latestCodingAgentSupportsCompact =
+    latestCodingAgentCompactCommand !== null;
 
   // Can compact if:
   // 1. There's a running coding agent that supports compact (send /compact to running process), OR
@@ -145,13 +153,15 @@ export function useAttemptExecution(attemptId?: string, taskId?: string) {
           setContextUsageResetVersion((version) => version + 1);
         }
       } else {
-        // If no running process, start a new follow-up with /compact as the prompt
+        // If no running process, start a new follow-up with the compact command as the prompt
         await attemptsApi.followUp(attemptId, {
-          prompt: '/compact',
+          prompt: latestCodingAgentCompactCommand ?? '/compact',
           variant: null,
           retry_process_id: null,
           force_when_dirty: null,
           perform_git_reset: null,
+          cleanup_script: null,
+          image_ids: null,
         });
         setContextUsageResetVersion((version) => version + 1);
       }
@@ -161,7 +171,12 @@ export function useAttemptExecution(attemptId?: string, taskId?: string) {
     } finally {
       setIsCompacting(false);
     }
-  }, [attemptId, runningCompactableAgent, isCompacting]);
+  }, [
+    attemptId,
+    runningCompactableAgent,
+    isCompacting,
+    latestCodingAgentCompactCommand,
+  ]);
 
   const isLoading =
     streamLoading || processDetailQueries.some((q) => q.isLoading);
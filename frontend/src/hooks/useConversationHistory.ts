@@ -12,6 +12,7 @@ import {
 import { useExecutionProcessesContext } from '@/contexts/ExecutionProcessesContext';
 import { useCallback, useEffect, useMemo, useRef } from 'react';
 import { streamJsonPatchEntries } from '@/utils/streamJsonPatchEntries';
+import type { NextActionEntryType } from '@/types/logs';
 
 // Module-level cache for historical execution process entries
 // This allows instant reopening of already-viewed executions
@@ -69,28 +70,34 @@ const nextActionPatch: (
   failed: boolean,
   execution_processes: number,
   needs_setup: boolean,
+  needs_reauth: boolean,
   setup_help_text?: string
 ) => PatchTypeWithKey = (
   failed,
   execution_processes,
   needs_setup,
+  needs_reauth,
   setup_help_text
-) => ({
-  type: 'NORMALIZED_ENTRY',
-  content: {
-    entry_type: {
-      type: 'next_action',
-      failed: failed,
-      execution_processes: execution_processes,
-      needs_setup: needs_setup,
-      setup_help_text: setup_help_text ?? null,
+) => {
+  const entry_type: NextActionEntryType = {
+    type: 'next_action',
+    failed: failed,
+    execution_processes: execution_processes,
+    needs_setup: needs_setup,
+    needs_reauth: needs_reauth,
+    setup_help_text: setup_help_text ?? null,
+  };
+  return {
+    type: 'NORMALIZED_ENTRY',
+    content: {
+      entry_type,
+      content: '',
+      timestamp: null,
     },
-    content: '',
-    timestamp: null,
-  },
-  patchKey: 'next_action',
-  executionProcessId: '',
-});
+    patchKey: 'next_action',
+    executionProcessId: '',
+  };
+};
 
 export const useConversationHistory = ({
   attempt,
@@ -203,6 +210,7 @@ export const useConversationHistory = ({
       let hasRunningProcess = false;
       let lastProcessFailedOrKilled = false;
       let needsSetup = false;
+      let needsReauth = false;
       let setupHelpText: string | undefined;
 
       // Create user messages + tool calls for setup/cleanup scripts
@@ -274,7 +282,8 @@ export const useConversationHistory = ({
               liveProcessStatus === ExecutionProcessStatus.running;
             const processFailedOrKilled =
               liveProcessStatus === ExecutionProcessStatus.failed ||
-              liveProcessStatus === ExecutionProcessStatus.killed;
+              liveProcessStatus === ExecutionProcessStatus.killed ||
+              liveProcessStatus === ExecutionProcessStatus.timedout;
 
             if (isProcessRunning) {
               hasRunningProcess = true;
@@ -286,14 +295,19 @@ export const useConversationHistory = ({
             ) {
               lastProcessFailedOrKilled = true;
 
-              // Check if this failed process has a SetupRequired entry
+              // Check if this failed process has a SetupRequired or AuthRequired entry
               const hasSetupRequired = entriesExcludingUser.some((entry) => {
                 if (entry.type !== 'NORMALIZED_ENTRY') return false;
+                if (entry.content.entry_type.type !== 'error_message') {
+                  return false;
+                }
+                const errorType = entry.content.entry_type.error_type.type;
                 if (
-                  entry.content.entry_type.type === 'error_message' &&
-                  entry.content.entry_type.error_type.type === 'setup_required'
+                  errorType === 'setup_required' ||
+                  errorType === 'auth_required'
                 ) {
                   setupHelpText = entry.content.content;
+                  needsReauth = errorType === 'auth_required';
                   return true;
                 }
                 return false;
@@ -336,7 +350,8 @@ export const useConversationHistory = ({
 
             if (
               (executionProcess?.status === ExecutionProcessStatus.failed ||
-                executionProcess?.status === ExecutionProcessStatus.killed) &&
+                executionProcess?.status === ExecutionProcessStatus.killed ||
+                executionProcess?.status === ExecutionProcessStatus.timedout) &&
               index === Object.keys(executionProcessState).length - 1
             ) {
               lastProcessFailedOrKilled = true;
@@ -400,6 +415,7 @@ export const useConversationHistory = ({
             lastProcessFailedOrKilled,
             Object.keys(executionProcessState).length,
             needsSetup,
+            needsReauth,
             setupHelpText
           )
         );
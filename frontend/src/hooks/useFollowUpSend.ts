@@ -49,6 +49,8 @@ export function useFollowUpSend({
         retry_process_id: null,
         force_when_dirty: null,
         perform_git_reset: null,
+        cleanup_script: null,
+        image_ids: null,
       };
       await attemptsApi.followUp(attemptId, body);
       clearComments();
@@ -67,6 +67,10 @@ export function useGitOperations(
         }
         return;
       }
+      if (errorData?.type === 'remote_not_found') {
+        setError(`Remote '${errorData.remote}' not found`);
+        return;
+      }
 
       const message =
         err && typeof err === 'object' && 'message' in err
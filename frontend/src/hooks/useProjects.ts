@@ -2,10 +2,13 @@ import { useQuery } from '@tanstack/react-query';
 import { projectsApi } from '@/lib/api';
 import type { Project } from 'shared/types';
 
-export function useProjects() {
+export function useProjects({ includeArchived = false } = {}) {
   return useQuery<Project[]>({
-    queryKey: ['projects'],
-    queryFn: () => projectsApi.getAll(),
+    // Mutations update the default list under ['projects'] in place
+    queryKey: includeArchived
+      ? ['projects', { includeArchived }]
+      : ['projects'],
+    queryFn: () => projectsApi.getAll(includeArchived),
     staleTime: 30000, // Consider data fresh for 30 seconds
   });
 }
@@ -57,6 +57,8 @@ export function useRetryProcess(
         retry_process_id: executionProcessId,
         force_when_dirty: modalResult.forceWhenDirty ?? false,
         perform_git_reset: modalResult.performGitReset ?? true,
+        cleanup_script: null,
+        image_ids: null,
       });
     },
     onSuccess: () => {
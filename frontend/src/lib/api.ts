@@ -3,15 +3,18 @@
 import {
   ApprovalStatus,
   ApiResponse,
+  BranchAheadBehind,
   BranchStatus,
   BranchWorktreeStatus,
   Config,
+  ConfigVersionInfo,
   CreateFollowUpAttempt,
   EditorType,
   CreateGitHubPrRequest,
   CreateTask,
   CreateAndStartTaskRequest,
   CreateTaskAttemptBody,
+  CreateTaskAttemptBatchBody,
   CreateTag,
   DirectoryListResponse,
   DirectoryEntry,
@@ -24,6 +27,9 @@ import {
   ShareTaskResponse,
   Task,
   TaskAttempt,
+  TaskAttemptBatchResult,
+  CloneTaskAttemptBody,
+  DevServerInfo,
   TaskRelationships,
   Tag,
   TagSearchParams,
@@ -44,6 +50,8 @@ import {
   ChangeTargetBranchResponse,
   RenameBranchRequest,
   RenameBranchResponse,
+  ResolveConflictRequest,
+  ResolveConflictResponse,
   CheckEditorAvailabilityResponse,
   AvailabilityInfo,
   BaseCodingAgent,
@@ -68,6 +76,21 @@ import {
   RemoteProject,
   ListInvitationsResponse,
   CommitCompareResult,
+  CommitInfo,
+  ProjectWorktreeSize,
+  StopAllProcessesResponse,
+  ProjectActivityPage,
+  TaskDependencies,
+  TaskSearchQuery,
+  AddTaskDependencyRequest,
+  TaskLabel,
+  AddTaskLabelRequest,
+  UpdateTaskLabelRequest,
+  SendInputRequest,
+  WorktreeSize,
+  Diff,
+  ExecutionSlots,
+  FileDiff,
   OpenEditorResponse,
   OpenEditorRequest,
   CreatePrError,
@@ -78,10 +101,19 @@ import {
   PushError,
   QueueStatus,
   CommitChangesRequest,
+  CommitChangesError,
   WorktreeStatusResponse,
   ExportResult,
+  JsonExportResult,
+  ProjectIndexStatus,
+  WarmFileSearchRequest,
+  WarmTarget,
   GenerateCommitMessageResponse,
   GenerateCommitMessageError,
+  MergeStrategy,
+  MergeTaskAttemptRequest,
+  PushTaskAttemptRequest,
+  RemoteSyncState,
 } from 'shared/types';
 
 class ApiError<E = unknown> extends Error {
@@ -237,8 +269,9 @@ export const healthApi = {
 
 // Project Management APIs
 export const projectsApi = {
-  getAll: async (): Promise<ProjectWithTaskCounts[]> => {
-    const response = await makeRequest('/api/projects');
+  getAll: async (includeArchived = false): Promise<ProjectWithTaskCounts[]> => {
+    const query = includeArchived ? '?include_archived=true' : '';
+    const response = await makeRequest(`/api/projects${query}`);
     return handleApiResponse<ProjectWithTaskCounts[]>(response);
   },
 
@@ -279,6 +312,20 @@ export const projectsApi = {
     return handleApiResponse<void>(response);
   },
 
+  archive: async (id: string): Promise<Project> => {
+    const response = await makeRequest(`/api/projects/${id}/archive`, {
+      method: 'POST',
+    });
+    return handleApiResponse<Project>(response);
+  },
+
+  unarchive: async (id: string): Promise<Project> => {
+    const response = await makeRequest(`/api/projects/${id}/unarchive`, {
+      method: 'POST',
+    });
+    return handleApiResponse<Project>(response);
+  },
+
   openEditor: async (
     id: string,
     data: OpenEditorRequest
@@ -295,6 +342,66 @@ export const projectsApi = {
     return handleApiResponse<GitBranch[]>(response);
   },
 
+  getWorktreeSize: async (id: string): Promise<ProjectWorktreeSize> => {
+    const response = await makeRequest(`/api/projects/${id}/worktree-size`);
+    return handleApiResponse<ProjectWorktreeSize>(response);
+  },
+
+  stopAll: async (id: string): Promise<StopAllProcessesResponse> => {
+    const response = await makeRequest(`/api/projects/${id}/stop-all`, {
+      method: 'POST',
+    });
+    return handleApiResponse<StopAllProcessesResponse>(response);
+  },
+
+  getActivity: async (
+    id: string,
+    before?: string,
+    limit?: number
+  ): Promise<ProjectActivityPage> => {
+    const params = new URLSearchParams();
+    if (before) {
+      params.set('before', before);
+    }
+    if (limit !== undefined) {
+      params.set('limit', String(limit));
+    }
+    const response = await makeRequest(
+      `/api/projects/${id}/activity?${params.toString()}`
+    );
+    return handleApiResponse<ProjectActivityPage>(response);
+  },
+
+  getLabels: async (id: string): Promise<TaskLabel[]> => {
+    const response = await makeRequest(`/api/projects/${id}/labels`);
+    return handleApiResponse<TaskLabel[]>(response);
+  },
+
+  updateLabel: async (
+    id: string,
+    labelId: string,
+    data: UpdateTaskLabelRequest
+  ): Promise<TaskLabel> => {
+    const response = await makeRequest(
+      `/api/projects/${id}/labels/${labelId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<TaskLabel>(response);
+  },
+
+  deleteLabel: async (id: string, labelId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${id}/labels/${labelId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
   checkBranchInWorktree: async (
     id: string,
     branch: string
@@ -399,6 +506,77 @@ export const tasksApi = {
     return handleApiResponse<ShareTaskResponse>(response);
   },
 
+  search: async (query: TaskSearchQuery): Promise<Task[]> => {
+    const params = new URLSearchParams({ q: query.q });
+    if (query.project_id) {
+      params.set('project_id', query.project_id);
+    }
+    if (query.status) {
+      params.set('status', query.status);
+    }
+    if (query.limit !== null) {
+      params.set('limit', String(query.limit));
+    }
+    const response = await makeRequest(
+      `/api/tasks/search?${params.toString()}`
+    );
+    return handleApiResponse<Task[]>(response);
+  },
+
+  getDependencies: async (taskId: string): Promise<TaskDependencies> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/dependencies`);
+    return handleApiResponse<TaskDependencies>(response);
+  },
+
+  addDependency: async (
+    taskId: string,
+    data: AddTaskDependencyRequest
+  ): Promise<TaskDependencies> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/dependencies`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskDependencies>(response);
+  },
+
+  removeDependency: async (
+    taskId: string,
+    blockedByTaskId: string
+  ): Promise<TaskDependencies> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/dependencies/${blockedByTaskId}`,
+      { method: 'DELETE' }
+    );
+    return handleApiResponse<TaskDependencies>(response);
+  },
+
+  getLabels: async (taskId: string): Promise<TaskLabel[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/labels`);
+    return handleApiResponse<TaskLabel[]>(response);
+  },
+
+  addLabel: async (
+    taskId: string,
+    data: AddTaskLabelRequest
+  ): Promise<TaskLabel[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/labels`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskLabel[]>(response);
+  },
+
+  removeLabel: async (
+    taskId: string,
+    labelId: string
+  ): Promise<TaskLabel[]> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/labels/${labelId}`,
+      { method: 'DELETE' }
+    );
+    return handleApiResponse<TaskLabel[]>(response);
+  },
+
   reassign: async (
     sharedTaskId: string,
     data: { new_assignee_user_id: string | null; version?: number | null }
@@ -425,6 +603,11 @@ export const tasksApi = {
     });
     return handleApiResponse<void>(response);
   },
+
+  getSyncStatus: async (): Promise<RemoteSyncState | null> => {
+    const response = await makeRequest('/api/shared-tasks/sync-status');
+    return handleApiResponse<RemoteSyncState | null>(response);
+  },
 };
 
 // Task Attempts APIs
@@ -454,6 +637,30 @@ export const attemptsApi = {
     return handleApiResponse<TaskAttempt>(response);
   },
 
+  createBatch: async (
+    data: CreateTaskAttemptBatchBody
+  ): Promise<TaskAttemptBatchResult[]> => {
+    const response = await makeRequest(`/api/task-attempts/batch`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskAttemptBatchResult[]>(response);
+  },
+
+  clone: async (
+    attemptId: string,
+    data: CloneTaskAttemptBody
+  ): Promise<TaskAttempt> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/clone`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<TaskAttempt>(response);
+  },
+
   stop: async (attemptId: string): Promise<void> => {
     const response = await makeRequest(`/api/task-attempts/${attemptId}/stop`, {
       method: 'POST',
@@ -510,6 +717,65 @@ export const attemptsApi = {
     return handleApiResponse<BranchStatus>(response);
   },
 
+  getAheadBehind: async (
+    attemptId: string,
+    branches: string[]
+  ): Promise<BranchAheadBehind[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/ahead-behind`,
+      {
+        method: 'POST',
+        body: JSON.stringify({ branches }),
+      }
+    );
+    return handleApiResponse<BranchAheadBehind[]>(response);
+  },
+
+  pinWorktree: async (attemptId: string): Promise<TaskAttempt> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/pin`, {
+      method: 'POST',
+    });
+    return handleApiResponse<TaskAttempt>(response);
+  },
+
+  unpinWorktree: async (attemptId: string): Promise<TaskAttempt> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/unpin`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<TaskAttempt>(response);
+  },
+
+  getWorktreeSize: async (attemptId: string): Promise<WorktreeSize> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/worktree-size`
+    );
+    return handleApiResponse<WorktreeSize>(response);
+  },
+
+  getDiff: async (
+    attemptId: string,
+    statsOnly = false
+  ): Promise<FileDiff[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/diff?stats_only=${statsOnly}`
+    );
+    return handleApiResponse<FileDiff[]>(response);
+  },
+
+  compare: async (
+    attemptIdA: string,
+    attemptIdB: string
+  ): Promise<FileDiff[]> => {
+    const params = new URLSearchParams({ a: attemptIdA, b: attemptIdB });
+    const response = await makeRequest(
+      `/api/task-attempts/compare?${params.toString()}`
+    );
+    return handleApiResponse<FileDiff[]>(response);
+  },
+
   getBatchBranchStatus: async (
     attemptIds: string[]
   ): Promise<Record<string, BranchStatus>> => {
@@ -520,28 +786,58 @@ export const attemptsApi = {
     return handleApiResponse<Record<string, BranchStatus>>(response);
   },
 
-  merge: async (attemptId: string): Promise<void> => {
+  merge: async (
+    attemptId: string,
+    strategy: MergeStrategy | null = null
+  ): Promise<void> => {
+    const payload: MergeTaskAttemptRequest = { strategy };
     const response = await makeRequest(
       `/api/task-attempts/${attemptId}/merge`,
       {
         method: 'POST',
+        body: JSON.stringify(payload),
       }
     );
     return handleApiResponse<void>(response);
   },
 
-  push: async (attemptId: string): Promise<Result<void, PushError>> => {
+  mergeDryRun: async (
+    attemptId: string,
+    strategy: MergeStrategy | null = null
+  ): Promise<Result<void, GitOperationError>> => {
+    const payload: MergeTaskAttemptRequest = { strategy };
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/merge?dry_run=true`,
+      {
+        method: 'POST',
+        body: JSON.stringify(payload),
+      }
+    );
+    return handleApiResponseAsResult<void, GitOperationError>(response);
+  },
+
+  push: async (
+    attemptId: string,
+    remote: string | null = null
+  ): Promise<Result<void, PushError>> => {
+    const payload: PushTaskAttemptRequest = { remote };
     const response = await makeRequest(`/api/task-attempts/${attemptId}/push`, {
       method: 'POST',
+      body: JSON.stringify(payload),
     });
     return handleApiResponseAsResult<void, PushError>(response);
   },
 
-  forcePush: async (attemptId: string): Promise<Result<void, PushError>> => {
+  forcePush: async (
+    attemptId: string,
+    remote: string | null = null
+  ): Promise<Result<void, PushError>> => {
+    const payload: PushTaskAttemptRequest = { remote };
     const response = await makeRequest(
       `/api/task-attempts/${attemptId}/push/force`,
       {
         method: 'POST',
+        body: JSON.stringify(payload),
       }
     );
     return handleApiResponseAsResult<void, PushError>(response);
@@ -563,10 +859,12 @@ export const attemptsApi = {
 
   change_target_branch: async (
     attemptId: string,
-    data: ChangeTargetBranchRequest
+    data: ChangeTargetBranchRequest,
+    preview = false
   ): Promise<ChangeTargetBranchResponse> => {
+    const query = preview ? '?preview=true' : '';
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/change-target-branch`,
+      `/api/task-attempts/${attemptId}/change-target-branch${query}`,
       {
         method: 'POST',
         body: JSON.stringify(data),
@@ -602,6 +900,20 @@ export const attemptsApi = {
     return handleApiResponse<void>(response);
   },
 
+  resolveConflict: async (
+    attemptId: string,
+    data: ResolveConflictRequest
+  ): Promise<ResolveConflictResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/conflicts/resolve`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ResolveConflictResponse>(response);
+  },
+
   createPR: async (
     attemptId: string,
     data: CreateGitHubPrRequest
@@ -613,6 +925,26 @@ export const attemptsApi = {
     return handleApiResponseAsResult<string, CreatePrError>(response);
   },
 
+  pausePrMonitoring: async (attemptId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/pause`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  resumePrMonitoring: async (attemptId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/resume`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
   startDevServer: async (attemptId: string): Promise<void> => {
     const response = await makeRequest(
       `/api/task-attempts/${attemptId}/start-dev-server`,
@@ -623,6 +955,13 @@ export const attemptsApi = {
     return handleApiResponse<void>(response);
   },
 
+  getDevServer: async (attemptId: string): Promise<DevServerInfo | null> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/dev-server`
+    );
+    return handleApiResponse<DevServerInfo | null>(response);
+  },
+
   setupGhCli: async (attemptId: string): Promise<ExecutionProcess> => {
     const response = await makeRequest(
       `/api/task-attempts/${attemptId}/gh-cli-setup`,
@@ -645,7 +984,7 @@ export const attemptsApi = {
   commit: async (
     attemptId: string,
     data: CommitChangesRequest
-  ): Promise<void> => {
+  ): Promise<Result<void, CommitChangesError>> => {
     const response = await makeRequest(
       `/api/task-attempts/${attemptId}/commit`,
       {
@@ -653,7 +992,7 @@ export const attemptsApi = {
         body: JSON.stringify(data),
       }
     );
-    return handleApiResponse<void>(response);
+    return handleApiResponseAsResult<void, CommitChangesError>(response);
   },
 
   generateCommitMessage: async (
@@ -671,12 +1010,37 @@ export const attemptsApi = {
     >(response);
   },
 
-  exportConversation: async (attemptId: string): Promise<ExportResult> => {
+  exportConversation: async (
+    attemptId: string,
+    includeRawLogs = false,
+    maxChars?: number
+  ): Promise<ExportResult> => {
+    const params = new URLSearchParams();
+    if (includeRawLogs) {
+      params.set('include_raw_logs', 'true');
+    }
+    if (maxChars !== undefined) {
+      params.set('max_chars', String(maxChars));
+    }
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/export-conversation`
+      `/api/task-attempts/${attemptId}/export-conversation?${params.toString()}`
     );
     return handleApiResponse<ExportResult>(response);
   },
+
+  exportConversationJson: async (
+    attemptId: string,
+    maxEntries?: number
+  ): Promise<JsonExportResult> => {
+    const params = new URLSearchParams({ format: 'json' });
+    if (maxEntries !== undefined) {
+      params.set('max_entries', String(maxEntries));
+    }
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/export-conversation?${params.toString()}`
+    );
+    return handleApiResponse<JsonExportResult>(response);
+  },
 };
 
 // Extra helpers
@@ -692,6 +1056,13 @@ export const commitsApi = {
     );
     return handleApiResponse(response);
   },
+
+  list: async (attemptId: string): Promise<CommitInfo[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/commits`
+    );
+    return handleApiResponse<CommitInfo[]>(response);
+  },
 };
 
 // Execution Process APIs
@@ -711,6 +1082,21 @@ export const executionProcessesApi = {
     return handleApiResponse<void>(response);
   },
 
+  getSlots: async (projectId?: string): Promise<ExecutionSlots> => {
+    const query = projectId ? `?project_id=${projectId}` : '';
+    const response = await makeRequest(
+      `/api/execution-processes/slots${query}`
+    );
+    return handleApiResponse<ExecutionSlots>(response);
+  },
+
+  getDiff: async (processId: string): Promise<Diff[]> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/diff`
+    );
+    return handleApiResponse<Diff[]>(response);
+  },
+
   compactExecutionProcess: async (processId: string): Promise<boolean> => {
     const response = await makeRequest(
       `/api/execution-processes/${processId}/compact`,
@@ -720,6 +1106,18 @@ export const executionProcessesApi = {
     );
     return handleApiResponse<boolean>(response);
   },
+
+  sendInput: async (processId: string, input: string): Promise<void> => {
+    const data: SendInputRequest = { input };
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/input`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
 };
 
 // File System APIs
@@ -741,6 +1139,23 @@ export const fileSystemApi = {
   },
 };
 
+// File search index APIs
+export const fileSearchApi = {
+  getStatus: async (): Promise<ProjectIndexStatus[]> => {
+    const response = await makeRequest('/api/file-search/status');
+    return handleApiResponse<ProjectIndexStatus[]>(response);
+  },
+
+  /** Starts indexing; the response body is an SSE stream of `progress` events */
+  warm: async (target: WarmTarget): Promise<Response> => {
+    const body: WarmFileSearchRequest = { target };
+    return makeRequest('/api/file-search/warm', {
+      method: 'POST',
+      body: JSON.stringify(body),
+    });
+  },
+};
+
 // Config APIs (backwards compatible)
 export const configApi = {
   getConfig: async (): Promise<UserSystemInfo> => {
@@ -754,6 +1169,10 @@ export const configApi = {
     });
     return handleApiResponse<Config>(response);
   },
+  getConfigVersion: async (): Promise<ConfigVersionInfo> => {
+    const response = await makeRequest('/api/config/version');
+    return handleApiResponse<ConfigVersionInfo>(response);
+  },
   checkEditorAvailability: async (
     editorType: EditorType
   ): Promise<CheckEditorAvailabilityResponse> => {
@@ -770,6 +1189,22 @@ export const configApi = {
     );
     return handleApiResponse<AvailabilityInfo>(response);
   },
+  getExecutorsAvailability: async (): Promise<
+    Partial<Record<BaseCodingAgent, AvailabilityInfo>>
+  > => {
+    const response = await makeRequest(`/api/executors/availability`);
+    return handleApiResponse<
+      Partial<Record<BaseCodingAgent, AvailabilityInfo>>
+    >(response);
+  },
+  getExecutorSchema: async (
+    agent: BaseCodingAgent
+  ): Promise<Record<string, unknown>> => {
+    const response = await makeRequest(
+      `/api/executors/${encodeURIComponent(agent)}/schema`
+    );
+    return handleApiResponse<Record<string, unknown>>(response);
+  },
 };
 
 // Task Tags APIs (all tags are global)
@@ -807,6 +1242,7 @@ export const tagsApi = {
 };
 
 // MCP Servers APIs
+
 export const mcpServersApi = {
   load: async (query: McpServerQuery): Promise<GetMcpServerResponse> => {
     const params = new URLSearchParams(query);
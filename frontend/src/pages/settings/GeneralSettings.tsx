@@ -622,6 +622,24 @@ export function GeneralSettings() {
               )}
             </p>
           </div>
+
+          <div className="flex items-center space-x-2">
+            <Checkbox
+              id="auto-open-pr"
+              checked={draft?.auto_open_pr_in_browser ?? true}
+              onCheckedChange={(checked: boolean) =>
+                updateDraft({ auto_open_pr_in_browser: checked })
+              }
+            />
+            <div className="space-y-0.5">
+              <Label htmlFor="auto-open-pr" className="cursor-pointer">
+                {t('settings.general.git.autoOpenPr.label')}
+              </Label>
+              <p className="text-sm text-muted-foreground">
+                {t('settings.general.git.autoOpenPr.helper')}
+              </p>
+            </div>
+          </div>
         </CardContent>
       </Card>
 
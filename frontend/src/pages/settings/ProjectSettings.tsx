@@ -54,12 +54,12 @@ export function ProjectSettings() {
   const projectIdParam = searchParams.get('projectId') ?? '';
   const { t } = useTranslation('settings');
 
-  // Fetch all projects
+  // Fetch all projects, archived ones included so they stay editable
   const {
     data: projects,
     isLoading: projectsLoading,
     error: projectsError,
-  } = useProjects();
+  } = useProjects({ includeArchived: true });
 
   // Selected project state
   const [selectedProjectId, setSelectedProjectId] = useState<string>(
@@ -214,6 +214,19 @@ export function ProjectSettings() {
         dev_script: draft.dev_script.trim() || null,
         cleanup_script: draft.cleanup_script.trim() || null,
         copy_files: draft.copy_files.trim() || null,
+        env_template: null,
+        merge_strategy: null,
+        auto_push: null,
+        abort_on_output_pattern: selectedProject.abort_on_output_pattern,
+        default_executor_profile_id:
+          selectedProject.default_executor_profile_id,
+        commit_message_template: selectedProject.commit_message_template,
+        branch_template: selectedProject.branch_template,
+        mcp_servers: null,
+        worktree_sparse_patterns: selectedProject.worktree_sparse_patterns,
+        max_concurrent_executions: selectedProject.max_concurrent_executions,
+        dev_server_url_pattern: selectedProject.dev_server_url_pattern,
+        script_language: selectedProject.script_language,
       };
 
       updateProject.mutate({
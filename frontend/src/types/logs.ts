@@ -1,4 +1,8 @@
-import type { NormalizedEntry, ExecutorAction } from 'shared/types';
+import type {
+  NormalizedEntry,
+  NormalizedEntryType,
+  ExecutorAction,
+} from 'shared/types';
 
 export interface UnifiedLogEntry {
   id: string;
@@ -16,3 +20,13 @@ export interface ProcessStartPayload {
   status: string;
   action?: ExecutorAction;
 }
+
+// The next action bar is built client-side, so it carries extra flags derived
+// from the conversation that the server never sends
+export type NextActionEntryType = Extract<
+  NormalizedEntryType,
+  { type: 'next_action' }
+> & {
+  needs_reauth: boolean;
+  setup_help_text: string | null;
+};
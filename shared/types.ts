@@ -8,18 +8,146 @@ export type DirectoryEntry = { name: string, path: string, is_directory: boolean
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
-export type Project = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
+export type MergeStrategy = "merge" | "squash" | "rebase";
 
-export type ProjectWithTaskCounts = { inprogress_count: bigint, inreview_count: bigint, id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
+export type Project = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, remote_project_id: string | null, 
+/**
+ * Environment variables for every execution; values may reference host vars as `${NAME}`
+ */
+env_template: Record<string, string>, merge_strategy: MergeStrategy, 
+/**
+ * Push the attempt branch to its remote after each successful auto-commit
+ */
+auto_push: boolean, 
+/**
+ * Regex matched against agent output; a match stops the execution
+ */
+abort_on_output_pattern: string | null, 
+/**
+ * Executor profile used for new attempts that don't specify one
+ */
+default_executor_profile_id: ExecutorProfileId | null, 
+/**
+ * Commit message template with `{title}`, `{description}`, `{task_id}` and `{branch}` placeholders
+ */
+commit_message_template: string | null, 
+/**
+ * Branch name template with `{prefix}`, `{short_id}`, `{title}` and `{task_id}` placeholders
+ */
+branch_template: string | null, 
+/**
+ * MCP servers added to this project's coding agent runs, without touching the agents' global configs
+ */
+mcp_servers: Array<McpServerDefinition>, 
+/**
+ * Sparse-checkout patterns (one per line, gitignore syntax) for new worktrees; empty checks out everything
+ */
+worktree_sparse_patterns: string | null, 
+/**
+ * Coding agent executions this project may run at once; the global cap still applies
+ */
+max_concurrent_executions: number | null, 
+/**
+ * Regex that picks the app URL (or port) out of the dev server's output; common patterns are tried when unset
+ */
+dev_server_url_pattern: string | null, 
+/**
+ * Interpreter for the setup, cleanup and dev scripts; the platform default when unset
+ */
+script_language: ScriptRequestLanguage | null, 
+/**
+ * Hidden from the default project list; still reachable by id
+ */
+archived: boolean, created_at: Date, updated_at: Date, };
+
+export type ProjectWithTaskCounts = { inprogress_count: bigint, inreview_count: bigint, id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, remote_project_id: string | null, 
+/**
+ * Environment variables for every execution; values may reference host vars as `${NAME}`
+ */
+env_template: Record<string, string>, merge_strategy: MergeStrategy, 
+/**
+ * Push the attempt branch to its remote after each successful auto-commit
+ */
+auto_push: boolean, 
+/**
+ * Regex matched against agent output; a match stops the execution
+ */
+abort_on_output_pattern: string | null, 
+/**
+ * Executor profile used for new attempts that don't specify one
+ */
+default_executor_profile_id: ExecutorProfileId | null, 
+/**
+ * Commit message template with `{title}`, `{description}`, `{task_id}` and `{branch}` placeholders
+ */
+commit_message_template: string | null, 
+/**
+ * Branch name template with `{prefix}`, `{short_id}`, `{title}` and `{task_id}` placeholders
+ */
+branch_template: string | null, 
+/**
+ * MCP servers added to this project's coding agent runs, without touching the agents' global configs
+ */
+mcp_servers: Array<McpServerDefinition>, 
+/**
+ * Sparse-checkout patterns (one per line, gitignore syntax) for new worktrees; empty checks out everything
+ */
+worktree_sparse_patterns: string | null, 
+/**
+ * Coding agent executions this project may run at once; the global cap still applies
+ */
+max_concurrent_executions: number | null, 
+/**
+ * Regex that picks the app URL (or port) out of the dev server's output; common patterns are tried when unset
+ */
+dev_server_url_pattern: string | null, 
+/**
+ * Interpreter for the setup, cleanup and dev scripts; the platform default when unset
+ */
+script_language: ScriptRequestLanguage | null, 
+/**
+ * Hidden from the default project list; still reachable by id
+ */
+archived: boolean, created_at: Date, updated_at: Date, };
 
-export type CreateProject = { name: string, git_repo_path: string, use_existing_repo: boolean, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, };
+export type CreateProject = { name: string, git_repo_path: string, use_existing_repo: boolean, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, env_template: { [key in string]?: string }, merge_strategy: MergeStrategy, auto_push: boolean, abort_on_output_pattern: string | null, default_executor_profile_id: ExecutorProfileId | null, commit_message_template: string | null, branch_template: string | null, mcp_servers: Array<McpServerDefinition>, worktree_sparse_patterns: string | null, max_concurrent_executions: number | null, dev_server_url_pattern: string | null, script_language: ScriptRequestLanguage | null, };
 
-export type UpdateProject = { name: string | null, git_repo_path: string | null, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, };
+export type UpdateProject = { name: string | null, git_repo_path: string | null, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, 
+/**
+ * Omit to keep the current template
+ */
+env_template: { [key in string]?: string } | null, 
+/**
+ * Omit to keep the current strategy
+ */
+merge_strategy: MergeStrategy | null, auto_push: boolean | null, abort_on_output_pattern: string | null, default_executor_profile_id: ExecutorProfileId | null, commit_message_template: string | null, branch_template: string | null, 
+/**
+ * Omit to keep the current servers
+ */
+mcp_servers: Array<McpServerDefinition> | null, 
+/**
+ * Omit to keep the current patterns, or send `null` to clear them
+ */
+worktree_sparse_patterns?: string | null, max_concurrent_executions: number | null, dev_server_url_pattern: string | null, script_language: ScriptRequestLanguage | null, };
 
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, };
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
 
+export type IndexStatus = { "status": "pending" } | { "status": "indexing" } | { "status": "done" } | { "status": "error", message: string, };
+
+export type WarmTarget = "all" | { "projects": Array<string> };
+
+export type WarmFileSearchRequest = { target: WarmTarget, };
+
+export type ProjectIndexStatus = { project_id: string, status: IndexStatus, };
+
+export type WarmProgress = { project_id: string, status: IndexStatus, 
+/**
+ * Projects whose indexing has finished, successfully or not
+ */
+finished: number, total: number, };
+
 export type CreateRemoteProjectRequest = { organization_id: string, name: string, };
 
 export type LinkToExistingRequest = { remote_project_id: string, };
@@ -34,8 +162,72 @@ in_worktree: boolean,
  */
 worktree_path: string | null, };
 
+export type ExportProjectBundleQuery = { 
+/**
+ * Include attempt metadata (branches and executors, never worktrees)
+ */
+include_attempts: boolean, };
+
+export type ImportProjectBundleRequest = { 
+/**
+ * Existing git repository the imported project will point at
+ */
+git_repo_path: string, bundle: ProjectBundle, };
+
+export type AttemptWorktreeSize = { attempt_id: string, bytes: bigint, };
+
+export type ProjectWorktreeSize = { total_bytes: bigint, attempts: Array<AttemptWorktreeSize>, };
+
+export type StopAllProcessesResponse = { 
+/**
+ * Number of execution processes that were killed
+ */
+stopped: number, };
+
+export type ActivityKind = "task_created" | "task_deleted" | "attempt_started" | "attempt_merged" | "pr_opened" | "dev_server_started";
+
+export type Activity = { id: string, project_id: string, kind: ActivityKind, task_id: string | null, attempt_id: string | null, 
+/**
+ * User id of the installation that performed the action
+ */
+actor: string, created_at: string, };
+
+export type ProjectActivityPage = { entries: Array<Activity>, 
+/**
+ * Pass as `before` to fetch the next, older page
+ */
+next_cursor: string | null, has_more: boolean, };
+
+export type UpdateTaskLabelRequest = { 
+/**
+ * New name, unique within the project ignoring case
+ */
+name: string | null, 
+/**
+ * New hex color such as `#ef4444`
+ */
+color: string | null, };
+
+export type ProjectBundle = { version: number, exported_at: Date, project: BundledProjectSettings, tasks: Array<BundledTask>, };
+
+export type BundledProjectSettings = { name: string, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, env_template: { [key in string]?: string }, merge_strategy: MergeStrategy, auto_push: boolean, abort_on_output_pattern: string | null, default_executor_profile_id: ExecutorProfileId | null, commit_message_template: string | null, branch_template: string | null, mcp_servers: Array<McpServerDefinition>, worktree_sparse_patterns: string | null, max_concurrent_executions: number | null, dev_server_url_pattern: string | null, script_language: ScriptRequestLanguage | null, };
+
+export type BundledTask = { title: string, description: string | null, status: TaskStatus, 
+/**
+ * Empty unless the bundle was exported with attempts
+ */
+attempts: Array<BundledAttempt>, };
+
+export type BundledAttempt = { branch: string, target_branch: string, executor: string, is_orchestrator: boolean, };
+
 export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, };
 
+export type McpServerDefinition = { 
+/**
+ * Key the server is stored under in the agent's config
+ */
+name: string, command: string, args: Array<string>, env: { [key in string]?: string }, };
+
 export type McpConfig = { servers: { [key in string]?: JsonValue }, servers_path: Array<string>, template: JsonValue, preconfigured: JsonValue, is_toml_config: boolean, };
 
 export type ExecutorActionType = { "type": "CodingAgentInitialRequest" } & CodingAgentInitialRequest | { "type": "CodingAgentFollowUpRequest" } & CodingAgentFollowUpRequest | { "type": "ScriptRequest" } & ScriptRequest;
@@ -44,7 +236,7 @@ export type ScriptContext = "SetupScript" | "CleanupScript" | "DevServer" | "Too
 
 export type ScriptRequest = { script: string, language: ScriptRequestLanguage, context: ScriptContext, };
 
-export type ScriptRequestLanguage = "Bash";
+export type ScriptRequestLanguage = "Bash" | "Sh" | "PowerShell" | "Cmd";
 
 export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT", DROID = "DROID" }
 
@@ -58,25 +250,95 @@ export type UpdateTag = { tag_name: string | null, content: string | null, };
 
 export type TagSearchParams = { search: string | null, };
 
+export type TaskTemplate = { id: string, 
+/**
+ * Project the template is scoped to; `None` for templates usable in any project
+ */
+project_id: string | null, name: string, 
+/**
+ * Title with `{placeholder}`s (or `{{placeholder}}`s) filled in on instantiation
+ */
+title: string, description: string | null, default_executor_profile_id: ExecutorProfileId | null, 
+/**
+ * Base branch for attempts started from the instantiated task
+ */
+default_base_branch: string | null, created_at: string, updated_at: string, };
+
+export type CreateTaskTemplate = { project_id: string | null, name: string, title: string, description: string | null, default_executor_profile_id: ExecutorProfileId | null, default_base_branch: string | null, };
+
+export type UpdateTaskTemplate = { name: string | null, title: string | null, 
+/**
+ * Omit to keep the current description, or send `null` to clear it
+ */
+description?: string | null, 
+/**
+ * Omit to keep the current profile, or send `null` to clear it
+ */
+default_executor_profile_id?: ExecutorProfileId | null, 
+/**
+ * Omit to keep the current base branch, or send `null` to clear it
+ */
+default_base_branch?: string | null, };
+
+export type TaskTemplateQuery = { 
+/**
+ * Limit results to global templates and those scoped to this project
+ */
+project_id: string | null, };
+
+export type InstantiateTaskTemplateRequest = { project_id: string, 
+/**
+ * Values for the template's `{placeholder}`s, keyed by placeholder name
+ */
+values: { [key in string]?: string }, };
+
+export type InstantiateTaskTemplateResponse = { task: Task, 
+/**
+ * Profile to start the task with, taken from the template's default
+ */
+executor_profile_id: ExecutorProfileId | null, 
+/**
+ * Base branch to start the task's attempt from, taken from the template's default
+ */
+base_branch: string | null, };
+
 export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
 
 export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
 
-export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, has_merged_attempt: boolean, last_attempt_failed: boolean, executor: string, latest_task_attempt_id: string | null, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
+export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, has_merged_attempt: boolean, last_attempt_failed: boolean, executor: string, latest_task_attempt_id: string | null, 
+/**
+ * The task's labels, by name
+ */
+labels: Array<TaskLabel>, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
 
 export type TaskRelationships = { parent_task: Task | null, current_attempt: TaskAttempt, children: Array<Task>, };
 
+export type TaskDependencies = { blocked_by: Array<Task>, blocking: Array<Task>, };
+
+export type TaskLabel = { id: string, project_id: string, name: string, 
+/**
+ * Hex color, e.g. `#ef4444`
+ */
+color: string, created_at: string, };
+
 export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, parent_task_attempt: string | null, image_ids: Array<string> | null, shared_task_id: string | null, };
 
 export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_task_attempt: string | null, image_ids: Array<string> | null, };
 
 export type SharedTask = { id: string, remote_project_id: string, title: string, description: string | null, status: TaskStatus, assignee_user_id: string | null, assignee_first_name: string | null, assignee_last_name: string | null, assignee_username: string | null, version: bigint, last_event_seq: bigint | null, created_at: Date, updated_at: Date, };
 
-export type DraftFollowUpData = { message: string, variant: string | null, };
+export type DraftFollowUpData = { message: string, variant: string | null, 
+/**
+ * Images attached to the draft
+ */
+image_ids: Array<string>, };
+
+export type QueuedFollowUpData = { data: DraftFollowUpData, queued_at: string, };
 
-export type ScratchPayload = { "type": "DRAFT_TASK", "data": string } | { "type": "DRAFT_FOLLOW_UP", "data": DraftFollowUpData };
+export type ScratchPayload = { "type": "DRAFT_TASK", "data": string } | { "type": "DRAFT_FOLLOW_UP", "data": DraftFollowUpData } | { "type": "FOLLOW_UP_QUEUE", "data": Array<QueuedFollowUpData> };
 
-export enum ScratchType { DRAFT_TASK = "DRAFT_TASK", DRAFT_FOLLOW_UP = "DRAFT_FOLLOW_UP" }
+export enum ScratchType { DRAFT_TASK = "DRAFT_TASK", DRAFT_FOLLOW_UP = "DRAFT_FOLLOW_UP", FOLLOW_UP_QUEUE = "FOLLOW_UP_QUEUE" }
 
 export type Scratch = { id: string, payload: ScratchPayload, created_at: string, updated_at: string, };
 
@@ -98,7 +360,43 @@ data: DraftFollowUpData,
  */
 queued_at: string, };
 
-export type QueueStatus = { "status": "empty" } | { "status": "queued", message: QueuedMessage, };
+export type QueueStatus = { "status": "empty" } | { "status": "queued", 
+/**
+ * The most recently queued message
+ */
+message: QueuedMessage, 
+/**
+ * Every queued message, in the order they will run
+ */
+pending: Array<QueuedMessage>, };
+
+export type BackgroundTaskStatus = "running" | "finished" | "cancelled";
+
+export type BackgroundTaskInfo = { id: string, label: string, status: BackgroundTaskStatus, 
+/**
+ * Whether the job can be aborted without leaving state inconsistent
+ */
+cancellable: boolean, started_at: Date, };
+
+export type GitHubRateLimit = { limit: number, remaining: number, 
+/**
+ * When `remaining` is restored to `limit`
+ */
+reset_at: Date, };
+
+export type PrSyncStatus = { rate_limit: GitHubRateLimit, 
+/**
+ * Set while PR polling is paused until the rate limit resets
+ */
+paused_until: Date | null, };
+
+export type AgentLoginStatus = { agent: BaseCodingAgent, last_auth_at: Date, 
+/**
+ * Age of the login in seconds
+ */
+age_secs: number, };
+
+export type DeletedWorktree = { attempt_id: string, path: string, };
 
 export type Image = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
@@ -184,11 +482,39 @@ export type CheckAgentAvailabilityQuery = { executor: BaseCodingAgent, };
 
 export type AvailabilityInfo = { "type": "LOGIN_DETECTED", last_auth_timestamp: bigint, } | { "type": "INSTALLATION_FOUND" } | { "type": "NOT_FOUND" };
 
-export type CreateFollowUpAttempt = { prompt: string, variant: string | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, };
+export type CreateFollowUpAttempt = { prompt: string, variant: string | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, 
+/**
+ * Replaces the attempt's cleanup script override; an empty string restores the project's
+ */
+cleanup_script: string | null, 
+/**
+ * Images to attach; ids with no stored image are skipped
+ */
+image_ids: Array<string> | null, };
 
 export type ChangeTargetBranchRequest = { new_target_branch: string, };
 
-export type ChangeTargetBranchResponse = { new_target_branch: string, status: [number, number], };
+export type ChangeTargetBranchResponse = { new_target_branch: string, status: [number, number], 
+/**
+ * Files that would conflict when rebasing onto the new target
+ */
+conflicting_files: Array<string>, 
+/**
+ * True when nothing was saved
+ */
+preview: boolean, };
+
+export type AheadBehindRequest = { 
+/**
+ * Branches to compare the attempt's branch against, e.g. `main` and `release/1.x`
+ */
+branches: Array<string>, };
+
+export type BranchAheadBehind = { branch: string, commits_ahead: number | null, commits_behind: number | null, 
+/**
+ * Why the comparison failed, e.g. the branch does not exist
+ */
+error: string | null, };
 
 export type RenameBranchRequest = { new_branch_name: string, };
 
@@ -196,6 +522,8 @@ export type RenameBranchResponse = { branch: string, };
 
 export type CommitCompareResult = { subject: string, head_oid: string, target_oid: string, ahead_from_head: number, behind_from_head: number, is_linear: boolean, };
 
+export type WorktreeSize = { bytes: bigint, };
+
 export type OpenEditorRequest = { editor_type: string | null, file_path: string | null, };
 
 export type OpenEditorResponse = { url: string | null, };
@@ -204,6 +532,12 @@ export type AssignSharedTaskRequest = { new_assignee_user_id: string | null, ver
 
 export type AssignSharedTaskResponse = { shared_task: SharedTask, };
 
+export type RemoteSyncState = { "status": "connecting" } | { "status": "connected" } | { "status": "reconnecting", 
+/**
+ * Consecutive failed attempts so far
+ */
+attempt: number, } | { "status": "failed", error: string, };
+
 export type ShareTaskResponse = { shared_task_id: string, };
 
 export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, base_branch: string, 
@@ -217,13 +551,79 @@ use_existing_branch: boolean,
  */
 custom_branch: string | null, };
 
-export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, };
+export type AdoptBranchRequest = { 
+/**
+ * Existing local branch to adopt
+ */
+branch: string, 
+/**
+ * Branch the work was started from; the attempt diffs against their merge base
+ */
+base: string, };
+
+export type AdoptBranchResponse = { attempt: TaskAttempt, base_commit: string, head_commit: string, commits_ahead: number, };
+
+export type AddTaskDependencyRequest = { blocked_by_task_id: string, };
+
+export type AddTaskLabelRequest = { 
+/**
+ * Label name; an existing project label with this name (ignoring case) is reused
+ */
+name: string, 
+/**
+ * Hex color such as `#ef4444`. Recolors the label if it already exists.
+ */
+color: string | null, };
+
+export type TaskSearchQuery = { 
+/**
+ * Text to find in task titles and descriptions
+ */
+q: string, project_id: string | null, status: TaskStatus | null, limit: number | null, };
+
+export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, 
+/**
+ * Fill the body from the attempt's conversation export when `body` isn't given
+ */
+body_from_conversation: boolean, };
 
 export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
 export type ImageMetadata = { exists: boolean, file_name: string | null, path: string | null, size_bytes: bigint | null, format: string | null, proxy_url: string | null, };
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, auto_commit_enabled: boolean, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, auto_commit_enabled: boolean, log_file_sink: LogFileSinkConfig | null, 
+/**
+ * Record an empty commit when a cleanup script leaves no changes
+ */
+allow_empty_cleanup_commit: boolean, line_endings: LineEndings, 
+/**
+ * Secrets injected into attempt processes according to their scope; values are redacted from logs
+ */
+secrets: Array<ScopedSecret>, 
+/**
+ * Seconds between periodic worktree cleanup passes
+ */
+worktree_cleanup_interval_secs: bigint, 
+/**
+ * Delete worktree directories that no longer belong to any task attempt
+ */
+worktree_orphan_cleanup_enabled: boolean, auto_commit_message_mode: AutoCommitMessageMode, webhooks: WebhooksConfig, 
+/**
+ * Days after which an agent login is flagged for renewal by re-running setup
+ */
+agent_login_max_age_days: number, execution_limits: ExecutionLimitsConfig, 
+/**
+ * Stop an execution that produces no output for this many seconds; zero or unset disables it
+ */
+execution_idle_timeout_secs: number | null, 
+/**
+ * Open newly created pull requests in the browser
+ */
+auto_open_pr_in_browser: boolean, 
+/**
+ * Open the app in the browser when the server starts (release builds only)
+ */
+open_browser_on_startup: boolean, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -237,14 +637,81 @@ export type EditorOpenError = { "type": "executable_not_found", executable: stri
 
 export type GitHubConfig = { pat: string | null, oauth_token: string | null, username: string | null, primary_email: string | null, default_pr_base: string | null, };
 
+export type LogFileSinkConfig = { path_template: string, max_file_bytes: bigint, 
+/**
+ * Maximum number of rotated files to keep per process (unbounded when unset)
+ */
+max_files: number | null, };
+
+export type LineEndings = "PRESERVE" | "LF" | "CRLF";
+
+export type AutoCommitMessageMode = "session_summary" | "task_title" | "generic";
+
+export type WebhooksConfig = { urls: Array<string>, 
+/**
+ * Statuses that trigger a webhook; empty sends every status change
+ */
+statuses: Array<TaskStatus>, };
+
+export type ExecutionLimitsConfig = { 
+/**
+ * Across all projects; unlimited when unset
+ */
+max_concurrent: number | null, 
+/**
+ * Seconds a chained agent start waits for a free slot before giving up
+ */
+slot_wait_secs: number, };
+
+export type ExecutionSlots = { running: number, 
+/**
+ * Global cap; unlimited when null
+ */
+max: number | null, 
+/**
+ * Running within the requested project, if any
+ */
+project_running: number | null, project_max: number | null, };
+
+export type ScopedSecret = { name: string, value: string, scope: SecretScope, };
+
+export type SecretScope = "ALL" | "CODING_AGENT" | "SCRIPT";
+
+export type EnvOverrides = { [key in string]?: string };
+
 export enum SoundFile { ABSTRACT_SOUND1 = "ABSTRACT_SOUND1", ABSTRACT_SOUND2 = "ABSTRACT_SOUND2", ABSTRACT_SOUND3 = "ABSTRACT_SOUND3", ABSTRACT_SOUND4 = "ABSTRACT_SOUND4", COW_MOOING = "COW_MOOING", PHONE_VIBRATION = "PHONE_VIBRATION", ROOSTER = "ROOSTER" }
 
 export type UiLanguage = "BROWSER" | "EN" | "JA" | "ES" | "KO";
 
 export type ShowcaseState = { seen_features: Array<string>, };
 
+export type ConfigVersionInfo = { file_exists: boolean, 
+/**
+ * `config_version` recorded in the file; null when there is no file, it isn't
+ * valid JSON, or it predates versioning (v1)
+ */
+detected_version: string | null, current_version: string, 
+/**
+ * Loading the file would upgrade it (or reset it to defaults if no schema fits)
+ */
+migration_needed: boolean, 
+/**
+ * The config loading the file would produce; the API masks its secret values
+ */
+migrated_config: Config, };
+
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
+export type LargeFileEntry = { path: string, size_bytes: number, is_binary: boolean, };
+
+export type CommitNote = { task_id: string, attempt_id: string, executor: string, };
+
+export type CommitInfo = { oid: string, subject: string, author_name: string, author_email: string, timestamp: Date, 
+/**
+ * Task metadata from the commit's note, if it was made by vibe-kanban
+ */
+note: CommitNote | null, };
+
 export type Diff = { change: DiffChangeKind, oldPath: string | null, newPath: string | null, oldContent: string | null, newContent: string | null, 
 /**
  * True when file contents are intentionally omitted (e.g., too large)
@@ -279,17 +746,21 @@ variant: string | null, };
 
 export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } };
 
-export type ExecutorConfigs = { executors: { [key in BaseCodingAgent]?: ExecutorConfig }, };
+export type ExecutorConfigs = { executors: { [key in BaseCodingAgent]?: ExecutorConfig }, 
+/**
+ * Model used by an executor's profiles that don't pick one themselves
+ */
+default_models?: { [key in BaseCodingAgent]?: string }, };
 
 export enum BaseAgentCapability { SESSION_FORK = "SESSION_FORK", SETUP_HELPER = "SETUP_HELPER" }
 
-export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type ClaudeCode = { append_prompt: AppendPrompt, prompt_prefix?: string | null, prompt_suffix?: string | null, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type Gemini = { append_prompt: AppendPrompt, model?: string | null, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Gemini = { append_prompt: AppendPrompt, prompt_prefix?: string | null, prompt_suffix?: string | null, model?: string | null, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type Amp = { append_prompt: AppendPrompt, dangerously_allow_all?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Amp = { append_prompt: AppendPrompt, prompt_prefix?: string | null, prompt_suffix?: string | null, dangerously_allow_all?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type Codex = { append_prompt: AppendPrompt, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_apply_patch_tool?: boolean | null, model_provider?: string | null, compact_prompt?: string | null, developer_instructions?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Codex = { append_prompt: AppendPrompt, prompt_prefix?: string | null, prompt_suffix?: string | null, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_apply_patch_tool?: boolean | null, model_provider?: string | null, compact_prompt?: string | null, developer_instructions?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
 export type SandboxMode = "auto" | "read-only" | "workspace-write" | "danger-full-access";
 
@@ -301,15 +772,15 @@ export type ReasoningSummary = "auto" | "concise" | "detailed" | "none";
 
 export type ReasoningSummaryFormat = "none" | "experimental";
 
-export type CursorAgent = { append_prompt: AppendPrompt, force?: boolean | null, model?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type CursorAgent = { append_prompt: AppendPrompt, prompt_prefix?: string | null, prompt_suffix?: string | null, force?: boolean | null, model?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type Copilot = { append_prompt: AppendPrompt, model?: string | null, allow_all_tools?: boolean | null, allow_tool?: string | null, deny_tool?: string | null, add_dir?: Array<string> | null, disable_mcp_server?: Array<string> | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Copilot = { append_prompt: AppendPrompt, prompt_prefix?: string | null, prompt_suffix?: string | null, model?: string | null, allow_all_tools?: boolean | null, allow_tool?: string | null, deny_tool?: string | null, add_dir?: Array<string> | null, disable_mcp_server?: Array<string> | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type Opencode = { append_prompt: AppendPrompt, model?: string | null, agent?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Opencode = { append_prompt: AppendPrompt, prompt_prefix?: string | null, prompt_suffix?: string | null, model?: string | null, agent?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type QwenCode = { append_prompt: AppendPrompt, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type QwenCode = { append_prompt: AppendPrompt, prompt_prefix?: string | null, prompt_suffix?: string | null, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type Droid = { append_prompt: AppendPrompt, autonomy: Autonomy, model?: string | null, reasoning_effort?: DroidReasoningEffort | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Droid = { append_prompt: AppendPrompt, prompt_prefix?: string | null, prompt_suffix?: string | null, autonomy: Autonomy, model?: string | null, reasoning_effort?: DroidReasoningEffort | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
 export type Autonomy = "normal" | "low" | "medium" | "high" | "skip-permissions-unsafe";
 
@@ -339,9 +810,9 @@ is_orchestrator: boolean, };
 
 export type CreateTaskAttemptBody = { task_id: string, 
 /**
- * Executor profile specification
+ * Executor profile specification; falls back to the project's default when omitted
  */
-executor_profile_id: ExecutorProfileId, base_branch: string, 
+executor_profile_id: ExecutorProfileId | null, base_branch: string, 
 /**
  * If true, use base_branch as the working branch instead of creating a new one
  */
@@ -355,7 +826,39 @@ custom_branch: string | null,
  * Conversation history from a previous attempt to prepend to the prompt.
  * Used when continuing a task with a different agent.
  */
-conversation_history: string | null, };
+conversation_history: string | null, 
+/**
+ * Cleanup script to run for this attempt instead of the project's
+ */
+cleanup_script_override: string | null, 
+/**
+ * Environment variables for every process of this attempt, over the project's
+ */
+env_overrides: { [key in string]?: string } | null, };
+
+export type CreateTaskAttemptBatchBody = { task_id: string, base_branch: string, 
+/**
+ * One attempt is started per profile, each on its own auto-generated branch
+ */
+executor_profile_ids: Array<ExecutorProfileId>, };
+
+export type TaskAttemptBatchResult = { executor_profile_id: ExecutorProfileId, attempt: TaskAttempt | null, 
+/**
+ * Why this profile's attempt could not be started
+ */
+error: string | null, };
+
+export type CloneTaskAttemptBody = { executor_profile_id: ExecutorProfileId, 
+/**
+ * Prepend the source attempt's conversation, exported as markdown, to the prompt
+ */
+include_conversation_history: boolean, };
+
+export type DevServerInfo = { execution_process_id: string, status: ExecutionProcessStatus, 
+/**
+ * Detected from the dev server's output; null until it prints one
+ */
+url: string | null, port: number | null, };
 
 export type RunAgentSetupRequest = { executor_profile_id: ExecutorProfileId, };
 
@@ -365,11 +868,24 @@ export type GhCliSetupError = "BREW_MISSING" | "SETUP_HELPER_NOT_SUPPORTED" | {
 
 export type RebaseTaskAttemptRequest = { old_base_branch: string | null, new_base_branch: string | null, };
 
+export type MergeTaskAttemptRequest = { 
+/**
+ * Strategy to merge with (`squash` collapses the attempt into one commit);
+ * must match the project's configured strategy
+ */
+strategy: MergeStrategy | null, };
+
 export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, } | { "type": "rebase_in_progress" };
 
-export type PushError = { "type": "force_push_required" };
+export type PushTaskAttemptRequest = { 
+/**
+ * Remote to push to; defaults to the remote the branch already tracks
+ */
+remote: string | null, };
 
-export type CreatePrError = { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, };
+export type PushError = { "type": "force_push_required" } | { "type": "remote_not_found", remote: string, };
+
+export type CreatePrError = { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "glab_cli_not_installed" } | { "type": "glab_cli_not_logged_in" } | { "type": "target_branch_not_found", branch: string, };
 
 export type BranchStatus = { commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
 /**
@@ -393,7 +909,13 @@ files: Array<string>,
 /**
  * Commit message.
  */
-message: string, };
+message: string, 
+/**
+ * Commit even if a rebase is in progress or files are still conflicted.
+ */
+force_when_dirty: boolean, };
+
+export type CommitChangesError = { "type": "commit_blocked_by_conflict", rebase_in_progress: boolean, conflicted_files: Array<string>, };
 
 export type WorktreeStatusResponse = { entries: Array<FileStatusEntry>, };
 
@@ -423,6 +945,78 @@ export type GenerateCommitMessageResponse = { message: string, };
 
 export type GenerateCommitMessageError = { "type": "no_changes" } | { "type": "claude_code_failed", message: string, };
 
+export type CommitMessagePreviewResponse = { 
+/**
+ * `None` when no execution in this attempt would be auto-committed
+ */
+message: string | null, execution_process_id: string | null, };
+
+export type SetLastViewedRequest = { 
+/**
+ * Defaults to the attempt's current HEAD
+ */
+oid: string | null, };
+
+export type LastViewedResponse = { oid: string, };
+
+export type FileDiff = { 
+/**
+ * Path after the change, or before it for deletions
+ */
+path: string, 
+/**
+ * Previous path of a renamed or copied file
+ */
+old_path: string | null, change: DiffChangeKind, additions: number, deletions: number, 
+/**
+ * Unified diff hunks; empty when contents were omitted
+ */
+hunks: Array<string>, 
+/**
+ * True when contents were left out because of `stats_only` or the size limit
+ */
+content_omitted: boolean, };
+
+export type DiffSinceLastResponse = { from_oid: string, to_oid: string, 
+/**
+ * True when no marker was set and the diff starts at the attempt's base commit
+ */
+from_base: boolean, diffs: Array<Diff>, };
+
+export type LargeFilesResponse = { threshold_bytes: number, files: Array<LargeFileEntry>, };
+
+export type ResetToCommitRequest = { oid: string, mode: ResetMode, 
+/**
+ * Allow a hard reset to discard uncommitted changes
+ */
+force: boolean, };
+
+export type ResetToCommitResponse = { head_oid: string, };
+
+export type ConversationExportFormat = "markdown" | "json";
+
+export type ConversationExport = ExportResult | JsonExportResult;
+
+export type ResolveConflictRequest = { file_path: string, strategy: ConflictSide, };
+
+export type ResolveConflictResponse = { 
+/**
+ * Files still conflicted after this one was resolved
+ */
+conflicted_files: Array<string>, };
+
+export type RawLogStream = "stdout" | "stderr";
+
+export type RawLogEntry = { seq: bigint, stream: RawLogStream, content: string, };
+
+export type RawLogPage = { entries: Array<RawLogEntry>, 
+/**
+ * Pass as `after_seq` to fetch the next page; unchanged when nothing new was returned
+ */
+next_cursor: bigint | null, has_more: boolean, };
+
+export type SendInputRequest = { input: string, };
+
 export type ExportResult = { 
 /**
  * The exported markdown text.
@@ -435,11 +1029,56 @@ message_count: number,
 /**
  * Whether the export was truncated due to length.
  */
-truncated: boolean, };
+truncated: boolean, 
+/**
+ * The length limit the conversation was truncated to, in characters.
+ */
+max_chars: number, };
+
+export type JsonExportResult = { 
+/**
+ * Name of the executor that generated the conversation.
+ */
+executor: string, 
+/**
+ * The task attempt the conversation belongs to.
+ */
+attempt_id: string, 
+/**
+ * Number of entries included in the export.
+ */
+message_count: number, 
+/**
+ * Whether older entries were dropped to fit `max_entries`.
+ */
+truncated: boolean, 
+/**
+ * The exported entries, oldest first.
+ */
+entries: Array<NormalizedEntry>, };
+
+export type McpToolCall = { server: string, tool: string, arguments: JsonValue | null, result: ToolResult | null, status: ToolStatus, };
 
 export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
-export type TaskAttempt = { id: string, task_id: string, container_ref: string | null, branch: string, target_branch: string, executor: string, worktree_deleted: boolean, setup_completed_at: string | null, is_orchestrator: boolean, created_at: string, updated_at: string, };
+export type ResetMode = "soft" | "mixed" | "hard";
+
+export type ConflictSide = "ours" | "theirs";
+
+export type TaskAttempt = { id: string, task_id: string, container_ref: string | null, branch: string, target_branch: string, executor: string, worktree_deleted: boolean, setup_completed_at: string | null, is_orchestrator: boolean, 
+/**
+ * Cleanup script run for this attempt instead of the project's
+ */
+cleanup_script_override: string | null, 
+/**
+ * Environment variables set on every process of this attempt, over the project's
+ */
+env_overrides: EnvOverrides | null, 
+/**
+ * Keep the worktree past expiry cleanup. Pinned worktrees still take up
+ * disk space; removing them is up to the user.
+ */
+pin_worktree: boolean, created_at: string, updated_at: string, };
 
 export type ExecutionProcess = { id: string, task_attempt_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, 
 /**
@@ -457,7 +1096,7 @@ after_head_commit: string | null, status: ExecutionProcessStatus, exit_code: big
  */
 dropped: boolean, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
 
-export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
+export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed", timedout = "timedout" }
 
 export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
 
@@ -465,10 +1104,16 @@ export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMe
 
 export type DirectMerge = { id: string, task_attempt_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
 
-export type PrMerge = { id: string, task_attempt_id: string, created_at: string, target_branch_name: string, pr_info: PullRequestInfo, };
+export type PrMerge = { id: string, task_attempt_id: string, created_at: string, target_branch_name: string, provider: GitProvider, pr_info: PullRequestInfo, 
+/**
+ * True when the PR monitor skips this PR while polling
+ */
+monitoring_paused: boolean, };
 
 export type MergeStatus = "open" | "merged" | "closed" | "unknown";
 
+export type GitProvider = "github" | "gitlab";
+
 export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, };
 
 export type CommandExitStatus = { "type": "exit_code", code: number, } | { "type": "success", success: boolean, };
@@ -527,9 +1172,15 @@ warning_level: ContextWarningLevel,
  */
 is_estimated: boolean, };
 
+export type TokenUsage = { input_tokens: bigint, output_tokens: bigint, 
+/**
+ * Input tokens served from (or written to) the prompt cache
+ */
+cached_tokens: bigint, };
+
 export type NormalizedEntry = { timestamp: string | null, entry_type: NormalizedEntryType, content: string, };
 
-export type NormalizedEntryType = { "type": "user_message" } | { "type": "user_feedback", denied_tool: string, } | { "type": "assistant_message" } | { "type": "tool_use", tool_name: string, action_type: ActionType, status: ToolStatus, } | { "type": "system_message" } | { "type": "error_message", error_type: NormalizedEntryError, } | { "type": "thinking" } | { "type": "loading" } | { "type": "next_action", failed: boolean, execution_processes: number, needs_setup: boolean, } | { "type": "context_usage", usage: ContextUsage, };
+export type NormalizedEntryType = { "type": "user_message" } | { "type": "user_feedback", denied_tool: string, } | { "type": "assistant_message" } | { "type": "tool_use", tool_name: string, action_type: ActionType, status: ToolStatus, } | { "type": "system_message" } | { "type": "error_message", error_type: NormalizedEntryError, } | { "type": "thinking" } | { "type": "loading" } | { "type": "next_action", failed: boolean, execution_processes: number, needs_setup: boolean, } | { "type": "context_usage", usage: ContextUsage, } | { "type": "usage", usage: TokenUsage, };
 
 export type FileChange = { "action": "write", content: string, } | { "action": "delete" } | { "action": "rename", new_path: string, } | { "action": "edit", 
 /**
@@ -545,7 +1196,7 @@ export type ActionType = { "action": "file_read", path: string, } | { "action":
 
 export type TodoItem = { content: string, status: string, priority: string | null, };
 
-export type NormalizedEntryError = { "type": "setup_required" } | { "type": "other" };
+export type NormalizedEntryError = { "type": "setup_required" } | { "type": "auth_required" } | { "type": "parse_error" } | { "type": "other" };
 
 export type ToolResult = { type: ToolResultValueType, 
 /**
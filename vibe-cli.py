@@ -432,7 +432,9 @@ def cmd_attempts_stop(args):
 
 def cmd_attempts_merge(args):
     """Merge an attempt's changes."""
-    result = api_request("POST", f"/task-attempts/{args.id}/merge")
+    result = api_request(
+        "POST", f"/task-attempts/{args.id}/merge", data={"strategy": args.strategy}
+    )
     print_json(result) if result else print(f"Attempt {args.id} merged")
 
 
@@ -746,6 +748,11 @@ For more information, visit: https://github.com/vibe-teams/vibe-kanban
     # attempts merge
     p = attempts_sub.add_parser("merge", help="Merge attempt changes")
     p.add_argument("id", help="Attempt ID (UUID)")
+    p.add_argument(
+        "--strategy",
+        choices=["merge", "squash", "rebase"],
+        help="Merge strategy (must match the project's configured strategy)",
+    )
 
     # attempts push
     p = attempts_sub.add_parser("push", help="Push attempt branch")